@@ -0,0 +1,238 @@
+use crate::models::Person;
+use crate::hashing;
+use crate::webhook::{Payload, Webhook};
+use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+// Appends read the previous entry's hash, so two writers must not interleave
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AuditAction {
+    PersonAdded,
+    PersonUpdated,
+    PersonDeleted,
+    InformationAdded,
+    InformationUpdated,
+    InformationRemoved,
+    QuoteAdded,
+    QuoteUpdated,
+    QuoteRemoved,
+    EvidenceAdded,
+    EvidenceUpdated,
+    EvidenceRenamed,
+    EvidenceDeleted,
+    EvidenceQuarantined,
+    CustomFieldUpdated,
+    TaskAdded,
+    TaskUpdated,
+    TaskRemoved,
+    ChecklistAdded,
+    ChecklistAnswered,
+    ChecklistUpdated,
+    ChecklistRemoved,
+    Imported,
+    Exported,
+    LegalHoldPlaced,
+    LegalHoldReleased,
+}
+
+impl AuditAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AuditAction::PersonAdded => "Person added",
+            AuditAction::PersonUpdated => "Person updated",
+            AuditAction::PersonDeleted => "Person deleted",
+            AuditAction::InformationAdded => "Information added",
+            AuditAction::InformationUpdated => "Information updated",
+            AuditAction::InformationRemoved => "Information removed",
+            AuditAction::QuoteAdded => "Quote added",
+            AuditAction::QuoteUpdated => "Quote updated",
+            AuditAction::QuoteRemoved => "Quote removed",
+            AuditAction::EvidenceAdded => "Evidence added",
+            AuditAction::EvidenceUpdated => "Evidence updated",
+            AuditAction::EvidenceRenamed => "Evidence renamed",
+            AuditAction::EvidenceDeleted => "Evidence deleted",
+            AuditAction::EvidenceQuarantined => "Evidence quarantined",
+            AuditAction::CustomFieldUpdated => "Custom field updated",
+            AuditAction::TaskAdded => "Task added",
+            AuditAction::TaskUpdated => "Task updated",
+            AuditAction::TaskRemoved => "Task removed",
+            AuditAction::ChecklistAdded => "Checklist added",
+            AuditAction::ChecklistAnswered => "Checklist answered",
+            AuditAction::ChecklistUpdated => "Checklist updated",
+            AuditAction::ChecklistRemoved => "Checklist removed",
+            AuditAction::Imported => "Imported",
+            AuditAction::Exported => "Exported",
+            AuditAction::LegalHoldPlaced => "Legal hold placed",
+            AuditAction::LegalHoldReleased => "Legal hold released",
+        }
+    }
+
+    /// Whether the action alters or removes something already recorded, which a legal hold forbids
+    pub fn changes_existing(&self) -> bool {
+        matches!(
+            self,
+            AuditAction::PersonDeleted
+                | AuditAction::InformationUpdated
+                | AuditAction::InformationRemoved
+                | AuditAction::QuoteUpdated
+                | AuditAction::QuoteRemoved
+                | AuditAction::EvidenceRenamed
+                | AuditAction::EvidenceDeleted
+                | AuditAction::CustomFieldUpdated
+                | AuditAction::TaskUpdated
+                | AuditAction::TaskRemoved
+                | AuditAction::ChecklistUpdated
+                | AuditAction::ChecklistRemoved
+        )
+    }
+
+    /// The event name sent to the webhook, for the actions that are sent at all
+    pub fn webhook_event(&self) -> Option<&'static str> {
+        match self {
+            AuditAction::PersonAdded => Some("person.added"),
+            AuditAction::EvidenceAdded => Some("evidence.added"),
+            AuditAction::Exported => Some("export.completed"),
+            _ => None,
+        }
+    }
+}
+
+/// One line of the audit log; each entry's hash covers the previous one so edits to history are detectable
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub person_id: Option<Uuid>,
+    pub person_name: String,
+    pub action: AuditAction,
+    pub details: String,
+    #[serde(default)]
+    pub operator: String, // Who was using the app, from Settings
+    pub previous_hash: String,
+    pub hash: String,
+}
+
+impl AuditEntry {
+    fn compute_hash(&self) -> String {
+        let fields = (
+            &self.previous_hash,
+            self.timestamp,
+            self.person_id,
+            &self.person_name,
+            self.action,
+            &self.details,
+        );
+        // Entries written before operators were recorded keep hashing as they did
+        let content = if self.operator.is_empty() {
+            serde_json::to_string(&fields)
+        } else {
+            serde_json::to_string(&(fields, &self.operator))
+        }
+        .unwrap_or_default();
+        hashing::sha256_hex(content.as_bytes())
+    }
+}
+
+/// Whether each entry, oldest first, still matches its hash and follows on from the entry
+/// before it. An entry fails when it was edited, or when entries before it were removed or reordered.
+pub fn verify_chain(entries: &[AuditEntry]) -> Vec<bool> {
+    let mut previous_hash = "";
+    entries
+        .iter()
+        .map(|entry| {
+            let verified = entry.previous_hash == previous_hash && entry.hash == entry.compute_hash();
+            previous_hash = &entry.hash;
+            verified
+        })
+        .collect()
+}
+
+/// Append-only log of changes, stored as JSON lines next to the evidence
+#[derive(Clone)]
+pub struct AuditLog {
+    path: PathBuf,
+    operator: String,
+    webhook: Option<Webhook>,
+}
+
+impl AuditLog {
+    pub fn new(evidence_dir: &Path) -> Self {
+        Self {
+            path: evidence_dir.join("audit_log.jsonl"),
+            operator: String::new(),
+            webhook: None,
+        }
+    }
+
+    /// Names the operator recorded on entries from now on
+    pub fn set_operator(&mut self, operator: &str) {
+        self.operator = operator.trim().to_string();
+    }
+
+    /// Where key events are sent from now on, once they are logged
+    pub fn set_webhook(&mut self, webhook: Option<Webhook>) {
+        self.webhook = webhook;
+    }
+
+    pub fn record(&self, person: Option<&Person>, action: AuditAction, details: impl Into<String>) -> Result<()> {
+        let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        let previous_hash = self.load()?
+            .last()
+            .map(|entry| entry.hash.clone())
+            .unwrap_or_default();
+
+        let mut entry = AuditEntry {
+            timestamp: Utc::now(),
+            person_id: person.map(|p| p.id),
+            person_name: person.map(|p| p.name.clone()).unwrap_or_default(),
+            action,
+            details: details.into(),
+            operator: self.operator.clone(),
+            previous_hash,
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+
+        let line = serde_json::to_string(&entry)
+            .context("Failed to serialize audit entry")?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .context("Failed to open audit log")?;
+        writeln!(file, "{}", line)
+            .context("Failed to write audit log")?;
+
+        if let Some(webhook) = &self.webhook
+            && let Some(event) = action.webhook_event()
+        {
+            webhook.send(Payload::from_entry(event, &entry));
+        }
+        Ok(())
+    }
+
+    /// All entries, oldest first
+    pub fn load(&self) -> Result<Vec<AuditEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&self.path)
+            .context("Failed to read audit log")?;
+
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).context("Failed to parse audit log entry"))
+            .collect()
+    }
+}
@@ -0,0 +1,278 @@
+use crate::file_manager::FileManager;
+use crate::models::{EvidenceFile, EvidenceType, Person};
+use crate::watermark::{self, GLYPH_HEIGHT, GLYPH_WIDTH};
+use anyhow::{Result, Context};
+use image::{Rgba, RgbaImage, imageops};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Kept in the person folder, so the board travels with .ema exports
+pub const FILE_NAME: &str = "board.json";
+
+pub const CARD_WIDTH: f32 = 200.0;
+pub const CARD_HEIGHT: f32 = 90.0;
+/// Space kept around the cards
+pub const MARGIN: f32 = 40.0;
+
+// Exported images are drawn at twice the on-screen size so the bitmap font stays readable
+const EXPORT_SCALE: u32 = 2;
+const THUMBNAIL_SIZE: u32 = 60;
+
+/// What a card shows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CardContent {
+    Person(Uuid),
+    Quote(Uuid), // One of the case person's quotes
+    Evidence(Uuid), // One of the case person's evidence files
+    Note, // Only the card's own annotation
+}
+
+/// A card pinned to the board
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Card {
+    pub id: Uuid,
+    pub content: CardContent,
+    pub x: f32, // Top-left corner, in board units
+    pub y: f32,
+    #[serde(default)]
+    pub note: String, // Annotation written on the card
+}
+
+impl Card {
+    pub fn center(&self) -> (f32, f32) {
+        (self.x + CARD_WIDTH / 2.0, self.y + CARD_HEIGHT / 2.0)
+    }
+
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + CARD_WIDTH && y >= self.y && y <= self.y + CARD_HEIGHT
+    }
+}
+
+/// A labeled string between two cards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Connection {
+    pub id: Uuid,
+    pub from: Uuid,
+    pub to: Uuid,
+    #[serde(default)]
+    pub label: String,
+}
+
+/// A freeform corkboard for one case; later cards are drawn on top
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Board {
+    #[serde(default)]
+    pub cards: Vec<Card>,
+    #[serde(default)]
+    pub connections: Vec<Connection>,
+}
+
+impl Board {
+    /// An empty board when none has been saved yet
+    pub fn load(person_folder: &Path) -> Result<Self> {
+        let path = person_folder.join(FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json = fs::read_to_string(&path)
+            .context("Failed to read board")?;
+        serde_json::from_str(&json)
+            .context("Failed to parse board")
+    }
+
+    pub fn save(&self, person_folder: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .context("Failed to serialize board")?;
+        fs::write(person_folder.join(FILE_NAME), json)
+            .context("Failed to write board")
+    }
+
+    pub fn card(&self, id: Uuid) -> Option<&Card> {
+        self.cards.iter().find(|card| card.id == id)
+    }
+
+    pub fn card_mut(&mut self, id: Uuid) -> Option<&mut Card> {
+        self.cards.iter_mut().find(|card| card.id == id)
+    }
+
+    /// The topmost card under the point
+    pub fn card_at(&self, x: f32, y: f32) -> Option<&Card> {
+        self.cards.iter().rev().find(|card| card.contains(x, y))
+    }
+
+    pub fn contains(&self, content: &CardContent) -> bool {
+        self.cards.iter().any(|card| card.content == *content)
+    }
+
+    /// Pins a new card in the next free spot of a grid, so new cards don't land on each other
+    pub fn add_card(&mut self, content: CardContent) -> Uuid {
+        const COLUMNS: usize = 4;
+        let slot = (0..)
+            .map(|index| (
+                MARGIN + (index % COLUMNS) as f32 * (CARD_WIDTH + MARGIN),
+                MARGIN + (index / COLUMNS) as f32 * (CARD_HEIGHT + MARGIN),
+            ))
+            .find(|(x, y)| !self.cards.iter().any(|card| (card.x - x).abs() < CARD_WIDTH && (card.y - y).abs() < CARD_HEIGHT))
+            .unwrap_or((MARGIN, MARGIN));
+        let id = Uuid::new_v4();
+        self.cards.push(Card { id, content, x: slot.0, y: slot.1, note: String::new() });
+        id
+    }
+
+    /// Moves a card and brings it to the front
+    pub fn move_card(&mut self, id: Uuid, x: f32, y: f32) {
+        if let Some(index) = self.cards.iter().position(|card| card.id == id) {
+            let mut card = self.cards.remove(index);
+            card.x = x.max(0.0);
+            card.y = y.max(0.0);
+            self.cards.push(card);
+        }
+    }
+
+    /// Removes a card and the strings attached to it
+    pub fn remove_card(&mut self, id: Uuid) {
+        self.cards.retain(|card| card.id != id);
+        self.connections.retain(|connection| connection.from != id && connection.to != id);
+    }
+
+    /// Ties a string between two cards; false if they already are
+    pub fn connect(&mut self, from: Uuid, to: Uuid) -> bool {
+        let connected = self.connections.iter().any(|c| (c.from == from && c.to == to) || (c.from == to && c.to == from));
+        if from == to || connected {
+            return false;
+        }
+        self.connections.push(Connection { id: Uuid::new_v4(), from, to, label: String::new() });
+        true
+    }
+
+    /// The area the cards take up, margin included
+    pub fn size(&self) -> (f32, f32) {
+        self.cards.iter().fold((0.0, 0.0), |(width, height), card| {
+            (width.max(card.x + CARD_WIDTH + MARGIN), height.max(card.y + CARD_HEIGHT + MARGIN))
+        })
+    }
+}
+
+/// The title and second line of a card, looked up in the case
+pub fn card_text(card: &Card, case: &Person, persons: &[Person], evidence_files: &[EvidenceFile]) -> (String, String) {
+    match &card.content {
+        CardContent::Person(id) => match persons.iter().find(|p| p.id == *id) {
+            Some(person) => (person.name.clone(), person.case_status.label().to_string()),
+            None => ("(deleted person)".to_string(), String::new()),
+        },
+        CardContent::Quote(id) => match case.quotes.iter().find(|q| q.id == *id) {
+            Some(quote) => (format!("\"{}\"", quote.quote), quote.date.clone()),
+            None => ("(deleted quote)".to_string(), String::new()),
+        },
+        CardContent::Evidence(id) => match evidence_files.iter().find(|f| f.id == *id) {
+            Some(file) => (file.original_name.clone(), file.file_type.folder_name().to_string()),
+            None => ("(deleted evidence)".to_string(), String::new()),
+        },
+        CardContent::Note => ("Note".to_string(), String::new()),
+    }
+}
+
+/// Shortens text to a number of characters, ending in "..." when cut
+pub fn truncate(text: &str, max_chars: usize) -> String {
+    let text = text.lines().next().unwrap_or_default();
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+    format!("{}...", kept)
+}
+
+/// Draws the board as a picture, with photos shown as thumbnails on their cards
+pub fn render_image(board: &Board, case: &Person, persons: &[Person], evidence_files: &[EvidenceFile], file_manager: &FileManager) -> RgbaImage {
+    let (width, height) = board.size();
+    let scale = |value: f32| (value.max(0.0) as u32) * EXPORT_SCALE;
+    let mut canvas = RgbaImage::from_pixel(scale(width.max(MARGIN)), scale(height.max(MARGIN)), Rgba([196, 154, 108, 255]));
+
+    for connection in &board.connections {
+        let (Some(from), Some(to)) = (board.card(connection.from), board.card(connection.to)) else {
+            continue;
+        };
+        let ((x1, y1), (x2, y2)) = (from.center(), to.center());
+        draw_line(&mut canvas, (x1 * EXPORT_SCALE as f32, y1 * EXPORT_SCALE as f32), (x2 * EXPORT_SCALE as f32, y2 * EXPORT_SCALE as f32), Rgba([190, 30, 30, 255]));
+    }
+
+    for card in &board.cards {
+        let (x, y) = (scale(card.x), scale(card.y));
+        let (card_width, card_height) = (scale(CARD_WIDTH), scale(CARD_HEIGHT));
+        fill_rect(&mut canvas, x + 4, y + 4, card_width, card_height, Rgba([90, 60, 30, 255]));
+        fill_rect(&mut canvas, x, y, card_width, card_height, card_color(&card.content));
+
+        let mut text_x = x + 8;
+        if let CardContent::Evidence(id) = &card.content
+            && let Some(file) = evidence_files.iter().find(|f| f.id == *id && f.file_type == EvidenceType::Image)
+            && let Ok(content) = file_manager.read_evidence(&file.file_path)
+            && let Ok(picture) = image::load_from_memory(&content)
+        {
+            let size = THUMBNAIL_SIZE * EXPORT_SCALE;
+            let thumbnail = picture.thumbnail(size, size).to_rgba8();
+            imageops::overlay(&mut canvas, &thumbnail, (x + 8) as i64, (y + 8) as i64);
+            text_x += size + 8;
+        }
+
+        let (title, detail) = card_text(card, case, persons, evidence_files);
+        let glyph_width = (GLYPH_WIDTH + 1) * EXPORT_SCALE;
+        let max_chars = ((x + card_width).saturating_sub(text_x + 8) / glyph_width) as usize;
+        let line_height = (GLYPH_HEIGHT + 5) * EXPORT_SCALE;
+        let lines = [
+            (truncate(&title, max_chars), Rgba([20, 20, 20, 255])),
+            (truncate(&detail, max_chars), Rgba([90, 90, 90, 255])),
+            (truncate(&card.note, max_chars), Rgba([40, 40, 140, 255])),
+        ];
+        for (index, (line, color)) in lines.iter().enumerate() {
+            watermark::draw_text(&mut canvas, line, text_x, y + 10 + index as u32 * line_height, EXPORT_SCALE, *color);
+        }
+    }
+
+    // Labels go on last so cards don't hide them
+    for connection in board.connections.iter().filter(|c| !c.label.trim().is_empty()) {
+        let (Some(from), Some(to)) = (board.card(connection.from), board.card(connection.to)) else {
+            continue;
+        };
+        let ((x1, y1), (x2, y2)) = (from.center(), to.center());
+        let label = truncate(connection.label.trim(), 40);
+        let text_width = label.chars().count() as u32 * (GLYPH_WIDTH + 1) * EXPORT_SCALE;
+        let center_x = scale((x1 + x2) / 2.0);
+        let center_y = scale((y1 + y2) / 2.0);
+        let left = center_x.saturating_sub(text_width / 2 + 4);
+        let top = center_y.saturating_sub(GLYPH_HEIGHT * EXPORT_SCALE / 2 + 4);
+        fill_rect(&mut canvas, left, top, text_width + 8, GLYPH_HEIGHT * EXPORT_SCALE + 8, Rgba([255, 255, 255, 255]));
+        watermark::draw_text(&mut canvas, &label, left + 4, top + 4, EXPORT_SCALE, Rgba([190, 30, 30, 255]));
+    }
+
+    canvas
+}
+
+pub fn card_color(content: &CardContent) -> Rgba<u8> {
+    match content {
+        CardContent::Person(_) => Rgba([214, 228, 250, 255]),
+        CardContent::Quote(_) => Rgba([255, 244, 160, 255]),
+        CardContent::Evidence(_) => Rgba([218, 240, 220, 255]),
+        CardContent::Note => Rgba([250, 250, 250, 255]),
+    }
+}
+
+fn fill_rect(canvas: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, color: Rgba<u8>) {
+    let (canvas_width, canvas_height) = canvas.dimensions();
+    for py in y..(y + height).min(canvas_height) {
+        for px in x..(x + width).min(canvas_width) {
+            canvas.put_pixel(px, py, color);
+        }
+    }
+}
+
+fn draw_line(canvas: &mut RgbaImage, from: (f32, f32), to: (f32, f32), color: Rgba<u8>) {
+    let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).ceil().max(1.0) as u32;
+    for step in 0..=steps {
+        let t = step as f32 / steps as f32;
+        let x = from.0 + (to.0 - from.0) * t;
+        let y = from.1 + (to.1 - from.1) * t;
+        fill_rect(canvas, x.max(0.0) as u32, y.max(0.0) as u32, EXPORT_SCALE + 1, EXPORT_SCALE + 1, color);
+    }
+}
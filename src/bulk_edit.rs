@@ -0,0 +1,94 @@
+use crate::models::Person;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkEditField {
+    Type,
+    Value,
+}
+
+impl BulkEditField {
+    pub fn all() -> [BulkEditField; 2] {
+        [BulkEditField::Type, BulkEditField::Value]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BulkEditField::Type => "Info type",
+            BulkEditField::Value => "Value",
+        }
+    }
+}
+
+impl std::fmt::Display for BulkEditField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// A find-and-replace over the information entries of every person
+#[derive(Debug, Clone)]
+pub struct BulkEdit {
+    pub field: BulkEditField,
+    pub find: String,
+    pub replace: String,
+    pub whole_value: bool, // Match the entire field ignoring case, e.g. to re-type "ph." as "Phone"
+}
+
+/// One information entry the edit would change
+#[derive(Debug, Clone)]
+pub struct BulkChange {
+    pub person_id: Uuid,
+    pub info_id: Uuid,
+    pub before: String,
+    pub after: String,
+}
+
+impl BulkEdit {
+    pub fn preview(&self, persons: &[Person]) -> Vec<BulkChange> {
+        if self.find.is_empty() {
+            return Vec::new();
+        }
+
+        let mut changes = Vec::new();
+        // People under legal hold are left out of bulk changes
+        for person in persons.iter().filter(|p| !p.legal_hold) {
+            for info in &person.information {
+                let before = match self.field {
+                    BulkEditField::Type => &info.info_type,
+                    BulkEditField::Value => &info.value,
+                };
+                if let Some(after) = self.replaced(before) && after != *before {
+                    changes.push(BulkChange {
+                        person_id: person.id,
+                        info_id: info.id,
+                        before: before.clone(),
+                        after,
+                    });
+                }
+            }
+        }
+        changes
+    }
+
+    fn replaced(&self, text: &str) -> Option<String> {
+        if self.whole_value {
+            text.trim().eq_ignore_ascii_case(self.find.trim()).then(|| self.replace.trim().to_string())
+        } else {
+            text.contains(&self.find).then(|| text.replace(&self.find, &self.replace))
+        }
+    }
+
+    /// Applies the previewed changes that belong to `person`
+    pub fn apply(&self, person: &mut Person, changes: &[BulkChange]) {
+        for change in changes.iter().filter(|c| c.person_id == person.id) {
+            if let Some(info) = person.information.iter_mut().find(|i| i.id == change.info_id) {
+                match self.field {
+                    BulkEditField::Type => info.info_type = change.after.clone(),
+                    BulkEditField::Value => info.value = change.after.clone(),
+                }
+            }
+        }
+        person.update_timestamp();
+    }
+}
@@ -0,0 +1,397 @@
+use crate::dates::UtcOffset;
+use anyhow::{Result, Context};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChatFormat {
+    WhatsApp,
+    Telegram,
+    Discord,
+}
+
+impl ChatFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChatFormat::WhatsApp => "WhatsApp",
+            ChatFormat::Telegram => "Telegram",
+            ChatFormat::Discord => "Discord",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatMessage {
+    pub sender: String,
+    pub date: String, // YYYY-MM-DD
+    pub time: Option<String>, // HH:MM
+    pub utc_offset: Option<UtcOffset>, // Given by the export itself (Discord); others are in the exporting phone's zone
+    pub text: String,
+}
+
+/// A parsed chat export, ready for the user to pick which messages become quotes
+#[derive(Debug, Clone)]
+pub struct ChatExport {
+    pub path: PathBuf,
+    pub format: ChatFormat,
+    pub messages: Vec<ChatMessage>,
+}
+
+impl ChatExport {
+    pub fn senders(&self) -> Vec<String> {
+        let mut senders: Vec<String> = Vec::new();
+        for message in &self.messages {
+            if !senders.contains(&message.sender) {
+                senders.push(message.sender.clone());
+            }
+        }
+        senders
+    }
+}
+
+pub fn parse_chat_export(path: &Path) -> Result<ChatExport> {
+    let content = fs::read_to_string(path)
+        .context("Failed to read chat export")?;
+
+    let is_json = path.extension()
+        .map(|e| e.eq_ignore_ascii_case("json"))
+        .unwrap_or(false);
+
+    let (format, messages) = if is_json {
+        let json: Value = serde_json::from_str(&content)
+            .context("Failed to parse chat export JSON")?;
+
+        // Discord exports (DiscordChatExporter) carry a guild/channel header, Telegram exports don't
+        if json.get("guild").is_some() || json.get("channel").is_some() {
+            (ChatFormat::Discord, parse_discord(&json)?)
+        } else {
+            (ChatFormat::Telegram, parse_telegram(&json)?)
+        }
+    } else {
+        (ChatFormat::WhatsApp, parse_whatsapp(&content))
+    };
+
+    if messages.is_empty() {
+        return Err(anyhow::anyhow!("No messages found in chat export"));
+    }
+
+    Ok(ChatExport {
+        path: path.to_path_buf(),
+        format,
+        messages,
+    })
+}
+
+fn parse_telegram(json: &Value) -> Result<Vec<ChatMessage>> {
+    let entries = json.get("messages")
+        .and_then(Value::as_array)
+        .context("Telegram export has no messages")?;
+
+    let mut messages = Vec::new();
+    for entry in entries {
+        if entry.get("type").and_then(Value::as_str) != Some("message") {
+            continue;
+        }
+
+        let text = match entry.get("text") {
+            Some(Value::String(text)) => text.clone(),
+            // Formatted messages are split into plain strings and entity objects
+            Some(Value::Array(parts)) => parts
+                .iter()
+                .map(|part| match part {
+                    Value::String(text) => text.as_str(),
+                    other => other.get("text").and_then(Value::as_str).unwrap_or(""),
+                })
+                .collect(),
+            _ => String::new(),
+        };
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        let Some(timestamp) = entry.get("date").and_then(Value::as_str)
+            .and_then(|d| NaiveDateTime::parse_from_str(d, "%Y-%m-%dT%H:%M:%S").ok())
+        else {
+            continue;
+        };
+
+        messages.push(ChatMessage {
+            sender: entry.get("from").and_then(Value::as_str).unwrap_or("Unknown").to_string(),
+            date: timestamp.format("%Y-%m-%d").to_string(),
+            time: Some(timestamp.format("%H:%M").to_string()),
+            utc_offset: None,
+            text,
+        });
+    }
+
+    Ok(messages)
+}
+
+fn parse_discord(json: &Value) -> Result<Vec<ChatMessage>> {
+    let entries = json.get("messages")
+        .and_then(Value::as_array)
+        .context("Discord export has no messages")?;
+
+    let mut messages = Vec::new();
+    for entry in entries {
+        let text = entry.get("content").and_then(Value::as_str).unwrap_or("");
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        let Some(timestamp) = entry.get("timestamp").and_then(Value::as_str)
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        else {
+            continue;
+        };
+
+        let author = entry.get("author");
+        let sender = author
+            .and_then(|a| a.get("nickname").or_else(|| a.get("name")))
+            .and_then(Value::as_str)
+            .unwrap_or("Unknown");
+
+        messages.push(ChatMessage {
+            sender: sender.to_string(),
+            date: timestamp.format("%Y-%m-%d").to_string(),
+            time: Some(timestamp.format("%H:%M").to_string()),
+            utc_offset: Some(UtcOffset(timestamp.offset().local_minus_utc() / 60)),
+            text: text.to_string(),
+        });
+    }
+
+    Ok(messages)
+}
+
+/// Date components in the order they appear, before day/month order is known
+type RawDate = (u32, u32, i32);
+
+/// A WhatsApp line header before the date order has been worked out
+struct RawWhatsAppMessage {
+    date_parts: RawDate,
+    time: Option<String>,
+    sender: String,
+    text: String,
+}
+
+/// Parses both Android (`12/31/23, 9:41 PM - Name: text`) and iOS (`[31.12.23, 21:41:05] Name: text`) exports
+fn parse_whatsapp(content: &str) -> Vec<ChatMessage> {
+    let mut raw_messages: Vec<RawWhatsAppMessage> = Vec::new();
+    let mut in_system_message = false;
+
+    for line in content.lines() {
+        let line = line.trim_start_matches('\u{feff}').trim_start_matches('\u{200e}');
+
+        match parse_whatsapp_header(line) {
+            Some((date_parts, time, rest)) => {
+                // Lines without "Name: " are system notices (encryption, joins, ...)
+                match rest.split_once(": ") {
+                    Some((sender, text)) => {
+                        raw_messages.push(RawWhatsAppMessage {
+                            date_parts,
+                            time,
+                            sender: sender.trim().to_string(),
+                            text: text.to_string(),
+                        });
+                        in_system_message = false;
+                    }
+                    None => in_system_message = true,
+                }
+            }
+            None => {
+                // Continuation of a multi-line message
+                if !in_system_message && let Some(last) = raw_messages.last_mut() {
+                    last.text.push('\n');
+                    last.text.push_str(line);
+                }
+            }
+        }
+    }
+
+    // Exports don't say whether dates are day- or month-first, so infer it from values above 12
+    let day_first = raw_messages.iter().any(|m| m.date_parts.0 > 12)
+        || !raw_messages.iter().any(|m| m.date_parts.1 > 12);
+
+    raw_messages
+        .into_iter()
+        .filter_map(|m| {
+            let (a, b, year) = m.date_parts;
+            let (day, month) = if day_first { (a, b) } else { (b, a) };
+            let date = NaiveDate::from_ymd_opt(year, month, day)?;
+            Some(ChatMessage {
+                sender: m.sender,
+                date: date.format("%Y-%m-%d").to_string(),
+                time: m.time,
+                utc_offset: None,
+                text: m.text,
+            })
+        })
+        .collect()
+}
+
+fn parse_whatsapp_header(line: &str) -> Option<(RawDate, Option<String>, &str)> {
+    let (header, rest) = if let Some(stripped) = line.strip_prefix('[') {
+        let (header, rest) = stripped.split_once(']')?;
+        (header, rest.trim_start())
+    } else {
+        let (header, rest) = line.split_once(" - ")?;
+        (header, rest)
+    };
+
+    let (date, time) = header.split_once(", ")?;
+    let parts: Vec<&str> = date.split(['/', '.', '-']).collect();
+    if parts.len() != 3 {
+        return None;
+    }
+
+    let a: u32 = parts[0].trim().parse().ok()?;
+    let b: u32 = parts[1].trim().parse().ok()?;
+    let mut year: i32 = parts[2].trim().parse().ok()?;
+    if year < 100 {
+        year += 2000;
+    }
+
+    Some(((a, b, year), parse_whatsapp_time(time), rest))
+}
+
+fn parse_whatsapp_time(time: &str) -> Option<String> {
+    // Newer exports put a narrow no-break space before AM/PM
+    let time = time.replace(['\u{202f}', '\u{a0}'], " ");
+    let time = time.trim();
+
+    ["%H:%M:%S", "%H:%M", "%I:%M:%S %p", "%I:%M %p"]
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(time, format).ok())
+        .map(|t| t.format("%H:%M").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn summary(messages: &[ChatMessage]) -> Vec<(&str, &str, Option<&str>, &str)> {
+        messages
+            .iter()
+            .map(|m| (m.sender.as_str(), m.date.as_str(), m.time.as_deref(), m.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn whatsapp_android_export() {
+        let content = "\u{feff}12/31/23, 9:41\u{202f}PM - Messages and calls are end-to-end encrypted. No one outside of this chat can read them.\n\
+            12/31/23, 9:41\u{202f}PM - Alice: Happy new year: see you there\n\
+            1/1/24, 12:05\u{202f}AM - Bob: Running late\n\
+            still at the station\n\
+            1/1/24, 12:07\u{202f}AM - Alice changed the group description\n\
+            this line belongs to the notice\n\
+            1/1/24, 12:09\u{202f}AM - Bob: <Media omitted>\n";
+        let messages = parse_whatsapp(content);
+        assert_eq!(summary(&messages), [
+            ("Alice", "2023-12-31", Some("21:41"), "Happy new year: see you there"),
+            ("Bob", "2024-01-01", Some("00:05"), "Running late\nstill at the station"),
+            ("Bob", "2024-01-01", Some("00:09"), "<Media omitted>"),
+        ]);
+        assert!(messages.iter().all(|m| m.utc_offset.is_none()));
+    }
+
+    #[test]
+    fn whatsapp_ios_export() {
+        let content = "[31.12.23, 21:41:05] Alice: Happy new year\n\
+            \u{200e}[31.12.23, 21:42:10] Bob: \u{200e}image omitted\n\
+            [01.01.24, 00:05:59] Alice: Home now\n";
+        assert_eq!(summary(&parse_whatsapp(content)), [
+            ("Alice", "2023-12-31", Some("21:41"), "Happy new year"),
+            ("Bob", "2023-12-31", Some("21:42"), "\u{200e}image omitted"),
+            ("Alice", "2024-01-01", Some("00:05"), "Home now"),
+        ]);
+    }
+
+    #[test]
+    fn whatsapp_date_order_is_inferred() {
+        // Nothing above 12 in either place reads day-first
+        let ambiguous = "03/04/24, 10:00 - Alice: Hello\n";
+        assert_eq!(parse_whatsapp(ambiguous)[0].date, "2024-04-03");
+        let month_first = "03/04/24, 10:00 - Alice: Hello\n03/14/24, 10:00 - Alice: Later\n";
+        assert_eq!(parse_whatsapp(month_first)[0].date, "2024-03-04");
+        let day_first = "03/04/2024, 10:00 - Alice: Hello\n14/04/2024, 10:00 - Alice: Later\n";
+        assert_eq!(parse_whatsapp(day_first)[0].date, "2024-04-03");
+    }
+
+    #[test]
+    fn telegram_export() {
+        let json = json!({
+            "name": "Bob",
+            "type": "personal_chat",
+            "id": 4815162342u64,
+            "messages": [
+                { "id": 1, "type": "service", "date": "2024-03-01T09:58:00", "actor": "Alice", "action": "phone_call" },
+                { "id": 2, "type": "message", "date": "2024-03-01T10:15:30", "from": "Alice", "text": "Call me back" },
+                {
+                    "id": 3, "type": "message", "date": "2024-03-01T10:16:02", "from": "Bob",
+                    "text": ["Details are at ", { "type": "link", "text": "https://example.com/meet" }, ", ", { "type": "bold", "text": "tonight" }]
+                },
+                { "id": 4, "type": "message", "date": "2024-03-01T10:17:00", "from": "Bob", "photo": "photos/photo_1.jpg", "text": "" },
+                { "id": 5, "type": "message", "date": "2024-03-01T10:18:00", "text": "Forwarded" }
+            ]
+        });
+        let messages = parse_telegram(&json).unwrap();
+        assert_eq!(summary(&messages), [
+            ("Alice", "2024-03-01", Some("10:15"), "Call me back"),
+            ("Bob", "2024-03-01", Some("10:16"), "Details are at https://example.com/meet, tonight"),
+            ("Unknown", "2024-03-01", Some("10:18"), "Forwarded"),
+        ]);
+        assert!(messages.iter().all(|m| m.utc_offset.is_none()));
+        assert!(parse_telegram(&json!({ "name": "Bob" })).is_err());
+    }
+
+    #[test]
+    fn discord_export_keeps_its_offset() {
+        let json = json!({
+            "guild": { "id": "1", "name": "Direct Messages" },
+            "channel": { "id": "2", "type": "DirectTextChat", "name": "alice" },
+            "messages": [
+                {
+                    "id": "3", "type": "Default", "timestamp": "2024-03-01T23:45:10.123+02:00",
+                    "content": "Meet at noon", "author": { "id": "4", "name": "alice", "nickname": "Alice" }
+                },
+                { "id": "5", "type": "Default", "timestamp": "2024-03-01T21:46:00+00:00", "content": "", "author": { "name": "bob" } },
+                { "id": "6", "type": "Default", "timestamp": "2024-03-01T16:17:00-05:30", "content": "ok", "author": { "name": "bob" } }
+            ]
+        });
+        let messages = parse_discord(&json).unwrap();
+        // Dates and times as written in the export, in the zone it gives
+        assert_eq!(summary(&messages), [
+            ("Alice", "2024-03-01", Some("23:45"), "Meet at noon"),
+            ("bob", "2024-03-01", Some("16:17"), "ok"),
+        ]);
+        assert_eq!(messages[0].utc_offset, Some(UtcOffset(120)));
+        assert_eq!(messages[1].utc_offset, Some(UtcOffset(-330)));
+    }
+
+    #[test]
+    fn export_format_is_detected() {
+        let dir = std::env::temp_dir().join(format!("evidence-manager-chat-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let discord = dir.join("alice.json");
+        fs::write(&discord, json!({ "channel": {}, "messages": [{ "timestamp": "2024-03-01T10:00:00Z", "content": "hi", "author": { "name": "alice" } }] }).to_string()).unwrap();
+        let telegram = dir.join("result.json");
+        fs::write(&telegram, json!({ "messages": [{ "type": "message", "date": "2024-03-01T10:00:00", "from": "Bob", "text": "hi" }] }).to_string()).unwrap();
+        let whatsapp = dir.join("WhatsApp Chat with Bob.txt");
+        fs::write(&whatsapp, "01/03/2024, 10:00 - Bob: hi\n").unwrap();
+        let empty = dir.join("empty.txt");
+        fs::write(&empty, "01/03/2024, 10:00 - Messages and calls are end-to-end encrypted.\n").unwrap();
+
+        let formats: Vec<_> = [&discord, &telegram, &whatsapp]
+            .into_iter()
+            .map(|path| parse_chat_export(path).map(|export| export.format).ok())
+            .collect();
+        let empty = parse_chat_export(&empty);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(formats, [Some(ChatFormat::Discord), Some(ChatFormat::Telegram), Some(ChatFormat::WhatsApp)]);
+        assert!(empty.is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::{Result, Context, bail};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Places the file itself on the system clipboard so it can be pasted into a mail or chat client.
+/// There's no portable API for this, so each platform's own tool is used.
+pub fn copy_file(path: &Path) -> Result<()> {
+    if cfg!(target_os = "windows") {
+        let literal = path.display().to_string().replace('\'', "''");
+        run(Command::new("powershell")
+            .args(["-NoProfile", "-Command", &format!("Set-Clipboard -LiteralPath '{}'", literal)]))
+    } else if cfg!(target_os = "macos") {
+        run(Command::new("osascript")
+            .args(["-e", "on run argv", "-e", "set the clipboard to (POSIX file (item 1 of argv))", "-e", "end run"])
+            .arg(path))
+    } else {
+        let uri_list = format!("{}\r\n", file_uri(path));
+        pipe(Command::new("wl-copy").args(["--type", "text/uri-list"]), &uri_list)
+            .or_else(|_| pipe(Command::new("xclip").args(["-selection", "clipboard", "-t", "text/uri-list"]), &uri_list))
+            .context("Copying files needs wl-copy (Wayland) or xclip (X11)")
+    }
+}
+
+/// Reads an image, such as a screenshot taken to the clipboard, as PNG bytes
+pub fn read_image() -> Result<Vec<u8>> {
+    let content = if cfg!(target_os = "windows") {
+        let path = std::env::temp_dir().join("evidence_manager_clipboard.png");
+        let literal = path.display().to_string().replace('\'', "''");
+        run(Command::new("powershell").args([
+            "-NoProfile",
+            "-Command",
+            &format!(
+                "Add-Type -AssemblyName System.Windows.Forms; $image = [Windows.Forms.Clipboard]::GetImage(); if ($image -eq $null) {{ exit 1 }}; $image.Save('{}', [Drawing.Imaging.ImageFormat]::Png)",
+                literal
+            ),
+        ]))
+        .context("The clipboard doesn't contain an image")?;
+        let content = std::fs::read(&path).context("Failed to read clipboard image")?;
+        let _ = std::fs::remove_file(&path);
+        content
+    } else if cfg!(target_os = "macos") {
+        let path = std::env::temp_dir().join("evidence_manager_clipboard.png");
+        run(Command::new("osascript")
+            .args([
+                "-e", "on run argv",
+                "-e", "set png to the clipboard as «class PNGf»",
+                "-e", "set f to open for access (POSIX file (item 1 of argv)) with write permission",
+                "-e", "set eof f to 0",
+                "-e", "write png to f",
+                "-e", "close access f",
+                "-e", "end run",
+            ])
+            .arg(&path))
+        .context("The clipboard doesn't contain an image")?;
+        let content = std::fs::read(&path).context("Failed to read clipboard image")?;
+        let _ = std::fs::remove_file(&path);
+        content
+    } else {
+        output(Command::new("wl-paste").args(["--no-newline", "--type", "image/png"]))
+            .or_else(|_| output(Command::new("xclip").args(["-selection", "clipboard", "-t", "image/png", "-o"])))
+            .context("Pasting images needs wl-paste (Wayland) or xclip (X11), and an image on the clipboard")?
+    };
+
+    if !content.starts_with(b"\x89PNG") {
+        bail!("The clipboard doesn't contain an image");
+    }
+    Ok(content)
+}
+
+fn output(command: &mut Command) -> Result<Vec<u8>> {
+    let output = command.output()
+        .context("Failed to run clipboard command")?;
+    if !output.status.success() {
+        bail!("Clipboard command exited with {}", output.status);
+    }
+    Ok(output.stdout)
+}
+
+fn run(command: &mut Command) -> Result<()> {
+    let status = command.status()
+        .context("Failed to run clipboard command")?;
+    if !status.success() {
+        bail!("Clipboard command exited with {}", status);
+    }
+    Ok(())
+}
+
+fn pipe(command: &mut Command, input: &str) -> Result<()> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("Failed to run clipboard command")?;
+    // Dropping stdin closes it so the tool knows the input is complete
+    child.stdin.take()
+        .context("Failed to open clipboard command input")?
+        .write_all(input.as_bytes())
+        .context("Failed to write to clipboard command")?;
+
+    // Both tools fork to keep serving the clipboard, so this returns once the input is taken
+    let status = child.wait()
+        .context("Failed to run clipboard command")?;
+    if !status.success() {
+        bail!("Clipboard command exited with {}", status);
+    }
+    Ok(())
+}
+
+fn file_uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    for byte in path.display().to_string().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => uri.push(byte as char),
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
@@ -0,0 +1,64 @@
+use crate::models::{EvidenceFile, EvidenceType, Person};
+use crate::settings::InfoTemplate;
+
+/// One thing a complete person record should have
+#[derive(Debug, Clone)]
+pub struct CompletenessCheck {
+    pub label: String,
+    pub done: bool,
+}
+
+/// How much of a person's record is filled in: each information type of the chosen template,
+/// then a photo, any evidence and any quotes
+#[derive(Debug, Clone, Default)]
+pub struct Completeness {
+    pub checks: Vec<CompletenessCheck>,
+}
+
+impl Completeness {
+    /// An information type counts once the person has a non-empty value for it, so the empty
+    /// slots a template pre-creates don't count. Any image evidence counts as a photo.
+    pub fn of(person: &Person, evidence_files: &[EvidenceFile], template: Option<&InfoTemplate>) -> Self {
+        let mut checks: Vec<CompletenessCheck> = template
+            .map(|template| template.info_types.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|info_type| CompletenessCheck {
+                label: info_type.clone(),
+                done: person.information.iter().any(|info| {
+                    info.info_type.trim().eq_ignore_ascii_case(info_type.trim()) && !info.value.trim().is_empty()
+                }),
+            })
+            .collect();
+
+        checks.push(CompletenessCheck {
+            label: "Photo".to_string(),
+            done: evidence_files.iter().any(|file| file.file_type == EvidenceType::Image),
+        });
+        checks.push(CompletenessCheck {
+            label: "Evidence".to_string(),
+            done: !evidence_files.is_empty(),
+        });
+        checks.push(CompletenessCheck {
+            label: "Quotes".to_string(),
+            done: !person.quotes.is_empty(),
+        });
+        Self { checks }
+    }
+
+    pub fn done(&self) -> usize {
+        self.checks.iter().filter(|check| check.done).count()
+    }
+
+    /// From 0.0 with nothing filled in to 1.0 when everything is
+    pub fn fraction(&self) -> f32 {
+        if self.checks.is_empty() {
+            return 1.0;
+        }
+        self.done() as f32 / self.checks.len() as f32
+    }
+
+    pub fn missing(&self) -> impl Iterator<Item = &CompletenessCheck> {
+        self.checks.iter().filter(|check| !check.done)
+    }
+}
@@ -0,0 +1,238 @@
+use crate::audit::{AuditAction, AuditLog};
+use crate::file_manager::FileManager;
+use crate::hashing;
+use crate::models::Person;
+use crate::profiles;
+use crate::settings::{Settings, StorageLayout};
+use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+// Each piece of content is kept once under its SHA-256, and every evidence entry holding it is a
+// hard link to that copy. The references say which entries those are, so the stored copy is
+// removed along with the last of them.
+const CONTENT_DIR: &str = ".blobs";
+const REFERENCES_FILE: &str = "references.json";
+
+/// An evidence entry holding stored content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reference {
+    pub person_id: Uuid,
+    pub file_id: Uuid,
+}
+
+pub struct ContentStore {
+    dir: PathBuf,
+}
+
+impl ContentStore {
+    pub fn new(evidence_dir: &Path) -> Self {
+        Self { dir: evidence_dir.join(CONTENT_DIR) }
+    }
+
+    /// Hard-links the content with this hash to `target`. False when it isn't stored yet or
+    /// can't be linked (e.g. on a drive without hard links), and it has to be copied instead.
+    pub fn link(&self, sha256: &str, target: &Path) -> bool {
+        self.content_path(sha256)
+            .is_some_and(|stored| stored.is_file() && fs::hard_link(stored, target).is_ok())
+    }
+
+    /// Swaps the file at `path` for a link to the stored content with this hash, freeing the
+    /// space of its own copy. False when the content isn't stored yet.
+    pub fn replace_with_link(&self, sha256: &str, path: &Path) -> Result<bool> {
+        let Some(stored) = self.content_path(sha256).filter(|stored| stored.is_file()) else {
+            return Ok(false);
+        };
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".link");
+        let temporary = PathBuf::from(temporary);
+        fs::hard_link(&stored, &temporary)
+            .with_context(|| format!("Failed to link {}", path.display()))?;
+        if let Err(e) = fs::rename(&temporary, path) {
+            let _ = fs::remove_file(&temporary);
+            return Err(e).with_context(|| format!("Failed to replace {}", path.display()));
+        }
+        Ok(true)
+    }
+
+    /// Records the entry at `path` as holding the content with this hash, storing the content
+    /// first if it's new. Content that can't be linked into the store stays unshared, and false
+    /// is returned.
+    pub fn add(&self, sha256: &str, path: &Path, reference: Reference) -> Result<bool> {
+        let Some(stored) = self.content_path(sha256) else {
+            return Ok(false);
+        };
+        if !stored.exists() {
+            fs::create_dir_all(&self.dir)
+                .context("Failed to create content folder")?;
+            if fs::hard_link(path, &stored).is_err() {
+                return Ok(false);
+            }
+        }
+        let mut references = self.references()?;
+        let holders = references.entry(sha256.to_string()).or_default();
+        if !holders.contains(&reference) {
+            holders.push(reference);
+        }
+        self.save_references(&references)?;
+        Ok(true)
+    }
+
+    /// Drops an entry's reference; the stored content is removed with its last one.
+    /// Returns how many entries still hold it.
+    pub fn release(&self, sha256: &str, file_id: Uuid) -> Result<usize> {
+        let mut references = self.references()?;
+        let Some(holders) = references.get_mut(sha256) else {
+            return Ok(0);
+        };
+        holders.retain(|r| r.file_id != file_id);
+        let remaining = holders.len();
+        if remaining == 0 {
+            references.remove(sha256);
+            if let Some(stored) = self.content_path(sha256) {
+                let _ = fs::remove_file(stored);
+            }
+        }
+        self.save_references(&references)?;
+        Ok(remaining)
+    }
+
+    /// The entries holding each stored piece of content, by hash
+    pub fn references(&self) -> Result<BTreeMap<String, Vec<Reference>>> {
+        let path = self.dir.join(REFERENCES_FILE);
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let json = fs::read_to_string(&path)
+            .context("Failed to read content references")?;
+        serde_json::from_str(&json)
+            .context("Failed to parse content references")
+    }
+
+    fn save_references(&self, references: &BTreeMap<String, Vec<Reference>>) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .context("Failed to create content folder")?;
+        let json = serde_json::to_string_pretty(references)
+            .context("Failed to serialize content references")?;
+        fs::write(self.dir.join(REFERENCES_FILE), json)
+            .context("Failed to write content references")
+    }
+
+    // Hashes come from index files, which may have been imported, so only a plain hash names a file
+    fn content_path(&self, sha256: &str) -> Option<PathBuf> {
+        (sha256.len() == 64 && sha256.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')))
+            .then(|| self.dir.join(sha256))
+    }
+}
+
+/// What moving a person's existing evidence into the blob store did
+#[derive(Debug, Clone, Default)]
+pub struct MigrationSummary {
+    pub stored: usize, // Files whose content went into the store
+    pub linked: usize, // Copies of content already stored, replaced by links to it
+    pub freed: u64, // Bytes those copies took
+    pub failed: Vec<String>,
+}
+
+impl MigrationSummary {
+    pub fn describe(&self) -> String {
+        let mut description = format!("{} stored, {} linked to identical content", self.stored, self.linked);
+        if !self.failed.is_empty() {
+            description.push_str(&format!(", {} not moved ({})", self.failed.len(), self.failed.join(", ")));
+        }
+        description
+    }
+}
+
+/// Moves a person's evidence added before the blob store, or under the person folders layout,
+/// into the store. Files whose content is already stored become links to it.
+pub fn migrate(file_manager: &FileManager, person: &Person) -> Result<MigrationSummary> {
+    let store = ContentStore::new(file_manager.get_evidence_dir());
+    let references = store.references()?;
+    let mut index = file_manager.load_evidence_index(person)?;
+    let mut summary = MigrationSummary::default();
+    let mut hashed = false;
+
+    for file in &mut index {
+        let in_store = file.sha256.as_ref()
+            .and_then(|sha256| references.get(sha256))
+            .is_some_and(|holders| holders.iter().any(|r| r.file_id == file.id));
+        if in_store {
+            continue;
+        }
+        if !file.file_path.is_file() {
+            summary.failed.push(format!("{}: file not found", file.original_name));
+            continue;
+        }
+        let sha256 = hashing::sha256_file(&file.file_path)?;
+        if file.sha256.as_ref().is_some_and(|recorded| *recorded != sha256) {
+            summary.failed.push(format!("{}: doesn't match its recorded hash", file.original_name));
+            continue;
+        }
+
+        let linked = store.replace_with_link(&sha256, &file.file_path)?;
+        if !store.add(&sha256, &file.file_path, Reference { person_id: person.id, file_id: file.id })? {
+            summary.failed.push(format!("{}: can't be linked on this drive", file.original_name));
+            continue;
+        }
+        if linked {
+            summary.linked += 1;
+            summary.freed += file.size;
+        } else {
+            summary.stored += 1;
+        }
+        if file.sha256.is_none() {
+            file.sha256 = Some(sha256);
+            hashed = true;
+        }
+    }
+
+    if hashed {
+        file_manager.save_evidence_index(person, &index)?;
+    }
+    Ok(summary)
+}
+
+/// `evidence-manager migrate-blobs`: moves everyone's evidence into the blob store and switches
+/// the storage layout to it. Returns the process exit code.
+pub fn run_migrate() -> i32 {
+    match migrate_all() {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(e) => {
+            eprintln!("migrate-blobs: {:#}", e);
+            2
+        }
+    }
+}
+
+/// Whether every file was moved
+fn migrate_all() -> Result<bool> {
+    let mut settings = Settings::load().context("Failed to load settings")?;
+    profiles::require_admin(settings.admin_passphrase.as_ref())?;
+    let file_manager = FileManager::new()?;
+    let mut audit_log = AuditLog::new(file_manager.get_evidence_dir());
+    audit_log.set_operator(&settings.operator);
+
+    // New evidence goes into the store too, so nothing is left behind once this is done
+    settings.storage_layout = StorageLayout::Blobs;
+    settings.save()?;
+
+    let mut freed = 0;
+    let mut complete = true;
+    for person in file_manager.load_all_persons()? {
+        let summary = migrate(&file_manager, &person)?;
+        let description = summary.describe();
+        println!("{}\t{}", person.name, description);
+        if summary.stored + summary.linked > 0 {
+            audit_log.record(Some(&person), AuditAction::EvidenceUpdated, format!("Evidence moved into the blob store: {}", description))?;
+        }
+        freed += summary.freed;
+        complete &= summary.failed.is_empty();
+    }
+    println!("Freed {} MB", freed / (1024 * 1024));
+    Ok(complete)
+}
@@ -0,0 +1,335 @@
+use crate::models::Quote;
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// How dates are shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DateFormat {
+    #[default]
+    System, // Guessed from the system locale
+    Iso, // 2024-05-31
+    DayMonthYear, // 31/05/2024
+    DayMonthYearDots, // 31.05.2024
+    MonthDayYear, // 05/31/2024
+    Long, // 31 May 2024
+}
+
+impl DateFormat {
+    pub fn all() -> [DateFormat; 6] {
+        [
+            DateFormat::System,
+            DateFormat::Iso,
+            DateFormat::DayMonthYear,
+            DateFormat::DayMonthYearDots,
+            DateFormat::MonthDayYear,
+            DateFormat::Long,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DateFormat::System => "System default",
+            DateFormat::Iso => "2024-05-31",
+            DateFormat::DayMonthYear => "31/05/2024",
+            DateFormat::DayMonthYearDots => "31.05.2024",
+            DateFormat::MonthDayYear => "05/31/2024",
+            DateFormat::Long => "31 May 2024",
+        }
+    }
+
+    fn pattern(&self) -> &'static str {
+        match self {
+            DateFormat::System => system_locale().date.pattern(),
+            DateFormat::Iso => "%Y-%m-%d",
+            DateFormat::DayMonthYear => "%d/%m/%Y",
+            DateFormat::DayMonthYearDots => "%d.%m.%Y",
+            DateFormat::MonthDayYear => "%m/%d/%Y",
+            DateFormat::Long => "%-d %b %Y",
+        }
+    }
+}
+
+impl std::fmt::Display for DateFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// How times of day are shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeFormat {
+    #[default]
+    System,
+    TwentyFourHour, // 14:05
+    TwelveHour, // 2:05 PM
+}
+
+impl TimeFormat {
+    pub fn all() -> [TimeFormat; 3] {
+        [TimeFormat::System, TimeFormat::TwentyFourHour, TimeFormat::TwelveHour]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeFormat::System => "System default",
+            TimeFormat::TwentyFourHour => "24-hour (14:05)",
+            TimeFormat::TwelveHour => "12-hour (2:05 PM)",
+        }
+    }
+
+    fn pattern(&self, seconds: bool) -> &'static str {
+        match (self, seconds) {
+            (TimeFormat::System, _) => system_locale().time.pattern(seconds),
+            (TimeFormat::TwentyFourHour, false) => "%H:%M",
+            (TimeFormat::TwentyFourHour, true) => "%H:%M:%S",
+            (TimeFormat::TwelveHour, false) => "%-I:%M %p",
+            (TimeFormat::TwelveHour, true) => "%-I:%M:%S %p",
+        }
+    }
+}
+
+impl std::fmt::Display for TimeFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+// Offsets in use somewhere in the world, in minutes east of UTC
+const OFFSETS: [i32; 38] = [
+    -720, -660, -600, -570, -540, -480, -420, -360, -300, -240, -210, -180, -120, -60,
+    0, 60, 120, 180, 210, 240, 270, 300, 330, 345, 360, 390, 420, 480, 525, 540, 570,
+    600, 630, 660, 720, 765, 780, 840,
+];
+
+/// A fixed offset from UTC in minutes east, such as the one a quote's date and time were given in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UtcOffset(pub i32);
+
+impl UtcOffset {
+    /// This computer's offset right now, daylight saving included
+    pub fn system() -> Self {
+        Self(Local::now().offset().local_minus_utc() / 60)
+    }
+
+    /// This computer's offset on a date typed as YYYY-MM-DD and a time as HH:MM, so a quote
+    /// from the other half of the year gets that season's daylight saving. Without a time, noon
+    /// is used, clear of the night-time changeover; a date that doesn't parse gets the offset now.
+    pub fn system_on(date: &str, time: Option<&str>) -> Self {
+        let Ok(date) = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d") else {
+            return Self::system();
+        };
+        let time = time
+            .and_then(|time| NaiveTime::parse_from_str(time.trim(), "%H:%M").ok())
+            .or(NaiveTime::from_hms_opt(12, 0, 0));
+        // A time skipped by the clocks going forward has no offset of its own
+        match time.and_then(|time| Local.from_local_datetime(&date.and_time(time)).earliest()) {
+            Some(local) => Self(local.offset().local_minus_utc() / 60),
+            None => Self::system(),
+        }
+    }
+
+    /// The offsets used around the world, for picking one
+    pub fn all() -> Vec<UtcOffset> {
+        OFFSETS.iter().map(|minutes| UtcOffset(*minutes)).collect()
+    }
+
+    pub fn fixed(&self) -> FixedOffset {
+        FixedOffset::east_opt(self.0 * 60).unwrap_or(Utc.fix())
+    }
+}
+
+impl std::fmt::Display for UtcOffset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 == 0 {
+            return write!(f, "UTC");
+        }
+        let sign = if self.0 < 0 { '-' } else { '+' };
+        write!(f, "UTC{}{:02}:{:02}", sign, self.0.abs() / 60, self.0.abs() % 60)
+    }
+}
+
+/// The zone typed quote dates and times are taken to be in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuoteZone {
+    #[default]
+    System, // This computer's, as it was on the quote's date
+    Fixed(UtcOffset),
+}
+
+impl QuoteZone {
+    pub fn all() -> Vec<QuoteZone> {
+        let mut zones = vec![QuoteZone::System];
+        zones.extend(UtcOffset::all().into_iter().map(QuoteZone::Fixed));
+        zones
+    }
+
+    /// The offset to record for a quote given on `date` at `time`
+    pub fn offset_on(&self, date: &str, time: Option<&str>) -> UtcOffset {
+        match self {
+            QuoteZone::System => UtcOffset::system_on(date, time),
+            QuoteZone::Fixed(offset) => *offset,
+        }
+    }
+}
+
+impl std::fmt::Display for QuoteZone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuoteZone::System => write!(f, "This computer's zone"),
+            QuoteZone::Fixed(offset) => write!(f, "{}", offset),
+        }
+    }
+}
+
+/// The zone times are shown in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayZone {
+    #[default]
+    Recorded, // Quotes in the zone they were given in, everything else in this computer's
+    System, // Everything in this computer's zone
+    Fixed(UtcOffset), // Everything at one offset, e.g. where the case is
+}
+
+impl DisplayZone {
+    pub fn all() -> Vec<DisplayZone> {
+        let mut zones = vec![DisplayZone::Recorded, DisplayZone::System];
+        zones.extend(UtcOffset::all().into_iter().map(DisplayZone::Fixed));
+        zones
+    }
+}
+
+impl std::fmt::Display for DisplayZone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DisplayZone::Recorded => write!(f, "As recorded"),
+            DisplayZone::System => write!(f, "This computer ({})", UtcOffset::system()),
+            DisplayZone::Fixed(offset) => write!(f, "{}", offset),
+        }
+    }
+}
+
+/// The chosen date and time formats and zone, for everything shown or printed for people to
+/// read. Files meant for other programs (CSV, JSON, manifests) keep RFC 3339.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DateDisplay {
+    pub date: DateFormat,
+    pub time: TimeFormat,
+    pub zone: DisplayZone,
+}
+
+impl DateDisplay {
+    pub fn date(&self, date: NaiveDate) -> String {
+        date.format(self.date.pattern()).to_string()
+    }
+
+    /// In the display zone, to the minute
+    pub fn date_time(&self, time: DateTime<Utc>) -> String {
+        self.local(self.in_zone(time), false)
+    }
+
+    /// In the display zone, to the second
+    pub fn date_time_seconds(&self, time: DateTime<Utc>) -> String {
+        self.local(self.in_zone(time), true)
+    }
+
+    fn in_zone(&self, time: DateTime<Utc>) -> NaiveDateTime {
+        match self.zone {
+            DisplayZone::Fixed(offset) => time.with_timezone(&offset.fixed()).naive_local(),
+            DisplayZone::Recorded | DisplayZone::System => time.with_timezone(&Local).naive_local(),
+        }
+    }
+
+    pub fn local(&self, time: NaiveDateTime, seconds: bool) -> String {
+        format!("{} {}", self.date(time.date()), self.time(time.time(), seconds))
+    }
+
+    pub fn time(&self, time: NaiveTime, seconds: bool) -> String {
+        time.format(self.time.pattern(seconds)).to_string()
+    }
+
+    pub fn now(&self) -> String {
+        self.date_time(Utc::now())
+    }
+
+    /// A date entered by hand, such as a quote's: YYYY-MM-DD is shown in the chosen format,
+    /// anything else as it was typed
+    pub fn typed_date(&self, typed: &str) -> String {
+        match NaiveDate::parse_from_str(typed.trim(), "%Y-%m-%d") {
+            Ok(date) => self.date(date),
+            Err(_) => typed.to_string(),
+        }
+    }
+
+    /// A time entered by hand as HH:MM, shown in the chosen format; anything else as typed
+    pub fn typed_time(&self, typed: &str) -> String {
+        match NaiveTime::parse_from_str(typed.trim(), "%H:%M") {
+            Ok(time) => self.time(time, false),
+            Err(_) => typed.to_string(),
+        }
+    }
+
+    /// A quote's date, and its time followed by the zone it's shown in. Quotes with a date, a
+    /// time and the zone they were given in are moved into the display zone; others are shown
+    /// as recorded.
+    pub fn quote(&self, quote: &Quote) -> (String, Option<String>) {
+        let Some(said) = quote.instant() else {
+            let time = quote.time.as_deref().map(|time| match quote.utc_offset {
+                Some(offset) => format!("{} {}", self.typed_time(time), offset),
+                None => self.typed_time(time),
+            });
+            return (self.typed_date(&quote.date), time);
+        };
+        let shown = match self.zone {
+            DisplayZone::Recorded => said,
+            DisplayZone::System => said.with_timezone(&Local.offset_from_utc_datetime(&said.naive_utc())),
+            DisplayZone::Fixed(offset) => said.with_timezone(&offset.fixed()),
+        };
+        let offset = UtcOffset(shown.offset().local_minus_utc() / 60);
+        (self.date(shown.date_naive()), Some(format!("{} {}", self.time(shown.time(), false), offset)))
+    }
+
+    /// A quote's date and time on one line
+    pub fn quote_line(&self, quote: &Quote) -> String {
+        match self.quote(quote) {
+            (date, Some(time)) => format!("{} {}", date, time),
+            (date, None) => date,
+        }
+    }
+}
+
+/// Formats used where the setting is "System default": the language and region of LC_ALL,
+/// LC_TIME or LANG, as in "en_US.UTF-8". Without them (e.g. on Windows) dates are ISO and times
+/// 24-hour. Read once, as it's needed for every date drawn.
+fn system_locale() -> &'static SystemFormats {
+    static FORMATS: OnceLock<SystemFormats> = OnceLock::new();
+    FORMATS.get_or_init(read_system_locale)
+}
+
+fn read_system_locale() -> SystemFormats {
+    let locale = ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+        .unwrap_or_default();
+    let locale = locale.split(['.', '@']).next().unwrap_or_default();
+    let (language, region) = locale.split_once(['_', '-']).unwrap_or((locale, ""));
+
+    let date = match (language, region) {
+        (_, "US" | "PH" | "BZ" | "FM") => DateFormat::MonthDayYear,
+        ("de" | "ru" | "pl" | "cs" | "sk" | "fi" | "nb" | "no" | "nn" | "da" | "tr" | "uk" | "ro" | "hr" | "sl" | "sr" | "bg" | "et" | "lv", _) => DateFormat::DayMonthYearDots,
+        ("sv" | "lt" | "hu" | "zh" | "ja" | "ko" | "", _) => DateFormat::Iso,
+        _ => DateFormat::DayMonthYear,
+    };
+    let time = match (language, region) {
+        (_, "US" | "CA" | "AU" | "NZ" | "PH" | "IN" | "PK" | "EG" | "SA") => TimeFormat::TwelveHour,
+        _ => TimeFormat::TwentyFourHour,
+    };
+    SystemFormats { date, time }
+}
+
+struct SystemFormats {
+    date: DateFormat,
+    time: TimeFormat,
+}
@@ -0,0 +1,188 @@
+use crate::audit::{AuditAction, AuditLog};
+use crate::file_manager::FileManager;
+use crate::models::{EvidenceFile, EvidenceType, Person, Provenance};
+use crate::plugins::Plugins;
+use crate::quarantine::{self, Quarantine, QuarantinedFile};
+use crate::settings::Settings;
+use anyhow::{Result, Context};
+use chrono::{DateTime, Local, Utc};
+use image::RgbaImage;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+// Where cameras and phones keep photos and videos, and phones their downloads. Phones mounted
+// over MTP show them one level down, under a storage folder like "Internal shared storage".
+const MEDIA_FOLDERS: [&str; 3] = ["dcim", "download", "downloads"];
+
+/// Edge length in pixels of the thumbnails in the import list
+pub const THUMBNAIL_SIZE: u32 = 64;
+
+// Bigger pictures are listed without a thumbnail rather than decoded
+const MAX_THUMBNAIL_SOURCE: u64 = 40 * 1024 * 1024;
+
+/// A file found on a device, not yet taken in
+#[derive(Debug, Clone)]
+pub struct DeviceItem {
+    pub path: PathBuf,
+    pub name: String,
+    pub size: u64,
+    pub date: Option<DateTime<Utc>>, // When the photo was taken, or the file last changed
+}
+
+/// What importing from a device did
+#[derive(Debug, Clone, Default)]
+pub struct DeviceImportSummary {
+    pub added: Vec<EvidenceFile>,
+    pub quarantined: Vec<QuarantinedFile>,
+    pub failed: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl DeviceImportSummary {
+    pub fn describe(&self) -> String {
+        let mut description = format!("{} added", self.added.len());
+        if !self.quarantined.is_empty() {
+            description.push_str(&format!(", {} quarantined", self.quarantined.len()));
+        }
+        if !self.failed.is_empty() {
+            description.push_str(&format!(", {} failed ({})", self.failed.len(), self.failed.join(", ")));
+        }
+        description
+    }
+}
+
+/// Mounted drives and phones that have a DCIM or Downloads folder
+pub fn volumes() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if cfg!(windows) {
+        candidates.extend((b'D'..=b'Z').map(|letter| PathBuf::from(format!("{}:\\", letter as char))));
+    } else if cfg!(target_os = "macos") {
+        candidates.extend(children(Path::new("/Volumes")));
+    } else {
+        let user = std::env::var("USER").unwrap_or_default();
+        for parent in [format!("/media/{}", user), format!("/run/media/{}", user), "/media".to_string(), "/mnt".to_string()] {
+            candidates.extend(children(Path::new(&parent)));
+        }
+        // Phones connected over MTP, as mounted by the desktop
+        if let Ok(runtime_dir) = std::env::var("XDG_RUNTIME_DIR") {
+            candidates.extend(children(&Path::new(&runtime_dir).join("gvfs")));
+        }
+    }
+    candidates.sort();
+    candidates.dedup();
+    candidates.retain(|volume| !media_folders(volume).is_empty());
+    candidates
+}
+
+/// The device's name as shown to the user and recorded as the evidence's source device
+pub fn label(volume: &Path) -> String {
+    volume.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| volume.display().to_string())
+}
+
+fn children(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|entry| entry.path()).filter(|path| path.is_dir()).collect())
+        .unwrap_or_default()
+}
+
+/// The DCIM and Downloads folders on a volume, at its top level or in its storage folders
+pub fn media_folders(volume: &Path) -> Vec<PathBuf> {
+    let is_media = |path: &PathBuf| {
+        path.file_name().is_some_and(|name| MEDIA_FOLDERS.contains(&name.to_string_lossy().to_lowercase().as_str()))
+    };
+    let top_level = children(volume);
+    let mut folders: Vec<PathBuf> = top_level.iter().filter(|path| is_media(path)).cloned().collect();
+    if folders.is_empty() {
+        for storage in &top_level {
+            folders.extend(children(storage).into_iter().filter(is_media));
+        }
+    }
+    folders
+}
+
+/// Every file in the volume's media folders, newest first. Hidden files and folders (e.g. a
+/// phone's ".thumbnails") are skipped.
+pub fn scan(volume: &Path) -> Result<Vec<DeviceItem>> {
+    let folders = media_folders(volume);
+    if folders.is_empty() {
+        anyhow::bail!("{} has no DCIM or Downloads folder", volume.display());
+    }
+    let mut items = Vec::new();
+    for folder in folders {
+        let entries = WalkDir::new(&folder)
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.'))
+            .flatten()
+            .filter(|entry| entry.file_type().is_file());
+        for entry in entries {
+            let size = entry.metadata().map(|m| m.len()).unwrap_or_default();
+            items.push(DeviceItem {
+                name: entry.file_name().to_string_lossy().to_string(),
+                size,
+                date: FileManager::detect_evidence_date(entry.path()),
+                path: entry.into_path(),
+            });
+        }
+    }
+    items.sort_by_key(|i| std::cmp::Reverse(i.date));
+    Ok(items)
+}
+
+/// A small version of a picture for the import list; None for videos and anything else that
+/// isn't an image this app can read
+pub fn thumbnail(path: &Path, size: u32) -> Option<RgbaImage> {
+    if fs::metadata(path).ok()?.len() > MAX_THUMBNAIL_SOURCE {
+        return None;
+    }
+    image::open(path).ok().map(|picture| picture.thumbnail(size, size).to_rgba8())
+}
+
+/// Takes the chosen items in for a person, recording the device as their source and keeping
+/// their original dates. Anything that could run code is quarantined instead, as when dropped.
+pub fn import(
+    file_manager: &FileManager,
+    audit_log: &AuditLog,
+    plugins: &Plugins,
+    settings: &Settings,
+    person: &mut Person,
+    device: &str,
+    items: &[DeviceItem],
+) -> Result<DeviceImportSummary> {
+    let quarantine = Quarantine::new(file_manager.get_evidence_dir());
+    let mut summary = DeviceImportSummary::default();
+    let obtained_at = Local::now().format("%Y-%m-%d").to_string();
+
+    for item in items {
+        let evidence_type = settings.evidence_type_for_file(&item.path).unwrap_or(EvidenceType::Other);
+        if let Some(reason) = quarantine::reason(&item.path) {
+            let held = quarantine.hold(person, &item.path, evidence_type, reason)?;
+            let details = format!("{}: {}, SHA-256 {}", held.original_name, held.reason, held.sha256);
+            audit_log.record(Some(person), AuditAction::EvidenceQuarantined, format!("{} from {}", details, device))?;
+            summary.quarantined.push(held);
+            continue;
+        }
+
+        let provenance = Provenance {
+            source_device: Some(device.to_string()),
+            obtained_at: Some(obtained_at.clone()),
+            collected_by: Some(settings.operator.trim().to_string()).filter(|name| !name.is_empty()),
+            ..Provenance::default()
+        };
+        let mut evidence_file = match file_manager.intake_file(person, &item.path, evidence_type, provenance, false, None) {
+            Ok(evidence_file) => evidence_file,
+            Err(e) => {
+                summary.failed.push(format!("{}: {:#}", item.name, e));
+                continue;
+            }
+        };
+        let details = format!("{} ({}) from {}", evidence_file.original_name, evidence_file.file_type.folder_name(), device);
+        audit_log.record(Some(person), AuditAction::EvidenceAdded, details)
+            .context("Failed to record import in the activity log")?;
+        summary.warnings.extend(plugins.evidence_added(file_manager, audit_log, person, &mut evidence_file));
+        summary.added.push(evidence_file);
+    }
+    Ok(summary)
+}
@@ -0,0 +1,99 @@
+use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// A form whose text is kept as a draft until it's submitted or closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DraftForm {
+    Quote,
+    Note,
+    PastedQuotes,
+}
+
+impl DraftForm {
+    pub const ALL: [DraftForm; 3] = [DraftForm::Quote, DraftForm::Note, DraftForm::PastedQuotes];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DraftForm::Quote => "quote",
+            DraftForm::Note => "text note",
+            DraftForm::PastedQuotes => "pasted quotes",
+        }
+    }
+}
+
+/// What was typed into one form for one person, by field name. The form's text is "text".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Draft {
+    pub person_id: Uuid,
+    pub form: DraftForm,
+    pub fields: BTreeMap<String, String>,
+    pub saved_at: DateTime<Utc>,
+}
+
+impl Draft {
+    pub fn field(&self, name: &str) -> String {
+        self.fields.get(name).cloned().unwrap_or_default()
+    }
+}
+
+/// Drafts of partly filled forms, kept in the config directory so text typed before the app
+/// closed can be restored on the next start
+#[derive(Debug, Clone, Default)]
+pub struct Drafts {
+    pub drafts: Vec<Draft>,
+}
+
+impl Drafts {
+    fn path() -> Result<PathBuf> {
+        let project_dirs = ProjectDirs::from("com", "Evidence-Manager", "Evidence-Manager")
+            .context("Failed to get user config directory")?;
+        Ok(project_dirs.config_dir().join("drafts.json"))
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let json = fs::read_to_string(&path)
+            .context("Failed to read drafts")?;
+        let drafts = serde_json::from_str(&json)
+            .context("Failed to parse drafts")?;
+        Ok(Self { drafts })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .context("Failed to create config directory")?;
+        }
+        let json = serde_json::to_string_pretty(&self.drafts)
+            .context("Failed to serialize drafts")?;
+        fs::write(&path, json)
+            .context("Failed to write drafts")?;
+        Ok(())
+    }
+
+    /// Keeps the form's fields as its draft, replacing any earlier one
+    pub fn set(&mut self, person_id: Uuid, form: DraftForm, fields: BTreeMap<String, String>) {
+        self.remove(person_id, form);
+        self.drafts.push(Draft { person_id, form, fields, saved_at: Utc::now() });
+    }
+
+    pub fn remove(&mut self, person_id: Uuid, form: DraftForm) {
+        self.drafts.retain(|draft| draft.person_id != person_id || draft.form != form);
+    }
+
+    /// Drops drafts for people who no longer exist
+    pub fn retain_persons(&mut self, exists: impl Fn(Uuid) -> bool) {
+        self.drafts.retain(|draft| exists(draft.person_id));
+    }
+}
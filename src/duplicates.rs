@@ -0,0 +1,104 @@
+use anyhow::{Result, Context};
+use image::imageops::FilterType;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Hashes closer than this many differing bits are flagged as possible duplicates
+pub const NEAR_DUPLICATE_THRESHOLD: u32 = 10;
+
+const HASH_INPUT_SIZE: usize = 32;
+const HASH_BLOCK_SIZE: usize = 8;
+
+#[derive(Debug, Clone)]
+pub struct DuplicatePair {
+    pub first: Uuid,
+    pub second: Uuid,
+    pub distance: u32,
+}
+
+/// DCT-based perceptual hash: survives resizing, recompression and small edits, unlike a byte hash
+pub fn perceptual_hash(path: &Path) -> Result<u64> {
+    let image = image::open(path)
+        .context("Failed to open image for hashing")?;
+
+    let pixels = image
+        .resize_exact(HASH_INPUT_SIZE as u32, HASH_INPUT_SIZE as u32, FilterType::Triangle)
+        .to_luma8();
+
+    let mut matrix = [[0f64; HASH_INPUT_SIZE]; HASH_INPUT_SIZE];
+    for (x, y, pixel) in pixels.enumerate_pixels() {
+        matrix[y as usize][x as usize] = pixel[0] as f64;
+    }
+
+    let dct = dct_2d(&matrix);
+
+    // Keep the low frequencies, skipping the DC term which only reflects overall brightness
+    let mut coefficients = Vec::with_capacity(HASH_BLOCK_SIZE * HASH_BLOCK_SIZE);
+    for row in dct.iter().take(HASH_BLOCK_SIZE) {
+        coefficients.extend_from_slice(&row[..HASH_BLOCK_SIZE]);
+    }
+
+    let mut sorted: Vec<f64> = coefficients[1..].to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let median = sorted[sorted.len() / 2];
+
+    Ok(coefficients
+        .iter()
+        .enumerate()
+        .fold(0u64, |hash, (bit, value)| if *value > median { hash | (1 << bit) } else { hash }))
+}
+
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+pub fn find_near_duplicates(hashes: &[(Uuid, u64)]) -> Vec<DuplicatePair> {
+    let mut pairs = Vec::new();
+    for (i, (first, first_hash)) in hashes.iter().enumerate() {
+        for (second, second_hash) in &hashes[i + 1..] {
+            let distance = hamming_distance(*first_hash, *second_hash);
+            if distance <= NEAR_DUPLICATE_THRESHOLD {
+                pairs.push(DuplicatePair {
+                    first: *first,
+                    second: *second,
+                    distance,
+                });
+            }
+        }
+    }
+    pairs.sort_by_key(|p| p.distance);
+    pairs
+}
+
+fn dct_2d(matrix: &[[f64; HASH_INPUT_SIZE]; HASH_INPUT_SIZE]) -> [[f64; HASH_INPUT_SIZE]; HASH_INPUT_SIZE] {
+    let mut rows = [[0f64; HASH_INPUT_SIZE]; HASH_INPUT_SIZE];
+    for (y, row) in matrix.iter().enumerate() {
+        rows[y] = dct_1d(row);
+    }
+
+    let mut result = [[0f64; HASH_INPUT_SIZE]; HASH_INPUT_SIZE];
+    for x in 0..HASH_INPUT_SIZE {
+        let mut column = [0f64; HASH_INPUT_SIZE];
+        for y in 0..HASH_INPUT_SIZE {
+            column[y] = rows[y][x];
+        }
+        let transformed = dct_1d(&column);
+        for y in 0..HASH_INPUT_SIZE {
+            result[y][x] = transformed[y];
+        }
+    }
+    result
+}
+
+fn dct_1d(input: &[f64; HASH_INPUT_SIZE]) -> [f64; HASH_INPUT_SIZE] {
+    let n = HASH_INPUT_SIZE as f64;
+    let mut output = [0f64; HASH_INPUT_SIZE];
+    for (k, out) in output.iter_mut().enumerate() {
+        *out = input
+            .iter()
+            .enumerate()
+            .map(|(i, value)| value * (std::f64::consts::PI / n * (i as f64 + 0.5) * k as f64).cos())
+            .sum();
+    }
+    output
+}
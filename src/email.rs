@@ -0,0 +1,72 @@
+use crate::models::EmailMetadata;
+use anyhow::{Result, Context};
+use mail_parser::{Address, MessageParser, MimeHeaders};
+
+#[derive(Debug, Clone)]
+pub struct EmailAttachment {
+    pub name: String,
+    pub content: Vec<u8>,
+}
+
+/// A parsed .eml file: header metadata, readable body, and attachments
+#[derive(Debug, Clone)]
+pub struct ParsedEmail {
+    pub metadata: EmailMetadata,
+    pub body: String,
+    pub attachments: Vec<EmailAttachment>,
+}
+
+pub fn is_email_extension(ext: &str) -> bool {
+    ext.eq_ignore_ascii_case("eml")
+}
+
+pub fn parse_email(content: &[u8]) -> Result<ParsedEmail> {
+    let message = MessageParser::default()
+        .parse(content)
+        .context("Failed to parse email")?;
+
+    let metadata = EmailMetadata {
+        from: format_addresses(message.from()),
+        to: format_addresses(message.to()),
+        date: message.date().map(|d| d.to_rfc3339()),
+        subject: message.subject().unwrap_or("(no subject)").to_string(),
+    };
+
+    // Fall back to the HTML part rendered as text for HTML-only messages
+    let body = message.body_text(0)
+        .map(|b| b.to_string())
+        .unwrap_or_default();
+
+    let attachments = message.attachments()
+        .enumerate()
+        .map(|(index, part)| EmailAttachment {
+            name: part.attachment_name()
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| format!("attachment_{}", index + 1)),
+            content: part.contents().to_vec(),
+        })
+        .collect();
+
+    Ok(ParsedEmail {
+        metadata,
+        body,
+        attachments,
+    })
+}
+
+fn format_addresses(address: Option<&Address>) -> String {
+    let Some(address) = address else {
+        return String::new();
+    };
+
+    address.iter()
+        .map(|addr| match (addr.name(), addr.address()) {
+            (Some(name), Some(email)) => format!("{} <{}>", name, email),
+            (None, Some(email)) => email.to_string(),
+            (Some(name), None) => name.to_string(),
+            (None, None) => String::new(),
+        })
+        .filter(|a| !a.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
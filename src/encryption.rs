@@ -0,0 +1,137 @@
+use anyhow::{Result, Context, anyhow, bail};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::num::NonZeroU32;
+use std::path::Path;
+
+/// Added after ".ema" once an archive is encrypted
+pub const EXTENSION: &str = "enc";
+
+// Layout: magic, salt, PBKDF2 iteration count (u32 BE), then chunks. Each chunk is a flag byte
+// (1 on the last chunk), the sealed length (u32 BE) and the AES-256-GCM ciphertext with its tag.
+// The nonce is the chunk's number and the flag is authenticated, so chunks can't be reordered
+// or dropped from the end without decryption failing.
+const MAGIC: &[u8; 8] = b"EMENC\0\0\x01";
+const SALT_LEN: usize = 16;
+const ITERATIONS: u32 = 600_000;
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Encrypts a file with a key derived from the passphrase
+pub fn encrypt_file(source: &Path, destination: &Path, passphrase: &str) -> Result<()> {
+    let result = write_encrypted(source, destination, passphrase);
+    if result.is_err() {
+        let _ = fs::remove_file(destination);
+    }
+    result
+}
+
+/// Decrypts a file written by `encrypt_file`; a wrong passphrase or altered file is an error
+pub fn decrypt_file(source: &Path, destination: &Path, passphrase: &str) -> Result<()> {
+    let result = write_decrypted(source, destination, passphrase);
+    if result.is_err() {
+        let _ = fs::remove_file(destination);
+    }
+    result
+}
+
+/// Whether the file starts like one written by `encrypt_file`
+pub fn is_encrypted(path: &Path) -> bool {
+    let mut magic = [0u8; MAGIC.len()];
+    File::open(path).and_then(|mut file| file.read_exact(&mut magic)).is_ok() && magic == *MAGIC
+}
+
+fn write_encrypted(source: &Path, destination: &Path, passphrase: &str) -> Result<()> {
+    let mut salt = [0u8; SALT_LEN];
+    SystemRandom::new().fill(&mut salt)
+        .map_err(|_| anyhow!("Failed to generate a salt"))?;
+    let key = derive_key(passphrase, &salt, ITERATIONS)?;
+
+    let mut reader = BufReader::new(File::open(source).context("Failed to open file to encrypt")?);
+    let mut writer = BufWriter::new(File::create(destination).context("Failed to create encrypted file")?);
+    writer.write_all(MAGIC)?;
+    writer.write_all(&salt)?;
+    writer.write_all(&ITERATIONS.to_be_bytes())?;
+
+    let mut chunk = read_chunk(&mut reader)?;
+    let mut index = 0u64;
+    loop {
+        // Reading one chunk ahead tells whether this one is the last
+        let next = if chunk.len() == CHUNK_SIZE { read_chunk(&mut reader)? } else { Vec::new() };
+        let last = next.is_empty();
+        let flag = [last as u8];
+        key.seal_in_place_append_tag(nonce(index), Aad::from(flag), &mut chunk)
+            .map_err(|_| anyhow!("Failed to encrypt"))?;
+        writer.write_all(&flag)?;
+        writer.write_all(&(chunk.len() as u32).to_be_bytes())?;
+        writer.write_all(&chunk)?;
+        if last {
+            break;
+        }
+        chunk = next;
+        index += 1;
+    }
+    writer.flush().context("Failed to write encrypted file")?;
+    Ok(())
+}
+
+fn write_decrypted(source: &Path, destination: &Path, passphrase: &str) -> Result<()> {
+    let mut reader = BufReader::new(File::open(source).context("Failed to open encrypted file")?);
+    let mut magic = [0u8; MAGIC.len()];
+    reader.read_exact(&mut magic).context("Not an encrypted archive")?;
+    if magic != *MAGIC {
+        bail!("Not an encrypted archive");
+    }
+    let mut salt = [0u8; SALT_LEN];
+    let mut iterations = [0u8; 4];
+    reader.read_exact(&mut salt)?;
+    reader.read_exact(&mut iterations)?;
+    let key = derive_key(passphrase, &salt, u32::from_be_bytes(iterations))?;
+
+    let mut writer = BufWriter::new(File::create(destination).context("Failed to create decrypted file")?);
+    let mut index = 0u64;
+    loop {
+        let mut header = [0u8; 5];
+        reader.read_exact(&mut header).context("The encrypted file is incomplete")?;
+        let length = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+        if length > CHUNK_SIZE + AES_256_GCM.tag_len() {
+            bail!("The encrypted file is damaged");
+        }
+        let mut chunk = vec![0u8; length];
+        reader.read_exact(&mut chunk).context("The encrypted file is incomplete")?;
+        let plain = key.open_in_place(nonce(index), Aad::from([header[0]]), &mut chunk)
+            .map_err(|_| anyhow!("Wrong passphrase, or the file has been altered"))?;
+        writer.write_all(plain)?;
+        if header[0] == 1 {
+            break;
+        }
+        index += 1;
+    }
+    writer.flush().context("Failed to write decrypted file")?;
+    Ok(())
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<LessSafeKey> {
+    let iterations = NonZeroU32::new(iterations).context("The encrypted file is damaged")?;
+    let mut key = [0u8; 32];
+    pbkdf2::derive(pbkdf2::PBKDF2_HMAC_SHA256, iterations, salt, passphrase.as_bytes(), &mut key);
+    let key = UnboundKey::new(&AES_256_GCM, &key)
+        .map_err(|_| anyhow!("Failed to set up encryption"))?;
+    Ok(LessSafeKey::new(key))
+}
+
+fn nonce(index: u64) -> Nonce {
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce[NONCE_LEN - 8..].copy_from_slice(&index.to_be_bytes());
+    Nonce::assume_unique_for_key(nonce)
+}
+
+/// Reads up to a full chunk; shorter only at the end of the file
+fn read_chunk(reader: &mut impl Read) -> Result<Vec<u8>> {
+    let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+    reader.take(CHUNK_SIZE as u64).read_to_end(&mut chunk)
+        .context("Failed to read file to encrypt")?;
+    Ok(chunk)
+}
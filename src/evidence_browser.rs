@@ -0,0 +1,140 @@
+use crate::file_manager::FileManager;
+use crate::models::{EvidenceFile, EvidenceType, Person, ReviewStatus};
+use crate::settings::Settings;
+use anyhow::Result;
+use uuid::Uuid;
+
+/// A column of the case-wide evidence list that it can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrowserColumn {
+    Person,
+    Category,
+    Name,
+    Date,
+    Size,
+    Tags,
+}
+
+impl BrowserColumn {
+    pub const ALL: [BrowserColumn; 6] = [
+        BrowserColumn::Person,
+        BrowserColumn::Category,
+        BrowserColumn::Name,
+        BrowserColumn::Date,
+        BrowserColumn::Size,
+        BrowserColumn::Tags,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BrowserColumn::Person => "Person",
+            BrowserColumn::Category => "Category",
+            BrowserColumn::Name => "Name",
+            BrowserColumn::Date => "Date",
+            BrowserColumn::Size => "Size",
+            BrowserColumn::Tags => "Tags",
+        }
+    }
+}
+
+/// An evidence file with the person holding it
+#[derive(Debug, Clone)]
+pub struct BrowserRow {
+    pub person_id: Uuid,
+    pub person_name: String,
+    pub tags: Vec<String>, // The person's tags; files have none of their own
+    pub category: String, // Display name of the file's category
+    pub file: EvidenceFile,
+}
+
+/// Every person's evidence in one list, with how it's filtered, sorted and which rows are selected
+#[derive(Debug, Clone, Default)]
+pub struct EvidenceBrowser {
+    pub rows: Vec<BrowserRow>,
+    pub loading: bool,
+    pub filter: String, // Matched against the person, file name and tags
+    pub category: Option<EvidenceType>,
+    pub review: Option<ReviewStatus>,
+    pub sort: Option<BrowserColumn>, // None keeps the order they were read in, person by person
+    pub descending: bool,
+    pub selected: Vec<Uuid>,
+}
+
+impl EvidenceBrowser {
+    /// Reads the evidence of every person. Superseded versions and quote files are left out, as
+    /// in the person's own tabs.
+    pub fn load(file_manager: &FileManager, settings: &Settings, persons: &[Person]) -> Result<Vec<BrowserRow>> {
+        let mut rows = Vec::new();
+        for person in persons {
+            for file in file_manager.scan_person_evidence(person, settings)? {
+                if file.file_type == EvidenceType::Quote || file.superseded_by.is_some() {
+                    continue;
+                }
+                rows.push(BrowserRow {
+                    person_id: person.id,
+                    person_name: person.name.clone(),
+                    tags: person.tags.clone(),
+                    category: settings.display_name(&file.file_type),
+                    file,
+                });
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Clicking the column already sorted by reverses it
+    pub fn sort_by(&mut self, column: BrowserColumn) {
+        if self.sort == Some(column) {
+            self.descending = !self.descending;
+        } else {
+            self.sort = Some(column);
+            self.descending = false;
+        }
+    }
+
+    /// The rows that pass the filters, in the chosen order
+    pub fn visible(&self) -> Vec<&BrowserRow> {
+        let filter = self.filter.trim().to_lowercase();
+        let mut rows: Vec<&BrowserRow> = self.rows
+            .iter()
+            .filter(|row| self.category.as_ref().is_none_or(|category| row.file.file_type == *category))
+            .filter(|row| self.review.is_none_or(|status| row.file.review_status == status))
+            .filter(|row| {
+                filter.is_empty()
+                    || row.person_name.to_lowercase().contains(&filter)
+                    || row.file.original_name.to_lowercase().contains(&filter)
+                    || row.tags.iter().any(|tag| tag.to_lowercase().contains(&filter))
+            })
+            .collect();
+
+        if let Some(column) = self.sort {
+            match column {
+                BrowserColumn::Person => rows.sort_by_key(|row| row.person_name.to_lowercase()),
+                BrowserColumn::Category => rows.sort_by_key(|row| row.category.to_lowercase()),
+                BrowserColumn::Name => rows.sort_by_key(|row| row.file.original_name.to_lowercase()),
+                BrowserColumn::Date => rows.sort_by_key(|row| row.file.evidence_date.unwrap_or(row.file.created_at)),
+                BrowserColumn::Size => rows.sort_by_key(|row| row.file.size),
+                BrowserColumn::Tags => rows.sort_by_key(|row| row.tags.join(", ").to_lowercase()),
+            }
+            if self.descending {
+                rows.reverse();
+            }
+        }
+        rows
+    }
+
+    /// Categories any of the rows are in, for the category filter
+    pub fn categories(&self) -> Vec<EvidenceType> {
+        let mut categories: Vec<EvidenceType> = Vec::new();
+        for row in &self.rows {
+            if !categories.contains(&row.file.file_type) {
+                categories.push(row.file.file_type.clone());
+            }
+        }
+        categories
+    }
+
+    pub fn selected_rows(&self) -> Vec<&BrowserRow> {
+        self.rows.iter().filter(|row| self.selected.contains(&row.file.id)).collect()
+    }
+}
@@ -0,0 +1,159 @@
+use crate::models::Coordinates;
+use chrono::NaiveDateTime;
+
+const DATE_TIME: u16 = 0x0132;
+const EXIF_IFD_POINTER: u16 = 0x8769;
+const DATE_TIME_ORIGINAL: u16 = 0x9003;
+const GPS_IFD_POINTER: u16 = 0x8825;
+const GPS_LATITUDE_REF: u16 = 0x0001;
+const GPS_LATITUDE: u16 = 0x0002;
+const GPS_LONGITUDE_REF: u16 = 0x0003;
+const GPS_LONGITUDE: u16 = 0x0004;
+
+/// Reads the GPS position from a JPEG's or TIFF's EXIF data, if it has one
+pub fn gps_coordinates(content: &[u8]) -> Option<Coordinates> {
+    let reader = TiffReader::new(tiff_data(content)?)?;
+
+    let first_ifd = reader.u32_at(4)? as usize;
+    let gps_ifd = reader.entries(first_ifd)?
+        .find(|entry| entry.tag == GPS_IFD_POINTER)
+        .map(|entry| entry.value_offset as usize)?;
+
+    let mut latitude = None;
+    let mut longitude = None;
+    let mut latitude_ref = b'N';
+    let mut longitude_ref = b'E';
+    for entry in reader.entries(gps_ifd)? {
+        match entry.tag {
+            // Single ASCII characters are stored inline in the value field
+            GPS_LATITUDE_REF => latitude_ref = reader.data.get(entry.field_offset + 8).copied()?,
+            GPS_LONGITUDE_REF => longitude_ref = reader.data.get(entry.field_offset + 8).copied()?,
+            GPS_LATITUDE => latitude = reader.degrees(entry.value_offset as usize),
+            GPS_LONGITUDE => longitude = reader.degrees(entry.value_offset as usize),
+            _ => {}
+        }
+    }
+
+    let mut latitude = latitude?;
+    let mut longitude = longitude?;
+    if latitude_ref == b'S' {
+        latitude = -latitude;
+    }
+    if longitude_ref == b'W' {
+        longitude = -longitude;
+    }
+
+    // Cameras without a fix sometimes write zeros
+    if latitude == 0.0 && longitude == 0.0 {
+        return None;
+    }
+    Some(Coordinates { latitude, longitude })
+}
+
+/// When the photo was taken, in the camera's local time: DateTimeOriginal, or the IFD0 DateTime if that's missing
+pub fn date_taken(content: &[u8]) -> Option<NaiveDateTime> {
+    let reader = TiffReader::new(tiff_data(content)?)?;
+    let first_ifd = reader.u32_at(4)? as usize;
+
+    let original = reader.entries(first_ifd)?
+        .find(|entry| entry.tag == EXIF_IFD_POINTER)
+        .and_then(|entry| reader.entries(entry.value_offset as usize))
+        .and_then(|mut entries| entries.find(|entry| entry.tag == DATE_TIME_ORIGINAL))
+        .and_then(|entry| reader.date_time(entry.value_offset as usize));
+    original.or_else(|| {
+        reader.entries(first_ifd)?
+            .find(|entry| entry.tag == DATE_TIME)
+            .and_then(|entry| reader.date_time(entry.value_offset as usize))
+    })
+}
+
+fn tiff_data(content: &[u8]) -> Option<&[u8]> {
+    if content.starts_with(&[0xFF, 0xD8]) {
+        find_jpeg_exif(content)
+    } else {
+        Some(content)
+    }
+}
+
+/// Returns the TIFF structure inside the JPEG's APP1 "Exif" segment
+fn find_jpeg_exif(content: &[u8]) -> Option<&[u8]> {
+    let mut position = 2;
+    while position + 4 <= content.len() {
+        if content[position] != 0xFF {
+            return None;
+        }
+        let marker = content[position + 1];
+        let length = u16::from_be_bytes([content[position + 2], content[position + 3]]) as usize;
+        let segment = content.get(position + 4..position + 2 + length)?;
+
+        if marker == 0xE1 && segment.starts_with(b"Exif\0\0") {
+            return Some(&segment[6..]);
+        }
+        // Start of scan: image data follows, no more metadata
+        if marker == 0xDA {
+            return None;
+        }
+        position += 2 + length;
+    }
+    None
+}
+
+struct IfdEntry {
+    tag: u16,
+    field_offset: usize,
+    value_offset: u32,
+}
+
+struct TiffReader<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> TiffReader<'a> {
+    fn new(data: &'a [u8]) -> Option<Self> {
+        let little_endian = match data.get(0..2)? {
+            b"II" => true,
+            b"MM" => false,
+            _ => return None,
+        };
+        Some(Self { data, little_endian })
+    }
+
+    fn u16_at(&self, offset: usize) -> Option<u16> {
+        let bytes: [u8; 2] = self.data.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if self.little_endian { u16::from_le_bytes(bytes) } else { u16::from_be_bytes(bytes) })
+    }
+
+    fn u32_at(&self, offset: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.data.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if self.little_endian { u32::from_le_bytes(bytes) } else { u32::from_be_bytes(bytes) })
+    }
+
+    fn entries(&self, ifd_offset: usize) -> Option<impl Iterator<Item = IfdEntry> + '_> {
+        let count = self.u16_at(ifd_offset)? as usize;
+        Some((0..count).filter_map(move |index| {
+            let field_offset = ifd_offset + 2 + index * 12;
+            Some(IfdEntry {
+                tag: self.u16_at(field_offset)?,
+                field_offset,
+                value_offset: self.u32_at(field_offset + 8)?,
+            })
+        }))
+    }
+
+    /// A 20-byte ASCII "YYYY:MM:DD HH:MM:SS" value; unset dates are written as blanks or zeros
+    fn date_time(&self, offset: usize) -> Option<NaiveDateTime> {
+        let text = std::str::from_utf8(self.data.get(offset..offset + 19)?).ok()?;
+        NaiveDateTime::parse_from_str(text, "%Y:%m:%d %H:%M:%S").ok()
+    }
+
+    /// Three RATIONALs: degrees, minutes, seconds
+    fn degrees(&self, offset: usize) -> Option<f64> {
+        let rational = |index: usize| -> Option<f64> {
+            let numerator = self.u32_at(offset + index * 8)? as f64;
+            let denominator = self.u32_at(offset + index * 8 + 4)? as f64;
+            (denominator != 0.0).then(|| numerator / denominator)
+        };
+        Some(rational(0)? + rational(1)? / 60.0 + rational(2)? / 3600.0)
+    }
+}
@@ -1,11 +1,124 @@
-use crate::models::Person;
-use crate::file_manager::FileManager;
-use anyhow::{Result, Context};
+use crate::models::{CaseRole, Person, EvidenceFile, EvidenceType, FieldValue};
+use crate::settings::CustomField;
+use crate::file_manager::{FileManager, METADATA_FILES};
+use crate::watermark::Watermark;
+use crate::hashing;
+use crate::paths;
+use crate::signing::{self, Signature, SigningKey};
+use crate::import_journal::ImportJournal;
+use crate::board;
+use anyhow::{Result, Context, bail};
+use image::{DynamicImage, ImageFormat};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::fs;
 use zip::ZipWriter;
 use zip::write::FileOptions;
-use std::io::{Read, Write};
+use std::io::{Cursor, Read, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+/// Options controlling how an export is written
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportOptions {
+    /// Stamp images and PDF pages with a watermark; originals in the store are left untouched
+    pub watermark: Option<Watermark>,
+    /// Sign .ema archives with this installation's key
+    #[serde(default)]
+    pub sign: bool,
+    /// Leave the evidence files out of .ema archives, keeping each person's data, quotes, board and
+    /// evidence index, with a manifest of the files' sizes and hashes in their place
+    #[serde(default)]
+    pub metadata_only: bool,
+    /// As metadata only, but with a small JPEG preview of each image under `thumbnails/`, for
+    /// sharing for triage; the manifest's hashes identify the originals to ask for
+    #[serde(default)]
+    pub thumbnails_only: bool,
+}
+
+/// Manifest at the root of a metadata-only archive, listing the evidence files it leaves out
+pub const METADATA_ONLY_MANIFEST: &str = "evidence_manifest.csv";
+
+/// Folder at the root of a thumbnail-only archive holding the previews, laid out like the store
+pub const THUMBNAILS_FOLDER: &str = "thumbnails";
+
+/// Note at the root of an archive whose images and PDFs were watermarked. Only then may their
+/// hashes differ from the evidence index on import; it's covered by the signature when signed.
+pub const WATERMARK_NOTE: &str = "watermarked.txt";
+
+// Longest edge of an exported preview in pixels
+const THUMBNAIL_EDGE: u32 = 480;
+
+// Reading, watermarking and compressing beyond this many files at once gains little on a single disk
+const MAX_EXPORT_WORKERS: usize = 8;
+
+// Files up to this size are compressed in memory by the workers; larger ones are streamed into
+// the archive by the writer, so an export holds at most a few of these in memory at once
+const IN_MEMORY_LIMIT: u64 = 16 * 1024 * 1024;
+
+/// A file on its way into an archive
+enum PreparedFile {
+    /// Compressed by a worker as the only entry of a small zip in memory, ready to be copied over
+    Compressed { zip_path: String, entry: Vec<u8>, sha256: String, warning: Option<String> },
+    /// Too large to hold in memory; the writer reads it itself
+    Large { path: PathBuf, zip_path: String },
+}
+
+/// Called with the number of files done and the total as an export or import goes along
+pub type ProgressCallback = Box<dyn Fn(usize, usize) + Send + Sync>;
+
+/// Shared flag a running export or import checks between files
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            bail!("Cancelled");
+        }
+        Ok(())
+    }
+}
+
+/// What an import did to the store
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    pub persons: Vec<Person>, // Every person in the archive, as now stored
+    pub persons_added: usize,
+    pub persons_merged: usize, // Already in the store; their files were merged with the archive's
+    pub files_written: usize,
+    pub files_skipped: usize, // Identical copies already in the store
+    pub signed_by: Option<String>, // Fingerprint of the key whose valid signature the archive carried
+    pub resumed: bool, // Picked up an import of the same archive that was interrupted
+    pub warnings: Vec<String>,
+}
+
+impl ImportReport {
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} people added, {} merged; {} files written, {} already present",
+            self.persons_added, self.persons_merged, self.files_written, self.files_skipped
+        );
+        if let Some(fingerprint) = &self.signed_by {
+            summary.push_str(&format!("; signature verified, key {}", fingerprint));
+        }
+        if self.resumed {
+            summary.push_str("; resumed after an interruption");
+        }
+        summary
+    }
+}
 
 #[derive(Clone)]
 pub struct ExportImportManager {
@@ -17,129 +130,643 @@ impl ExportImportManager {
         Self { file_manager }
     }
 
-    pub fn export_to_ema(&self, output_path: &Path, persons: &[Person], progress_callback: Option<Box<dyn Fn(String) + Send + Sync>>) -> Result<()> {
+    /// Returns warnings about files exported without the watermark that was asked for
+    pub fn export_to_ema(&self, output_path: &Path, persons: &[Person], options: &ExportOptions, cancel: &CancellationToken, progress_callback: Option<ProgressCallback>) -> Result<Vec<String>> {
+        let mut files = self.collect_person_files(persons)?;
+        let mut extra = Vec::new();
+        let mut warnings = Vec::new();
+        if options.metadata_only || options.thumbnails_only {
+            files.retain(|(path, _)| path.file_name().and_then(|n| n.to_str()).is_some_and(|n| METADATA_FILES.contains(&n)));
+            let evidence = persons.iter()
+                .map(|person| Ok((person.clone(), self.file_manager.load_evidence_index(person)?)))
+                .collect::<Result<Vec<_>>>()?;
+            extra.push((METADATA_ONLY_MANIFEST.to_string(), self.hash_manifest_csv(&evidence)?.0.into_bytes()));
+            if options.thumbnails_only {
+                extra.extend(self.thumbnails(&evidence, options, cancel, &mut warnings)?);
+            }
+        } else if options.watermark.is_some() {
+            let note = "Images and PDFs in this archive carry a watermark, so their SHA-256 differs from the evidence index. The stored originals aren't included.\n";
+            extra.push((WATERMARK_NOTE.to_string(), note.as_bytes().to_vec()));
+        }
+        let needed = files.iter()
+            .map(|(path, _)| fs::metadata(path).map(|m| m.len()).unwrap_or_default())
+            .sum();
+        ensure_room_for(output_path, needed)?;
+
         // Create the zip file
         let file = fs::File::create(output_path)
             .context("Failed to create output file")?;
-        let mut zip = ZipWriter::new(file);
+        let result = Self::write_ema(ZipWriter::new(file), files, extra, options, cancel, progress_callback);
+        if result.is_err() {
+            // A truncated archive would look like a finished export
+            let _ = fs::remove_file(output_path);
+        }
+        warnings.extend(result?);
+        Ok(warnings)
+    }
 
+    /// Every file in the folders of the given persons, with its path inside the archive
+    fn collect_person_files(&self, persons: &[Person]) -> Result<Vec<(PathBuf, String)>> {
         let evidence_dir = self.file_manager.get_evidence_dir();
-        
-        // Count total files for progress tracking
-        let mut total_files = 0;
-        let mut processed_files = 0;
-        
-        // First pass: count total files for selected persons only
+        let mut files = Vec::new();
         for person in persons {
             let person_dir = evidence_dir.join(person.folder_name());
             if person_dir.exists() {
                 for entry in walkdir::WalkDir::new(&person_dir) {
                     let entry = entry.context("Failed to read directory entry")?;
                     if entry.file_type().is_file() {
-                        total_files += 1;
+                        let relative_path = entry.path().strip_prefix(evidence_dir)
+                            .context("Failed to strip evidence directory prefix")?;
+                        let zip_path = relative_path.to_string_lossy().replace('\\', "/");
+                        files.push((entry.path().to_path_buf(), zip_path));
                     }
                 }
             }
         }
+        Ok(files)
+    }
+
+    /// Writes the files, then `extra` entries made in memory, such as a manifest. Returns a warning
+    /// for each file that couldn't be watermarked and went into the archive as it is.
+    fn write_ema(mut zip: ZipWriter<fs::File>, files: Vec<(PathBuf, String)>, extra: Vec<(String, Vec<u8>)>, options: &ExportOptions, cancel: &CancellationToken, progress_callback: Option<ProgressCallback>) -> Result<Vec<String>> {
+        // Loaded up front so a key problem stops the export before any work is done
+        let signing_key = if options.sign { Some(SigningKey::load_or_create()?) } else { None };
+        let mut signed_files = BTreeMap::new();
+        let mut warnings = Vec::new();
+        let total_files = files.len();
         
-        // Second pass: add files for selected persons only
-        for person in persons {
-            let person_dir = evidence_dir.join(person.folder_name());
-            if person_dir.exists() {
-                for entry in walkdir::WalkDir::new(&person_dir) {
-                    let entry = entry.context("Failed to read directory entry")?;
-                    let path = entry.path();
-                    
-                    if entry.file_type().is_file() {
-                        let relative_path = path.strip_prefix(evidence_dir)
-                            .context("Failed to strip evidence directory prefix")?;
-                        
-                        let zip_path = relative_path.to_string_lossy().replace('\\', "/");
-                        
-                        zip.start_file(&zip_path, FileOptions::default())
-                            .context("Failed to start file in zip")?;
-                        
-                        let file_content = fs::read(path)
-                            .context("Failed to read file")?;
-                        
-                        zip.write_all(&file_content)
-                            .context("Failed to write file to zip")?;
-                        
-                        processed_files += 1;
-                        
-                        if let Some(ref callback) = progress_callback {
-                            let progress = (processed_files as f32 / total_files as f32 * 100.0) as u32;
-                            callback(format!("Exporting... {}%", progress));
+        // Workers read, watermark and compress files in parallel while this thread, the only
+        // writer, copies the compressed entries into the zip in the order they finish
+        let workers = std::thread::available_parallelism().map_or(1, |n| n.get()).clamp(1, MAX_EXPORT_WORKERS);
+        let next_file = AtomicUsize::new(0);
+        // Bounded so fast workers can't get far ahead of the writer; with entries under
+        // IN_MEMORY_LIMIT that caps what's held in memory
+        let (sender, receiver) = mpsc::sync_channel::<Result<PreparedFile>>(workers * 2);
+        
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                let sender = sender.clone();
+                let (files, next_file) = (&files, &next_file);
+                scope.spawn(move || {
+                    while !cancel.is_cancelled() {
+                        let Some((path, zip_path)) = files.get(next_file.fetch_add(1, Ordering::Relaxed)) else {
+                            break;
+                        };
+                        // The writer stopped after an error
+                        if sender.send(Self::compress_file(path, zip_path, options)).is_err() {
+                            break;
                         }
                     }
+                });
+            }
+            drop(sender);
+            
+            let mut processed_files = 0;
+            for prepared in receiver {
+                cancel.check()?;
+                let (zip_path, sha256, warning) = match prepared? {
+                    PreparedFile::Compressed { zip_path, entry, sha256, warning } => {
+                        let mut entry = zip::ZipArchive::new(Cursor::new(entry))
+                            .context("Failed to read compressed file")?;
+                        zip.raw_copy_file(entry.by_index_raw(0).context("Failed to read compressed file")?)
+                            .context("Failed to write file to zip")?;
+                        (zip_path, sha256, warning)
+                    }
+                    PreparedFile::Large { path, zip_path } => {
+                        let (sha256, warning) = Self::stream_file(&mut zip, &path, &zip_path, options)?;
+                        (zip_path, sha256, warning)
+                    }
+                };
+                warnings.extend(warning);
+                if signing_key.is_some() {
+                    signed_files.insert(zip_path, sha256);
+                }
+                
+                processed_files += 1;
+                
+                if let Some(ref callback) = progress_callback {
+                    callback(processed_files, total_files);
                 }
             }
+            // Workers stop early when cancelled, so a short archive must not count as finished
+            cancel.check()
+        })?;
+
+        for (zip_path, content) in extra {
+            zip.start_file(&zip_path, FileOptions::default())
+                .context("Failed to start file in zip")?;
+            zip.write_all(&content)
+                .context("Failed to write file to zip")?;
+            if signing_key.is_some() {
+                signed_files.insert(zip_path, hashing::sha256_hex(&content));
+            }
+        }
+
+        if let Some(key) = signing_key {
+            let signature = serde_json::to_string_pretty(&Signature::create(&key, signed_files))
+                .context("Failed to serialize signature")?;
+            zip.start_file(signing::ARCHIVE_SIGNATURE_ENTRY, FileOptions::default())
+                .context("Failed to start signature in zip")?;
+            zip.write_all(signature.as_bytes())
+                .context("Failed to write signature to zip")?;
         }
 
         zip.finish()
             .context("Failed to finish zip file")?;
 
-        Ok(())
+        Ok(warnings)
+    }
+
+    /// A JPEG preview of every image in the evidence, watermarked if requested. Images that can't
+    /// be decoded are left out; they're still in the manifest. Images too large to read get a
+    /// short text note in place of their preview, so one large photo doesn't stop the export. A
+    /// preview that can't be watermarked is kept without it, with a warning.
+    fn thumbnails(&self, evidence: &[(Person, Vec<EvidenceFile>)], options: &ExportOptions, cancel: &CancellationToken, warnings: &mut Vec<String>) -> Result<Vec<(String, Vec<u8>)>> {
+        let evidence_dir = self.file_manager.get_evidence_dir();
+        let mut thumbnails = Vec::new();
+        for evidence_file in evidence.iter().flat_map(|(_, files)| files) {
+            if evidence_file.file_type != EvidenceType::Image || !evidence_file.file_path.exists() {
+                continue;
+            }
+            cancel.check()?;
+            let relative_path = evidence_file.file_path.strip_prefix(evidence_dir)
+                .unwrap_or(&evidence_file.file_path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let content = match self.file_manager.read_evidence(&evidence_file.file_path) {
+                Ok(content) => content,
+                Err(e) => {
+                    let note = format!("No preview of {}: {:#}. The evidence manifest lists its size and SHA-256.\n", evidence_file.original_name, e);
+                    thumbnails.push((format!("{}/{}.txt", THUMBNAILS_FOLDER, relative_path), note.into_bytes()));
+                    continue;
+                }
+            };
+            let Ok(picture) = image::load_from_memory(&content) else {
+                continue;
+            };
+            // JPEG has no alpha channel
+            let preview = DynamicImage::ImageRgb8(picture.thumbnail(THUMBNAIL_EDGE, THUMBNAIL_EDGE).to_rgb8());
+            let mut content = Cursor::new(Vec::new());
+            preview.write_to(&mut content, ImageFormat::Jpeg)
+                .with_context(|| format!("Failed to make a preview of {}", evidence_file.file_path.display()))?;
+            let mut content = content.into_inner();
+            if let Some(ref watermark) = options.watermark {
+                match watermark.apply_to_image("jpg", &content) {
+                    Ok(stamped) => content = stamped,
+                    Err(e) => warnings.push(format!("The preview of {} has no watermark: {:#}", relative_path, e)),
+                }
+            }
+
+            thumbnails.push((format!("{}/{}.jpg", THUMBNAILS_FOLDER, relative_path), content));
+        }
+        Ok(thumbnails)
+    }
+
+    /// Runs on an export worker: compresses a file into a zip entry in memory, or leaves it for the
+    /// writer to stream when it's too large to hold
+    fn compress_file(path: &Path, zip_path: &str, options: &ExportOptions) -> Result<PreparedFile> {
+        let size = fs::metadata(path)
+            .with_context(|| format!("Failed to read {}", zip_path))?
+            .len();
+        if size > IN_MEMORY_LIMIT {
+            return Ok(PreparedFile::Large { path: path.to_path_buf(), zip_path: zip_path.to_string() });
+        }
+        let (content, warning) = Self::prepare_file(path, zip_path, options)?;
+        let mut entry = ZipWriter::new(Cursor::new(Vec::new()));
+        entry.start_file(zip_path, FileOptions::default())
+            .context("Failed to start file in zip")?;
+        entry.write_all(&content)
+            .context("Failed to compress file")?;
+        let entry = entry.finish()
+            .context("Failed to compress file")?
+            .into_inner();
+        Ok(PreparedFile::Compressed { zip_path: zip_path.to_string(), entry, sha256: hashing::sha256_hex(&content), warning })
+    }
+
+    /// Writes a large file into the archive on the writer thread; returns the SHA-256 of what was
+    /// written and any warning from watermarking it. Only files that are watermarked are read
+    /// whole, as stamping needs the decoded image or PDF.
+    fn stream_file(zip: &mut ZipWriter<fs::File>, path: &Path, zip_path: &str, options: &ExportOptions) -> Result<(String, Option<String>)> {
+        let zip64 = |size: u64| FileOptions::default().large_file(size >= u32::MAX as u64);
+        if options.watermark.is_some() && Watermark::supports_path(path) {
+            let (content, warning) = Self::prepare_file(path, zip_path, options)?;
+            zip.start_file(zip_path, zip64(content.len() as u64))
+                .context("Failed to start file in zip")?;
+            zip.write_all(&content)
+                .context("Failed to write file to zip")?;
+            return Ok((hashing::sha256_hex(&content), warning));
+        }
+        let mut file = fs::File::open(path)
+            .with_context(|| format!("Failed to read {}", zip_path))?;
+        let size = file.metadata()
+            .with_context(|| format!("Failed to read {}", zip_path))?
+            .len();
+        zip.start_file(zip_path, zip64(size))
+            .context("Failed to start file in zip")?;
+        let sha256 = hashing::copy_hashing(&mut file, zip, |_| {})
+            .with_context(|| format!("Failed to write {} to zip", zip_path))?;
+        Ok((sha256, None))
+    }
+
+    /// Reads a file for export, watermarking it if requested. A file that can't be stamped, such
+    /// as a damaged image or an unusual PDF, is exported as it is, with a warning saying so.
+    fn prepare_file(path: &Path, zip_path: &str, options: &ExportOptions) -> Result<(Vec<u8>, Option<String>)> {
+        let file_content = fs::read(path)
+            .with_context(|| format!("Failed to read {}", zip_path))?;
+        Ok(Self::watermarked(file_content, path, zip_path, options))
+    }
+
+    /// The content stamped with the export's watermark when it has one and the file's type can be stamped
+    fn watermarked(content: Vec<u8>, path: &Path, zip_path: &str, options: &ExportOptions) -> (Vec<u8>, Option<String>) {
+        let Some(ref watermark) = options.watermark else {
+            return (content, None);
+        };
+        if !Watermark::supports_path(path) {
+            return (content, None);
+        }
+        let extension = path.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+        match watermark.apply(&extension, &content) {
+            Ok(stamped) => (stamped, None),
+            Err(e) => {
+                let warning = format!("{} was exported without a watermark: {:#}", zip_path, e);
+                (content, Some(warning))
+            }
+        }
+    }
+
+    /// Writes only the given files into a plain zip, optionally with a CSV manifest of their SHA-256
+    /// hashes. Returns warnings about files exported without the watermark that was asked for.
+    pub fn export_selection(&self, output_path: &Path, files: &[EvidenceFile], options: &ExportOptions, include_manifest: bool, cancel: &CancellationToken, progress_callback: Option<ProgressCallback>) -> Result<Vec<String>> {
+        ensure_room_for(output_path, files.iter().map(|f| f.size).sum())?;
+
+        let file = fs::File::create(output_path)
+            .context("Failed to create output file")?;
+        let result = self.write_selection(ZipWriter::new(file), files, options, include_manifest, cancel, progress_callback);
+        if result.is_err() {
+            let _ = fs::remove_file(output_path);
+        }
+        result
     }
 
-    pub fn import_from_ema(&self, input_path: &Path, progress_callback: Option<Box<dyn Fn(String) + Send + Sync>>) -> Result<Vec<Person>> {
+    fn write_selection(&self, mut zip: ZipWriter<fs::File>, files: &[EvidenceFile], options: &ExportOptions, include_manifest: bool, cancel: &CancellationToken, progress_callback: Option<ProgressCallback>) -> Result<Vec<String>> {
+        let mut manifest = String::from("file,size,sha256,original_sha256\n");
+        let mut warnings = Vec::new();
+
+        for (i, evidence_file) in files.iter().enumerate() {
+            if let Some(ref callback) = progress_callback {
+                callback(i, files.len());
+            }
+            cancel.check()?;
+            let file_name = evidence_file.file_path.file_name()
+                .context("Evidence file has no name")?
+                .to_string_lossy();
+            let zip_path = format!("{}/{}", evidence_file.file_type.folder_name(), file_name);
+
+            let original = fs::read(&evidence_file.file_path)
+                .with_context(|| format!("Failed to read {}", zip_path))?;
+            let (file_content, warning) = Self::watermarked(original.clone(), &evidence_file.file_path, &zip_path, options);
+            warnings.extend(warning);
+
+            zip.start_file(&zip_path, FileOptions::default())
+                .context("Failed to start file in zip")?;
+            zip.write_all(&file_content)
+                .context("Failed to write file to zip")?;
+
+            if include_manifest {
+                // Both hashes are listed so a watermarked copy can still be matched to the stored original
+                manifest.push_str(&format!(
+                    "{},{},{},{}\n",
+                    csv_field(&zip_path),
+                    file_content.len(),
+                    hashing::sha256_hex(&file_content),
+                    hashing::sha256_hex(&original),
+                ));
+            }
+        }
+
+        if include_manifest {
+            zip.start_file("manifest.csv", FileOptions::default())
+                .context("Failed to start manifest in zip")?;
+            zip.write_all(manifest.as_bytes())
+                .context("Failed to write manifest to zip")?;
+        }
+
+        zip.finish()
+            .context("Failed to finish zip file")?;
+
+        Ok(warnings)
+    }
+
+    pub fn import_from_ema(&self, input_path: &Path, cancel: &CancellationToken, progress_callback: Option<ProgressCallback>) -> Result<ImportReport> {
         let file = fs::File::open(input_path)
             .context("Failed to open input file")?;
         let mut zip = zip::ZipArchive::new(file)
             .context("Failed to read zip file")?;
 
         let evidence_dir = self.file_manager.get_evidence_dir();
-        let mut persons = Vec::new();
+        let mut report = ImportReport::default();
         
         let total_files = zip.len();
         
-        // Extract all files directly to the Evidence directory
+        // Refuse the whole archive before writing anything if any entry would land outside the store
+        let mut outpaths = Vec::with_capacity(total_files);
+        let mut needed = 0u64;
         for i in 0..total_files {
+            let file = zip.by_index(i)
+                .context("Failed to read file from zip")?;
+            needed = needed.saturating_add(file.size());
+            let name = file.enclosed_name()
+                .with_context(|| format!("Archive entry \"{}\" points outside the Evidence folder", file.name()))?;
+            let is_symlink = file.unix_mode().is_some_and(|mode| mode & 0o170000 == 0o120000);
+            if is_symlink {
+                bail!("Archive entry \"{}\" is a symbolic link", file.name());
+            }
+            outpaths.push((!file.is_dir()).then(|| name.to_path_buf()));
+        }
+        // Sizes in the zip directory are what extraction writes, at most; files already present are skipped
+        FileManager::ensure_free_space(evidence_dir, needed)?;
+        
+        // A signed archive must match its signature exactly before anything is extracted
+        let signature_index = outpaths.iter().position(|path| path.as_deref() == Some(Path::new(signing::ARCHIVE_SIGNATURE_ENTRY)));
+        if let Some(index) = signature_index {
+            report.signed_by = Some(Self::verify_signature(&mut zip, &outpaths, index)?);
+            outpaths[index] = None;
+        }
+        
+        // The manifest of a metadata-only archive describes files it doesn't carry, so it isn't
+        // extracted, and neither are the previews of a thumbnail-only one
+        if let Some(index) = outpaths.iter().position(|path| path.as_deref() == Some(Path::new(METADATA_ONLY_MANIFEST))) {
+            outpaths[index] = None;
+            report.warnings.push("The archive holds metadata only: the evidence files it lists aren't included and show as missing under Verify Store".to_string());
+        }
+        for path in outpaths.iter_mut() {
+            if path.as_ref().is_some_and(|path| path.starts_with(THUMBNAILS_FOLDER)) {
+                *path = None;
+            }
+        }
+        let watermark_note = outpaths.iter().position(|path| path.as_deref() == Some(Path::new(WATERMARK_NOTE)));
+        if let Some(index) = watermark_note {
+            outpaths[index] = None;
+        }
+        let watermarked = watermark_note.is_some();
+        
+        // Each person is a top-level folder in the archive
+        let mut person_folders: Vec<PathBuf> = outpaths
+            .iter()
+            .flatten()
+            .filter(|path| path.components().count() > 1)
+            .filter_map(|path| path.components().next())
+            .map(|folder| PathBuf::from(folder.as_os_str()))
+            .collect();
+        person_folders.sort();
+        person_folders.dedup();
+        let existing_folders: Vec<bool> = person_folders
+            .iter()
+            .map(|folder| evidence_dir.join(folder).join("person_data.json").exists())
+            .collect();
+        // Persons under legal hold keep their stored records and files; the archive can only add to them
+        let held_folders: Vec<PathBuf> = person_folders
+            .iter()
+            .filter(|folder| self.file_manager.load_person_data(&evidence_dir.join(folder)).is_ok_and(|person| person.legal_hold))
+            .cloned()
+            .collect();
+        
+        // The journal lets an import cut short by a crash be resumed or rolled back at the next start.
+        // One left by an interrupted import of this same archive is picked up where it stopped.
+        let archive_sha256 = hashing::sha256_file(input_path)?;
+        let mut journal = match ImportJournal::load(evidence_dir)? {
+            Some(journal) if journal.archive_sha256 == archive_sha256 => {
+                report.resumed = true;
+                journal
+            }
+            Some(journal) => bail!("An import of {} was interrupted; resume or roll it back before importing another archive", journal.archive.display()),
+            None => ImportJournal::begin(evidence_dir, input_path, archive_sha256, outpaths.iter().flatten().count())?,
+        };
+        
+        // Everything is extracted into a staging folder and checked there before any of it is moved
+        // into the Evidence folder, so a damaged archive never leaves the store half-imported
+        let staging_dir = journal.staging_dir();
+        let result = self.stage_entries(&mut zip, &outpaths, &staging_dir, cancel, &mut journal, progress_callback)
+            .and_then(|()| {
+                self.validate_staged(&staging_dir, &person_folders, watermarked, &mut report)?;
+                self.commit_staged(&outpaths, &staging_dir, watermarked, &held_folders, &mut journal, &mut report)
+            });
+        // Every file the import put in place, including those moved by an interrupted run
+        let written: Vec<PathBuf> = journal.created.iter().chain(&journal.replaced).cloned().collect();
+        match result {
+            Ok(()) => journal.finish()?,
+            Err(e) => {
+                // Files the import added are taken out again and files it overwrote are restored
+                let _ = journal.roll_back(evidence_dir);
+                return Err(e);
+            }
+        }
+        
+        // Now load the persons the archive contained and ensure all subdirectories exist
+        for (folder, existed) in person_folders.into_iter().zip(existing_folders) {
+            let folder = evidence_dir.join(folder);
+            match self.file_manager.load_person_data(&folder) {
+                Ok(person) => {
+                    // Ensure all required subdirectories exist for this person
+                    self.ensure_person_subdirectories(&person)?;
+                    // The files are in place either way; only sharing their content is left undone
+                    if let Err(e) = self.file_manager.store_imported_content(&person, &written) {
+                        report.warnings.push(format!("{}: imported files weren't added to the blob store ({:#})", person.name, e));
+                    }
+                    if existed {
+                        report.persons_merged += 1;
+                    } else {
+                        report.persons_added += 1;
+                    }
+                    report.persons.push(person);
+                }
+                Err(e) => {
+                    let name = folder.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                    report.warnings.push(format!("{}: files were extracted but no person could be loaded ({})", name, e));
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Checks the archive's signature and that every file matches the hash it signed; returns the key's fingerprint
+    fn verify_signature(zip: &mut zip::ZipArchive<fs::File>, outpaths: &[Option<PathBuf>], signature_index: usize) -> Result<String> {
+        let mut json = String::new();
+        zip.by_index(signature_index)
+            .context("Failed to read signature from zip")?
+            .read_to_string(&mut json)
+            .context("Failed to read signature from zip")?;
+        let signature: Signature = serde_json::from_str(&json)
+            .context("Failed to parse the archive's signature")?;
+        signature.verify()?;
+
+        let mut actual_files = BTreeMap::new();
+        for (i, outpath) in outpaths.iter().enumerate() {
+            if i == signature_index || outpath.is_none() {
+                continue;
+            }
+            let mut file = zip.by_index(i)
+                .context("Failed to read file from zip")?;
+            let mut content = Vec::new();
+            file.read_to_end(&mut content)
+                .context("Failed to read file from zip")?;
+            actual_files.insert(file.name().to_string(), hashing::sha256_hex(&content));
+        }
+        if actual_files != signature.files {
+            bail!("The archive was changed after it was signed: its files don't match the signed list");
+        }
+        Ok(signature.fingerprint)
+    }
+
+    /// Extracts the archive's files into the staging folder, skipping those an interrupted run already staged
+    fn stage_entries(&self, zip: &mut zip::ZipArchive<fs::File>, outpaths: &[Option<PathBuf>], staging_dir: &Path, cancel: &CancellationToken, journal: &mut ImportJournal, progress_callback: Option<ProgressCallback>) -> Result<()> {
+        let total_files = outpaths.len();
+        for (i, outpath) in outpaths.iter().enumerate() {
+            cancel.check()?;
             let mut file = zip.by_index(i)
                 .context("Failed to read file from zip")?;
             
             if let Some(ref callback) = progress_callback {
-                let progress = ((i + 1) as f32 / total_files as f32 * 100.0) as u32;
-                callback(format!("Importing... {}%", progress));
+                callback(i + 1, total_files);
             }
             
-            let outpath = match file.enclosed_name() {
-                Some(path) => evidence_dir.join(path),
-                None => continue,
+            let Some(relative_path) = outpath else {
+                continue;
             };
-            
-            // Ensure the target directory exists
-            if let Some(parent) = outpath.parent() {
+            if journal.completed.contains(&i) {
+                continue;
+            }
+            let staged = paths::join_within(staging_dir, relative_path)?;
+            if let Some(parent) = staged.parent() {
                 fs::create_dir_all(parent)
-                    .context("Failed to create target directory")?;
+                    .context("Failed to create staging directory")?;
             }
             
-            // Extract the file
+            // The zip reader checks each file's CRC as it reads to the end
             let mut file_content = Vec::new();
             file.read_to_end(&mut file_content)
-                .context("Failed to read file from zip")?;
-            
-            fs::write(&outpath, file_content)
+                .with_context(|| format!("Failed to read {} from zip", relative_path.display()))?;
+            fs::write(&staged, file_content)
                 .context("Failed to write extracted file")?;
+            journal.done(i)?;
         }
+        Ok(())
+    }
+
+    /// Checks that every person in the archive has readable person data and evidence index, and
+    /// that every staged evidence file matches the SHA-256 its index records. Files already moved
+    /// into place by an interrupted run are checked where they now are; their hashes were checked
+    /// before that run started moving them. Images and PDFs may differ only in a watermarked archive.
+    fn validate_staged(&self, staging_dir: &Path, person_folders: &[PathBuf], watermarked: bool, report: &mut ImportReport) -> Result<()> {
+        let evidence_dir = self.file_manager.get_evidence_dir();
+        let read = |relative: &Path| {
+            let staged = staging_dir.join(relative);
+            fs::read_to_string(if staged.exists() { staged } else { evidence_dir.join(relative) })
+        };
         
-        // Now load all persons from the extracted data and ensure all subdirectories exist
-        for entry in fs::read_dir(&evidence_dir)
-            .context("Failed to read Evidence directory")?
-        {
-            let entry = entry.context("Failed to read directory entry")?;
-            let path = entry.path();
-
-            if path.is_dir() && path.file_name().and_then(|n| n.to_str()).map(|s| s != ".").unwrap_or(false) {
-                if let Ok(person) = self.file_manager.load_person_data(&path) {
-                    // Ensure all required subdirectories exist for this person
-                    self.ensure_person_subdirectories(&person)?;
-                    persons.push(person);
+        for folder in person_folders {
+            let person_data = read(&folder.join("person_data.json"))
+                .with_context(|| format!("{} has no person data", folder.display()))?;
+            serde_json::from_str::<Person>(&person_data)
+                .with_context(|| format!("{} has damaged person data", folder.display()))?;
+            if let Ok(index) = read(&folder.join("evidence_index.json")) {
+                let index = serde_json::from_str::<Vec<EvidenceFile>>(&index)
+                    .with_context(|| format!("{} has a damaged evidence index", folder.display()))?;
+                // Entries for files the archive doesn't carry are left to Verify Store
+                for entry in &index {
+                    let Some(expected) = &entry.sha256 else {
+                        continue;
+                    };
+                    let Ok(staged) = paths::join_within(&staging_dir.join(folder), &entry.file_path) else {
+                        continue;
+                    };
+                    if !staged.exists() {
+                        continue;
+                    }
+                    if hashing::sha256_file(&staged)? == *expected {
+                        continue;
+                    }
+                    // Watermarked exports carry stamped copies of images and PDFs under the originals' hashes
+                    if watermarked && Watermark::supports_path(&entry.file_path) {
+                        report.warnings.push(format!("{} in {} is a watermarked copy; its SHA-256 differs from the evidence index", entry.file_path.display(), folder.display()));
+                    } else {
+                        bail!("{} in {} doesn't match the SHA-256 in its evidence index", entry.file_path.display(), folder.display());
+                    }
                 }
             }
         }
+        Ok(())
+    }
 
-        Ok(persons)
+    /// Moves the staged files into the Evidence folder. Each move is recorded in the journal first,
+    /// and files it overwrites are moved to the journal's backup folder, so the store can be put
+    /// back as it was if the import fails or the app stops partway. Originals already in the store
+    /// are kept rather than replaced by a watermarked archive's stamped copies, and nothing stored
+    /// in the folder of a person under legal hold is replaced.
+    fn commit_staged(&self, outpaths: &[Option<PathBuf>], staging_dir: &Path, watermarked: bool, held_folders: &[PathBuf], journal: &mut ImportJournal, report: &mut ImportReport) -> Result<()> {
+        let evidence_dir = self.file_manager.get_evidence_dir();
+        for (i, relative_path) in outpaths.iter().enumerate() {
+            let Some(relative_path) = relative_path else {
+                continue;
+            };
+            let staged = staging_dir.join(relative_path);
+            // Moved already before the import was interrupted
+            if !staged.exists() {
+                continue;
+            }
+            // Symlinks already in the store are checked right before writing
+            let outpath = paths::join_within(evidence_dir, relative_path)?;
+            if let Some(parent) = outpath.parent() {
+                fs::create_dir_all(parent)
+                    .context("Failed to create target directory")?;
+            }
+            
+            let (created, replaced) = if !outpath.exists() {
+                (Some(relative_path.as_path()), None)
+            } else if journal.created.contains(relative_path) {
+                // Added by the interrupted run
+                (None, None)
+            } else if fs::read(&outpath).is_ok_and(|existing| fs::read(&staged).is_ok_and(|content| content == existing)) {
+                report.files_skipped += 1;
+                fs::remove_file(&staged)
+                    .context("Failed to remove staged file")?;
+                continue;
+            } else if held_folders.iter().any(|folder| relative_path.starts_with(folder)) {
+                report.files_skipped += 1;
+                report.warnings.push(format!("Kept the stored {}: its person is under legal hold", relative_path.display()));
+                fs::remove_file(&staged)
+                    .context("Failed to remove staged file")?;
+                continue;
+            } else if watermarked && Watermark::supports_path(relative_path) {
+                report.files_skipped += 1;
+                report.warnings.push(format!("Kept the stored {} rather than the archive's watermarked copy", relative_path.display()));
+                fs::remove_file(&staged)
+                    .context("Failed to remove staged file")?;
+                continue;
+            } else {
+                if !matches!(outpath.file_name().and_then(|n| n.to_str()), Some("person_data.json") | Some("evidence_index.json") | Some(board::FILE_NAME)) {
+                    // Person records are expected to be updated; evidence being replaced is worth knowing about
+                    report.warnings.push(format!("Replaced {} with the archive's version", relative_path.display()));
+                }
+                (None, Some(relative_path.as_path()))
+            };
+            journal.writing(i, created, replaced)?;
+            if replaced.is_some() {
+                // Moved aside rather than overwritten, so a roll back can put it back
+                let backup = paths::join_within(&journal.backup_dir(), relative_path)?;
+                if let Some(parent) = backup.parent() {
+                    fs::create_dir_all(parent)
+                        .context("Failed to create import backup directory")?;
+                }
+                fs::rename(&outpath, &backup)
+                    .with_context(|| format!("Failed to back up {}", relative_path.display()))?;
+            }
+            fs::rename(&staged, &outpath)
+                .context("Failed to move imported file into place")?;
+            report.files_written += 1;
+        }
+        Ok(())
     }
 
     /// Ensures all required subdirectories exist for a person
@@ -149,7 +776,7 @@ impl ExportImportManager {
         let person_folder = self.file_manager.get_evidence_dir().join(person.folder_name());
         
         // Create all required subdirectories
-        for evidence_type in [EvidenceType::Image, EvidenceType::Audio, EvidenceType::Video, EvidenceType::Document, EvidenceType::Quote] {
+        for evidence_type in EvidenceType::built_in() {
             let subfolder = person_folder.join(evidence_type.folder_name());
             fs::create_dir_all(&subfolder)
                 .context("Failed to create evidence subfolder")?;
@@ -158,4 +785,209 @@ impl ExportImportManager {
         Ok(())
     }
 
+    /// Writes one row per person with their information and custom fields, for use in a spreadsheet
+    pub fn export_persons_csv(&self, output_path: &Path, persons: &[Person], custom_fields: &[CustomField]) -> Result<()> {
+        let mut header = vec!["name", "role", "case_status", "priority", "created", "updated", "information"];
+        header.extend(custom_fields.iter().map(|field| field.name.as_str()));
+        let mut csv = header.iter().map(|h| csv_field(h)).collect::<Vec<_>>().join(",");
+        csv.push('\n');
+
+        for person in persons {
+            let information = person.information
+                .iter()
+                .map(|info| format!("{}: {}", info.info_type, info.value))
+                .collect::<Vec<_>>()
+                .join("; ");
+            let mut row = vec![
+                person.name.clone(),
+                if person.role == CaseRole::Unassigned { String::new() } else { person.role.label().to_string() },
+                person.case_status.label().to_string(),
+                person.priority.label().to_string(),
+                person.created_at.to_rfc3339(),
+                person.updated_at.to_rfc3339(),
+                information,
+            ];
+            // Machine-readable values rather than the display form, so spreadsheets parse them
+            row.extend(custom_fields.iter().map(|field| match person.custom_fields.get(&field.name) {
+                Some(FieldValue::Boolean(value)) => value.to_string(),
+                Some(value) => value.to_string(),
+                None => String::new(),
+            }));
+            csv.push_str(&row.iter().map(|value| csv_field(value)).collect::<Vec<_>>().join(","));
+            csv.push('\n');
+        }
+
+        fs::write(output_path, csv)
+            .context("Failed to write CSV file")?;
+        Ok(())
+    }
+
+    /// Writes a CSV listing every evidence file of the given persons with its size, SHA-256 and
+    /// intake date, to hand over alongside the evidence. Returns the number of files listed.
+    pub fn export_hash_manifest(&self, output_path: &Path, evidence: &[(Person, Vec<EvidenceFile>)]) -> Result<usize> {
+        let (csv, count) = self.hash_manifest_csv(evidence)?;
+        fs::write(output_path, csv)
+            .context("Failed to write hash manifest")?;
+        Ok(count)
+    }
+
+    /// One CSV row per evidence file with its path, size, SHA-256 and intake date, and the number of rows
+    fn hash_manifest_csv(&self, evidence: &[(Person, Vec<EvidenceFile>)]) -> Result<(String, usize)> {
+        let evidence_dir = self.file_manager.get_evidence_dir();
+        let mut csv = String::from("person,path,size,sha256,intake_date\n");
+        let mut count = 0;
+
+        for (person, files) in evidence {
+            // Entries whose file is gone have nothing to hand over
+            for evidence_file in files.iter().filter(|f| f.file_path.exists()) {
+                // Hashed from the file on disk, not the index, so the manifest reflects what is handed over.
+                // Streamed, so large videos don't have to fit in memory.
+                let real_path = self.file_manager.resolve_evidence(&evidence_file.file_path)
+                    .with_context(|| format!("Failed to read {}", evidence_file.file_path.display()))?;
+                let size = fs::metadata(&real_path)
+                    .with_context(|| format!("Failed to read {}", evidence_file.file_path.display()))?
+                    .len();
+                let sha256 = hashing::sha256_file(&real_path)
+                    .with_context(|| format!("Failed to hash {}", evidence_file.file_path.display()))?;
+                let path = evidence_file.file_path.strip_prefix(evidence_dir)
+                    .unwrap_or(&evidence_file.file_path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                csv.push_str(&format!(
+                    "{},{},{},{},{}\n",
+                    csv_field(&person.name),
+                    csv_field(&path),
+                    size,
+                    sha256,
+                    evidence_file.created_at.to_rfc3339(),
+                ));
+                count += 1;
+            }
+        }
+        Ok((csv, count))
+    }
+
+}
+
+/// Checks there is room for an export of about `needed` bytes where `output_path` will be written.
+/// Compression usually makes the archive smaller, so this errs on the safe side.
+fn ensure_room_for(output_path: &Path, needed: u64) -> Result<()> {
+    let dir = output_path.parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    FileManager::ensure_free_space(dir, needed)
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    /// A fresh folder under the system temp folder, removed when dropped
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("evidence-manager-{}-{}", name, uuid::Uuid::new_v4()));
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// Text-like content that compresses about as well as documents do
+    fn sample_content(seed: usize, size: usize) -> Vec<u8> {
+        let line = format!("Statement {} taken at the scene, page {{}} of the interview notes.\n", seed);
+        let mut content = Vec::with_capacity(size);
+        let mut page = 0;
+        while content.len() < size {
+            content.extend_from_slice(line.replace("{}", &page.to_string()).as_bytes());
+            page += 1;
+        }
+        content.truncate(size);
+        content
+    }
+
+    /// Writes the files into an archive, and returns each entry's content as read back from it
+    fn write_and_read(dir: &Path, files: Vec<(PathBuf, String)>) -> BTreeMap<String, Vec<u8>> {
+        let archive = dir.join("export.ema");
+        let zip = ZipWriter::new(fs::File::create(&archive).unwrap());
+        ExportImportManager::write_ema(zip, files, Vec::new(), &ExportOptions::default(), &CancellationToken::default(), None).unwrap();
+
+        let mut zip = zip::ZipArchive::new(fs::File::open(&archive).unwrap()).unwrap();
+        (0..zip.len())
+            .map(|i| {
+                let mut entry = zip.by_index(i).unwrap();
+                let mut content = Vec::new();
+                entry.read_to_end(&mut content).unwrap();
+                (entry.name().to_string(), content)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn write_ema_keeps_small_and_streamed_files_intact() {
+        let dir = TempDir::new("export");
+        let mut expected = BTreeMap::new();
+        let mut files = Vec::new();
+        // Small files go through the workers; the last is over IN_MEMORY_LIMIT and is streamed
+        let sizes = [0, 1, 4096, 300_000, IN_MEMORY_LIMIT as usize + 1];
+        for (i, size) in sizes.into_iter().enumerate() {
+            let content = sample_content(i, size);
+            let path = dir.0.join(format!("file{}.txt", i));
+            fs::write(&path, &content).unwrap();
+            let zip_path = format!("Person_1/Documents/file{}.txt", i);
+            files.push((path, zip_path.clone()));
+            expected.insert(zip_path, content);
+        }
+
+        assert_eq!(write_and_read(&dir.0, files), expected);
+    }
+
+    /// Times an export of 400 files of 1 MB and one of 200 MB. Not run by default; run it with
+    /// `cargo test --release export_timing -- --ignored --nocapture` before and after changing
+    /// `write_ema` and compare the throughput it prints.
+    #[test]
+    #[ignore]
+    fn export_timing() {
+        let dir = TempDir::new("export-timing");
+        let mut files = Vec::new();
+        let mut total = 0;
+        for i in 0..401 {
+            let size = if i == 400 { 200 * 1024 * 1024 } else { 1024 * 1024 };
+            let path = dir.0.join(format!("file{}.txt", i));
+            fs::write(&path, sample_content(i, size)).unwrap();
+            files.push((path, format!("Person_1/Documents/file{}.txt", i)));
+            total += size;
+        }
+
+        let archive = dir.0.join("export.ema");
+        let started = Instant::now();
+        let zip = ZipWriter::new(fs::File::create(&archive).unwrap());
+        ExportImportManager::write_ema(zip, files, Vec::new(), &ExportOptions::default(), &CancellationToken::default(), None).unwrap();
+        let elapsed = started.elapsed();
+
+        let archive_size = fs::metadata(&archive).unwrap().len();
+        println!(
+            "{} MB in {:.2?} ({:.0} MB/s), archive {} MB",
+            total / (1024 * 1024),
+            elapsed,
+            total as f64 / (1024.0 * 1024.0) / elapsed.as_secs_f64(),
+            archive_size / (1024 * 1024)
+        );
+    }
 }
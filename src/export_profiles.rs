@@ -0,0 +1,166 @@
+use crate::encryption;
+use crate::export_import::{CancellationToken, ExportImportManager, ExportOptions, ProgressCallback};
+use crate::models::Person;
+use crate::naming;
+use crate::settings::ExportProfile;
+use anyhow::{Result, Context, bail};
+use chrono::Local;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Environment variable `unpack` reads the passphrase of an encrypted archive from
+const PASSPHRASE_VARIABLE: &str = "EVIDENCE_MANAGER_ARCHIVE_PASSPHRASE";
+
+const MB: u64 = 1024 * 1024;
+
+/// Exports the profile's people to its destination, then encrypts and splits the archive as the
+/// profile asks. Returns the files written and the export's warnings.
+pub fn run(
+    profile: &ExportProfile,
+    persons: &[Person],
+    manager: &ExportImportManager,
+    options: &ExportOptions,
+    cancel: &CancellationToken,
+    progress: Option<ProgressCallback>,
+) -> Result<(Vec<PathBuf>, Vec<String>)> {
+    if !profile.destination.is_dir() {
+        bail!("The destination folder {} doesn't exist", profile.destination.display());
+    }
+    if profile.encrypt && profile.passphrase.is_empty() {
+        bail!("The profile encrypts its archives and its passphrase hasn't been entered since the app started; use Run Now to enter it");
+    }
+    let stamp = Local::now().format("%Y-%m-%d_%H%M").to_string();
+    let name = naming::sanitize_file_name(&format!("{}_{}", profile.name.replace(' ', "_"), stamp));
+    let mut archive = profile.destination.join(format!("{}.ema", name));
+    let warnings = manager.export_to_ema(&archive, persons, options, cancel, progress)?;
+
+    if profile.encrypt {
+        let encrypted = profile.destination.join(format!("{}.ema.{}", name, encryption::EXTENSION));
+        let result = encryption::encrypt_file(&archive, &encrypted, &profile.passphrase);
+        // The unencrypted archive isn't left behind either way
+        fs::remove_file(&archive)
+            .context("Failed to remove unencrypted archive")?;
+        result?;
+        archive = encrypted;
+    }
+
+    if profile.split_size_mb > 0 {
+        return Ok((split_file(&archive, profile.split_size_mb * MB)?, warnings));
+    }
+    Ok((vec![archive], warnings))
+}
+
+/// Cuts a file into parts of at most `part_size` bytes, named "<file>.001", "<file>.002" and
+/// so on, and removes the original. A file that fits in one part is left as it is.
+pub fn split_file(path: &Path, part_size: u64) -> Result<Vec<PathBuf>> {
+    let size = fs::metadata(path).context("Failed to read archive size")?.len();
+    if size <= part_size {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut reader = BufReader::new(File::open(path).context("Failed to open archive to split")?);
+    let mut parts = Vec::new();
+    let mut written = 0;
+    while written < size {
+        let part = part_path(path, parts.len() + 1);
+        let mut writer = BufWriter::new(File::create(&part).context("Failed to create archive part")?);
+        written += io::copy(&mut (&mut reader).take(part_size), &mut writer)
+            .context("Failed to write archive part")?;
+        writer.flush().context("Failed to write archive part")?;
+        parts.push(part);
+    }
+    fs::remove_file(path)
+        .context("Failed to remove archive after splitting")?;
+    Ok(parts)
+}
+
+/// Joins "<file>.001" and the parts after it into `destination`; returns the number of parts
+pub fn join_parts(first: &Path, destination: &Path) -> Result<usize> {
+    let Some(whole) = first.to_str().and_then(|path| path.strip_suffix(".001")) else {
+        bail!("{} isn't the first part of a split archive (.001)", first.display());
+    };
+    let whole = Path::new(whole);
+    let mut writer = BufWriter::new(File::create(destination).context("Failed to create joined archive")?);
+    let mut count = 0;
+    while part_path(whole, count + 1).exists() {
+        let mut part = File::open(part_path(whole, count + 1)).context("Failed to open archive part")?;
+        io::copy(&mut part, &mut writer).context("Failed to join archive parts")?;
+        count += 1;
+    }
+    writer.flush().context("Failed to write joined archive")?;
+    Ok(count)
+}
+
+fn part_path(path: &Path, number: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{:03}", number));
+    PathBuf::from(name)
+}
+
+/// `evidence-manager unpack <file> [output]`: joins a split archive (given its .001 part) and
+/// decrypts an encrypted one, leaving a plain .ema to import. Returns the process exit code.
+pub fn unpack(input: Option<String>, output: Option<String>) -> i32 {
+    let Some(input) = input else {
+        eprintln!("usage: evidence-manager unpack <archive.ema.enc | archive.ema.001> [output.ema]");
+        return 2;
+    };
+    match unpack_archive(Path::new(&input), output.map(PathBuf::from)) {
+        Ok(path) => {
+            println!("{}", path.display());
+            0
+        }
+        Err(e) => {
+            eprintln!("unpack: {:#}", e);
+            1
+        }
+    }
+}
+
+fn unpack_archive(input: &Path, output: Option<PathBuf>) -> Result<PathBuf> {
+    let mut current = input.to_path_buf();
+    let mut temporary = None;
+    if let Some(whole) = input.to_str().and_then(|path| path.strip_suffix(".001")) {
+        let joined = PathBuf::from(whole);
+        if joined.exists() {
+            bail!("{} already exists", joined.display());
+        }
+        let count = join_parts(input, &joined)?;
+        eprintln!("Joined {} parts", count);
+        temporary = Some(joined.clone());
+        current = joined;
+    }
+
+    if !encryption::is_encrypted(&current) {
+        return Ok(match output {
+            Some(output) => {
+                fs::rename(&current, &output).context("Failed to move joined archive")?;
+                output
+            }
+            None => current,
+        });
+    }
+
+    let destination = output.unwrap_or_else(|| {
+        let path = current.to_string_lossy().to_string();
+        PathBuf::from(path.strip_suffix(&format!(".{}", encryption::EXTENSION)).unwrap_or(&format!("{}.ema", path)).to_string())
+    });
+    if destination.exists() {
+        bail!("{} already exists", destination.display());
+    }
+    let passphrase = match std::env::var(PASSPHRASE_VARIABLE) {
+        Ok(passphrase) => passphrase,
+        Err(_) => {
+            eprint!("Passphrase: ");
+            io::stderr().flush().ok();
+            let mut line = String::new();
+            io::stdin().lock().read_line(&mut line).context("Failed to read passphrase")?;
+            line.trim_end_matches(['\r', '\n']).to_string()
+        }
+    };
+    let result = encryption::decrypt_file(&current, &destination, &passphrase);
+    if let Some(temporary) = temporary {
+        let _ = fs::remove_file(temporary);
+    }
+    result?;
+    Ok(destination)
+}
@@ -0,0 +1,363 @@
+use crate::audit::AuditLog;
+use crate::file_manager::{FileManager, Intake, IntakeContext};
+use crate::hashing;
+use crate::models::{EvidenceFile, EvidenceType, Person, Provenance};
+use crate::plugins::Plugins;
+use crate::quarantine::QuarantinedFile;
+use crate::settings::Settings;
+use anyhow::{Result, Context, bail};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Evidence details a column of a file list can fill in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MappedField {
+    File, // Where the exported file is, relative to the CSV
+    OriginalPath, // Where the tool found it on the source machine
+    Hash,
+    Collected,
+    Custodian,
+}
+
+impl MappedField {
+    pub const ALL: [MappedField; 5] = [
+        MappedField::File,
+        MappedField::OriginalPath,
+        MappedField::Hash,
+        MappedField::Collected,
+        MappedField::Custodian,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MappedField::File => "Exported file",
+            MappedField::OriginalPath => "Original path",
+            MappedField::Hash => "MD5 / SHA hash",
+            MappedField::Collected => "Collected date",
+            MappedField::Custodian => "Custodian",
+        }
+    }
+
+    // Header names, lowercase without spaces or punctuation, in order of preference
+    fn guesses(&self) -> &'static [&'static str] {
+        match self {
+            MappedField::File => &["exportedpath", "exportpath", "relativepath", "filepath", "file", "filename", "path", "name"],
+            MappedField::OriginalPath => &["originalpath", "sourcepath", "fullpath", "original", "source", "location"],
+            MappedField::Hash => &["sha256", "sha256hash", "sha1", "sha1hash", "md5", "md5hash", "hash"],
+            MappedField::Collected => &["collecteddate", "datecollected", "collected", "collectiondate", "acquisitiondate", "dateacquired", "acquired", "date"],
+            MappedField::Custodian => &["custodian", "custodianname", "owner"],
+        }
+    }
+}
+
+/// Which column, by index, fills each field
+pub type ColumnMapping = HashMap<MappedField, usize>;
+
+/// A "file list + metadata" CSV written by a forensic collection tool, with the files it lists
+/// exported next to it
+#[derive(Debug, Clone)]
+pub struct FileList {
+    pub csv_path: PathBuf,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// One listed file with the details the mapping picked out for it
+#[derive(Debug, Clone)]
+pub struct FileListRow {
+    pub path: Option<PathBuf>, // None when the row doesn't say which file it is
+    pub original_path: Option<String>,
+    pub hash: Option<String>,
+    pub collected: Option<String>,
+    pub custodian: Option<String>,
+}
+
+impl FileListRow {
+    pub fn name(&self) -> String {
+        self.path.as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .or_else(|| self.original_path.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn exists(&self) -> bool {
+        self.path.as_ref().is_some_and(|path| path.is_file())
+    }
+}
+
+impl FileList {
+    /// Reads a CSV with a header row, separated by commas, semicolons or tabs, whichever the
+    /// header uses most
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .context("Failed to read file list")?;
+        let text = text.trim_start_matches('\u{feff}');
+        let header_line = text.lines().next().unwrap_or_default();
+        let delimiter = [',', ';', '\t']
+            .into_iter()
+            .max_by_key(|delimiter| header_line.matches(*delimiter).count())
+            .unwrap_or(',');
+
+        let mut rows = parse_csv(text, delimiter);
+        if rows.is_empty() {
+            bail!("{} is empty", path.display());
+        }
+        let headers = rows.remove(0).into_iter().map(|header| header.trim().to_string()).collect();
+        Ok(Self { csv_path: path.to_path_buf(), headers, rows })
+    }
+
+    /// Matches the headers against names collection tools commonly use
+    pub fn guess_mapping(&self) -> ColumnMapping {
+        let normalized: Vec<String> = self.headers
+            .iter()
+            .map(|header| header.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase())
+            .collect();
+        let mut mapping = ColumnMapping::new();
+        for field in MappedField::ALL {
+            let column = field.guesses()
+                .iter()
+                .find_map(|guess| normalized.iter().position(|header| header == guess))
+                .filter(|column| !mapping.values().any(|taken| taken == column));
+            if let Some(column) = column {
+                mapping.insert(field, column);
+            }
+        }
+        mapping
+    }
+
+    /// The rows as mapped. Without an exported file column, the file is looked for next to the
+    /// CSV under the original path's file name.
+    pub fn mapped_rows(&self, mapping: &ColumnMapping) -> Vec<FileListRow> {
+        let folder = self.csv_path.parent().unwrap_or(Path::new("."));
+        let value = |row: &Vec<String>, field: MappedField| {
+            mapping.get(&field)
+                .and_then(|column| row.get(*column))
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        self.rows
+            .iter()
+            .map(|row| {
+                let original_path = value(row, MappedField::OriginalPath);
+                let path = match value(row, MappedField::File) {
+                    Some(file) => Some(folder.join(file.replace('\\', "/"))),
+                    None => original_path.as_ref()
+                        .and_then(|original| original.rsplit(['/', '\\']).next())
+                        .filter(|name| !name.is_empty())
+                        .map(|name| folder.join(name)),
+                };
+                FileListRow {
+                    path,
+                    original_path,
+                    hash: value(row, MappedField::Hash),
+                    collected: value(row, MappedField::Collected),
+                    custodian: value(row, MappedField::Custodian),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Splits CSV text into rows of fields, with quoted fields that may hold separators, doubled
+/// quotes and line breaks. Blank lines are dropped.
+fn parse_csv(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            if c != '"' {
+                field.push(c);
+            } else if chars.peek() == Some(&'"') {
+                field.push('"');
+                chars.next();
+            } else {
+                quoted = false;
+            }
+        } else if c == '"' {
+            quoted = true;
+        } else if c == delimiter {
+            row.push(std::mem::take(&mut field));
+        } else if c == '\n' || c == '\r' {
+            if c == '\r' && chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            row.push(std::mem::take(&mut field));
+            rows.push(std::mem::take(&mut row));
+        } else {
+            field.push(c);
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|row| row.iter().any(|field| !field.trim().is_empty()));
+    rows
+}
+
+/// What importing a file list did
+#[derive(Debug, Clone, Default)]
+pub struct FileListImportSummary {
+    pub added: Vec<EvidenceFile>,
+    pub quarantined: Vec<QuarantinedFile>,
+    pub failed: Vec<String>,
+    pub storage_warnings: Vec<String>, // Each limit gone over, once
+    pub warnings: Vec<String>,
+}
+
+impl FileListImportSummary {
+    pub fn describe(&self) -> String {
+        let mut description = format!("{} added", self.added.len());
+        if !self.quarantined.is_empty() {
+            description.push_str(&format!(", {} quarantined", self.quarantined.len()));
+        }
+        if !self.failed.is_empty() {
+            description.push_str(&format!(", {} failed ({})", self.failed.len(), self.failed.join(", ")));
+        }
+        description
+    }
+}
+
+/// Takes the listed files in for a person with the provenance the list gives: the original path,
+/// the collected date and the custodian. A file whose listed hash doesn't match isn't taken in;
+/// the rest go through the same intake as a dropped file.
+pub fn import(
+    file_manager: &FileManager,
+    audit_log: &AuditLog,
+    plugins: &Plugins,
+    settings: &Settings,
+    person: &mut Person,
+    list: &FileList,
+    rows: &[FileListRow],
+) -> Result<FileListImportSummary> {
+    let context = IntakeContext { audit_log, plugins, settings };
+    let mut summary = FileListImportSummary::default();
+    let list_name = list.csv_path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    for row in rows {
+        let name = row.name();
+        let Some(path) = row.path.as_ref().filter(|path| path.is_file()) else {
+            summary.failed.push(format!("{}: file not found", name));
+            continue;
+        };
+        let source_hash = match &row.hash {
+            Some(hash) => match hashing::verify_file_digest(path, hash) {
+                Ok(algorithm) => Some(format!("{} {}", algorithm, hash.trim())),
+                Err(e) => {
+                    summary.failed.push(format!("{}: {:#}", name, e));
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        let evidence_type = settings.evidence_type_for_file(path).unwrap_or(EvidenceType::Other);
+        let provenance = Provenance {
+            source_device: Some(format!("File list {}", list_name)),
+            provided_by: row.custodian.clone(),
+            obtained_at: row.collected.clone(),
+            collected_by: Some(settings.operator.trim().to_string()).filter(|name| !name.is_empty()),
+            source_hash: source_hash.clone(),
+            ..Provenance::default()
+        };
+        let mut origin = format!(" from file list {}", list_name);
+        if let Some(source_hash) = &source_hash {
+            origin.push_str(&format!(", {} verified", source_hash));
+        }
+        let intake = file_manager.intake(&context, person, path, evidence_type.clone(), &origin, |person| {
+            let mut evidence_file = file_manager.intake_file(person, path, evidence_type, provenance, false, None)?;
+            // Where the tool found it, not the export folder it was read from
+            if let Some(original_path) = &row.original_path
+                && let Some(provenance) = &mut evidence_file.provenance
+            {
+                provenance.original_path = original_path.clone();
+                file_manager.update_evidence_metadata(person, &evidence_file)?;
+            }
+            Ok(evidence_file)
+        });
+        match intake {
+            Ok(Intake::Added { evidence_file, storage_warnings, warnings }) => {
+                // Every file after the limit is reached would repeat the same warning
+                for warning in storage_warnings {
+                    if !summary.storage_warnings.contains(&warning) {
+                        summary.storage_warnings.push(warning);
+                    }
+                }
+                summary.warnings.extend(warnings);
+                summary.added.push(evidence_file);
+            }
+            Ok(Intake::Quarantined(held)) => summary.quarantined.push(held),
+            Err(e) => summary.failed.push(format!("{}: {:#}", name, e)),
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_csv_reads_quoted_fields() {
+        let text = "Name,Full Path,Comment\r\n\
+            \"report, final.docx\",\"C:\\Users\\jsmith\\Documents\\report, final.docx\",\"Reviewed by \"\"JS\"\"\r\nsecond line\"\r\n\
+            \r\n\
+            photo.jpg,/home/jsmith/photo.jpg,\"line one\nline two\n\"\n\
+            empty.txt,,";
+        assert_eq!(parse_csv(text, ','), [
+            vec!["Name", "Full Path", "Comment"],
+            vec!["report, final.docx", "C:\\Users\\jsmith\\Documents\\report, final.docx", "Reviewed by \"JS\"\r\nsecond line"],
+            vec!["photo.jpg", "/home/jsmith/photo.jpg", "line one\nline two\n"],
+            vec!["empty.txt", "", ""],
+        ]);
+    }
+
+    #[test]
+    fn parse_csv_uses_the_given_delimiter() {
+        let text = "Datei;Pfad\n\"a;b.txt\";\"x\ty\"\nc.txt;\n\n;\n";
+        assert_eq!(parse_csv(text, ';'), [vec!["Datei", "Pfad"], vec!["a;b.txt", "x\ty"], vec!["c.txt", ""]]);
+        assert_eq!(parse_csv("a\tb\n1\t\"2\"", '\t'), [vec!["a", "b"], vec!["1", "2"]]);
+        assert!(parse_csv("\n\r\n", ',').is_empty());
+    }
+
+    #[test]
+    fn load_guesses_the_delimiter_and_mapping() {
+        let dir = std::env::temp_dir().join(format!("evidence-manager-file-list-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let csv_path = dir.join("export.csv");
+        // As a collection tool writes it: a BOM, semicolons, and a note that spans lines
+        let text = "\u{feff}Exported Path;Original Path;SHA-256;Date Collected;Custodian;Note\n\
+            files/IMG_0001.JPG;\"C:\\Users\\jsmith\\Pictures\\IMG_0001.JPG\";9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08;2024-03-01;John Smith;\"Found on desktop;\nmoved later\"\n\
+            ;\\\\server\\share\\notes.txt;;2024-03-02;;\n";
+        fs::write(&csv_path, text).unwrap();
+        let list = FileList::load(&csv_path);
+        let _ = fs::remove_dir_all(&dir);
+
+        let list = list.unwrap();
+        assert_eq!(list.headers, ["Exported Path", "Original Path", "SHA-256", "Date Collected", "Custodian", "Note"]);
+        assert_eq!(list.rows.len(), 2);
+        assert_eq!(list.rows[0][5], "Found on desktop;\nmoved later");
+
+        let mapping = list.guess_mapping();
+        assert_eq!(mapping.get(&MappedField::File), Some(&0));
+        assert_eq!(mapping.get(&MappedField::OriginalPath), Some(&1));
+        assert_eq!(mapping.get(&MappedField::Hash), Some(&2));
+        assert_eq!(mapping.get(&MappedField::Collected), Some(&3));
+        assert_eq!(mapping.get(&MappedField::Custodian), Some(&4));
+
+        let rows = list.mapped_rows(&mapping);
+        assert_eq!(rows[0].path, Some(dir.join("files/IMG_0001.JPG")));
+        assert_eq!(rows[0].custodian.as_deref(), Some("John Smith"));
+        // Without an exported path the file is looked for next to the CSV
+        assert_eq!(rows[1].path, Some(dir.join("notes.txt")));
+        assert_eq!(rows[1].original_path.as_deref(), Some("\\\\server\\share\\notes.txt"));
+        assert_eq!(rows[1].hash, None);
+    }
+}
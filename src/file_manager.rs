@@ -1,15 +1,84 @@
-use crate::models::{Person, EvidenceFile, EvidenceType};
-use anyhow::{Result, Context};
+use crate::models::{Person, EvidenceFile, EvidenceType, Provenance};
+use crate::settings::{Settings, StorageLayout};
+use crate::paths;
+use crate::hashing;
+use crate::board::{self, Board};
+use crate::content_store::{ContentStore, Reference};
+use crate::quarantine::{self, Quarantine, QuarantinedFile};
+use crate::audit::{AuditAction, AuditLog};
+use crate::plugins::Plugins;
+use crate::timestamp;
+use anyhow::{Result, Context, bail};
 use std::path::{Path, PathBuf};
 use std::fs;
 use walkdir::WalkDir;
-use chrono::Utc;
-use uuid::Uuid;
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use std::io::{self, BufWriter, Read};
 use directories::ProjectDirs;
 
+// Larger files are opened in their own application rather than loaded into memory
+const MAX_READ_SIZE: u64 = 50 * 1024 * 1024;
+
+const MB: u64 = 1024 * 1024;
+
+// A copy that doesn't match its source is removed and made again this many times in all
+const COPY_ATTEMPTS: usize = 3;
+
+/// Called with the bytes done and the total as a file is copied into the store and then read back
+/// to verify it, so the total is twice the file's size
+pub type CopyProgress = dyn Fn(u64, u64) + Send + Sync;
+
+/// Files in a person's folder that hold the store's own data rather than evidence
+pub const METADATA_FILES: [&str; 4] = ["person_data.json", "evidence_index.json", INDEX_BACKUP, board::FILE_NAME];
+
+// Where an unreadable index is kept when the index is rebuilt without it
+const INDEX_BACKUP: &str = "evidence_index.json.bak";
+
+/// What rebuilding a person's evidence index did
+#[derive(Debug, Clone, Default)]
+pub struct RebuildSummary {
+    pub kept: usize, // Entries whose file was where the index said
+    pub moved: usize, // Entries whose file was found elsewhere by its hash, or by name and size
+    pub added: usize, // Files that had no entry
+    pub dropped: Vec<String>, // Original names of entries whose file wasn't found
+    pub index_unreadable: bool, // The old index couldn't be read, so nothing could be kept
+}
+
+impl RebuildSummary {
+    pub fn describe(&self) -> String {
+        let mut description = format!("{} kept, {} moved, {} added", self.kept, self.moved, self.added);
+        if !self.dropped.is_empty() {
+            description.push_str(&format!(", {} dropped ({})", self.dropped.len(), self.dropped.join(", ")));
+        }
+        if self.index_unreadable {
+            description.push_str(&format!("; the old index couldn't be read and was kept as {}", INDEX_BACKUP));
+        }
+        description
+    }
+}
+
+/// What every way of taking a file in needs besides the store itself
+pub struct IntakeContext<'a> {
+    pub audit_log: &'a AuditLog,
+    pub plugins: &'a Plugins,
+    pub settings: &'a Settings,
+}
+
+/// What taking a file in came to
+#[derive(Debug, Clone)]
+pub enum Intake {
+    Added {
+        evidence_file: EvidenceFile,
+        storage_warnings: Vec<String>, // Limits the file goes over, kept apart so they can be shown as they happen
+        warnings: Vec<String>,
+    },
+    Quarantined(QuarantinedFile), // It could run code, so it waits to be accepted
+}
+
 #[derive(Clone)]
 pub struct FileManager {
     evidence_dir: PathBuf,
+    layout: StorageLayout,
 }
 
 impl FileManager {
@@ -26,13 +95,107 @@ impl FileManager {
         fs::create_dir_all(&evidence_dir)
             .context("Failed to create Evidence directory")?;
 
-        Ok(Self { evidence_dir })
+        Ok(Self { evidence_dir, layout: StorageLayout::default() })
+    }
+
+    /// Sets how evidence added from now on is stored; files already stored stay as they are
+    pub fn set_storage_layout(&mut self, layout: StorageLayout) {
+        self.layout = layout;
+    }
+
+    // Where new content goes, unless every person keeps their own copies
+    fn content_store(&self) -> Option<ContentStore> {
+        (self.layout == StorageLayout::Blobs).then(|| ContentStore::new(&self.evidence_dir))
     }
 
     pub fn get_evidence_dir(&self) -> &Path {
         &self.evidence_dir
     }
 
+    /// Free space on the drive holding the Evidence folder
+    pub fn available_space(&self) -> Result<u64> {
+        fs2::available_space(&self.evidence_dir)
+            .context("Failed to check free disk space")
+    }
+
+    /// Refuses a write of `size` bytes up front rather than leaving a truncated file when the drive fills up
+    fn ensure_space_for(&self, size: u64) -> Result<()> {
+        Self::ensure_free_space(&self.evidence_dir, size)
+    }
+
+    /// Fails when the drive holding `dir` has less than `needed` bytes free
+    pub fn ensure_free_space(dir: &Path, needed: u64) -> Result<()> {
+        let available = fs2::available_space(dir)
+            .context("Failed to check free disk space")?;
+        if needed > available {
+            bail!("Not enough free disk space: {} MB needed, {} MB available", needed.div_ceil(MB), available / MB);
+        }
+        Ok(())
+    }
+
+    /// Warnings for adding `incoming` bytes to a person's evidence, against the limits in the settings
+    pub fn storage_warnings(&self, person: &Person, incoming: u64, settings: &Settings) -> Result<Vec<String>> {
+        let mut warnings = Vec::new();
+
+        if settings.person_quota_mb > 0 || settings.person_quota_files > 0 {
+            let files = self.scan_person_evidence(person, settings)?;
+            let total = files.iter().map(|f| f.size).sum::<u64>() + incoming;
+            if settings.person_quota_mb > 0 && total > settings.person_quota_mb * MB {
+                warnings.push(format!("{}'s evidence is {} MB, over the {} MB limit", person.name, total.div_ceil(MB), settings.person_quota_mb));
+            }
+            if settings.person_quota_files > 0 && files.len() + 1 > settings.person_quota_files {
+                warnings.push(format!("{} has {} evidence files, over the limit of {}", person.name, files.len() + 1, settings.person_quota_files));
+            }
+        }
+
+        if settings.free_space_floor_mb > 0 {
+            let remaining = self.available_space()?.saturating_sub(incoming);
+            if remaining < settings.free_space_floor_mb * MB {
+                warnings.push(format!("Only {} MB of disk space left for evidence, below the {} MB minimum", remaining / MB, settings.free_space_floor_mb));
+            }
+        }
+
+        Ok(warnings)
+    }
+
+    /// Resolves an evidence file's real path, refusing anything that resolves outside the
+    /// Evidence folder (including through symlinks)
+    pub fn resolve_evidence(&self, path: &Path) -> Result<PathBuf> {
+        let root = self.evidence_dir.canonicalize()
+            .context("Failed to resolve Evidence directory")?;
+        let path = path.canonicalize()
+            .context("Failed to resolve evidence file")?;
+        if !path.starts_with(&root) {
+            anyhow::bail!("File is outside the Evidence folder");
+        }
+        Ok(path)
+    }
+
+    /// The SHA-256 of an evidence file, streamed so files of any size can be hashed
+    pub fn evidence_digest(&self, path: &Path) -> Result<[u8; 32]> {
+        hashing::sha256_file_digest(&self.resolve_evidence(path)?)
+    }
+
+    /// Reads an evidence file for display, refusing anything outside the Evidence folder or
+    /// too large to hold in memory
+    pub fn read_evidence(&self, path: &Path) -> Result<Vec<u8>> {
+        let path = self.resolve_evidence(path)?;
+
+        let file = fs::File::open(&path)
+            .context("Failed to open evidence file")?;
+        let size = file.metadata()
+            .context("Failed to read evidence file metadata")?
+            .len();
+        if size > MAX_READ_SIZE {
+            anyhow::bail!("File is too large to preview ({} MB), open it instead", size / (1024 * 1024));
+        }
+
+        let mut content = Vec::with_capacity(size as usize);
+        file.take(MAX_READ_SIZE).read_to_end(&mut content)
+            .context("Failed to read evidence file")?;
+        Ok(content)
+    }
+
     pub fn create_person_folder(&self, person: &Person) -> Result<PathBuf> {
         let person_folder = self.evidence_dir.join(person.folder_name());
         
@@ -41,7 +204,7 @@ impl FileManager {
                 .context("Failed to create person folder")?;
             
             // Create subfolders for different media types
-            for evidence_type in [EvidenceType::Image, EvidenceType::Audio, EvidenceType::Video, EvidenceType::Document, EvidenceType::Quote] {
+            for evidence_type in EvidenceType::built_in() {
                 let subfolder = person_folder.join(evidence_type.folder_name());
                 fs::create_dir_all(&subfolder)
                     .context("Failed to create evidence subfolder")?;
@@ -102,6 +265,14 @@ impl FileManager {
     pub fn delete_person(&self, person: &Person) -> Result<()> {
         let person_folder = self.evidence_dir.join(person.folder_name());
         
+        // Content shared with others stays for them
+        let content = ContentStore::new(&self.evidence_dir);
+        for file in self.load_evidence_index(person).unwrap_or_default() {
+            if let Some(sha256) = &file.sha256 {
+                content.release(sha256, file.id)?;
+            }
+        }
+        
         if person_folder.exists() {
             fs::remove_dir_all(&person_folder)
                 .context("Failed to delete person folder")?;
@@ -110,7 +281,266 @@ impl FileManager {
         Ok(())
     }
 
-    pub fn copy_file_to_evidence(&self, person: &Person, source_path: &Path, evidence_type: EvidenceType) -> Result<EvidenceFile> {
+    /// Renames a person and moves their folder to match; the evidence index is relative to the folder and moves with it
+    pub fn rename_person(&self, person: &Person, new_name: &str) -> Result<Person> {
+        let mut renamed = person.clone();
+        renamed.name = new_name.to_string();
+        renamed.update_timestamp();
+
+        let old_folder = self.evidence_dir.join(person.folder_name());
+        let new_folder = self.evidence_dir.join(renamed.folder_name());
+        if new_folder != old_folder {
+            if new_folder.exists() {
+                bail!("A folder named \"{}\" already exists", renamed.folder_name());
+            }
+            if old_folder.exists() {
+                fs::rename(&old_folder, &new_folder)
+                    .context("Failed to rename person folder")?;
+            }
+        }
+
+        self.save_person_data(&renamed)?;
+        Ok(renamed)
+    }
+
+    /// Deletes an evidence file and drops it from the person's index
+    pub fn delete_evidence(&self, person: &Person, evidence_file: &EvidenceFile) -> Result<()> {
+        let person_folder = self.evidence_dir.join(person.folder_name());
+        if !evidence_file.file_path.strip_prefix(&person_folder).is_ok_and(paths::is_contained) {
+            bail!("{} is not in the person's folder", evidence_file.file_path.display());
+        }
+
+        fs::remove_file(&evidence_file.file_path)
+            .with_context(|| format!("Failed to delete {}", evidence_file.file_path.display()))?;
+        if let Some(sha256) = &evidence_file.sha256 {
+            ContentStore::new(&self.evidence_dir).release(sha256, evidence_file.id)?;
+        }
+
+        let mut index = self.load_evidence_index(person)?;
+        index.retain(|f| f.id != evidence_file.id);
+        // The version it replaced becomes the current one again
+        for file in index.iter_mut().filter(|f| f.superseded_by == Some(evidence_file.id)) {
+            file.superseded_by = None;
+        }
+        self.save_evidence_index(person, &index)
+    }
+
+    /// Adds `source_path` as a new version of `current`. The old file moves into a "versions"
+    /// folder beside it, named with its version number (e.g. "report.v2.pdf"), and stays in the
+    /// index linked to the new one, so the new file keeps its own name instead of getting a suffix.
+    pub fn replace_with_version(&self, person: &Person, current: &EvidenceFile, source_path: &Path) -> Result<EvidenceFile> {
+        self.store_new_version(person, current, || {
+            self.intake_file(person, source_path, current.file_type.clone(), Provenance::default(), false, None)
+        })
+    }
+
+    /// As `replace_with_version`, with the new version's content in memory, e.g. a note edited in the app
+    pub fn replace_with_bytes(&self, person: &Person, current: &EvidenceFile, file_name: &str, content: &[u8]) -> Result<EvidenceFile> {
+        self.store_new_version(person, current, || {
+            self.store_bytes_as_evidence(person, file_name, content, current.file_type.clone())
+        })
+    }
+
+    /// Moves `current` into the versions folder, then links the file `store` adds to it
+    fn store_new_version(&self, person: &Person, current: &EvidenceFile, store: impl FnOnce() -> Result<EvidenceFile>) -> Result<EvidenceFile> {
+        let index = self.load_evidence_index(person)?;
+        if current.superseded_by.is_some() {
+            bail!("{} has already been replaced by a newer version", current.original_name);
+        }
+        let version = current.previous_versions(&index).len() + 1;
+
+        let folder = current.file_path.parent()
+            .context("Evidence file has no parent folder")?;
+        let versions_folder = folder.join("versions");
+        fs::create_dir_all(&versions_folder)
+            .context("Failed to create versions folder")?;
+        let stem = current.file_path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let name = match current.file_path.extension() {
+            Some(extension) => format!("{}.v{}.{}", stem, version, extension.to_string_lossy()),
+            None => format!("{}.v{}", stem, version),
+        };
+        let archived_path = Self::unique_target_path(&versions_folder, &name);
+        fs::rename(&current.file_path, &archived_path)
+            .with_context(|| format!("Failed to move {} to the versions folder", current.file_path.display()))?;
+
+        let mut new_version = match store() {
+            Ok(file) => file,
+            Err(e) => {
+                // Put the old version back where it was
+                let _ = fs::rename(&archived_path, &current.file_path);
+                return Err(e);
+            }
+        };
+        new_version.previous_version = Some(current.id);
+        self.update_evidence_metadata(person, &new_version)?;
+
+        let mut old_version = current.clone();
+        old_version.file_path = archived_path;
+        old_version.superseded_by = Some(new_version.id);
+        self.update_evidence_metadata(person, &old_version)?;
+
+        Ok(new_version)
+    }
+
+    /// Under the blob layout, puts the files an import wrote for a person (paths relative to the
+    /// Evidence folder) into the content store, as intake does, and releases the content they
+    /// replaced. Under the person folders layout, imported files stay as they are.
+    pub fn store_imported_content(&self, person: &Person, written: &[PathBuf]) -> Result<()> {
+        let Some(store) = self.content_store() else {
+            return Ok(());
+        };
+        let references = store.references()?;
+        for file in self.load_evidence_index(person)? {
+            let is_written = file.file_path.strip_prefix(&self.evidence_dir)
+                .is_ok_and(|relative| written.iter().any(|path| path == relative));
+            if !is_written || !file.file_path.is_file() {
+                continue;
+            }
+            // Hashed again, as a watermarked copy doesn't match its index
+            let sha256 = hashing::sha256_file(&file.file_path)?;
+            for (stored, holders) in &references {
+                if *stored != sha256 && holders.iter().any(|r| r.file_id == file.id) {
+                    store.release(stored, file.id)?;
+                }
+            }
+            store.replace_with_link(&sha256, &file.file_path)?;
+            store.add(&sha256, &file.file_path, Reference { person_id: person.id, file_id: file.id })?;
+        }
+        Ok(())
+    }
+
+    /// Adds one person's evidence file to another's, as a second entry holding the same stored
+    /// content rather than a copy. Dates and provenance carry over; notes and review start fresh.
+    pub fn share_evidence(&self, from: &Person, file: &EvidenceFile, to: &Person) -> Result<EvidenceFile> {
+        // Files added before content was stored once go into the store now
+        if let Some(content) = self.content_store() {
+            let sha256 = hashing::sha256_file(&file.file_path)?;
+            if file.sha256.as_ref().is_some_and(|recorded| *recorded != sha256) {
+                bail!("{} no longer matches its recorded hash", file.original_name);
+            }
+            content.add(&sha256, &file.file_path, Reference { person_id: from.id, file_id: file.id })?;
+        }
+
+        let mut shared = self.copy_file_to_evidence(to, &file.file_path, file.file_type.clone(), None)?;
+        shared.original_name = file.original_name.clone();
+        shared.evidence_date = file.evidence_date;
+        shared.provenance = file.provenance.clone();
+        self.update_evidence_metadata(to, &shared)?;
+        Ok(shared)
+    }
+
+    fn evidence_index_path(&self, person: &Person) -> PathBuf {
+        self.evidence_dir.join(person.folder_name()).join("evidence_index.json")
+    }
+
+    /// Loads the persisted evidence metadata for a person, resolving paths against the person folder
+    pub fn load_evidence_index(&self, person: &Person) -> Result<Vec<EvidenceFile>> {
+        let index_file = self.evidence_index_path(person);
+
+        if !index_file.exists() {
+            return Ok(Vec::new());
+        }
+
+        let json = fs::read_to_string(&index_file)
+            .context("Failed to read evidence index")?;
+
+        let mut evidence_files: Vec<EvidenceFile> = serde_json::from_str(&json)
+            .context("Failed to parse evidence index")?;
+
+        // An index from an imported archive could name any file on disk
+        let person_folder = self.evidence_dir.join(person.folder_name());
+        evidence_files.retain(|file| {
+            paths::is_contained(&file.file_path)
+                || file.file_path.strip_prefix(&person_folder).is_ok_and(paths::is_contained)
+        });
+        for file in &mut evidence_files {
+            file.file_path = person_folder.join(&file.file_path);
+        }
+
+        Ok(evidence_files)
+    }
+
+    /// Saves evidence metadata for a person; paths are stored relative to the person folder so archives stay portable
+    pub fn save_evidence_index(&self, person: &Person, evidence_files: &[EvidenceFile]) -> Result<()> {
+        let person_folder = self.create_person_folder(person)?;
+
+        let relative_files: Vec<EvidenceFile> = evidence_files
+            .iter()
+            .map(|file| {
+                let mut file = file.clone();
+                if let Ok(relative_path) = file.file_path.strip_prefix(&person_folder) {
+                    file.file_path = relative_path.to_path_buf();
+                }
+                file
+            })
+            .collect();
+
+        let json = serde_json::to_string_pretty(&relative_files)
+            .context("Failed to serialize evidence index")?;
+
+        fs::write(self.evidence_index_path(person), json)
+            .context("Failed to write evidence index")?;
+
+        Ok(())
+    }
+
+    /// The person's evidence board; empty until one is saved
+    pub fn load_board(&self, person: &Person) -> Result<Board> {
+        Board::load(&self.evidence_dir.join(person.folder_name()))
+    }
+
+    pub fn save_board(&self, person: &Person, board: &Board) -> Result<()> {
+        board.save(&self.create_person_folder(person)?)
+    }
+
+    /// Adds or replaces an entry in the person's evidence index
+    pub fn update_evidence_metadata(&self, person: &Person, evidence_file: &EvidenceFile) -> Result<()> {
+        let mut index = self.load_evidence_index(person)?;
+        match index.iter_mut().find(|f| f.id == evidence_file.id) {
+            Some(existing) => *existing = evidence_file.clone(),
+            None => index.push(evidence_file.clone()),
+        }
+        self.save_evidence_index(person, &index)
+    }
+
+    /// Returns a path in `target_folder` that doesn't clash with an existing file
+    fn unique_target_path(target_folder: &Path, file_name: &str) -> PathBuf {
+        let mut final_path = target_folder.join(file_name);
+        let original = Path::new(file_name);
+        let stem = original.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let extension = original.extension().map(|e| e.to_string_lossy().to_string());
+
+        // Handle duplicate file names; a dangling symlink counts as taken so nothing is written through it
+        let mut counter = 1;
+        while fs::symlink_metadata(&final_path).is_ok() {
+            let new_name = match &extension {
+                Some(extension) => format!("{}_{}.{}", stem, counter, extension),
+                None => format!("{}_{}", stem, counter),
+            };
+            final_path = target_folder.join(new_name);
+            counter += 1;
+        }
+
+        final_path
+    }
+
+    pub fn copy_file_to_evidence(&self, person: &Person, source_path: &Path, evidence_type: EvidenceType, progress: Option<&CopyProgress>) -> Result<EvidenceFile> {
+        self.store_in_evidence(person, source_path, evidence_type, progress, false)
+    }
+
+    /// Like `copy_file_to_evidence`, but the source file doesn't stay where it was: it's renamed into
+    /// the person's folder on the same drive, and copied, verified and then deleted from another.
+    /// A source that can't be deleted is left in place.
+    pub fn move_file_to_evidence(&self, person: &Person, source_path: &Path, evidence_type: EvidenceType, progress: Option<&CopyProgress>) -> Result<EvidenceFile> {
+        self.store_in_evidence(person, source_path, evidence_type, progress, true)
+    }
+
+    fn store_in_evidence(&self, person: &Person, source_path: &Path, evidence_type: EvidenceType, progress: Option<&CopyProgress>, move_source: bool) -> Result<EvidenceFile> {
+        // Only regular files are taken in, not folders, devices or pipes
+        if !fs::metadata(source_path).context("Failed to read source file")?.is_file() {
+            anyhow::bail!("\"{}\" is not a regular file", source_path.display());
+        }
+
         let person_folder = self.create_person_folder(person)?;
         let target_folder = person_folder.join(evidence_type.folder_name());
         
@@ -118,43 +548,381 @@ impl FileManager {
             .context("Source file has no name")?
             .to_string_lossy();
         
-        let target_path = target_folder.join(&*file_name);
-        
-        // Handle duplicate file names
-        let mut final_path = target_path.clone();
-        let mut counter = 1;
-        while final_path.exists() {
-            let stem = source_path.file_stem()
-                .context("Source file has no stem")?
-                .to_string_lossy();
-            let extension = source_path.extension()
-                .context("Source file has no extension")?
-                .to_string_lossy();
-            
-            let new_name = format!("{}_{}.{}", stem, counter, extension);
-            final_path = target_folder.join(new_name);
-            counter += 1;
+        fs::create_dir_all(&target_folder)
+            .context("Failed to create evidence subfolder")?;
+        let final_path = Self::unique_target_path(&target_folder, &file_name);
+        // Read before a move takes the source away
+        let evidence_date = Self::detect_evidence_date(source_path);
+        let content = self.content_store();
+
+        if move_source && fs::rename(source_path, &final_path).is_ok() {
+            let sha256 = hashing::sha256_file(&final_path)?;
+            // Content already stored replaces the moved file with a link, as a copy would have been
+            if let Some(content) = &content {
+                content.replace_with_link(&sha256, &final_path)?;
+            }
+            return self.record_stored_file(person, final_path, evidence_type, &file_name, evidence_date, sha256);
         }
 
-        fs::copy(source_path, &final_path)
-            .context("Failed to copy file to evidence folder")?;
+        // Content already in the store, e.g. a video held for someone else, is linked rather than copied
+        let linked = match &content {
+            Some(content) => {
+                let source_sha256 = hashing::sha256_file(source_path)?;
+                content.link(&source_sha256, &final_path).then_some(source_sha256)
+            }
+            None => None,
+        };
+        let sha256 = match linked {
+            Some(sha256) => sha256,
+            None => {
+                self.ensure_space_for(fs::metadata(source_path).context("Failed to read source file")?.len())?;
+                Self::copy_verified(source_path, &final_path, progress)?
+            }
+        };
+
+        let evidence_file = self.record_stored_file(person, final_path, evidence_type, &file_name, evidence_date, sha256)?;
+        // Only once the copy is verified and recorded
+        if move_source {
+            let _ = fs::remove_file(source_path);
+        }
+        Ok(evidence_file)
+    }
 
-        let metadata = fs::metadata(&final_path)
+    fn record_stored_file(&self, person: &Person, path: PathBuf, evidence_type: EvidenceType, file_name: &str, evidence_date: Option<DateTime<Utc>>, sha256: String) -> Result<EvidenceFile> {
+        let metadata = fs::metadata(&path)
             .context("Failed to get file metadata")?;
 
-        Ok(EvidenceFile {
-            id: Uuid::new_v4(),
-            person_id: person.id,
-            file_path: final_path,
-            file_type: evidence_type,
-            original_name: file_name.to_string(),
-            size: metadata.len(),
-            created_at: Utc::now(),
-            notes: String::new(),
-        })
+        let mut evidence_file = EvidenceFile::new(person.id, path, evidence_type, file_name.to_string(), metadata.len(), Utc::now());
+        evidence_file.evidence_date = evidence_date;
+        evidence_file.sha256 = Some(sha256.clone());
+        if let Some(content) = self.content_store() {
+            content.add(&sha256, &evidence_file.file_path, Reference { person_id: person.id, file_id: evidence_file.id })?;
+        }
+        self.update_evidence_metadata(person, &evidence_file)?;
+
+        Ok(evidence_file)
+    }
+
+    /// Copies a file in chunks, then reads the copy back and checks it against the hash of what was
+    /// read from the source. A copy that fails or doesn't match is removed and tried again, and none
+    /// is left behind if every attempt fails. Returns the hash.
+    fn copy_verified(source_path: &Path, target_path: &Path, progress: Option<&CopyProgress>) -> Result<String> {
+        let mut attempt = 1;
+        loop {
+            match Self::copy_once(source_path, target_path, progress) {
+                Ok(sha256) => return Ok(sha256),
+                Err(e) => {
+                    let _ = fs::remove_file(target_path);
+                    if attempt == COPY_ATTEMPTS {
+                        return Err(e.context(format!("Failed to copy {} after {} attempts", source_path.display(), COPY_ATTEMPTS)));
+                    }
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    fn copy_once(source_path: &Path, target_path: &Path, progress: Option<&CopyProgress>) -> Result<String> {
+        let size = fs::metadata(source_path).context("Failed to read source file")?.len();
+        let report = |done: u64| {
+            if let Some(progress) = progress {
+                progress(done, size * 2);
+            }
+        };
+
+        let mut source = fs::File::open(source_path)
+            .context("Failed to open source file")?;
+        let mut writer = BufWriter::new(fs::File::create(target_path).context("Failed to create file in evidence folder")?);
+        let source_sha256 = hashing::copy_hashing(&mut source, &mut writer, report)
+            .context("Failed to copy file to evidence folder")?;
+        // On disk before it's read back, so the check isn't only of what's still in memory
+        writer.into_inner()
+            .map_err(|e| e.into_error())
+            .and_then(|file| file.sync_all())
+            .context("Failed to write file to evidence folder")?;
+
+        let mut copy = fs::File::open(target_path)
+            .context("Failed to open copied file")?;
+        let copied_sha256 = hashing::copy_hashing(&mut copy, &mut io::sink(), |done| report(size + done))
+            .context("Failed to read copied file")?;
+        if copied_sha256 != source_sha256 {
+            bail!("The copy of {} doesn't match the original", source_path.display());
+        }
+        Ok(copied_sha256)
+    }
+
+    /// When the evidence was made: the photo's EXIF date if it has one, otherwise the file's modification time
+    pub fn detect_evidence_date(source_path: &Path) -> Option<DateTime<Utc>> {
+        // EXIF sits near the start of the file
+        let mut header = Vec::new();
+        if fs::File::open(source_path)
+            .and_then(|f| f.take(256 * 1024).read_to_end(&mut header))
+            .is_ok()
+            && let Some(taken) = crate::exif::date_taken(&header).and_then(Self::local_to_utc)
+        {
+            return Some(taken);
+        }
+        fs::metadata(source_path)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(DateTime::<Utc>::from)
+    }
+
+    // EXIF dates carry no time zone; the camera's clock is assumed to match this machine's
+    fn local_to_utc(date_time: NaiveDateTime) -> Option<DateTime<Utc>> {
+        date_time.and_local_timezone(Local).earliest().map(|d| d.with_timezone(&Utc))
+    }
+
+    /// Copies a file in and records where it came from; the original is left untouched unless
+    /// `move_source` moves it in instead
+    pub fn intake_file(&self, person: &Person, source_path: &Path, evidence_type: EvidenceType, mut provenance: Provenance, move_source: bool, progress: Option<&CopyProgress>) -> Result<EvidenceFile> {
+        provenance.original_path = source_path.display().to_string();
+        provenance.original_modified = fs::metadata(source_path)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(DateTime::<Utc>::from);
+
+        let mut evidence_file = self.store_in_evidence(person, source_path, evidence_type, progress, move_source)?;
+        evidence_file.provenance = Some(provenance);
+        self.update_evidence_metadata(person, &evidence_file)?;
+
+        Ok(evidence_file)
+    }
+
+    /// Writes in-memory content (e.g. an extracted email attachment) into the person's evidence folder
+    pub fn store_bytes_as_evidence(&self, person: &Person, file_name: &str, content: &[u8], evidence_type: EvidenceType) -> Result<EvidenceFile> {
+        let person_folder = self.create_person_folder(person)?;
+        let target_folder = person_folder.join(evidence_type.folder_name());
+
+        // Never trust names from inside a file to stay within the folder
+        let file_name = Path::new(file_name)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| "unnamed".to_string());
+
+        fs::create_dir_all(&target_folder)
+            .context("Failed to create evidence subfolder")?;
+        let final_path = Self::unique_target_path(&target_folder, &file_name);
+
+        let store = self.content_store();
+        let sha256 = hashing::sha256_hex(content);
+        if !store.as_ref().is_some_and(|store| store.link(&sha256, &final_path)) {
+            self.ensure_space_for(content.len() as u64)?;
+            fs::write(&final_path, content)
+                .context("Failed to write file to evidence folder")?;
+        }
+
+        let mut evidence_file = EvidenceFile::new(person.id, final_path, evidence_type, file_name, content.len() as u64, Utc::now());
+        evidence_file.evidence_date = crate::exif::date_taken(content).and_then(Self::local_to_utc);
+        evidence_file.sha256 = Some(sha256.clone());
+        if let Some(store) = &store {
+            store.add(&sha256, &evidence_file.file_path, Reference { person_id: person.id, file_id: evidence_file.id })?;
+        }
+        self.update_evidence_metadata(person, &evidence_file)?;
+
+        Ok(evidence_file)
+    }
+
+    /// Stores a screenshot under a name built from its capture time, which also becomes its evidence date
+    pub fn store_screenshot(&self, person: &Person, content: &[u8], ext: &str, captured_at: DateTime<Utc>, pattern: &str) -> Result<EvidenceFile> {
+        let file_name = crate::naming::render_pattern(pattern, &crate::naming::NameContext {
+            date: captured_at,
+            person: &person.folder_name(),
+            name: "screenshot",
+            ext,
+            counter: 1,
+            counter_width: 1,
+        });
+
+        let mut evidence_file = self.store_bytes_as_evidence(person, &file_name, content, EvidenceType::Image)?;
+        evidence_file.evidence_date = Some(captured_at);
+        self.update_evidence_metadata(person, &evidence_file)?;
+
+        Ok(evidence_file)
+    }
+
+    /// Takes in a screenshot file, renamed by capture time; its modification time is taken as the capture time
+    pub fn intake_screenshot(&self, person: &Person, source_path: &Path, pattern: &str, provenance: Option<Provenance>) -> Result<EvidenceFile> {
+        let captured_at = fs::metadata(source_path)
+            .and_then(|m| m.modified())
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now());
+        let content = fs::read(source_path)
+            .context("Failed to read screenshot")?;
+        let ext = source_path.extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_else(|| "png".to_string());
+
+        let mut evidence_file = self.store_screenshot(person, &content, &ext, captured_at, pattern)?;
+        // Keep the tool's name so the file can be traced back
+        evidence_file.original_name = source_path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| evidence_file.original_name.clone());
+        if let Some(mut provenance) = provenance {
+            provenance.original_path = source_path.display().to_string();
+            provenance.original_modified = Some(captured_at);
+            evidence_file.provenance = Some(provenance);
+        }
+        self.update_evidence_metadata(person, &evidence_file)?;
+
+        Ok(evidence_file)
+    }
+
+    /// Renames evidence files on disk according to a pattern and updates the evidence index to match
+    pub fn rename_evidence_files(&self, person: &Person, files: &[EvidenceFile], pattern: &str) -> Result<Vec<EvidenceFile>> {
+        let counter_width = files.len().to_string().len();
+        let mut renamed = Vec::new();
+
+        for (index, file) in files.iter().enumerate() {
+            let new_name = Self::preview_name(person, file, pattern, index + 1, counter_width);
+            let folder = file.file_path.parent()
+                .context("Evidence file has no parent folder")?;
+
+            if file.file_path.file_name().map(|n| n.to_string_lossy() == new_name).unwrap_or(false) {
+                continue;
+            }
+
+            let new_path = Self::unique_target_path(folder, &new_name);
+            fs::rename(&file.file_path, &new_path)
+                .with_context(|| format!("Failed to rename {}", file.file_path.display()))?;
+
+            let mut file = file.clone();
+            file.file_path = new_path;
+            self.update_evidence_metadata(person, &file)?;
+            renamed.push(file);
+        }
+
+        Ok(renamed)
+    }
+
+    /// The name a file would get from a rename pattern, before clash handling
+    pub fn preview_name(person: &Person, file: &EvidenceFile, pattern: &str, counter: usize, counter_width: usize) -> String {
+        let stem = file.file_path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let ext = file.file_path.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+        let name = crate::naming::render_pattern(pattern, &crate::naming::NameContext {
+            date: file.created_at,
+            person: &person.folder_name(),
+            name: &stem,
+            ext: &ext,
+            counter,
+            counter_width,
+        });
+        // "{name}.{ext}" on an extension-less file would otherwise leave a trailing dot
+        name.trim_end_matches('.').to_string()
+    }
+
+    /// Records an email's headers on its evidence entry and stores its attachments as linked evidence.
+    /// Attachments that could run code are held in quarantine instead, like a dropped file, and
+    /// returned alongside the entry.
+    pub fn ingest_email(&self, person: &Person, mut evidence_file: EvidenceFile) -> Result<(EvidenceFile, Vec<QuarantinedFile>)> {
+        let content = fs::read(&evidence_file.file_path)
+            .context("Failed to read email file")?;
+        let email = crate::email::parse_email(&content)?;
+        let mut held = Vec::new();
+
+        for attachment in &email.attachments {
+            let evidence_type = crate::sniff::detect(&attachment.content)
+                .or_else(|| {
+                    Path::new(&attachment.name)
+                        .extension()
+                        .and_then(|ext| EvidenceType::from_extension(&ext.to_string_lossy()))
+                })
+                .unwrap_or(EvidenceType::Other);
+            if let Some(reason) = quarantine::reason_for_content(&attachment.name, &attachment.content) {
+                let origin = format!("{} (attachment)", evidence_file.file_path.display());
+                held.push(Quarantine::new(&self.evidence_dir).hold_bytes(person, &attachment.name, &attachment.content, evidence_type, reason, origin)?);
+                continue;
+            }
+            let stored = self.store_bytes_as_evidence(person, &attachment.name, &attachment.content, evidence_type)?;
+            evidence_file.linked_evidence.push(stored.id);
+        }
+
+        evidence_file.email = Some(email.metadata);
+        self.update_evidence_metadata(person, &evidence_file)?;
+
+        Ok((evidence_file, held))
     }
 
-    pub fn scan_person_evidence(&self, person: &Person) -> Result<Vec<EvidenceFile>> {
+    /// Takes a file in the one way every intake path shares. Anything that could run code is held
+    /// in quarantine; otherwise the storage limits are checked, `store` puts the file in the
+    /// person's folder, and the addition is logged, an email's attachments are extracted, plugins
+    /// run and, with a TSA set, the file is timestamped. `origin` ends the activity log entries,
+    /// e.g. " via ingest". A TSA that can't be reached doesn't undo the file; it's a warning.
+    pub fn intake(
+        &self,
+        context: &IntakeContext,
+        person: &mut Person,
+        source_path: &Path,
+        evidence_type: EvidenceType,
+        origin: &str,
+        store: impl FnOnce(&Person) -> Result<EvidenceFile>,
+    ) -> Result<Intake> {
+        let audit_log = context.audit_log;
+        if let Some(reason) = quarantine::reason(source_path) {
+            let held = Quarantine::new(&self.evidence_dir).hold(person, source_path, evidence_type, reason)?;
+            let details = format!("{}: {}, SHA-256 {}", held.original_name, held.reason, held.sha256);
+            audit_log.record(Some(person), AuditAction::EvidenceQuarantined, format!("{}{}", details, origin))?;
+            return Ok(Intake::Quarantined(held));
+        }
+
+        // A failed check isn't a reason to refuse the file; running out of space is caught by the copy
+        let size = fs::metadata(source_path).map(|m| m.len()).unwrap_or_default();
+        let storage_warnings = self.storage_warnings(person, size, context.settings)
+            .unwrap_or_else(|e| vec![e.to_string()]);
+
+        let mut evidence_file = store(person)?;
+        let mut warnings = Vec::new();
+        let details = format!("{} ({}){}", evidence_file.original_name, evidence_file.file_type.folder_name(), origin);
+        audit_log.record(Some(person), AuditAction::EvidenceAdded, details)?;
+        if source_path.extension().is_some_and(|ext| crate::email::is_email_extension(&ext.to_string_lossy())) {
+            let (ingested, held) = self.ingest_email(person, evidence_file)?;
+            evidence_file = ingested;
+            for held in held {
+                let details = format!("{}: {}, SHA-256 {}", held.original_name, held.reason, held.sha256);
+                audit_log.record(Some(person), AuditAction::EvidenceQuarantined, format!("{} attached to {}{}", details, evidence_file.original_name, origin))?;
+                warnings.push(format!("{} attached to it looks like a {} and is held in quarantine until you accept it", held.original_name, held.reason.to_lowercase()));
+            }
+        }
+        warnings.extend(context.plugins.evidence_added(self, audit_log, person, &mut evidence_file));
+
+        let tsa_url = context.settings.tsa_url.trim();
+        if !tsa_url.is_empty() {
+            let timestamped = self.evidence_digest(&evidence_file.file_path)
+                .and_then(|digest| timestamp::request(tsa_url, digest));
+            match timestamped {
+                Ok(timestamp) => {
+                    let details = format!("{} timestamped by {} at {}", evidence_file.original_name, tsa_url, timestamp.time.to_rfc3339());
+                    evidence_file.timestamp = Some(timestamp);
+                    self.update_evidence_metadata(person, &evidence_file)?;
+                    audit_log.record(Some(person), AuditAction::EvidenceUpdated, details)?;
+                }
+                Err(e) => warnings.push(format!("Not timestamped: {:#}", e)),
+            }
+        }
+
+        Ok(Intake::Added { evidence_file, storage_warnings, warnings })
+    }
+
+    /// Works out a file's category from the folder it sits in, falling back to its extension
+    fn classify_file(settings: &Settings, path: &Path, relative_path: &Path) -> Option<EvidenceType> {
+        let mut components = relative_path.components();
+        let folder = components.next().and_then(|c| c.as_os_str().to_str());
+        let in_subfolder = components.next().is_some();
+
+        if let Some(folder) = folder.filter(|_| in_subfolder) {
+            if let Some(built_in) = EvidenceType::built_in().into_iter().find(|t| t.folder_name() == folder && *t != EvidenceType::Quote) {
+                return Some(built_in);
+            }
+            if let Some(category) = settings.category(folder) {
+                return Some(category.evidence_type());
+            }
+        }
+
+        settings.evidence_type_for_file(path)
+    }
+
+    pub fn scan_person_evidence(&self, person: &Person, settings: &Settings) -> Result<Vec<EvidenceFile>> {
         let person_folder = self.evidence_dir.join(person.folder_name());
         let mut evidence_files = Vec::new();
 
@@ -162,6 +930,11 @@ impl FileManager {
             return Ok(evidence_files);
         }
 
+        // Files already in the index keep their IDs, notes and intake dates. An unreadable index is
+        // an error rather than an empty one, so the entries in it are never overwritten.
+        let index = self.load_evidence_index(person)?;
+        let mut found = vec![false; index.len()];
+
         for entry in WalkDir::new(&person_folder)
             .into_iter()
             .filter_map(|e| e.ok())
@@ -171,37 +944,170 @@ impl FileManager {
             let relative_path = path.strip_prefix(&person_folder)
                 .context("Failed to strip prefix")?;
 
-            // Skip person_data.json
-            if relative_path.file_name().and_then(|n| n.to_str()) == Some("person_data.json") {
+            // Skip person_data.json, the evidence index and the board
+            if relative_path.file_name().and_then(|n| n.to_str()).is_some_and(|n| METADATA_FILES.contains(&n)) {
                 continue;
             }
 
-            if let Some(extension) = path.extension() {
-                if let Some(evidence_type) = EvidenceType::from_extension(extension.to_string_lossy().as_ref()) {
-                    let metadata = fs::metadata(path)
-                        .context("Failed to get file metadata")?;
-
-                    evidence_files.push(EvidenceFile {
-                        id: Uuid::new_v4(),
-                        person_id: person.id,
-                        file_path: path.to_path_buf(),
-                        file_type: evidence_type,
-                        original_name: path.file_name()
-                            .context("File has no name")?
-                            .to_string_lossy()
-                            .to_string(),
-                        size: metadata.len(),
-                        created_at: metadata.created()
-                            .ok()
-                            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                            .map(|d| chrono::DateTime::from_timestamp(d.as_secs() as i64, 0).unwrap_or_else(Utc::now))
-                            .unwrap_or_else(Utc::now),
-                        notes: String::new(),
-                    });
+            if let Some(position) = index.iter().position(|f| f.file_path == path) {
+                found[position] = true;
+                let mut indexed = index[position].clone();
+                if let Ok(metadata) = fs::metadata(path) {
+                    indexed.size = metadata.len();
                 }
+                evidence_files.push(indexed);
+                continue;
+            }
+
+            if let Some(evidence_type) = Self::classify_file(settings, path, relative_path) {
+                evidence_files.push(Self::new_entry(person, path, evidence_type)?);
             }
         }
 
+        // Entries whose file is gone stay listed, so Verify Store and a rebuild can still account for them
+        let added = evidence_files.len() - found.iter().filter(|f| **f).count();
+        evidence_files.extend(index.into_iter().zip(found).filter(|(_, found)| !found).map(|(file, _)| file));
+
+        // Only new files are written back, so they keep the IDs they were given here
+        if added > 0 {
+            self.save_evidence_index(person, &evidence_files)?;
+        }
+
         Ok(evidence_files)
     }
+
+    /// Regenerates the person's index from the files in their folder. Every file is hashed and
+    /// matched to an existing entry by path and hash, then by hash alone (a moved file), then by
+    /// path or by name and size for entries with no recorded hash, so notes, review status and
+    /// provenance carry over. Files matching no entry get a new one. Entries whose file wasn't
+    /// found are dropped, unless the person is under legal hold.
+    pub fn rebuild_evidence_index(&self, person: &Person, settings: &Settings) -> Result<RebuildSummary> {
+        let person_folder = self.create_person_folder(person)?;
+        let mut summary = RebuildSummary::default();
+        let mut old_entries = match self.load_evidence_index(person) {
+            Ok(index) => index,
+            Err(_) => {
+                summary.index_unreadable = true;
+                fs::copy(self.evidence_index_path(person), person_folder.join(INDEX_BACKUP))
+                    .context("Failed to back up the unreadable evidence index")?;
+                Vec::new()
+            }
+        };
+
+        let mut on_disk = Vec::new();
+        for entry in WalkDir::new(&person_folder)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            if entry.file_name().to_str().is_some_and(|n| METADATA_FILES.contains(&n)) {
+                continue;
+            }
+            let sha256 = hashing::sha256_file(entry.path())?;
+            let size = entry.metadata().map(|m| m.len()).unwrap_or_default();
+            on_disk.push((entry.into_path(), sha256, size));
+        }
+        on_disk.sort();
+
+        let mut index: Vec<Option<EvidenceFile>> = vec![None; on_disk.len()];
+        // Each pass only takes what the ones before left unclaimed, so a file and an entry pair up once
+        for pass in 0..4 {
+            for (slot, (path, sha256, size)) in index.iter_mut().zip(&on_disk) {
+                if slot.is_some() {
+                    continue;
+                }
+                let matches = |entry: &EvidenceFile| match pass {
+                    0 => entry.file_path == *path && entry.sha256.as_ref().is_none_or(|h| h == sha256),
+                    1 => entry.sha256.as_ref() == Some(sha256),
+                    2 => entry.file_path == *path,
+                    _ => entry.sha256.is_none()
+                        && entry.size == *size
+                        && path.file_name().is_some_and(|n| n.to_string_lossy() == entry.original_name || Some(n) == entry.file_path.file_name()),
+                };
+                if let Some(position) = old_entries.iter().position(matches) {
+                    *slot = Some(old_entries.remove(position));
+                }
+            }
+        }
+
+        let mut rebuilt = Vec::new();
+        for (slot, (path, sha256, size)) in index.into_iter().zip(on_disk) {
+            let relative_path = path.strip_prefix(&person_folder)
+                .context("Failed to strip prefix")?;
+            let file = match slot {
+                Some(mut file) => {
+                    if file.file_path == path {
+                        summary.kept += 1;
+                    } else {
+                        summary.moved += 1;
+                        // A file moved to another category's folder now belongs to it
+                        if let Some(evidence_type) = Self::classify_file(settings, &path, relative_path) {
+                            file.file_type = evidence_type;
+                        }
+                        file.file_path = path;
+                    }
+                    file.size = size;
+                    // A changed file keeps the hash it was added with, so the store check still flags it
+                    file.sha256.get_or_insert(sha256);
+                    file
+                }
+                None => {
+                    summary.added += 1;
+                    let evidence_type = Self::classify_file(settings, &path, relative_path).unwrap_or(EvidenceType::Other);
+                    let mut file = Self::new_entry(person, &path, evidence_type)?;
+                    file.sha256 = Some(sha256);
+                    file
+                }
+            };
+            rebuilt.push(file);
+        }
+
+        // The store check keeps reporting these as missing
+        if person.legal_hold {
+            rebuilt.extend(old_entries);
+        } else {
+            summary.dropped = old_entries.iter().map(|entry| entry.original_name.clone()).collect();
+        }
+        self.save_evidence_index(person, &rebuilt)?;
+
+        Ok(summary)
+    }
+
+    /// Adds a file already in the person's folder to their index, hashing it as it is now.
+    /// Files that match no category go under Other.
+    pub fn index_file(&self, person: &Person, path: &Path, settings: &Settings) -> Result<EvidenceFile> {
+        let person_folder = self.evidence_dir.join(person.folder_name());
+        let Some(relative_path) = path.strip_prefix(&person_folder).ok().filter(|p| paths::is_contained(p)) else {
+            bail!("{} is not in the person's folder", path.display());
+        };
+        let evidence_type = Self::classify_file(settings, path, relative_path).unwrap_or(EvidenceType::Other);
+
+        let mut evidence_file = Self::new_entry(person, path, evidence_type)?;
+        evidence_file.sha256 = Some(hashing::sha256_file(path)?);
+        self.update_evidence_metadata(person, &evidence_file)?;
+
+        Ok(evidence_file)
+    }
+
+    /// An index entry for a file found on disk, dated by the file's creation time
+    fn new_entry(person: &Person, path: &Path, evidence_type: EvidenceType) -> Result<EvidenceFile> {
+        let metadata = fs::metadata(path)
+            .context("Failed to get file metadata")?;
+
+        Ok(EvidenceFile::new(
+            person.id,
+            path.to_path_buf(),
+            evidence_type,
+            path.file_name()
+                .context("File has no name")?
+                .to_string_lossy()
+                .to_string(),
+            metadata.len(),
+            metadata.created()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| chrono::DateTime::from_timestamp(d.as_secs() as i64, 0).unwrap_or_else(Utc::now))
+                .unwrap_or_else(Utc::now),
+        ))
+    }
 }
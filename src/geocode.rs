@@ -0,0 +1,129 @@
+use crate::models::Coordinates;
+use anyhow::{Result, Context};
+use directories::ProjectDirs;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+pub const NOMINATIM_URL: &str = "https://nominatim.openstreetmap.org/search?format=json&limit=1&q={query}";
+
+// Lookups run in the background and may overlap, so gazetteer writes are serialized
+static GAZETTEER_LOCK: Mutex<()> = Mutex::new(());
+
+/// Resolves a free-text place to coordinates. Coordinates typed directly and places in the
+/// local gazetteer never leave the machine; anything else goes to `provider_url`, if one is set,
+/// and the answer is added to the gazetteer.
+pub fn geocode(place: &str, provider_url: &str) -> Result<Option<Coordinates>> {
+    let place = place.trim();
+    if place.is_empty() {
+        return Ok(None);
+    }
+    if let Some(coordinates) = Coordinates::parse(place) {
+        return Ok(Some(coordinates));
+    }
+
+    let _guard = GAZETTEER_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut gazetteer = Gazetteer::load()?;
+    if let Some(coordinates) = gazetteer.places.get(&Gazetteer::key(place)) {
+        return Ok(Some(*coordinates));
+    }
+    if provider_url.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let coordinates = lookup(place, provider_url)?;
+    if let Some(coordinates) = coordinates {
+        gazetteer.places.insert(Gazetteer::key(place), coordinates);
+        gazetteer.save()?;
+    }
+    Ok(coordinates)
+}
+
+#[derive(Deserialize)]
+struct NominatimResult {
+    lat: String,
+    lon: String,
+}
+
+/// Queries a Nominatim-compatible search endpoint; `{query}` in the URL is replaced by the place
+fn lookup(place: &str, provider_url: &str) -> Result<Option<Coordinates>> {
+    let url = provider_url.trim().replace("{query}", &url_encode(place));
+    let response = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .get(&url)
+        .set("User-Agent", concat!("Evidence-Manager/", env!("CARGO_PKG_VERSION")))
+        .call()
+        .context("Failed to reach geocoding service")?;
+
+    let mut body = String::new();
+    response.into_reader().read_to_string(&mut body)
+        .context("Failed to read geocoding response")?;
+    let results: Vec<NominatimResult> = serde_json::from_str(&body)
+        .context("Failed to parse geocoding response")?;
+
+    Ok(results.first().and_then(|result| Coordinates::parse(&format!("{}, {}", result.lat, result.lon))))
+}
+
+fn url_encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Known places, kept as JSON in the config directory so it can be edited or shared by hand
+#[derive(Default)]
+struct Gazetteer {
+    places: BTreeMap<String, Coordinates>, // Lowercased place name
+}
+
+impl Gazetteer {
+    fn path() -> Result<PathBuf> {
+        let project_dirs = ProjectDirs::from("com", "Evidence-Manager", "Evidence-Manager")
+            .context("Failed to get user config directory")?;
+        Ok(project_dirs.config_dir().join("gazetteer.json"))
+    }
+
+    fn key(place: &str) -> String {
+        place.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+    }
+
+    fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let json = fs::read_to_string(&path)
+            .context("Failed to read gazetteer")?;
+        let places: BTreeMap<String, Coordinates> = serde_json::from_str(&json)
+            .context("Failed to parse gazetteer")?;
+        // Hand-edited names may not be normalized
+        Ok(Self {
+            places: places.into_iter().map(|(place, coordinates)| (Self::key(&place), coordinates)).collect(),
+        })
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .context("Failed to create config directory")?;
+        }
+
+        let json = serde_json::to_string_pretty(&self.places)
+            .context("Failed to serialize gazetteer")?;
+        fs::write(&path, json)
+            .context("Failed to write gazetteer")?;
+        Ok(())
+    }
+}
@@ -1,63 +1,421 @@
-use crate::models::{Person, EvidenceFile, EvidenceType};
-use crate::state::{AppState, Message};
+use crate::models::{Person, PersonInfo, EvidenceFile, EvidenceType, CaseRole, CaseStatus, ColorLabel, FieldType, FieldValue, Priority, Provenance, Quote, ReviewStatus, Task};
+use crate::name_list::NameStatus;
+use crate::evidence_browser::BrowserColumn;
+use crate::lineup::LineupOrder;
+use crate::dates::{DateDisplay, DateFormat, DisplayZone, QuoteZone, TimeFormat};
+use crate::state::{AppState, BulkAddDialog, ChatImportDialog, DeviceImportDialog, FileListDialog, IntakeDialog, LineupDialog, Message, NoteEditor, QuotePasteDialog};
+use crate::quarantine::QuarantinedFile;
+use crate::device_import;
+use crate::file_list_import::MappedField;
+use crate::notes;
+use crate::email::ParsedEmail;
+use crate::duplicates::DuplicatePair;
+use crate::file_manager::FileManager;
+use crate::naming;
+use crate::reports;
+use crate::bulk_edit::BulkEditField;
+use crate::map;
+use crate::geocode;
+use crate::statistics;
+use crate::settings::{ChecklistTemplate, ExportSchedule, Settings, StorageLayout, UI_SCALE_STEPS};
+use crate::jobs::JobStatus;
+use crate::profiles::Role;
+use crate::import_journal::ImportJournal;
+use crate::drafts::Draft;
+use crate::completeness::Completeness;
+use crate::store_check::IssueKind;
+use crate::board::{self, Board, CardContent, CARD_HEIGHT, CARD_WIDTH};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use chrono::Local;
+use uuid::Uuid;
 use iced::{
     widget::{
-        button, column, container, row, scrollable, text, text_input, 
+        button, canvas, checkbox, column, container, image, mouse_area, pick_list, progress_bar, row, scrollable, text, text_editor, text_input, tooltip, 
         Column, Row, Space,
     },
-    Element, Length, Alignment, Color, theme,
+    Element, Length, Alignment, Color, Point, Radians, Rectangle, Renderer, Size, Theme, Vector, event, mouse, theme,
 };
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum EvidenceTab {
     Information,
+    All, // Every evidence file, whatever its category
     Images,
     Audio,
     Videos,
     Documents,
+    Other,
     Quotes,
+    Tasks,
+    Map,
+    Board,
+    Custom(String), // Folder name of a user-defined category
 }
 
 impl EvidenceTab {
-    fn all() -> Vec<EvidenceTab> {
-        vec![
+    pub fn all(settings: &Settings) -> Vec<EvidenceTab> {
+        let mut tabs = vec![
             EvidenceTab::Information,
+            EvidenceTab::All,
             EvidenceTab::Images,
             EvidenceTab::Audio,
             EvidenceTab::Videos,
             EvidenceTab::Documents,
-            EvidenceTab::Quotes,
-        ]
+            EvidenceTab::Other,
+        ];
+        tabs.extend(settings.custom_categories.iter().map(|c| EvidenceTab::Custom(c.folder_name.clone())));
+        tabs.push(EvidenceTab::Quotes);
+        tabs.push(EvidenceTab::Tasks);
+        tabs.push(EvidenceTab::Map);
+        tabs.push(EvidenceTab::Board);
+        tabs
+    }
+
+    /// The tab listing files of the given category
+    pub fn for_evidence_type(evidence_type: &EvidenceType) -> EvidenceTab {
+        match evidence_type {
+            EvidenceType::Image => EvidenceTab::Images,
+            EvidenceType::Audio => EvidenceTab::Audio,
+            EvidenceType::Video => EvidenceTab::Videos,
+            EvidenceType::Document => EvidenceTab::Documents,
+            EvidenceType::Other => EvidenceTab::Other,
+            EvidenceType::Quote => EvidenceTab::Quotes,
+            EvidenceType::Custom(folder_name) => EvidenceTab::Custom(folder_name.clone()),
+        }
     }
     
-    fn label(&self) -> &'static str {
+    fn label(&self, settings: &Settings) -> String {
+        match self {
+            EvidenceTab::Information => "Information".to_string(),
+            EvidenceTab::All => "All Evidence".to_string(),
+            EvidenceTab::Images => "Images".to_string(),
+            EvidenceTab::Audio => "Audio".to_string(),
+            EvidenceTab::Videos => "Videos".to_string(),
+            EvidenceTab::Documents => "Documents".to_string(),
+            EvidenceTab::Other => "Other".to_string(),
+            EvidenceTab::Quotes => "Quotes".to_string(),
+            EvidenceTab::Tasks => "Tasks".to_string(),
+            EvidenceTab::Map => "Map".to_string(),
+            EvidenceTab::Board => "Board".to_string(),
+            EvidenceTab::Custom(folder_name) => settings.display_name(&EvidenceType::Custom(folder_name.clone())),
+        }
+    }
+
+    /// How many files the tab lists and their total size, counted as the tab counts them; None
+    /// for tabs that don't list files
+    fn file_stats(&self, evidence_files: &[EvidenceFile]) -> Option<(usize, u64)> {
+        let listed: Vec<&EvidenceFile> = match self {
+            EvidenceTab::All => evidence_files
+                .iter()
+                .filter(|f| f.file_type != EvidenceType::Quote && f.superseded_by.is_none())
+                .collect(),
+            EvidenceTab::Images
+            | EvidenceTab::Audio
+            | EvidenceTab::Videos
+            | EvidenceTab::Documents
+            | EvidenceTab::Other
+            | EvidenceTab::Custom(_) => evidence_files
+                .iter()
+                .filter(|f| EvidenceTab::for_evidence_type(&f.file_type) == *self && f.superseded_by.is_none())
+                .collect(),
+            _ => return None,
+        };
+        Some((listed.len(), listed.iter().map(|f| f.size).sum()))
+    }
+}
+
+/// A byte count in the largest unit that keeps it at one or more, e.g. "180 MB"
+fn format_size(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = 1024 * KB;
+    const GB: u64 = 1024 * MB;
+    match bytes {
+        0..KB => format!("{} B", bytes),
+        KB..MB => format!("{} KB", bytes / KB),
+        MB..GB => format!("{} MB", bytes / MB),
+        _ => format!("{:.1} GB", bytes as f64 / GB as f64),
+    }
+}
+
+/// Order of the files in the All Evidence tab
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EvidenceSort {
+    #[default]
+    Date, // Oldest first, as in the category tabs
+    Name,
+    Category,
+    Size, // Largest first
+}
+
+impl EvidenceSort {
+    pub fn all() -> [EvidenceSort; 4] {
+        [EvidenceSort::Date, EvidenceSort::Name, EvidenceSort::Category, EvidenceSort::Size]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EvidenceSort::Date => "Sort by date",
+            EvidenceSort::Name => "Sort by name",
+            EvidenceSort::Category => "Sort by category",
+            EvidenceSort::Size => "Sort by size",
+        }
+    }
+
+    pub fn sort(&self, files: &mut [&EvidenceFile], settings: &Settings) {
+        match self {
+            EvidenceSort::Date => files.sort_by_key(|f| f.evidence_date.unwrap_or(f.created_at)),
+            EvidenceSort::Name => files.sort_by_key(|f| f.original_name.to_lowercase()),
+            // Within a category, oldest first
+            EvidenceSort::Category => files.sort_by_key(|f| (settings.display_name(&f.file_type), f.evidence_date.unwrap_or(f.created_at))),
+            EvidenceSort::Size => files.sort_by_key(|f| std::cmp::Reverse(f.size)),
+        }
+    }
+}
+
+impl std::fmt::Display for EvidenceSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// How the sidebar groups people into collapsible sections
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PersonGrouping {
+    #[default]
+    None,
+    Tag,
+    CaseStatus,
+    Priority,
+    Letter, // First letter of the name, with an A-Z strip to jump between them
+}
+
+impl PersonGrouping {
+    pub fn all() -> [PersonGrouping; 5] {
+        [PersonGrouping::None, PersonGrouping::Tag, PersonGrouping::CaseStatus, PersonGrouping::Priority, PersonGrouping::Letter]
+    }
+
+    pub fn label(&self) -> &'static str {
         match self {
-            EvidenceTab::Information => "Information",
-            EvidenceTab::Images => "Images",
-            EvidenceTab::Audio => "Audio",
-            EvidenceTab::Videos => "Videos",
-            EvidenceTab::Documents => "Documents",
-            EvidenceTab::Quotes => "Quotes",
+            PersonGrouping::None => "No grouping",
+            PersonGrouping::Tag => "Group by tag",
+            PersonGrouping::CaseStatus => "Group by case status",
+            PersonGrouping::Priority => "Group by priority",
+            PersonGrouping::Letter => "Group by first letter",
         }
     }
+
+    /// Identifies a group in the saved collapsed groups, e.g. "Tag:witness"
+    pub fn group_key(&self, group: &str) -> String {
+        format!("{:?}:{}", self, group)
+    }
+}
+
+impl std::fmt::Display for PersonGrouping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// A person offered in a pick list, shown by their name
+#[derive(Debug, Clone, PartialEq)]
+struct PersonChoice {
+    id: Uuid,
+    name: String,
+}
+
+impl std::fmt::Display for PersonChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Something that can be pinned to the board, shown as "Kind: name"
+#[derive(Debug, Clone, PartialEq)]
+struct BoardCardChoice {
+    content: CardContent,
+    label: String,
+}
+
+impl std::fmt::Display for BoardCardChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+/// An evidence file offered in a pick list, shown by its name
+#[derive(Debug, Clone, PartialEq)]
+struct EvidenceChoice {
+    id: Uuid,
+    name: String,
+}
+
+impl std::fmt::Display for EvidenceChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// An attached device offered in a pick list, shown by its volume name
+#[derive(Debug, Clone, PartialEq)]
+struct DeviceChoice {
+    path: PathBuf,
+    label: String,
+}
+
+impl std::fmt::Display for DeviceChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
 }
 
+/// A file list column offered in a pick list, by its header
+#[derive(Debug, Clone, PartialEq)]
+struct ColumnChoice {
+    column: Option<usize>, // None leaves the field empty
+    label: String,
+}
+
+impl std::fmt::Display for ColumnChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+fn evidence_name(state: &AppState, evidence_id: Uuid) -> Option<&str> {
+    state.evidence_files
+        .iter()
+        .find(|f| f.id == evidence_id)
+        .map(|f| f.original_name.as_str())
+}
+
+// Lists whose scroll position is kept in the session snapshot. Only one tab's list shows at a
+// time, so they share an id.
+pub const PERSON_LIST_SCROLL: &str = "person-list";
+
+// Rows drawn at once in the All Evidence browser; more would make scrolling sluggish
+const BROWSER_ROW_LIMIT: usize = 500;
+
+// Heights of a group header and a person in the sidebar list, for working out where to scroll
+// to a letter; iced can't scroll to a widget directly
+const PERSON_GROUP_HEADER_HEIGHT: f32 = 33.0;
+const PERSON_ENTRY_HEIGHT: f32 = 48.0;
+
+/// Letters of the A-Z strip; names starting with anything else are under "#"
+pub const INDEX_LETTERS: [char; 27] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M',
+    'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '#',
+];
+pub const TAB_SCROLL: &str = "tab-list";
+
 pub fn view(state: &AppState) -> Element<'_, Message> {
+    if state.role.is_none() {
+        return profile_picker(state);
+    }
+    if state.quick_capture {
+        return quick_capture(state);
+    }
+
     let content = row![
         // Left sidebar
         sidebar(state),
+        // Drag handle for resizing the sidebar
+        mouse_area(container(Space::new(Length::Fixed(4.0), Length::Fill)))
+            .on_press(Message::SidebarDragStarted)
+            .interaction(mouse::Interaction::ResizingHorizontally),
         // Main content
         main_content(state),
     ]
-    .spacing(10)
+    .spacing(3)
     .padding(10);
 
-    let mut layout = column![content];
+    let mut layout = Column::new();
+    if let Some(journal) = &state.interrupted_import {
+        layout = layout.push(interrupted_import_bar(journal, state.settings.dates()));
+    }
+    if let Some((name, passphrase)) = &state.export_passphrase_prompt {
+        layout = layout.push(export_passphrase_bar(name, passphrase));
+    }
+    for draft in &state.restorable_drafts {
+        if let Some(person) = state.persons.iter().find(|p| p.id == draft.person_id) {
+            layout = layout.push(draft_bar(person, draft, state.settings.dates()));
+        }
+    }
+    layout = layout.push(content);
 
     // Add modal dialogs
     if state.show_add_person_dialog {
         layout = layout.push(add_person_dialog(state).unwrap());
     }
+    if let Some(dialog) = &state.bulk_add {
+        layout = layout.push(bulk_add_dialog(state, dialog));
+    }
+    if let Some(dialog) = &state.lineup {
+        layout = layout.push(lineup_dialog(state, dialog));
+    }
+
+    if let Some(running) = &state.running_job
+        && let Some(job) = state.jobs.jobs.iter().find(|job| job.id == running.id)
+    {
+        let queued = state.jobs.jobs.iter().filter(|job| job.status == JobStatus::Queued).count();
+        let label = if queued > 0 {
+            format!("{}… ({} more queued)", job.kind.label(), queued)
+        } else {
+            format!("{}…", job.kind.label())
+        };
+        layout = layout.push(
+            container(
+                row![
+                    text(label),
+                    Space::with_width(Length::Fill),
+                    text(format!("{} / {} files", running.shown_progress.0, running.shown_progress.1))
+                        .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+                    progress_bar(0.0..=running.shown_progress.1.max(1) as f32, running.shown_progress.0 as f32)
+                        .width(Length::Fixed(200.0))
+                        .height(Length::Fixed(12.0)),
+                    button("Jobs")
+                        .on_press(Message::ShowJobs(true)),
+                    button("Cancel")
+                        .on_press_maybe((!running.cancel.is_cancelled()).then_some(Message::CancelJob(running.id)))
+                        .style(theme::Button::Destructive),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center)
+            )
+            .padding(5)
+            .style(theme::Container::Box)
+        );
+    }
+
+    // Copies still going at the last refresh, so quick ones never show
+    for copy in state.file_copies.iter().filter(|copy| copy.shown_progress.1 > 0) {
+        const MB: u64 = 1024 * 1024;
+        let (done, total) = copy.shown_progress;
+        let size = total / 2;
+        let (label, done_mb) = if done < size {
+            ("Copying", done / MB)
+        } else {
+            ("Verifying", (done - size) / MB)
+        };
+        layout = layout.push(
+            container(
+                row![
+                    text(format!("{} {}…", label, copy.name)),
+                    Space::with_width(Length::Fill),
+                    text(format!("{} / {} MB", done_mb, size / MB))
+                        .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+                    progress_bar(0.0..=total as f32, done as f32)
+                        .width(Length::Fixed(200.0))
+                        .height(Length::Fixed(12.0)),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center)
+            )
+            .padding(5)
+            .style(theme::Container::Box)
+        );
+    }
 
     // Add status bar at bottom
     if !state.status_message.is_empty() {
@@ -74,12 +432,248 @@ pub fn view(state: &AppState) -> Element<'_, Message> {
     layout.into()
 }
 
+fn draft_bar<'a>(person: &Person, draft: &'a Draft, dates: DateDisplay) -> Element<'a, Message> {
+    // The start of the text, so it's clear which draft this is
+    let mut preview = draft.field("text")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if preview.chars().count() > 60 {
+        preview = format!("{}…", preview.chars().take(60).collect::<String>());
+    }
+    container(
+        row![
+            text(format!(
+                "Unsaved {} for {} from {}: \"{}\" Restore draft?",
+                draft.form.label(),
+                person.name,
+                dates.date_time(draft.saved_at),
+                preview,
+            ))
+            .style(theme::Text::Color(Color::from_rgb(0.8, 0.5, 0.1))),
+            Space::with_width(Length::Fill),
+            button("Restore")
+                .on_press(Message::RestoreDraft(draft.person_id, draft.form))
+                .style(theme::Button::Primary),
+            button("Discard")
+                .on_press(Message::DiscardDraft(draft.person_id, draft.form))
+                .style(theme::Button::Destructive),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center)
+    )
+    .padding(5)
+    .style(theme::Container::Box)
+    .into()
+}
+
+fn interrupted_import_bar(journal: &ImportJournal, dates: DateDisplay) -> Element<'_, Message> {
+    let archive = journal.archive.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| journal.archive.display().to_string());
+    container(
+        row![
+            text(format!(
+                "The import of {} started {} was interrupted after {} of {} files",
+                archive,
+                dates.date_time(journal.started_at),
+                journal.completed.len(),
+                journal.total,
+            ))
+            .style(theme::Text::Color(Color::from_rgb(0.8, 0.5, 0.1))),
+            Space::with_width(Length::Fill),
+            button("Resume")
+                .on_press(Message::ResumeInterruptedImport)
+                .style(theme::Button::Primary),
+            button("Roll Back")
+                .on_press(Message::RollBackInterruptedImport)
+                .style(theme::Button::Destructive),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center)
+    )
+    .padding(5)
+    .style(theme::Container::Box)
+    .into()
+}
+
+/// Asks for an encrypted export profile's passphrase, which isn't kept between runs of the app
+fn export_passphrase_bar<'a>(name: &str, passphrase: &'a str) -> Element<'a, Message> {
+    container(
+        row![
+            text(format!("Export profile \"{}\" encrypts its archives: enter its passphrase to run it", name))
+                .style(theme::Text::Color(Color::from_rgb(0.8, 0.5, 0.1))),
+            Space::with_width(Length::Fill),
+            text_input("Passphrase", passphrase)
+                .secure(true)
+                .on_input(Message::ExportProfilePassphraseChanged)
+                .on_submit(Message::ExportProfilePassphraseSubmitted)
+                .width(Length::Fixed(220.0)),
+            button("Run")
+                .on_press_maybe((!passphrase.is_empty()).then_some(Message::ExportProfilePassphraseSubmitted))
+                .style(theme::Button::Primary),
+            button("Not Now")
+                .on_press(Message::ExportProfilePassphraseCancelled),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center)
+    )
+    .padding(5)
+    .style(theme::Container::Box)
+    .into()
+}
+
+/// Shown at launch once an admin passphrase is set
+fn profile_picker(state: &AppState) -> Element<'_, Message> {
+    let mut content = column![
+        text("Evidence Manager").size(20).style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8))),
+        Space::with_height(10),
+        text("Admin").size(16),
+        row![
+            text_input("Admin passphrase", &state.profile_passphrase)
+                .secure(true)
+                .on_input(Message::ProfilePassphraseChanged)
+                .on_submit(Message::OpenAsAdmin)
+                .width(Length::Fixed(250.0)),
+            button("Open as Admin")
+                .on_press(Message::OpenAsAdmin)
+                .style(theme::Button::Primary),
+        ]
+        .spacing(5),
+        Space::with_height(10),
+        text("Viewer").size(16),
+        text("Browse people and evidence, print reports and export, without changing anything")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        button("Open as Viewer")
+            .on_press(Message::OpenAsViewer),
+    ]
+    .spacing(5)
+    .max_width(500);
+
+    if !state.status_message.is_empty() {
+        content = content.push(
+            text(&state.status_message)
+                .style(theme::Text::Color(Color::from_rgb(0.8, 0.2, 0.2)))
+        );
+    }
+
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x()
+        .center_y()
+        .into()
+}
+
+/// The small always-on-top panel for adding evidence to someone without the rest of the app
+fn quick_capture(state: &AppState) -> Element<'_, Message> {
+    let people: Vec<PersonChoice> = state.persons
+        .iter()
+        .map(|p| PersonChoice { id: p.id, name: p.name.clone() })
+        .collect();
+    let selected = people.iter().find(|p| Some(p.id) == state.selected_person).cloned();
+    let has_person = selected.is_some();
+
+    let mut content = column![
+        text("Quick Capture").size(18),
+        pick_list(people, selected, |choice| Message::PersonSelected(choice.id))
+            .placeholder("Pick a person")
+            .width(Length::Fill),
+        row![
+            button("Choose File...")
+                .on_press_maybe(has_person.then_some(Message::SelectFileClicked)),
+            button("Paste Screenshot")
+                .on_press_maybe(has_person.then_some(Message::PasteScreenshotClicked)),
+        ]
+        .spacing(5),
+        text("or drop a file onto this window")
+            .size(12)
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        checkbox("Move into evidence", state.move_files)
+            .on_toggle(Message::MoveFilesToggled),
+        row![
+            text_input("Type or paste text to save as a quote", &state.quick_capture_text)
+                .on_input(Message::QuickCaptureTextChanged)
+                .on_submit(Message::QuickCaptureQuoteSubmitted),
+            button("Save")
+                .on_press_maybe(has_person.then_some(Message::QuickCaptureQuoteSubmitted)),
+        ]
+        .spacing(5),
+    ]
+    .spacing(8);
+
+    // Files still need their provenance or category here, as in the full window
+    if let Some(intake) = &state.intake {
+        content = content.push(intake_dialog(state, intake));
+    } else if let Some(path) = &state.uncategorized_file {
+        content = content.push(uncategorized_file_prompt(state, path));
+    }
+    if !state.status_message.is_empty() {
+        content = content.push(
+            text(&state.status_message)
+                .size(12)
+                .style(theme::Text::Color(Color::from_rgb(0.0, 0.5, 0.0)))
+        );
+    }
+    content = content.push(
+        row![
+            button("Hide")
+                .on_press(Message::QuickCaptureHidden),
+            Space::with_width(Length::Fill),
+            button("Open Full App")
+                .on_press(Message::QuickCaptureClosed),
+        ]
+        .spacing(5)
+    );
+
+    scrollable(container(content).padding(10)).into()
+}
+
+fn plugin_list(state: &AppState) -> Element<'_, Message> {
+    if state.plugin_files.is_empty() {
+        return text("No plugins installed")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+            .into();
+    }
+    let mut list = Column::new().spacing(2);
+    for plugin in &state.plugin_files {
+        let name = plugin.name.clone();
+        list = list.push(
+            checkbox(&plugin.name, plugin.enabled)
+                .on_toggle(move |enabled| Message::PluginToggled(name.clone(), enabled))
+        );
+    }
+    list.into()
+}
+
 fn sidebar(state: &AppState) -> Element<'_, Message> {
     let mut sidebar_content = column![
         text("Evidence Manager").size(20).style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8))),
         Space::with_height(10),
     ];
 
+    // Profile switching only exists once an admin passphrase is set
+    if state.settings.admin_passphrase.is_some() {
+        let role = state.role.unwrap_or(Role::Viewer);
+        let label = match role {
+            Role::Admin => text(role.label()),
+            Role::Viewer => text(format!("{} (read-only)", role.label()))
+                .style(theme::Text::Color(Color::from_rgb(0.8, 0.5, 0.1))),
+        };
+        sidebar_content = sidebar_content.push(
+            row![
+                label,
+                Space::with_width(Length::Fill),
+                button("Switch Profile")
+                    .on_press(Message::SwitchProfile)
+                    .style(theme::Button::Secondary),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+        sidebar_content = sidebar_content.push(Space::with_height(5));
+    }
+
     // Action buttons
     sidebar_content = sidebar_content.push(
         column![
@@ -90,94 +684,711 @@ fn sidebar(state: &AppState) -> Element<'_, Message> {
                 .on_press(Message::ImportClicked),
             button("Export All")
                 .on_press(Message::ExportClicked),
+            button("Export Each Person")
+                .on_press(Message::ExportEachPersonClicked),
+            button("Export CSV")
+                .on_press(Message::ExportCsvClicked),
+            button("Export Hash Manifest")
+                .on_press(Message::ExportHashManifestClicked(None)),
+            button("Export Custody Log")
+                .on_press(Message::ExportCustodyLogClicked(None)),
+            button("Export Inventory")
+                .on_press(Message::ExportInventoryClicked(None)),
+            button("Export Timeline")
+                .on_press(Message::ExportTimelineClicked(None)),
+            button("Export Graph")
+                .on_press(Message::ExportGraphClicked),
+            button("Export Lineup")
+                .on_press(Message::ExportLineupClicked),
+            button("Quick Capture")
+                .on_press(Message::QuickCaptureOpened),
+            button("Dashboard")
+                .on_press(Message::ShowDashboard),
+            button("Activity")
+                .on_press(Message::ShowActivity(true)),
+            button("All Evidence")
+                .on_press(Message::ShowEvidenceBrowser(true)),
+            button("Jobs")
+                .on_press(Message::ShowJobs(true)),
+            button("Verify Store")
+                .on_press(Message::ShowStoreCheck(true)),
+            button("Settings")
+                .on_press(Message::ShowSettings(true)),
             button("Check Updates")
                 .on_press(Message::StatusMessage("No updates available".to_string())),
         ]
         .spacing(5)
     );
 
+    // Export options
+    let mut export_options = column![
+        checkbox("Watermark exported media", state.watermark_exports)
+            .on_toggle(Message::WatermarkExportsToggled),
+        checkbox("Metadata only (no media files)", state.metadata_only_exports)
+            .on_toggle(Message::MetadataOnlyExportsToggled),
+        checkbox("Thumbnails only (small image previews)", state.thumbnails_only_exports)
+            .on_toggle(Message::ThumbnailsOnlyExportsToggled),
+    ]
+    .spacing(5);
+    if state.watermark_exports {
+        export_options = export_options.push(
+            text_input("Case name", &state.watermark_case_name)
+                .on_input(Message::WatermarkCaseNameChanged)
+        );
+    }
+    sidebar_content = sidebar_content.push(Space::with_height(5));
+    sidebar_content = sidebar_content.push(export_options);
+
     sidebar_content = sidebar_content.push(Space::with_height(10));
     sidebar_content = sidebar_content.push(text("People").size(16));
 
     // Search bar
     sidebar_content = sidebar_content.push(
-        text_input("Search people...", &state.search_query)
+        text_input("Search people, e.g. type:phone value:555*", &state.search_query)
             .on_input(Message::SearchQueryChanged)
     );
+    if let Some(error) = &state.search_error {
+        sidebar_content = sidebar_content.push(
+            text(error)
+                .size(12)
+                .style(theme::Text::Color(Color::from_rgb(0.8, 0.2, 0.2)))
+        );
+    }
+    sidebar_content = sidebar_content.push(
+        row![
+            text_input("Name this search...", &state.new_saved_search_name)
+                .on_input(Message::SavedSearchNameChanged)
+                .on_submit(Message::SaveSearch),
+            button("Save")
+                .on_press(Message::SaveSearch)
+                .style(theme::Button::Secondary),
+        ]
+        .spacing(2)
+    );
+
+    // Smart views: saved searches, highlighted while their filters are in effect
+    for saved_search in &state.settings.saved_searches {
+        let is_active = saved_search.query == state.search_query.trim()
+            && saved_search.case_status == state.case_status_filter
+            && saved_search.sort_by_priority == state.sort_by_priority
+            && saved_search.color_label == state.color_label_filter;
+        sidebar_content = sidebar_content.push(
+            row![
+                button(text(format!("🔎 {}", saved_search.name)))
+                    .on_press(Message::ApplySavedSearch(saved_search.name.clone()))
+                    .style(if is_active { theme::Button::Primary } else { theme::Button::Text })
+                    .width(Length::Fill),
+                button("×")
+                    .on_press(Message::RemoveSavedSearch(saved_search.name.clone()))
+                    .style(theme::Button::Text),
+            ]
+            .spacing(2)
+        );
+    }
+
+    // Case status filter and sorting
+    let mut status_filter = row![case_status_filter_button(state, "All", None)].spacing(2);
+    for status in CaseStatus::all() {
+        status_filter = status_filter.push(case_status_filter_button(state, status.label(), Some(status)));
+    }
+    sidebar_content = sidebar_content.push(status_filter);
+    let mut color_filter = row![color_label_button(None, state.color_label_filter.is_none(), Message::ColorLabelFilterChanged(None))]
+        .spacing(2)
+        .align_items(Alignment::Center);
+    for color in ColorLabel::all() {
+        color_filter = color_filter.push(
+            color_label_button(Some(color), state.color_label_filter == Some(color), Message::ColorLabelFilterChanged(Some(color)))
+        );
+    }
+    sidebar_content = sidebar_content.push(color_filter);
+    sidebar_content = sidebar_content.push(
+        checkbox("Sort by priority", state.sort_by_priority)
+            .on_toggle(Message::SortByPriorityToggled)
+    );
 
-    // Person list
+    // Person list, optionally in collapsible groups
+    sidebar_content = sidebar_content.push(
+        pick_list(PersonGrouping::all().to_vec(), Some(state.settings.layout.person_grouping), Message::PersonGroupingChanged)
+            .width(Length::Fill)
+    );
     let person_list: Element<Message> = if state.filtered_persons.is_empty() {
         text("No people found").style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))).into()
     } else {
         let mut person_buttons = Column::new().spacing(2);
+        let grouping = state.settings.layout.person_grouping;
         
-        for person_id in &state.filtered_persons {
-            if let Some(person) = state.persons.iter().find(|p| p.id == *person_id) {
-                let is_selected = state.selected_person == Some(person.id);
-                let button_style = if is_selected {
-                    theme::Button::Primary
-                } else {
-                    theme::Button::Secondary
-                };
-                
+        if grouping == PersonGrouping::None {
+            for person in filtered_people(state) {
+                person_buttons = person_buttons.push(person_entry(state, person));
+            }
+        } else {
+            for (group, people) in person_groups(state, grouping) {
+                let key = grouping.group_key(&group);
+                let collapsed = state.settings.layout.collapsed_groups.contains(&key);
                 person_buttons = person_buttons.push(
-                    button(&*person.name)
-                        .on_press(Message::PersonSelected(person.id))
-                        .style(button_style)
+                    button(text(format!("{} {} ({})", if collapsed { "▸" } else { "▾" }, group, people.len())))
+                        .on_press(Message::PersonGroupToggled(key))
+                        .style(theme::Button::Text)
                         .width(Length::Fill)
                 );
+                if !collapsed {
+                    for person in people {
+                        person_buttons = person_buttons.push(person_entry(state, person));
+                    }
+                }
             }
         }
         
-        scrollable(person_buttons)
-            .height(Length::Fill)
-            .into()
+        let list = scrollable(person_buttons)
+            .id(scrollable::Id::new(PERSON_LIST_SCROLL))
+            .on_scroll(|viewport| Message::PersonListScrolled(viewport.relative_offset().y))
+            .width(Length::Fill)
+            .height(Length::Fill);
+        if grouping == PersonGrouping::Letter {
+            row![list, letter_strip(state)].spacing(2).into()
+        } else {
+            list.into()
+        }
     };
 
     sidebar_content = sidebar_content.push(person_list);
 
     container(sidebar_content)
-        .width(Length::Fixed(300.0))
+        .width(Length::Fixed(state.settings.layout.sidebar_width))
         .height(Length::Fill)
         .padding(10)
         .style(theme::Container::Box)
         .into()
 }
 
-fn main_content(state: &AppState) -> Element<'_, Message> {
-    if let Some(person_id) = state.selected_person {
-        if let Some(person) = state.persons.iter().find(|p| p.id == person_id) {
-            let mut content = column![
-                // Header with person name and actions
-                row![
-                    text(format!("Evidence for: {}", person.name))
-                        .size(18)
-                        .style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8))),
-                    Space::with_width(Length::Fill),
-                    button("Delete Person")
-                        .on_press(Message::DeletePerson(person.id))
-                        .style(theme::Button::Destructive),
-                    button("Export Evidence")
-                        .on_press(Message::ExportPersonClicked),
-                ]
-                .spacing(10)
-                .align_items(Alignment::Center),
-                
-                Space::with_height(10),
-            ];
+fn filtered_people(state: &AppState) -> impl Iterator<Item = &Person> {
+    state.filtered_persons
+        .iter()
+        .filter_map(|id| state.persons.iter().find(|p| p.id == *id))
+}
 
-            // Tab navigation
-            let mut tab_row = Row::new().spacing(5);
-            for tab in EvidenceTab::all() {
-                let is_selected = state.current_tab == tab;
+/// The filtered people split into named groups, each keeping the list order. People with several
+/// tags are listed under each of them.
+fn person_groups(state: &AppState, grouping: PersonGrouping) -> Vec<(String, Vec<&Person>)> {
+    match grouping {
+        PersonGrouping::None => vec![(String::new(), filtered_people(state).collect())],
+        PersonGrouping::Tag => {
+            let mut tags: Vec<&String> = filtered_people(state).flat_map(|p| &p.tags).collect();
+            tags.sort_by_key(|tag| tag.to_lowercase());
+            tags.dedup();
+            let mut groups: Vec<(String, Vec<&Person>)> = tags
+                .into_iter()
+                .map(|tag| (tag.clone(), filtered_people(state).filter(|p| p.tags.contains(tag)).collect()))
+                .collect();
+            let untagged: Vec<&Person> = filtered_people(state).filter(|p| p.tags.is_empty()).collect();
+            if !untagged.is_empty() {
+                groups.push(("Untagged".to_string(), untagged));
+            }
+            groups
+        }
+        PersonGrouping::CaseStatus => CaseStatus::all()
+            .into_iter()
+            .map(|status| (status.label().to_string(), filtered_people(state).filter(|p| p.case_status == status).collect::<Vec<_>>()))
+            .filter(|(_, people)| !people.is_empty())
+            .collect(),
+        // Most pressing first
+        PersonGrouping::Priority => Priority::all()
+            .into_iter()
+            .rev()
+            .map(|priority| (priority.label().to_string(), filtered_people(state).filter(|p| p.priority == priority).collect::<Vec<_>>()))
+            .filter(|(_, people)| !people.is_empty())
+            .collect(),
+        PersonGrouping::Letter => INDEX_LETTERS
+            .into_iter()
+            .map(|letter| (letter.to_string(), filtered_people(state).filter(|p| name_letter(&p.name) == letter).collect::<Vec<_>>()))
+            .filter(|(_, people)| !people.is_empty())
+            .collect(),
+    }
+}
+
+/// The index letter a name is listed under, ignoring case and accents on Latin letters
+fn name_letter(name: &str) -> char {
+    let first = name.trim().chars().next().unwrap_or('#');
+    let first = match first.to_ascii_uppercase() {
+        letter if letter.is_ascii_uppercase() => letter,
+        _ => first.to_uppercase().next().unwrap_or(first),
+    };
+    let letter = match first {
+        'À'..='Å' => 'A',
+        'Ç' => 'C',
+        'È'..='Ë' => 'E',
+        'Ì'..='Ï' => 'I',
+        'Ñ' => 'N',
+        'Ò'..='Ö' | 'Ø' => 'O',
+        'Ù'..='Ü' => 'U',
+        'Ý' => 'Y',
+        other => other,
+    };
+    if letter.is_ascii_uppercase() { letter } else { '#' }
+}
+
+/// Where a letter's section starts in the sidebar list, estimated from the entry heights. None
+/// when nobody in the list is under the letter.
+pub fn letter_offset(state: &AppState, letter: char) -> Option<f32> {
+    let mut offset = 0.0;
+    for (group, people) in person_groups(state, PersonGrouping::Letter) {
+        if group == letter.to_string() {
+            return Some(offset);
+        }
+        offset += PERSON_GROUP_HEADER_HEIGHT;
+        if !state.settings.layout.collapsed_groups.contains(&PersonGrouping::Letter.group_key(&group)) {
+            offset += people.len() as f32 * PERSON_ENTRY_HEIGHT;
+        }
+    }
+    None
+}
+
+/// The A-Z jump strip beside the person list; letters nobody is listed under are greyed out
+fn letter_strip(state: &AppState) -> Element<'_, Message> {
+    let used: Vec<String> = person_groups(state, PersonGrouping::Letter)
+        .into_iter()
+        .map(|(group, _)| group)
+        .collect();
+    let mut strip = Column::new().align_items(Alignment::Center);
+    for letter in INDEX_LETTERS {
+        strip = strip.push(
+            button(text(letter).size(11))
+                .on_press_maybe(used.contains(&letter.to_string()).then_some(Message::JumpToLetter(letter)))
+                .padding([1, 4])
+                .style(theme::Button::Text)
+        );
+    }
+    scrollable(strip).height(Length::Fill).into()
+}
+
+/// A person's button in the sidebar, with their right-click actions under it when open
+fn person_entry<'a>(state: &'a AppState, person: &'a Person) -> Element<'a, Message> {
+    let mut entry = Column::new().spacing(2);
+    let is_selected = state.selected_person == Some(person.id);
+    let button_style = if is_selected {
+        theme::Button::Primary
+    } else {
+        theme::Button::Secondary
+    };
+    
+    let files = state.evidence_counts.get(&person.id).copied().unwrap_or_default();
+    let counts = format!(
+        "{} {} · {} {}",
+        files, if files == 1 { "file" } else { "files" },
+        person.quotes.len(), if person.quotes.len() == 1 { "quote" } else { "quotes" },
+    );
+    let mut name = person.name.clone();
+    if person.legal_hold {
+        name = format!("🔒 {}", name);
+    }
+    if person.pinned {
+        name = format!("📌 {}", name);
+    }
+    entry = entry.push(
+        mouse_area(button(
+            column![
+                row![
+                    color_dot(person.color_label),
+                    text(name).width(Length::Fill),
+                    text(if person.role == CaseRole::Unassigned { "" } else { person.role.label() })
+                        .style(theme::Text::Color(Color::from_rgb(0.4, 0.4, 0.4))),
+                    text(person.priority.label()).style(theme::Text::Color(priority_color(person.priority))),
+                    text(person.case_status.label()).style(theme::Text::Color(case_status_color(person.case_status))),
+                ]
+                .spacing(5),
+                text(counts)
+                    .size(12)
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            ]
+        )
+            .on_press(Message::PersonSelected(person.id))
+            .style(button_style)
+            .width(Length::Fill))
+        .on_right_press(Message::PersonActionsToggled(Some(person.id)))
+    );
+
+    // Right-click actions, shown under the person
+    if state.person_actions == Some(person.id) {
+        entry = entry.push(person_actions(state, person));
+    }
+    entry.into()
+}
+
+fn person_actions<'a>(state: &'a AppState, person: &'a Person) -> Element<'a, Message> {
+    let mut tags = Row::new().spacing(2);
+    for tag in &person.tags {
+        tags = tags.push(
+            button(text(format!("{} ×", tag)).size(12))
+                .on_press(Message::RemovePersonTag(person.id, tag.clone()))
+                .style(theme::Button::Text)
+        );
+    }
+
+    container(
+        column![
+            row![
+                text_input("Name", &state.person_name_draft)
+                    .on_input(Message::PersonNameDraftChanged)
+                    .on_submit(Message::RenamePerson(person.id)),
+                button("Rename")
+                    .on_press(Message::RenamePerson(person.id)),
+            ]
+            .spacing(2),
+            row![
+                text_input("Add tag", &state.person_tag_draft)
+                    .on_input(Message::PersonTagDraftChanged)
+                    .on_submit(Message::AddPersonTag(person.id)),
+                button("Tag")
+                    .on_press(Message::AddPersonTag(person.id)),
+            ]
+            .spacing(2),
+            tags,
+            color_label_palette(person),
+            row![
+                button(if person.pinned { "Unpin" } else { "Pin" })
+                    .on_press(Message::TogglePersonPinned(person.id)),
+                button("Open Folder")
+                    .on_press(Message::OpenPersonFolder(person.id)),
+                button("Rebuild Index")
+                    .on_press(Message::RebuildIndex(person.id)),
+                button("Copy ID")
+                    .on_press(Message::CopyPersonId(person.id)),
+                button("Export")
+                    .on_press(Message::ExportPersonClicked(person.id)),
+                button("Hash Manifest")
+                    .on_press(Message::ExportHashManifestClicked(Some(person.id))),
+                button("Custody Log")
+                    .on_press(Message::ExportCustodyLogClicked(Some(person.id))),
+                button("Inventory")
+                    .on_press(Message::ExportInventoryClicked(Some(person.id))),
+                button("Timeline")
+                    .on_press(Message::ExportTimelineClicked(Some(person.id))),
+            ]
+            .spacing(2),
+            if person.legal_hold {
+                button("Release Legal Hold")
+                    .on_press(Message::ReleaseLegalHoldClicked(Some(person.id)))
+            } else {
+                button("Legal Hold")
+                    .on_press(Message::PlaceLegalHold(person.id))
+            },
+            row![
+                button("Delete")
+                    .on_press(Message::DeletePerson(person.id))
+                    .style(theme::Button::Destructive),
+                Space::with_width(Length::Fill),
+                button("Close")
+                    .on_press(Message::PersonActionsToggled(None))
+                    .style(theme::Button::Secondary),
+            ]
+            .spacing(2),
+        ]
+        .spacing(4)
+    )
+    .padding(5)
+    .style(theme::Container::Box)
+    .into()
+}
+
+fn case_status_filter_button<'a>(state: &AppState, label: &'a str, filter: Option<CaseStatus>) -> Element<'a, Message> {
+    let style = if state.case_status_filter == filter {
+        theme::Button::Primary
+    } else {
+        theme::Button::Secondary
+    };
+    button(text(label))
+        .on_press(Message::CaseStatusFilterChanged(filter))
+        .style(style)
+        .into()
+}
+
+/// Picks a person's color label, or clears it
+fn color_label_palette(person: &Person) -> Element<'_, Message> {
+    let mut palette = row![text("Color").size(12)].spacing(2).align_items(Alignment::Center);
+    palette = palette.push(color_label_button(None, person.color_label.is_none(), Message::SetColorLabel(person.id, None)));
+    for color in ColorLabel::all() {
+        palette = palette.push(
+            color_label_button(Some(color), person.color_label == Some(color), Message::SetColorLabel(person.id, Some(color)))
+        );
+    }
+    palette.into()
+}
+
+/// A dot in the label's color; no label is an empty circle
+fn color_label_button<'a>(color_label: Option<ColorLabel>, selected: bool, message: Message) -> Element<'a, Message> {
+    let dot = match color_label {
+        Some(color) => text("●").style(theme::Text::Color(color_label_color(color))),
+        None => text("○"),
+    };
+    button(dot)
+        .on_press(message)
+        .style(if selected { theme::Button::Primary } else { theme::Button::Text })
+        .padding([2, 6])
+        .into()
+}
+
+/// The person's color label as a dot, or nothing without one
+fn color_dot<'a>(color_label: Option<ColorLabel>) -> Element<'a, Message> {
+    match color_label {
+        Some(color) => text("●").style(theme::Text::Color(color_label_color(color))).into(),
+        None => Space::with_width(0).into(),
+    }
+}
+
+fn color_label_color(color_label: ColorLabel) -> Color {
+    match color_label {
+        ColorLabel::Red => Color::from_rgb(0.85, 0.1, 0.1),
+        ColorLabel::Orange => Color::from_rgb(0.95, 0.55, 0.0),
+        ColorLabel::Yellow => Color::from_rgb(0.9, 0.8, 0.0),
+        ColorLabel::Green => Color::from_rgb(0.1, 0.65, 0.2),
+        ColorLabel::Blue => Color::from_rgb(0.15, 0.4, 0.9),
+        ColorLabel::Purple => Color::from_rgb(0.6, 0.2, 0.8),
+    }
+}
+
+/// A ring filled clockwise from the top in proportion to how complete a record is
+struct ProgressRing {
+    fraction: f32,
+}
+
+impl canvas::Program<Message> for ProgressRing {
+    type State = ();
+
+    fn draw(&self, _state: &Self::State, renderer: &Renderer, _theme: &Theme, bounds: Rectangle, _cursor: mouse::Cursor) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let center = frame.center();
+        let radius = bounds.width.min(bounds.height) / 2.0 - 3.0;
+        let ring = |color: Color| canvas::Stroke::default().with_color(color).with_width(4.0);
+
+        frame.stroke(&canvas::Path::circle(center, radius), ring(Color::from_rgb(0.85, 0.85, 0.85)));
+        if self.fraction > 0.0 {
+            let start = -std::f32::consts::FRAC_PI_2;
+            let arc = canvas::Path::new(|builder| {
+                builder.arc(canvas::path::Arc {
+                    center,
+                    radius,
+                    start_angle: Radians(start),
+                    end_angle: Radians(start + self.fraction.min(1.0) * std::f32::consts::TAU),
+                });
+            });
+            let color = if self.fraction >= 1.0 {
+                Color::from_rgb(0.1, 0.65, 0.2)
+            } else {
+                Color::from_rgb(0.15, 0.4, 0.9)
+            };
+            frame.stroke(&arc, ring(color));
+        }
+        vec![frame.into_geometry()]
+    }
+}
+
+/// The ring and count shown in the person header; clicking it opens the checklist
+fn completeness_ring<'a>(completeness: &Completeness, show: bool) -> Element<'a, Message> {
+    let ring = canvas(ProgressRing { fraction: completeness.fraction() })
+        .width(Length::Fixed(28.0))
+        .height(Length::Fixed(28.0));
+    tooltip(
+        mouse_area(
+            row![
+                ring,
+                text(format!("{}/{}", completeness.done(), completeness.checks.len())).size(13),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        )
+        .on_press(Message::ShowCompleteness(!show)),
+        text(completeness_hint(completeness)),
+        tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+/// Names what's still missing, so the ring says what to fill in without opening the checklist
+fn completeness_hint(completeness: &Completeness) -> String {
+    let missing: Vec<&str> = completeness.missing().map(|check| check.label.as_str()).collect();
+    if missing.is_empty() {
+        "Record complete; click for the checklist".to_string()
+    } else {
+        format!("Missing {}; click for the checklist", missing.join(", "))
+    }
+}
+
+fn completeness_panel<'a>(state: &AppState, completeness: &Completeness) -> Element<'a, Message> {
+    let mut checklist = column![
+        row![
+            text("Completeness").size(16),
+            Space::with_width(Length::Fill),
+            button("Close")
+                .on_press(Message::ShowCompleteness(false))
+                .style(theme::Button::Secondary),
+        ]
+        .align_items(Alignment::Center),
+    ]
+    .spacing(5);
+
+    if state.settings.completeness_template().is_none() {
+        checklist = checklist.push(
+            text("Choose an information template under Settings to also check for its information types")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+    for check in &completeness.checks {
+        let (mark, color) = if check.done {
+            ("✓", Color::from_rgb(0.1, 0.65, 0.2))
+        } else {
+            ("✗", Color::from_rgb(0.8, 0.2, 0.2))
+        };
+        checklist = checklist.push(
+            row![
+                text(mark).style(theme::Text::Color(color)),
+                text(check.label.clone()),
+            ]
+            .spacing(8)
+        );
+    }
+
+    container(checklist)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+fn case_status_color(status: CaseStatus) -> Color {
+    match status {
+        CaseStatus::Active => Color::from_rgb(0.0, 0.6, 0.0),
+        CaseStatus::OnHold => Color::from_rgb(0.8, 0.6, 0.0),
+        CaseStatus::Closed => Color::from_rgb(0.5, 0.5, 0.5),
+    }
+}
+
+fn priority_color(priority: Priority) -> Color {
+    match priority {
+        Priority::Low => Color::from_rgb(0.5, 0.5, 0.5),
+        Priority::Normal => Color::from_rgb(0.2, 0.2, 0.8),
+        Priority::High => Color::from_rgb(0.9, 0.5, 0.0),
+        Priority::Urgent => Color::from_rgb(0.8, 0.0, 0.0),
+    }
+}
+
+fn main_content(state: &AppState) -> Element<'_, Message> {
+    if state.show_settings {
+        return settings_view(state);
+    }
+
+    if state.show_activity {
+        return activity_view(state);
+    }
+
+    if state.show_jobs {
+        return jobs_view(state);
+    }
+
+    if state.show_store_check {
+        return store_check_view(state);
+    }
+
+    if state.show_evidence_browser {
+        return evidence_browser_view(state);
+    }
+
+    if let Some(person_id) = state.selected_person {
+        if let Some(person) = state.persons.iter().find(|p| p.id == person_id) {
+            let completeness = Completeness::of(person, &state.evidence_files, state.settings.completeness_template());
+            let mut content = column![
+                // Header with person name and actions
+                row![
+                    color_dot(person.color_label),
+                    text(format!("Evidence for: {}", person.name))
+                        .size(18)
+                        .style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8))),
+                    completeness_ring(&completeness, state.show_completeness),
+                    Space::with_width(Length::Fill),
+                    pick_list(CaseStatus::all().to_vec(), Some(person.case_status), Message::CaseStatusChanged),
+                    pick_list(Priority::all().to_vec(), Some(person.priority), Message::PriorityChanged),
+                    pick_list(CaseRole::all().to_vec(), Some(person.role), Message::CaseRoleChanged),
+                    if person.legal_hold {
+                        button("Release Legal Hold")
+                            .on_press(Message::ReleaseLegalHoldClicked(Some(person.id)))
+                    } else {
+                        button("Legal Hold")
+                            .on_press(Message::PlaceLegalHold(person.id))
+                    },
+                    button("Delete Person")
+                        .on_press_maybe((!person.legal_hold).then_some(Message::DeletePerson(person.id)))
+                        .style(theme::Button::Destructive),
+                    pick_list(
+                        state.settings.all_report_templates().into_iter().map(|t| t.name).collect::<Vec<_>>(),
+                        Some(state.settings.report_template().name),
+                        Message::ReportTemplateSelected,
+                    ),
+                    button("Print")
+                        .on_press(Message::PrintPersonClicked),
+                    button("Export Evidence")
+                        .on_press(Message::ExportPersonClicked(person.id)),
+                    button("Export Hash Manifest")
+                        .on_press(Message::ExportHashManifestClicked(Some(person.id))),
+                    button("Export Custody Log")
+                        .on_press(Message::ExportCustodyLogClicked(Some(person.id))),
+                    button("Export Inventory")
+                        .on_press(Message::ExportInventoryClicked(Some(person.id))),
+                    button("Export Timeline")
+                        .on_press(Message::ExportTimelineClicked(Some(person.id))),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+                
+                Space::with_height(10),
+            ];
+
+            if state.show_completeness {
+                content = content.push(completeness_panel(state, &completeness));
+                content = content.push(Space::with_height(10));
+            }
+            if person.legal_hold {
+                content = content.push(
+                    text("🔒 Under legal hold: entries can be added, but existing ones can't be changed or deleted")
+                        .style(theme::Text::Color(Color::from_rgb(0.8, 0.5, 0.1)))
+                );
+            }
+            if state.confirm_hold_release == Some(person.id) {
+                content = content.push(
+                    container(
+                        row![
+                            text(format!("Release the legal hold on {}? Their existing entries can then be changed and deleted again. The release is recorded in the audit log.", person.name))
+                                .width(Length::Fill),
+                            button("Release Hold")
+                                .on_press(Message::ReleaseLegalHoldConfirmed(person.id))
+                                .style(theme::Button::Destructive),
+                            button("Keep Hold")
+                                .on_press(Message::ReleaseLegalHoldClicked(None))
+                                .style(theme::Button::Secondary),
+                        ]
+                        .spacing(10)
+                        .align_items(Alignment::Center)
+                    )
+                    .padding(10)
+                    .style(theme::Container::Box)
+                );
+            }
+
+            // Tab navigation
+            let mut tab_row = Row::new().spacing(5);
+            for tab in EvidenceTab::all(&state.settings) {
+                let is_selected = state.current_tab == tab;
                 let button_style = if is_selected {
                     theme::Button::Primary
                 } else {
                     theme::Button::Secondary
                 };
                 
+                // Counted from the cached scan, so they follow files being added and removed
+                let label = match tab.file_stats(&state.evidence_files) {
+                    Some((0, _)) => format!("{} (0)", tab.label(&state.settings)),
+                    Some((count, size)) => format!("{} ({} · {})", tab.label(&state.settings), count, format_size(size)),
+                    None => tab.label(&state.settings),
+                };
                 tab_row = tab_row.push(
-                    button(tab.label())
+                    button(text(label))
                         .on_press(Message::TabChanged(tab.clone()))
                         .style(button_style)
                 );
@@ -185,11 +1396,38 @@ fn main_content(state: &AppState) -> Element<'_, Message> {
             content = content.push(tab_row);
             content = content.push(Space::with_height(10));
 
+            let quarantined: Vec<&QuarantinedFile> = state.quarantined.iter().filter(|f| f.person_id == person.id).collect();
+            if !quarantined.is_empty() {
+                content = content.push(quarantine_panel(state, &quarantined));
+                content = content.push(Space::with_height(10));
+            }
+
+            if let Some(dialog) = &state.device_import {
+                content = content.push(device_import_dialog(person, dialog, state.settings.dates()));
+                content = content.push(Space::with_height(10));
+            }
+
+            if let Some(dialog) = &state.file_list_import {
+                content = content.push(file_list_dialog(person, dialog));
+                content = content.push(Space::with_height(10));
+            }
+
+            if let Some(intake) = &state.intake {
+                content = content.push(intake_dialog(state, intake));
+                content = content.push(Space::with_height(10));
+            } else if let Some(path) = &state.uncategorized_file {
+                content = content.push(uncategorized_file_prompt(state, path));
+                content = content.push(Space::with_height(10));
+            }
+
             // Tab content
-            match state.current_tab {
+            match &state.current_tab {
                 EvidenceTab::Information => {
                     content = content.push(information_tab(state, person));
                 }
+                EvidenceTab::All => {
+                    content = content.push(all_evidence_tab(state));
+                }
                 EvidenceTab::Images => {
                     content = content.push(media_tab(state, EvidenceType::Image));
                 }
@@ -202,8 +1440,29 @@ fn main_content(state: &AppState) -> Element<'_, Message> {
                 EvidenceTab::Documents => {
                     content = content.push(media_tab(state, EvidenceType::Document));
                 }
+                EvidenceTab::Other => {
+                    content = content.push(media_tab(state, EvidenceType::Other));
+                }
                 EvidenceTab::Quotes => {
-                    content = content.push(quotes_tab(state, person));
+                    if let Some(dialog) = &state.chat_import {
+                        content = content.push(chat_import_dialog(dialog, state.settings.dates()));
+                    } else if let Some(dialog) = &state.quote_paste {
+                        content = content.push(quote_paste_dialog(dialog, state.settings.dates()));
+                    } else {
+                        content = content.push(quotes_tab(state, person));
+                    }
+                }
+                EvidenceTab::Tasks => {
+                    content = content.push(tasks_tab(state, person));
+                }
+                EvidenceTab::Map => {
+                    content = content.push(map_tab(state));
+                }
+                EvidenceTab::Board => {
+                    content = content.push(board_tab(state, person));
+                }
+                EvidenceTab::Custom(folder_name) => {
+                    content = content.push(media_tab(state, EvidenceType::Custom(folder_name.clone())));
                 }
             }
 
@@ -224,22 +1483,56 @@ fn main_content(state: &AppState) -> Element<'_, Message> {
             .into()
         }
     } else {
-        container(
-            column![
-                text("Select a person to view evidence")
-                    .size(16)
-                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
-                Space::with_height(10),
-                text("Use the 'Select File to Add' button in each tab to add evidence files")
-                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
-            ]
-            .align_items(Alignment::Center)
-        )
+        dashboard(state)
+    }
+}
+
+fn dashboard(state: &AppState) -> Element<'_, Message> {
+    let stats = &state.statistics;
+    let muted = theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5));
+
+    let mut by_type = column![text("Evidence by type").size(16)].spacing(3);
+    for (evidence_type, count) in &stats.files_by_type {
+        by_type = by_type.push(text(format!("{} {}: {}", state.settings.icon(evidence_type), state.settings.display_name(evidence_type), count)));
+    }
+
+    let mut by_status = column![text("Review progress").size(16)].spacing(3);
+    for (status, count) in &stats.files_by_status {
+        by_status = by_status.push(text(format!("{} {}: {}", status.icon(), status.label(), count)));
+    }
+
+    let content = column![
+        text("Dashboard")
+            .size(18)
+            .style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8))),
+        text(format!(
+            "{} people · {} evidence files ({:.1} MB) · {} quotes",
+            stats.person_count,
+            stats.file_count(),
+            stats.total_size as f64 / (1024.0 * 1024.0),
+            stats.quote_count,
+        )),
+        Space::with_height(10),
+        row![
+            container(by_type).width(Length::FillPortion(1)).padding(10).style(theme::Container::Box),
+            container(by_status).width(Length::FillPortion(1)).padding(10).style(theme::Container::Box),
+        ]
+        .spacing(10),
+        Space::with_height(10),
+        upcoming_tasks(state),
+        Space::with_height(10),
+        shared_identifiers_panel(state),
+        Space::with_height(10),
+        text("Select a person to view their evidence")
+            .style(muted),
+    ]
+    .spacing(5);
+
+    container(scrollable(content))
         .width(Length::Fill)
         .height(Length::Fill)
         .padding(20)
         .into()
-    }
 }
 
 fn information_tab<'a>(state: &'a AppState, person: &'a Person) -> Element<'a, Message> {
@@ -264,6 +1557,11 @@ fn information_tab<'a>(state: &'a AppState, person: &'a Person) -> Element<'a, M
 
     content = content.push(Space::with_height(10));
 
+    if !state.settings.custom_fields.is_empty() {
+        content = content.push(custom_fields_editor(state, person));
+        content = content.push(Space::with_height(10));
+    }
+
     // Information table
     if person.information.is_empty() {
         content = content.push(
@@ -271,31 +1569,46 @@ fn information_tab<'a>(state: &'a AppState, person: &'a Person) -> Element<'a, M
                 .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
         );
     } else {
+        let grouped = state.settings.layout.group_information;
         content = content.push(
-            text("Information")
-                .size(14)
-                .style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8)))
+            row![
+                text("Information")
+                    .size(14)
+                    .style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8))),
+                Space::with_width(Length::Fill),
+                checkbox("Group by type", grouped)
+                    .on_toggle(Message::InfoGroupingToggled),
+            ]
+            .align_items(Alignment::Center)
         );
 
         let mut info_list = Column::new().spacing(2);
-        for info in &person.information {
-            info_list = info_list.push(
-                row![
-                    text(&info.info_type)
-                        .width(Length::FillPortion(1)),
-                    text(&info.value)
-                        .width(Length::FillPortion(2)),
-                    button("Delete")
-                        .on_press(Message::RemoveInfo(info.id))
-                        .style(theme::Button::Destructive),
-                ]
-                .spacing(5)
-                .align_items(Alignment::Center)
-            );
+        if grouped {
+            for (info_type, entries) in information_groups(person) {
+                let key = info_type.to_lowercase();
+                let collapsed = state.settings.layout.collapsed_info_types.contains(&key);
+                info_list = info_list.push(
+                    button(text(format!("{} {} ({})", if collapsed { "▸" } else { "▾" }, info_type, entries.len())))
+                        .on_press(Message::InfoTypeGroupToggled(key))
+                        .style(theme::Button::Text)
+                        .width(Length::Fill)
+                );
+                if !collapsed {
+                    for info in entries {
+                        info_list = info_list.push(row![Space::with_width(15), info_row(state, info, false)]);
+                    }
+                }
+            }
+        } else {
+            for info in &person.information {
+                info_list = info_list.push(info_row(state, info, true));
+            }
         }
         
         content = content.push(
             scrollable(info_list)
+                .id(scrollable::Id::new(TAB_SCROLL))
+                .on_scroll(|viewport| Message::TabScrolled(viewport.relative_offset().y))
                 .height(Length::Fixed(300.0))
         );
     }
@@ -306,143 +1619,3294 @@ fn information_tab<'a>(state: &'a AppState, person: &'a Person) -> Element<'a, M
         .into()
 }
 
-fn media_tab(state: &AppState, media_type: EvidenceType) -> Element<'_, Message> {
-    let type_label = match media_type {
-        EvidenceType::Image => "Image",
-        EvidenceType::Audio => "Audio",
-        EvidenceType::Video => "Video",
-        EvidenceType::Document => "Document",
-        EvidenceType::Quote => "Quote",
-    };
+/// A person's information in sections by type, A to Z; types differing only in case share one,
+/// named as first entered
+fn information_groups(person: &Person) -> Vec<(&str, Vec<&PersonInfo>)> {
+    let mut groups: Vec<(&str, Vec<&PersonInfo>)> = Vec::new();
+    for info in &person.information {
+        match groups.iter_mut().find(|(info_type, _)| info_type.eq_ignore_ascii_case(&info.info_type)) {
+            Some((_, entries)) => entries.push(info),
+            None => groups.push((&info.info_type, vec![info])),
+        }
+    }
+    groups.sort_by_key(|(info_type, _)| info_type.to_lowercase());
+    groups
+}
 
-    let mut content = column![
-        text(format!("{} Files", type_label)).size(16),
-        Space::with_height(5),
-        button("Select File to Add")
-            .on_press(Message::SelectFileClicked)
-            .style(theme::Button::Primary),
-        Space::with_height(10),
-    ];
+fn info_row<'a>(state: &'a AppState, info: &'a PersonInfo, show_type: bool) -> Element<'a, Message> {
+    let mut info_row = Row::new().spacing(5).align_items(Alignment::Center);
+    if show_type {
+        info_row = info_row.push(
+            text(&info.info_type)
+                .width(Length::FillPortion(1))
+        );
+    }
+    info_row
+        .push(info_value(state, info))
+        .push(
+            text(info.normalized.as_deref().filter(|n| *n != info.value).unwrap_or_default())
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        )
+        .push(
+            button("Copy")
+                .on_press(Message::CopyText(info.value.clone()))
+                .style(theme::Button::Secondary)
+        )
+        .push(
+            button("Delete")
+                .on_press(Message::RemoveInfo(info.id))
+                .style(theme::Button::Destructive)
+        )
+        .into()
+}
 
-    let filtered_files: Vec<&EvidenceFile> = state.evidence_files
-        .iter()
-        .filter(|f| f.file_type == media_type)
-        .collect();
+fn custom_fields_editor<'a>(state: &'a AppState, person: &'a Person) -> Element<'a, Message> {
+    let mut fields = column![
+        text("Custom Fields")
+            .size(14)
+            .style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8))),
+    ]
+    .spacing(2);
 
-    if filtered_files.is_empty() {
-        content = content.push(
-            text(format!("No {} files found", type_label.to_lowercase()))
-                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
-        );
-    } else {
-        let mut file_list = Column::new().spacing(2);
-        for file in filtered_files {
-            let icon = match file.file_type {
-                EvidenceType::Image => "🖼",
-                EvidenceType::Audio => "🎵",
-                EvidenceType::Video => "🎬",
-                EvidenceType::Document => "📄",
-                EvidenceType::Quote => "💬",
-            };
-            
-            file_list = file_list.push(
-                row![
-                    text(icon),
-                    text(&file.original_name)
-                        .width(Length::Fill),
-                    text(format!("{} KB", file.size / 1024))
-                        .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+    for field in &state.settings.custom_fields {
+        // A value stored before the field's type changed is shown as text to be re-entered
+        let value = person.custom_fields.get(&field.name).filter(|v| v.field_type() == field.field_type);
+        let editor: Element<Message> = match (field.field_type, value) {
+            (FieldType::Boolean, value) => {
+                let name = field.name.clone();
+                checkbox("", matches!(value, Some(FieldValue::Boolean(true))))
+                    .on_toggle(move |checked| Message::CustomFieldToggled(name.clone(), checked))
+                    .into()
+            }
+            (field_type, value) => {
+                let name = field.name.clone();
+                let draft = state.custom_field_drafts.get(&field.name).cloned()
+                    .unwrap_or_else(|| value.map(|v| v.to_string()).unwrap_or_default());
+                let placeholder = match field_type {
+                    FieldType::Date => "YYYY-MM-DD",
+                    FieldType::Number => "Number",
+                    _ => "https://...",
+                };
+                let mut editor = row![
+                    text_input(placeholder, &draft)
+                        .on_input(move |text| Message::CustomFieldDraftChanged(name.clone(), text))
+                        .on_submit(Message::CustomFieldSubmitted(field.name.clone())),
                 ]
                 .spacing(5)
-                .align_items(Alignment::Center)
-            );
-        }
-        
-        content = content.push(
+                .align_items(Alignment::Center);
+                if state.custom_field_drafts.contains_key(&field.name) {
+                    // Validation only happens on save, so show whether the draft would be accepted
+                    let valid = draft.trim().is_empty() || FieldValue::parse(field_type, &draft).is_ok();
+                    editor = editor.push(
+                        button(if valid { "Save" } else { "Invalid" })
+                            .on_press_maybe(valid.then(|| Message::CustomFieldSubmitted(field.name.clone())))
+                            .style(theme::Button::Secondary)
+                    );
+                }
+                if let Some(FieldValue::Url(url)) = value {
+                    editor = editor.push(
+                        button("Open")
+                            .on_press(Message::OpenUrl(url.clone()))
+                            .style(theme::Button::Secondary)
+                    );
+                }
+                editor.into()
+            }
+        };
+
+        fields = fields.push(
+            row![
+                text(format!("{} ({})", field.name, field.field_type.label()))
+                    .width(Length::FillPortion(1)),
+                container(editor).width(Length::FillPortion(2)),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+
+    fields.into()
+}
+
+/// Slots created empty from a template can be filled in place
+fn info_value<'a>(state: &'a AppState, info: &'a PersonInfo) -> Element<'a, Message> {
+    if info.value.is_empty() {
+        let info_id = info.id;
+        text_input("Enter value and press Enter", state.info_drafts.get(&info_id).map(String::as_str).unwrap_or_default())
+            .on_input(move |value| Message::InfoDraftChanged(info_id, value))
+            .on_submit(Message::InfoDraftSubmitted(info_id))
+            .width(Length::FillPortion(2))
+            .into()
+    } else if let Some(link) = info.link() {
+        // Emails, phone numbers and web addresses open in the system's handler
+        tooltip(
+            button(text(&info.value).style(theme::Text::Color(Color::from_rgb(0.2, 0.4, 0.9))))
+                .on_press(Message::OpenUrl(link.clone()))
+                .style(theme::Button::Text)
+                .padding(0)
+                .width(Length::FillPortion(2)),
+            text(link),
+            tooltip::Position::Bottom,
+        )
+        .into()
+    } else {
+        text(&info.value)
+            .width(Length::FillPortion(2))
+            .into()
+    }
+}
+
+fn media_tab(state: &AppState, media_type: EvidenceType) -> Element<'_, Message> {
+    let type_label = state.settings.display_name(&media_type);
+
+    let mut content = column![
+        text(format!("{} Files", type_label)).size(16),
+        Space::with_height(5),
+        row![
+            button("Select File to Add")
+                .on_press(Message::SelectFileClicked)
+                .style(theme::Button::Primary),
+            button("Import from Device")
+                .on_press(Message::ImportFromDeviceClicked),
+            button("Import File List")
+                .on_press(Message::ImportFileListClicked),
+            checkbox("Move into evidence", state.move_files)
+                .on_toggle(Message::MoveFilesToggled),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+        Space::with_height(10),
+    ];
+
+    if media_type == EvidenceType::Image {
+        content = content.push(
+            row![
+                button("Paste Screenshot")
+                    .on_press(Message::PasteScreenshotClicked),
+                button("Find Possible Duplicates")
+                    .on_press(Message::FindDuplicatesClicked),
+            ]
+            .spacing(5)
+        );
+        content = content.push(Space::with_height(10));
+        if let Some(pairs) = &state.duplicate_review {
+            content = content.push(duplicate_review_panel(state, pairs));
+            content = content.push(Space::with_height(10));
+        }
+        if let Some((evidence_id, handle)) = &state.image_preview
+            && let Some(file) = state.evidence_files.iter().find(|f| f.id == *evidence_id)
+        {
+            content = content.push(image_preview_panel(file, handle));
+            content = content.push(Space::with_height(10));
+        }
+    }
+
+    if media_type == EvidenceType::Audio {
+        content = content.push(recorder_bar(state));
+        content = content.push(Space::with_height(10));
+    }
+
+    if media_type == EvidenceType::Document {
+        match &state.note_editor {
+            Some(editor) => content = content.push(note_editor_panel(editor)),
+            None => content = content.push(
+                button("New Text Note")
+                    .on_press(Message::NewNoteClicked)
+            ),
+        }
+        content = content.push(Space::with_height(10));
+    }
+
+    if media_type == EvidenceType::Document && let Some(email) = &state.email_preview {
+        content = content.push(email_preview_panel(email));
+        content = content.push(Space::with_height(10));
+    }
+
+    // Older versions are listed under the file that replaced them
+    let type_files: Vec<&EvidenceFile> = state.evidence_files
+        .iter()
+        .filter(|f| f.file_type == media_type && f.superseded_by.is_none())
+        .collect();
+    content = content.push(review_filter_bar(state, &type_files));
+    content = content.push(Space::with_height(10));
+
+    let mut filtered_files: Vec<&EvidenceFile> = type_files
+        .into_iter()
+        .filter(|f| state.review_filter.is_none_or(|status| f.review_status == status))
+        .collect();
+    // Oldest evidence first; files without an evidence date go by when they were added
+    filtered_files.sort_by_key(|f| f.evidence_date.unwrap_or(f.created_at));
+
+    if !state.selected_evidence.is_empty() {
+        content = content.push(selection_bar(state));
+        content = content.push(Space::with_height(10));
+    }
+
+    if filtered_files.is_empty() {
+        content = content.push(
+            text(format!("No {} files found", type_label.to_lowercase()))
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    } else {
+        let file_list = evidence_file_list(state, filtered_files);
+        
+        content = content.push(
             scrollable(file_list)
+                .id(scrollable::Id::new(TAB_SCROLL))
+                .on_scroll(|viewport| Message::TabScrolled(viewport.relative_offset().y))
+                .height(Length::Fixed(400.0))
+        );
+    }
+
+    container(content)
+        .width(Length::Fill)
+        .padding(10)
+        .into()
+}
+
+/// Every file of the person in one list, narrowed by category chips and sorted as chosen, with
+/// the same actions as the category tabs
+fn all_evidence_tab(state: &AppState) -> Element<'_, Message> {
+    let mut content = column![
+        text("All Evidence").size(16),
+        Space::with_height(5),
+        row![
+            button("Select File to Add")
+                .on_press(Message::SelectFileClicked)
+                .style(theme::Button::Primary),
+            button("Import from Device")
+                .on_press(Message::ImportFromDeviceClicked),
+            button("Import File List")
+                .on_press(Message::ImportFileListClicked),
+            checkbox("Move into evidence", state.move_files)
+                .on_toggle(Message::MoveFilesToggled),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+        Space::with_height(10),
+    ];
+
+    if let Some((evidence_id, handle)) = &state.image_preview
+        && let Some(file) = state.evidence_files.iter().find(|f| f.id == *evidence_id)
+    {
+        content = content.push(image_preview_panel(file, handle));
+        content = content.push(Space::with_height(10));
+    }
+    if let Some(email) = &state.email_preview {
+        content = content.push(email_preview_panel(email));
+        content = content.push(Space::with_height(10));
+    }
+    if let Some(editor) = &state.note_editor {
+        content = content.push(note_editor_panel(editor));
+        content = content.push(Space::with_height(10));
+    }
+
+    // Quotes have their own tab
+    let all_files: Vec<&EvidenceFile> = state.evidence_files
+        .iter()
+        .filter(|f| f.file_type != EvidenceType::Quote && f.superseded_by.is_none())
+        .collect();
+
+    // A chip per category the person has files in; none selected shows them all
+    let mut categories: Vec<&EvidenceType> = Vec::new();
+    for file in &all_files {
+        if !categories.contains(&&file.file_type) {
+            categories.push(&file.file_type);
+        }
+    }
+    categories.sort_by_key(|category| state.settings.display_name(category));
+    let mut chips = row![
+        button(text(format!("All ({})", all_files.len())))
+            .on_press_maybe((!state.evidence_type_filter.is_empty()).then_some(Message::EvidenceTypeFilterCleared))
+            .style(if state.evidence_type_filter.is_empty() { theme::Button::Primary } else { theme::Button::Secondary }),
+    ]
+    .spacing(5);
+    for category in categories {
+        let count = all_files.iter().filter(|f| f.file_type == *category).count();
+        let style = if state.evidence_type_filter.contains(category) {
+            theme::Button::Primary
+        } else {
+            theme::Button::Secondary
+        };
+        chips = chips.push(
+            button(text(format!("{} {} ({})", state.settings.icon(category), state.settings.display_name(category), count)))
+                .on_press(Message::EvidenceTypeFilterToggled(category.clone()))
+                .style(style)
+        );
+    }
+    content = content.push(
+        row![
+            chips,
+            Space::with_width(Length::Fill),
+            pick_list(EvidenceSort::all().to_vec(), Some(state.evidence_sort), Message::EvidenceSortChanged),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center)
+    );
+    content = content.push(Space::with_height(5));
+
+    let type_files: Vec<&EvidenceFile> = all_files
+        .into_iter()
+        .filter(|f| state.evidence_type_filter.is_empty() || state.evidence_type_filter.contains(&f.file_type))
+        .collect();
+    content = content.push(review_filter_bar(state, &type_files));
+    content = content.push(Space::with_height(10));
+
+    let mut filtered_files: Vec<&EvidenceFile> = type_files
+        .into_iter()
+        .filter(|f| state.review_filter.is_none_or(|status| f.review_status == status))
+        .collect();
+    state.evidence_sort.sort(&mut filtered_files, &state.settings);
+
+    if !state.selected_evidence.is_empty() {
+        content = content.push(selection_bar(state));
+        content = content.push(Space::with_height(10));
+    }
+
+    if filtered_files.is_empty() {
+        content = content.push(
+            text("No evidence files found")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    } else {
+        content = content.push(
+            scrollable(evidence_file_list(state, filtered_files))
+                .id(scrollable::Id::new(TAB_SCROLL))
+                .on_scroll(|viewport| Message::TabScrolled(viewport.relative_offset().y))
                 .height(Length::Fixed(400.0))
         );
     }
 
-    container(content)
+    container(content)
+        .width(Length::Fill)
+        .padding(10)
+        .into()
+}
+
+/// Rows of evidence files with their review toggles, and the right-click actions under the one
+/// they're open for
+fn evidence_file_list<'a>(state: &'a AppState, files: Vec<&'a EvidenceFile>) -> Column<'a, Message> {
+    let dates = state.settings.dates();
+    let mut file_list = Column::new().spacing(2);
+    for file in files {
+        let icon = state.settings.icon(&file.file_type);
+        
+        let current_name = file.file_path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| file.original_name.clone());
+        let mut name_label = if current_name == file.original_name {
+            current_name
+        } else {
+            format!("{} (originally {})", current_name, file.original_name)
+        };
+        let previous_versions = file.previous_versions(&state.evidence_files);
+        if !previous_versions.is_empty() {
+            name_label.push_str(&format!(" · v{}", previous_versions.len() + 1));
+        }
+
+        let mut file_row = row![
+            checkbox("", state.selected_evidence.contains(&file.id))
+                .on_toggle(move |selected| Message::EvidenceSelectionToggled(file.id, selected)),
+            text(icon),
+            text(name_label)
+                .width(Length::Fill),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center);
+
+        if let Some(email) = &file.email {
+            file_row = file_row.push(
+                text(format!("{} — {}", email.from, email.subject))
+                    .width(Length::Fill)
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+            );
+            if !file.linked_evidence.is_empty() {
+                file_row = file_row.push(text(format!("📎 {}", file.linked_evidence.len())));
+            }
+            file_row = file_row.push(
+                button("Preview")
+                    .on_press(Message::PreviewEmail(file.id))
+            );
+        }
+
+        if file.file_type == EvidenceType::Image {
+            file_row = file_row.push(
+                button("Preview")
+                    .on_press(Message::PreviewImage(file.id))
+            );
+        }
+
+        if notes::is_note(file) {
+            file_row = file_row.push(
+                button("Edit")
+                    .on_press(Message::EditNote(file.id))
+            );
+        }
+
+        if let Some(summary) = file.provenance.as_ref().and_then(provenance_summary) {
+            file_row = file_row.push(
+                text(summary)
+                    .width(Length::Fill)
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+            );
+        }
+
+        if let Some(evidence_date) = file.evidence_date {
+            file_row = file_row.push(
+                text(dates.date_time(evidence_date))
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+            );
+        }
+
+        file_row = file_row.push(
+            text(format!("{} KB", file.size / 1024))
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+        file_list = file_list.push(
+            mouse_area(file_row.push(review_toggles(file)))
+                .on_right_press(Message::EvidenceActionsToggled(Some(file.id)))
+        );
+
+        // Right-click actions, shown under the row
+        if state.evidence_actions == Some(file.id) {
+            file_list = file_list.push(
+                row![
+                    Space::with_width(30),
+                    button("Open")
+                        .on_press(Message::OpenEvidence(file.id)),
+                    button("Open Folder")
+                        .on_press(Message::OpenEvidenceFolder(file.id)),
+                    button("Copy Path")
+                        .on_press(Message::CopyEvidencePath(file.id)),
+                    button("Copy File")
+                        .on_press(Message::CopyEvidenceFile(file.id)),
+                    button("Export")
+                        .on_press(Message::ExportEvidenceFile(file.id)),
+                    match file.timestamp {
+                        Some(_) => button("Check Timestamp Hash")
+                            .on_press(Message::CheckTimestamp(file.id)),
+                        None => button("Timestamp")
+                            .on_press_maybe((!state.settings.tsa_url.trim().is_empty()).then_some(Message::TimestampEvidence(file.id))),
+                    },
+                    text("Evidence date"),
+                    text_input("YYYY-MM-DD HH:MM", &state.evidence_date_draft)
+                        .on_input(Message::EvidenceDateDraftChanged)
+                        .on_submit(Message::SaveEvidenceDate(file.id))
+                        .width(Length::Fixed(160.0)),
+                    button("Save Date")
+                        .on_press(Message::SaveEvidenceDate(file.id)),
+                    button("Replace with New Version")
+                        .on_press(Message::ReplaceEvidenceClicked(file.id)),
+                    pick_list(other_people(state, file.person_id), None::<PersonChoice>, move |choice| Message::ShareEvidenceWith(file.id, choice.id))
+                        .placeholder("Also add to..."),
+                    button("Delete")
+                        .on_press(Message::DeleteEvidence(file.id))
+                        .style(theme::Button::Destructive),
+                    button("Close")
+                        .on_press(Message::EvidenceActionsToggled(None))
+                        .style(theme::Button::Secondary),
+                ]
+                .spacing(5)
+            );
+            let holders = shared_with(state, file);
+            if !holders.is_empty() {
+                file_list = file_list.push(
+                    row![
+                        Space::with_width(30),
+                        text(format!("Stored once, also held by: {}", holders.join(", ")))
+                            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+                    ]
+                );
+            }
+            if !previous_versions.is_empty() {
+                file_list = file_list.push(version_history(&previous_versions, state.settings.dates()));
+            }
+        }
+    }
+    file_list
+}
+
+/// Earlier versions of a file, newest first, under its right-click actions
+/// Everyone but the given person, to add evidence to
+fn other_people(state: &AppState, person_id: Uuid) -> Vec<PersonChoice> {
+    state.persons
+        .iter()
+        .filter(|p| p.id != person_id)
+        .map(|p| PersonChoice { id: p.id, name: p.name.clone() })
+        .collect()
+}
+
+/// Who else holds the file's content, by name; another of the same person's entries counts too
+fn shared_with(state: &AppState, file: &EvidenceFile) -> Vec<String> {
+    let Some(references) = file.sha256.as_ref().and_then(|sha256| state.shared_content.get(sha256)) else {
+        return Vec::new();
+    };
+    references
+        .iter()
+        .filter(|r| r.file_id != file.id)
+        .filter_map(|r| state.persons.iter().find(|p| p.id == r.person_id))
+        .map(|p| p.name.clone())
+        .collect()
+}
+
+fn version_history<'a>(versions: &[&'a EvidenceFile], dates: DateDisplay) -> Element<'a, Message> {
+    let mut history = column![
+        text("Version history")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+    ]
+    .spacing(2);
+    for (index, version) in versions.iter().enumerate() {
+        let mut version_row = row![
+            text(format!("v{}", versions.len() - index))
+                .width(Length::Fixed(30.0)),
+            text(&version.original_name)
+                .width(Length::Fill),
+            text(format!("added {}", dates.date_time(version.created_at)))
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            text(format!("{} KB", version.size / 1024))
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center);
+        if !version.notes.is_empty() {
+            version_row = version_row.push(
+                text(&version.notes)
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+            );
+        }
+        history = history.push(
+            version_row.push(
+                button("Open")
+                    .on_press(Message::OpenEvidence(version.id))
+                    .style(theme::Button::Secondary)
+            )
+        );
+    }
+    row![Space::with_width(30), history].into()
+}
+
+fn review_filter_bar<'a>(state: &AppState, files: &[&EvidenceFile]) -> Element<'a, Message> {
+    let filter_button = |label: String, filter: Option<ReviewStatus>| {
+        let style = if state.review_filter == filter {
+            theme::Button::Primary
+        } else {
+            theme::Button::Secondary
+        };
+        button(text(label))
+            .on_press(Message::ReviewFilterChanged(filter))
+            .style(style)
+    };
+
+    let mut bar = row![filter_button(format!("All ({})", files.len()), None)].spacing(5);
+    for (status, count) in statistics::status_counts(files.iter().copied()) {
+        bar = bar.push(filter_button(format!("{} {} ({})", status.icon(), status.label(), count), Some(status)));
+    }
+    bar.into()
+}
+
+// Clicking the active status again puts the file back to New
+fn review_toggles(file: &EvidenceFile) -> Element<'_, Message> {
+    let mut toggles = Row::new().spacing(2);
+    for status in [ReviewStatus::Reviewed, ReviewStatus::Flagged, ReviewStatus::Irrelevant] {
+        let is_active = file.review_status == status;
+        let (style, next) = if is_active {
+            (theme::Button::Primary, ReviewStatus::New)
+        } else {
+            (theme::Button::Secondary, status)
+        };
+        toggles = toggles.push(
+            tooltip(
+                button(text(status.icon()))
+                    .on_press(Message::ReviewStatusChanged(file.id, next))
+                    .style(style),
+                status.label(),
+                tooltip::Position::Top,
+            )
+        );
+    }
+    toggles.into()
+}
+
+fn selection_bar(state: &AppState) -> Element<'_, Message> {
+    let selected = state.selected_evidence_files();
+
+    // Show what the first selected file would be renamed to
+    let preview = state.selected_person
+        .and_then(|id| state.persons.iter().find(|p| p.id == id))
+        .zip(selected.first())
+        .map(|(person, file)| {
+            let width = selected.len().to_string().len();
+            FileManager::preview_name(person, file, &state.rename_pattern, 1, width)
+        })
+        .unwrap_or_default();
+
+    container(
+        column![
+            row![
+                text(format!("{} selected", selected.len())),
+                Space::with_width(Length::Fill),
+                pick_list(ReviewStatus::all().to_vec(), None::<ReviewStatus>, Message::SelectedReviewStatusChanged)
+                    .placeholder("Mark as..."),
+                button("Clear Selection")
+                    .on_press(Message::ClearEvidenceSelection),
+            ]
+            .align_items(Alignment::Center),
+            row![
+                checkbox("Include SHA-256 manifest", state.export_selection_manifest)
+                    .on_toggle(Message::ExportSelectionManifestToggled),
+                Space::with_width(Length::Fill),
+                button("Export Selection…")
+                    .on_press(Message::ExportSelectionClicked),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center),
+            row![
+                text_input("Rename pattern", &state.rename_pattern)
+                    .on_input(Message::RenamePatternChanged),
+                button("Rename Selected")
+                    .on_press(Message::BatchRenameClicked),
+            ]
+            .spacing(5),
+            text(format!("Placeholders: {}    e.g. {}", naming::PATTERN_HELP, preview))
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        ]
+        .spacing(5)
+    )
+    .width(Length::Fill)
+    .padding(10)
+    .style(theme::Container::Box)
+    .into()
+}
+
+fn duplicate_review_panel<'a>(state: &'a AppState, pairs: &'a [DuplicatePair]) -> Element<'a, Message> {
+    let mut panel = column![
+        row![
+            text("Possible duplicates").size(16),
+            Space::with_width(Length::Fill),
+            button("Close")
+                .on_press(Message::CloseDuplicateReview),
+        ]
+        .align_items(Alignment::Center),
+    ]
+    .spacing(3);
+
+    if pairs.is_empty() {
+        panel = panel.push(
+            text("No near-duplicate images found")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+
+    for pair in pairs {
+        let first = evidence_name(state, pair.first).unwrap_or("Missing file");
+        let second = evidence_name(state, pair.second).unwrap_or("Missing file");
+        // Share of the 64 hash bits that match
+        let similarity = 100 - pair.distance * 100 / 64;
+        panel = panel.push(
+            row![
+                text(format!("{}  ↔  {}", first, second))
+                    .width(Length::Fill),
+                text(format!("{}% similar", similarity))
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+                button("Not a duplicate")
+                    .on_press(Message::DismissDuplicate(pair.first, pair.second)),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+
+    container(panel)
+        .width(Length::Fill)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+fn intake_dialog<'a>(state: &'a AppState, intake: &'a IntakeDialog) -> Element<'a, Message> {
+    let file_name = intake.path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| intake.path.display().to_string());
+
+    let dialog = column![
+        text(format!("Intake: {} → {}", file_name, state.settings.display_name(&intake.evidence_type))).size(16),
+        text(if state.move_files {
+            "The original file is moved into evidence, not modified"
+        } else {
+            "The original file is copied, never moved or modified"
+        })
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        row![
+            text_input("Source device (e.g. iPhone of J. Doe)", &state.intake_source_device)
+                .on_input(Message::IntakeSourceDeviceChanged),
+            text_input("Source URL", &state.intake_source_url)
+                .on_input(Message::IntakeSourceUrlChanged),
+        ]
+        .spacing(5),
+        row![
+            text_input("Provided by", &state.intake_provided_by)
+                .on_input(Message::IntakeProvidedByChanged),
+            text_input("Obtained on (YYYY-MM-DD)", &state.intake_obtained_at)
+                .on_input(Message::IntakeObtainedAtChanged),
+            text_input("Collected by", &state.intake_collected_by)
+                .on_input(Message::IntakeCollectedByChanged)
+                .on_submit(Message::IntakeSubmitted),
+        ]
+        .spacing(5),
+        row![
+            button("Add Evidence")
+                .on_press(Message::IntakeSubmitted),
+            button("Cancel")
+                .on_press(Message::CancelIntake)
+                .style(theme::Button::Secondary),
+        ]
+        .spacing(5),
+    ]
+    .spacing(5);
+
+    container(dialog)
+        .width(Length::Fill)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+/// The files in a camera's or phone's DCIM and Downloads folders, newest first, to pick from
+fn device_import_dialog<'a>(person: &'a Person, dialog: &'a DeviceImportDialog, dates: DateDisplay) -> Element<'a, Message> {
+    let selected_count = dialog.selected.iter().filter(|s| **s).count();
+    let choices: Vec<DeviceChoice> = dialog.volumes
+        .iter()
+        .map(|path| DeviceChoice { path: path.clone(), label: device_import::label(path) })
+        .collect();
+    let current = choices.iter().find(|choice| Some(&choice.path) == dialog.volume.as_ref()).cloned();
+
+    let mut content = column![
+        text("Import from Device").size(16),
+        row![
+            pick_list(choices, current, |choice| Message::DeviceSelected(choice.path))
+                .placeholder("Select a device..."),
+            button("Choose Folder...")
+                .on_press(Message::ChooseDeviceFolder),
+            button("Refresh")
+                .on_press(Message::ImportFromDeviceClicked)
+                .style(theme::Button::Secondary),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center),
+    ]
+    .spacing(5);
+
+    if dialog.volumes.is_empty() && dialog.volume.is_none() {
+        content = content.push(
+            text("No camera or phone with a DCIM or Downloads folder found. Connect one and refresh, or choose its folder.")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    } else if dialog.scanning {
+        content = content.push(
+            text("Reading device...")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    } else if dialog.volume.is_some() {
+        content = content.push(
+            row![
+                text(format!("{} files found, {} selected", dialog.items.len(), selected_count))
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+                    .width(Length::Fill),
+                button("Select All")
+                    .on_press(Message::DeviceSelectAll(true)),
+                button("Select None")
+                    .on_press(Message::DeviceSelectAll(false)),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+
+        let thumbnail_size = device_import::THUMBNAIL_SIZE as f32;
+        let mut item_list = Column::new().spacing(2);
+        for (index, (item, selected)) in dialog.items.iter().zip(&dialog.selected).enumerate() {
+            let thumbnail: Element<'_, Message> = match dialog.thumbnails.get(&item.path) {
+                Some(handle) => image(handle.clone())
+                    .width(Length::Fixed(thumbnail_size))
+                    .height(Length::Fixed(thumbnail_size))
+                    .into(),
+                None => Space::new(Length::Fixed(thumbnail_size), Length::Fixed(thumbnail_size)).into(),
+            };
+            item_list = item_list.push(
+                row![
+                    checkbox("", *selected)
+                        .on_toggle(move |checked| Message::DeviceItemToggled(index, checked)),
+                    thumbnail,
+                    text(&item.name)
+                        .width(Length::Fill),
+                    text(item.date.map(|date| dates.date_time(date)).unwrap_or_default())
+                        .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+                    text(format!("{} KB", item.size / 1024))
+                        .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center)
+            );
+        }
+        content = content.push(
+            scrollable(item_list)
+                .height(Length::Fixed(400.0))
+        );
+    }
+
+    content = content.push(
+        row![
+            button("Cancel")
+                .on_press(Message::DeviceImportCancelled)
+                .style(theme::Button::Secondary),
+            Space::with_width(Length::Fill),
+            button(text(format!("Import {} to {}", selected_count, person.name)))
+                .on_press_maybe((selected_count > 0).then_some(Message::DeviceImportConfirmed))
+                .style(theme::Button::Primary),
+        ]
+        .spacing(10)
+    );
+
+    container(content)
+        .width(Length::Fill)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+// Rows shown in the file list preview; the rest are counted
+const FILE_LIST_PREVIEW_ROWS: usize = 50;
+
+fn file_list_dialog<'a>(person: &'a Person, dialog: &'a FileListDialog) -> Element<'a, Message> {
+    let found = dialog.rows.iter().filter(|row| row.exists()).count();
+    let mut choices = vec![ColumnChoice { column: None, label: "(none)".to_string() }];
+    choices.extend(dialog.list.headers.iter().enumerate().map(|(index, header)| ColumnChoice {
+        column: Some(index),
+        label: if header.is_empty() { format!("Column {}", index + 1) } else { header.clone() },
+    }));
+
+    let mut mapping = Column::new().spacing(5);
+    for field in MappedField::ALL {
+        let current = choices.iter().find(|choice| choice.column == dialog.mapping.get(&field).copied()).cloned();
+        mapping = mapping.push(
+            row![
+                text(field.label()).width(Length::Fixed(130.0)),
+                pick_list(choices.clone(), current, move |choice| Message::FileListColumnSelected(field, choice.column)),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+
+    let mut row_list = Column::new().spacing(2);
+    for row in dialog.rows.iter().take(FILE_LIST_PREVIEW_ROWS) {
+        row_list = row_list.push(
+            row![
+                text(row.name()).width(Length::Fill),
+                text(row.hash.as_deref().unwrap_or(""))
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+                text(row.collected.as_deref().unwrap_or(""))
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+                text(row.custodian.as_deref().unwrap_or(""))
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+                if row.exists() {
+                    text("Found").style(theme::Text::Color(Color::from_rgb(0.0, 0.5, 0.0)))
+                } else {
+                    text("Missing").style(theme::Text::Color(Color::from_rgb(0.8, 0.0, 0.0)))
+                },
+            ]
+            .spacing(10)
+        );
+    }
+    if dialog.rows.len() > FILE_LIST_PREVIEW_ROWS {
+        row_list = row_list.push(
+            text(format!("…and {} more", dialog.rows.len() - FILE_LIST_PREVIEW_ROWS))
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+
+    container(
+        column![
+            text("Import File List").size(16),
+            text("Pick the columns that hold each detail. Files are looked for relative to the list's folder; listed hashes are checked before a file is taken in.")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            mapping,
+            text(format!("{} rows, {} files found", dialog.rows.len(), found))
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            scrollable(row_list)
+                .height(Length::Fixed(300.0)),
+            row![
+                button("Cancel")
+                    .on_press(Message::FileListImportCancelled)
+                    .style(theme::Button::Secondary),
+                Space::with_width(Length::Fill),
+                button(text(format!("Import {} to {}", found, person.name)))
+                    .on_press_maybe((found > 0).then_some(Message::FileListImportConfirmed))
+                    .style(theme::Button::Primary),
+            ]
+            .spacing(10),
+        ]
+        .spacing(5)
+    )
+    .width(Length::Fill)
+    .padding(10)
+    .style(theme::Container::Box)
+    .into()
+}
+
+/// Writes a text note, or edits one, without leaving the app
+fn note_editor_panel(editor: &NoteEditor) -> Element<'_, Message> {
+    let mut panel = column![
+        row![
+            text(if editor.editing.is_some() { "Edit Note" } else { "New Text Note" }).size(16),
+            Space::with_width(Length::Fill),
+            text_input("File name (.md or .txt)", &editor.name)
+                .on_input(Message::NoteNameChanged)
+                .width(Length::Fixed(300.0)),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+        text_editor(&editor.content)
+            .on_action(Message::NoteEdited)
+            .height(Length::Fixed(300.0)),
+        row![
+            button("Save Note")
+                .on_press(Message::SaveNote)
+                .style(theme::Button::Primary),
+            button("Cancel")
+                .on_press(Message::CloseNote)
+                .style(theme::Button::Secondary),
+        ]
+        .spacing(5),
+    ]
+    .spacing(5);
+
+    if editor.editing.is_some() {
+        panel = panel.push(
+            text("Saving keeps the note as it was as an earlier version")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+
+    container(panel)
+        .width(Length::Fill)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+/// Records a statement from the microphone straight into the person's audio evidence
+fn recorder_bar(state: &AppState) -> Element<'_, Message> {
+    let Some((person_id, recording)) = &state.recording else {
+        return button("🎙 Record Audio")
+            .on_press(Message::StartRecording)
+            .into();
+    };
+
+    let seconds = (chrono::Utc::now() - recording.started_at).num_seconds().max(0);
+    let mut bar = row![
+        text(format!("● Recording {:02}:{:02}", seconds / 60, seconds % 60))
+            .style(theme::Text::Color(Color::from_rgb(0.8, 0.2, 0.2))),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+    if Some(*person_id) != state.selected_person {
+        let name = state.persons.iter().find(|p| p.id == *person_id).map(|p| p.name.as_str()).unwrap_or_default();
+        bar = bar.push(
+            text(format!("for {}", name))
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+    bar.push(
+        button("Stop and Save")
+            .on_press(Message::StopRecording)
+            .style(theme::Button::Primary)
+    )
+    .push(
+        button("Discard")
+            .on_press(Message::DiscardRecording)
+            .style(theme::Button::Destructive)
+    )
+    .into()
+}
+
+/// Files held back from intake, each to accept as evidence (after confirming) or discard
+fn quarantine_panel<'a>(state: &AppState, files: &[&'a QuarantinedFile]) -> Element<'a, Message> {
+    let warning = Color::from_rgb(0.8, 0.2, 0.2);
+    let mut panel = column![
+        text("⚠ Quarantined files").size(16).style(theme::Text::Color(warning)),
+        text("These could run code when opened. Accept one only if you're sure it's evidence; it's stored exactly as it is.")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+    ]
+    .spacing(5);
+
+    for file in files {
+        panel = panel.push(
+            row![
+                text(&file.original_name)
+                    .width(Length::Fill),
+                text(&file.reason)
+                    .style(theme::Text::Color(warning)),
+                text(format!("{} KB", file.size / 1024))
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+                button("Accept as Evidence")
+                    .on_press(Message::AcceptQuarantinedClicked(Some(file.id))),
+                button("Discard")
+                    .on_press(Message::DiscardQuarantined(file.id))
+                    .style(theme::Button::Destructive),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+        panel = panel.push(
+            text(format!("SHA-256 {}", file.sha256))
+                .size(12)
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+        if state.quarantine_confirm == Some(file.id) {
+            panel = panel.push(
+                row![
+                    text(format!("Accept {} ({}) as evidence?", file.original_name, file.reason.to_lowercase()))
+                        .width(Length::Fill),
+                    button("Accept")
+                        .on_press(Message::AcceptQuarantinedConfirmed(file.id))
+                        .style(theme::Button::Destructive),
+                    button("Cancel")
+                        .on_press(Message::AcceptQuarantinedClicked(None))
+                        .style(theme::Button::Secondary),
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center)
+            );
+        }
+    }
+
+    container(panel)
+        .width(Length::Fill)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+fn provenance_summary(provenance: &Provenance) -> Option<String> {
+    let parts: Vec<String> = [
+        provenance.provided_by.as_ref().map(|p| format!("from {}", p)),
+        provenance.source_device.clone(),
+        provenance.source_url.clone(),
+        provenance.obtained_at.as_ref().map(|d| format!("obtained {}", d)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" · "))
+    }
+}
+
+fn uncategorized_file_prompt<'a>(state: &'a AppState, path: &'a Path) -> Element<'a, Message> {
+    let file_name = path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string());
+
+    let prompt = column![
+        text(format!("\"{}\" isn't a recognised file type. Where should it be stored?", file_name)),
+        row![
+            pick_list(category_choices(&state.settings), None::<CategoryChoice>, |choice| {
+                Message::UncategorizedFileCategorySelected(choice.evidence_type)
+            })
+            .placeholder("Choose a category..."),
+            button("Store as Other")
+                .on_press(Message::UncategorizedFileCategorySelected(EvidenceType::Other)),
+            button("Cancel")
+                .on_press(Message::CancelUncategorizedFile)
+                .style(theme::Button::Secondary),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center),
+    ]
+    .spacing(5);
+
+    container(prompt)
+        .width(Length::Fill)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+fn image_preview_panel<'a>(file: &'a EvidenceFile, handle: &image::Handle) -> Element<'a, Message> {
+    container(
+        column![
+            row![
+                text(file.file_path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_else(|| file.original_name.clone())).size(16),
+                Space::with_width(Length::Fill),
+                button("Close")
+                    .on_press(Message::CloseImagePreview),
+            ]
+            .align_items(Alignment::Center),
+            image(handle.clone())
+                .width(Length::Fill)
+                .height(Length::Fixed(400.0)),
+        ]
+        .spacing(5)
+    )
+    .padding(10)
+    .style(theme::Container::Box)
+    .into()
+}
+
+fn email_preview_panel(email: &ParsedEmail) -> Element<'_, Message> {
+    let mut details = column![
+        row![
+            text(&email.metadata.subject).size(16),
+            Space::with_width(Length::Fill),
+            button("Close")
+                .on_press(Message::CloseEmailPreview),
+        ]
+        .align_items(Alignment::Center),
+        text(format!("From: {}", email.metadata.from)),
+        text(format!("To: {}", email.metadata.to)),
+        text(format!("Date: {}", email.metadata.date.as_deref().unwrap_or("-"))),
+    ]
+    .spacing(3);
+
+    if !email.attachments.is_empty() {
+        let names: Vec<&str> = email.attachments.iter().map(|a| a.name.as_str()).collect();
+        details = details.push(text(format!("Attachments: {}", names.join(", "))));
+    }
+
+    details = details.push(Space::with_height(5));
+    details = details.push(
+        scrollable(text(&email.body))
+            .height(Length::Fixed(200.0))
+    );
+
+    container(details)
+        .width(Length::Fill)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+fn quotes_tab<'a>(state: &'a AppState, person: &'a Person) -> Element<'a, Message> {
+    let dates = state.settings.dates();
+    let mut content = column![
+        text("Add Quote").size(16),
+        Space::with_height(5),
+    ];
+
+    // Add quote form
+    content = content.push(
+        column![
+            text_input("Quote", &state.new_quote_text)
+                .on_input(Message::AddQuoteTextChanged),
+            text_input("Date", &state.new_quote_date)
+                .on_input(Message::AddQuoteDateChanged),
+            row![
+                text_input("Time (optional)", &state.new_quote_time)
+                    .on_input(Message::AddQuoteTimeChanged),
+                pick_list(QuoteZone::all(), Some(state.new_quote_zone), Message::AddQuoteZoneSelected),
+                text_input("Place (optional)", &state.new_quote_place)
+                    .on_input(Message::AddQuotePlaceChanged),
+                text_input("Source (optional)", &state.new_quote_source)
+                    .on_input(Message::AddQuoteSourceChanged),
+            ]
+            .spacing(5),
+            linked_evidence_picker(state),
+            row![
+                button("Add Quote")
+                    .on_press(Message::AddQuoteSubmitted)
+                    .style(theme::Button::Primary),
+                button("Import from Chat Export")
+                    .on_press(Message::ImportChatClicked),
+                button("Paste Quotes")
+                    .on_press(Message::PasteQuotesClicked),
+            ]
+            .spacing(5),
+        ]
+        .spacing(5)
+    );
+
+    content = content.push(Space::with_height(10));
+
+    content = content.push(
+        text_input("Search quotes, places and sources", &state.quote_filter)
+            .on_input(Message::QuoteFilterChanged)
+    );
+    content = content.push(Space::with_height(5));
+
+    content = content.push(
+        row![
+            text("Near"),
+            text_input("Place or latitude, longitude", &state.quote_near_place)
+                .on_input(Message::QuoteNearPlaceChanged)
+                .on_submit(Message::QuoteNearSubmitted)
+                .width(Length::FillPortion(3)),
+            text("within"),
+            text_input("km", &state.quote_near_radius)
+                .on_input(Message::QuoteNearRadiusChanged)
+                .on_submit(Message::QuoteNearSubmitted)
+                .width(Length::Fixed(60.0)),
+            text("km"),
+            button("Search")
+                .on_press(Message::QuoteNearSubmitted),
+            button("Clear")
+                .on_press_maybe(state.quote_near.map(|_| Message::ClearQuoteNear))
+                .style(theme::Button::Secondary),
+            button("Locate Places")
+                .on_press(Message::LocateQuotePlaces)
+                .style(theme::Button::Secondary),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center)
+    );
+    content = content.push(Space::with_height(10));
+
+    // Quotes within the proximity filter, with their distance from its centre
+    let search = state.quote_filter.trim();
+    let quotes: Vec<(&Quote, Option<f64>)> = person.quotes
+        .iter()
+        .filter(|quote| {
+            search.is_empty()
+                || [Some(&quote.quote), quote.place.as_ref(), quote.source.as_ref()]
+                    .into_iter()
+                    .flatten()
+                    .any(|value| !match_ranges(value, search).is_empty())
+        })
+        .filter_map(|quote| match state.quote_near {
+            Some((center, radius)) => quote.coordinates
+                .map(|coordinates| coordinates.distance_km(&center))
+                .filter(|distance| *distance <= radius)
+                .map(|distance| (quote, Some(distance))),
+            None => Some((quote, None)),
+        })
+        .collect();
+
+    // Quotes table
+    if person.quotes.is_empty() {
+        content = content.push(
+            text("No quotes added yet")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    } else if quotes.is_empty() {
+        let message = match state.quote_near {
+            Some(_) if !search.is_empty() => format!("No located quotes within that distance match \"{}\"", search),
+            Some(_) => "No located quotes within that distance".to_string(),
+            None => format!("No quotes match \"{}\"", search),
+        };
+        content = content.push(
+            text(message)
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    } else {
+        content = content.push(
+            text("Quotes")
+                .size(14)
+                .style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8)))
+        );
+
+        let mut quote_list = Column::new().spacing(2);
+        for (quote, distance) in quotes {
+            let place = match (&quote.place, distance) {
+                (Some(place), Some(distance)) => format!("{} ({:.1} km)", place, distance),
+                (Some(place), None) => place.clone(),
+                (None, _) => "-".to_string(),
+            };
+            let (date, time) = dates.quote(quote);
+            quote_list = quote_list.push(
+                row![
+                    container(highlighted(&quote.quote, search))
+                        .width(Length::FillPortion(2)),
+                    text(date)
+                        .width(Length::FillPortion(1)),
+                    text(time.unwrap_or_else(|| "-".to_string()))
+                        .width(Length::FillPortion(1)),
+                    container(highlighted(&place, search))
+                        .width(Length::FillPortion(1)),
+                    container(highlighted(quote.source.as_deref().unwrap_or("-"), search))
+                        .width(Length::FillPortion(1)),
+                    text(linked_evidence_label(state, &quote.linked_evidence))
+                        .width(Length::FillPortion(1)),
+                    button("Delete")
+                        .on_press(Message::RemoveQuote(quote.id))
+                        .style(theme::Button::Destructive),
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center)
+            );
+        }
+        
+        content = content.push(
+            scrollable(quote_list)
+                .id(scrollable::Id::new(TAB_SCROLL))
+                .on_scroll(|viewport| Message::TabScrolled(viewport.relative_offset().y))
+                .height(Length::Fixed(300.0))
+        );
+    }
+
+    container(content)
+        .width(Length::Fill)
+        .padding(10)
+        .into()
+}
+
+/// Byte ranges where `needle` appears in `value`, ignoring case
+fn match_ranges(value: &str, needle: &str) -> Vec<std::ops::Range<usize>> {
+    let needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+    let mut ranges = Vec::new();
+    if needle.is_empty() {
+        return ranges;
+    }
+    let mut start = 0;
+    while let Some((offset, _)) = value[start..].char_indices().find(|(offset, _)| {
+        let mut rest = value[start + offset..].chars().flat_map(char::to_lowercase);
+        needle.iter().all(|c| rest.next() == Some(*c))
+    }) {
+        let from = start + offset;
+        // Ends after as many characters as the needle, counted in the original text
+        let mut lowered = 0;
+        let length = value[from..]
+            .char_indices()
+            .find(|(_, c)| {
+                let done = lowered >= needle.len();
+                lowered += c.to_lowercase().count();
+                done
+            })
+            .map_or(value.len() - from, |(end, _)| end);
+        ranges.push(from..from + length);
+        start = from + length;
+    }
+    ranges
+}
+
+/// Text with the parts matching `needle` highlighted. iced has no styled spans within one text,
+/// so a match splits the value into a row of pieces, which doesn't wrap.
+fn highlighted<'a>(value: &str, needle: &str) -> Element<'a, Message> {
+    let ranges = match_ranges(value, needle);
+    if ranges.is_empty() {
+        return text(value.to_string()).into();
+    }
+    let mut pieces = Row::new();
+    let mut position = 0;
+    for range in ranges {
+        if range.start > position {
+            pieces = pieces.push(text(value[position..range.start].to_string()));
+        }
+        pieces = pieces.push(
+            container(
+                text(value[range.clone()].to_string())
+                    .style(theme::Text::Color(Color::from_rgb(0.8, 0.4, 0.0)))
+            )
+            .style(theme::Container::Box)
+        );
+        position = range.end;
+    }
+    if position < value.len() {
+        pieces = pieces.push(text(value[position..].to_string()));
+    }
+    pieces.into()
+}
+
+fn tasks_tab<'a>(state: &'a AppState, person: &'a Person) -> Element<'a, Message> {
+    let mut content = column![
+        text("Add Task").size(16),
+        Space::with_height(5),
+        row![
+            text_input("Description", &state.new_task_description)
+                .on_input(Message::NewTaskDescriptionChanged)
+                .width(Length::FillPortion(3)),
+            text_input("Due date (YYYY-MM-DD, optional)", &state.new_task_due)
+                .on_input(Message::NewTaskDueChanged)
+                .on_submit(Message::AddTaskSubmitted)
+                .width(Length::FillPortion(1)),
+            button("Add Task")
+                .on_press(Message::AddTaskSubmitted)
+                .style(theme::Button::Primary),
+        ]
+        .spacing(5),
+        Space::with_height(10),
+    ];
+
+    if person.tasks.is_empty() {
+        content = content.push(
+            text("No tasks yet")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    } else {
+        let today = Local::now().date_naive();
+        let mut task_list = Column::new().spacing(2);
+        for task in &person.tasks {
+            let due_color = if task.is_due(today) {
+                Color::from_rgb(0.8, 0.0, 0.0)
+            } else {
+                Color::from_rgb(0.5, 0.5, 0.5)
+            };
+            let due_label = task.due_date
+                .map(|due| format!("Due {}", state.settings.dates().date(due)))
+                .unwrap_or_else(|| "No due date".to_string());
+
+            task_list = task_list.push(
+                row![
+                    checkbox(&task.description, task.done)
+                        .on_toggle(move |done| Message::TaskDoneToggled(task.id, done))
+                        .width(Length::Fill),
+                    text(due_label)
+                        .style(theme::Text::Color(due_color)),
+                    button("Delete")
+                        .on_press(Message::RemoveTask(task.id))
+                        .style(theme::Button::Destructive),
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center)
+            );
+        }
+
+        content = content.push(
+            scrollable(task_list)
+                .id(scrollable::Id::new(TAB_SCROLL))
+                .on_scroll(|viewport| Message::TabScrolled(viewport.relative_offset().y))
+                .height(Length::Fixed(300.0))
+        );
+    }
+
+    content = content.push(Space::with_height(15));
+    content = content.push(checklists_section(state, person));
+
+    container(content)
+        .width(Length::Fill)
+        .padding(10)
+        .into()
+}
+
+/// Interview and intake checklists, each question with its answer field and the share answered
+fn checklists_section<'a>(state: &'a AppState, person: &'a Person) -> Element<'a, Message> {
+    let mut section = column![
+        row![
+            text("Checklists").size(16),
+            Space::with_width(Length::Fill),
+            pick_list(state.settings.checklist_templates.clone(), None::<ChecklistTemplate>, Message::AttachChecklist)
+                .placeholder("Attach checklist..."),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+    ]
+    .spacing(5);
+
+    if person.checklists.is_empty() {
+        let hint = if state.settings.checklist_templates.is_empty() {
+            "No checklists yet. Set up checklist templates in Settings to attach them here"
+        } else {
+            "No checklists yet"
+        };
+        section = section.push(
+            text(hint)
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+
+    for checklist in &person.checklists {
+        let answered = checklist.answered();
+        let total = checklist.items.len();
+        section = section.push(
+            row![
+                text(&checklist.name)
+                    .width(Length::Fill),
+                text(if checklist.is_complete() { "Complete".to_string() } else { format!("{} of {} answered", answered, total) })
+                    .style(theme::Text::Color(if checklist.is_complete() {
+                        Color::from_rgb(0.1, 0.6, 0.1)
+                    } else {
+                        Color::from_rgb(0.5, 0.5, 0.5)
+                    })),
+                progress_bar(0.0..=total.max(1) as f32, answered as f32)
+                    .width(Length::Fixed(120.0))
+                    .height(Length::Fixed(8.0)),
+                button("Remove")
+                    .on_press(Message::RemoveChecklist(checklist.id))
+                    .style(theme::Button::Destructive),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center)
+        );
+
+        for item in &checklist.items {
+            let value = state.checklist_drafts.get(&item.id).unwrap_or(&item.answer);
+            section = section.push(
+                row![
+                    text(&item.question)
+                        .width(Length::FillPortion(2)),
+                    text_input("Answer", value)
+                        .on_input(move |answer| Message::ChecklistAnswerChanged(item.id, answer))
+                        .on_submit(Message::ChecklistAnswerSubmitted(item.id))
+                        .width(Length::FillPortion(3)),
+                    button("Save")
+                        .on_press_maybe(state.checklist_drafts.contains_key(&item.id).then_some(Message::ChecklistAnswerSubmitted(item.id))),
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center)
+            );
+        }
+    }
+
+    section.into()
+}
+
+fn map_tab(state: &AppState) -> Element<'_, Message> {
+    let mut content = column![
+        row![
+            text("Evidence photos with GPS data and quotes whose place is given as \"latitude, longitude\"")
+                .width(Length::Fill)
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            button("Refresh")
+                .on_press_maybe((!state.map_loading).then_some(Message::LoadMap)),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+    ]
+    .spacing(10);
+
+    match &state.map_view {
+        _ if state.map_loading => {
+            content = content.push(text("Loading map..."));
+        }
+        Some(map_view) if map_view.points.is_empty() => {
+            content = content.push(
+                text("No locations found for this person")
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+            );
+        }
+        Some(map_view) => {
+            if map_view.missing_tiles > 0 {
+                content = content.push(
+                    text("Some map tiles couldn't be downloaded; only cached areas are shown")
+                        .style(theme::Text::Color(Color::from_rgb(0.8, 0.5, 0.0)))
+                );
+            }
+            content = content.push(image(map_view.image.clone()));
+
+            let mut point_list = column![].spacing(3);
+            for (index, point) in map_view.points.iter().enumerate() {
+                point_list = point_list.push(
+                    row![
+                        text(format!("{}.", index + 1)).width(Length::Fixed(30.0)),
+                        text(&point.label).width(Length::Fill),
+                        text(format!("{:.5}, {:.5}", point.coordinates.latitude, point.coordinates.longitude))
+                            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+                        button("Open")
+                            .on_press(Message::OpenMapPoint(point.target))
+                            .style(theme::Button::Secondary),
+                    ]
+                    .spacing(5)
+                    .align_items(Alignment::Center)
+                );
+            }
+            content = content.push(scrollable(point_list).height(Length::Fixed(200.0)));
+        }
+        None => {}
+    }
+
+    container(scrollable(content))
+        .width(Length::Fill)
+        .padding(10)
+        .into()
+}
+
+fn board_tab<'a>(state: &'a AppState, person: &'a Person) -> Element<'a, Message> {
+    let mut choices = vec![BoardCardChoice { content: CardContent::Note, label: "Note".to_string() }];
+    choices.extend(state.persons.iter().map(|p| BoardCardChoice { content: CardContent::Person(p.id), label: format!("Person: {}", p.name) }));
+    choices.extend(person.quotes.iter().map(|q| BoardCardChoice { content: CardContent::Quote(q.id), label: format!("Quote: {}", board::truncate(&q.quote, 50)) }));
+    choices.extend(state.evidence_files.iter().filter(|f| f.superseded_by.is_none()).map(|f| BoardCardChoice { content: CardContent::Evidence(f.id), label: format!("Evidence: {}", f.original_name) }));
+    // Notes can be pinned any number of times, everything else once
+    choices.retain(|choice| choice.content == CardContent::Note || !state.board.contains(&choice.content));
+
+    let toolbar = row![
+        text("Pin people, quotes and evidence, drag them into place and tie strings between them")
+            .width(Length::Fill)
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        pick_list(choices, None::<BoardCardChoice>, |choice| Message::BoardCardAdded(choice.content))
+            .placeholder("Add card..."),
+        button("Export Image")
+            .on_press_maybe((!state.board.cards.is_empty()).then_some(Message::ExportBoardClicked)),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let texts: HashMap<Uuid, (String, String)> = state.board.cards
+        .iter()
+        .map(|card| (card.id, board::card_text(card, person, &state.persons, &state.evidence_files)))
+        .collect();
+    let (_, height) = state.board.size();
+    let corkboard = canvas(BoardCanvas {
+        board: &state.board,
+        texts: texts.clone(),
+        selected: state.board_selected,
+        connecting: state.board_connecting,
+        editable: state.role == Some(Role::Admin),
+    })
+    .width(Length::Fill)
+    // Room below the lowest card to drag others into
+    .height(Length::Fixed(height.max(500.0) + CARD_HEIGHT * 2.0));
+
+    let mut board_row = row![scrollable(corkboard).width(Length::Fill).height(Length::Fill)].spacing(10);
+    if let Some(card) = state.board_selected.and_then(|id| state.board.card(id)) {
+        board_row = board_row.push(board_card_panel(state, card, &texts));
+    }
+
+    column![toolbar, board_row]
+        .spacing(10)
+        .height(Length::Fill)
+        .into()
+}
+
+/// Details of the selected card: its note, its strings and a thumbnail for photos
+fn board_card_panel<'a>(state: &'a AppState, card: &'a board::Card, texts: &HashMap<Uuid, (String, String)>) -> Element<'a, Message> {
+    let id = card.id;
+    let (title, detail) = texts.get(&id).cloned().unwrap_or_default();
+    let mut panel = column![text(title).size(16)].spacing(5);
+    if !detail.is_empty() {
+        panel = panel.push(text(detail).style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))));
+    }
+    if let CardContent::Evidence(file_id) = &card.content
+        && let Some(file) = state.evidence_files.iter().find(|f| f.id == *file_id)
+    {
+        if file.file_type == EvidenceType::Image {
+            panel = panel.push(image(image::Handle::from_path(&file.file_path)).width(Length::Fill));
+        }
+        panel = panel.push(button("Open").on_press(Message::OpenEvidence(file.id)).style(theme::Button::Secondary));
+    }
+
+    panel = panel.push(text("Note").size(14));
+    panel = panel.push(text_input("Write on the card...", &card.note).on_input(move |note| Message::BoardNoteChanged(id, note)));
+
+    panel = panel.push(text("Strings").size(14));
+    if state.board_connecting == Some(id) {
+        panel = panel.push(
+            row![
+                text("Click the card to tie it to").width(Length::Fill),
+                button("Cancel").on_press(Message::BoardConnectClicked(None)).style(theme::Button::Secondary),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    } else {
+        panel = panel.push(button("Connect to...").on_press(Message::BoardConnectClicked(Some(id))));
+    }
+    for connection in state.board.connections.iter().filter(|c| c.from == id || c.to == id) {
+        let other = if connection.from == id { connection.to } else { connection.from };
+        let other_title = texts.get(&other).map(|(title, _)| board::truncate(title, 30)).unwrap_or_default();
+        let connection_id = connection.id;
+        panel = panel.push(
+            column![
+                text(format!("To {}", other_title)),
+                row![
+                    text_input("Label", &connection.label)
+                        .on_input(move |label| Message::BoardConnectionLabelChanged(connection_id, label)),
+                    button("Remove")
+                        .on_press(Message::BoardConnectionRemoved(connection_id))
+                        .style(theme::Button::Destructive),
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center),
+            ]
+            .spacing(3)
+        );
+    }
+
+    panel = panel.push(Space::with_height(10));
+    panel = panel.push(button("Remove Card").on_press(Message::BoardCardRemoved(id)).style(theme::Button::Destructive));
+
+    container(scrollable(panel))
+        .width(Length::Fixed(260.0))
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+/// Draws the board and turns clicks and drags on it into messages
+struct BoardCanvas<'a> {
+    board: &'a Board,
+    texts: HashMap<Uuid, (String, String)>, // Title and second line of each card
+    selected: Option<Uuid>,
+    connecting: Option<Uuid>,
+    editable: bool, // Viewers can look and select, but not move cards
+}
+
+fn board_color(content: &CardContent) -> Color {
+    let rgba = board::card_color(content);
+    Color::from_rgb8(rgba[0], rgba[1], rgba[2])
+}
+
+impl canvas::Program<Message> for BoardCanvas<'_> {
+    // The card being dragged and where on it the cursor grabbed it
+    type State = Option<(Uuid, Vector)>;
+
+    fn update(&self, dragging: &mut Self::State, event: canvas::Event, bounds: Rectangle, cursor: mouse::Cursor) -> (event::Status, Option<Message>) {
+        // A drag ends wherever the button is let go
+        if let canvas::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) = event
+            && dragging.take().is_some()
+        {
+            return (event::Status::Captured, Some(Message::BoardCardDropped));
+        }
+        let Some(position) = cursor.position_in(bounds) else {
+            return (event::Status::Ignored, None);
+        };
+        match event {
+            canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let message = match self.board.card_at(position.x, position.y) {
+                    Some(card) => {
+                        if self.editable && self.connecting.is_none() {
+                            *dragging = Some((card.id, Vector::new(position.x - card.x, position.y - card.y)));
+                        }
+                        Message::BoardCardPressed(card.id)
+                    }
+                    None => Message::BoardBackgroundPressed,
+                };
+                (event::Status::Captured, Some(message))
+            }
+            canvas::Event::Mouse(mouse::Event::CursorMoved { .. }) => match dragging {
+                Some((id, grab)) => {
+                    // Cards stay within the width of the board
+                    let x = (position.x - grab.x).min(bounds.width - CARD_WIDTH);
+                    (event::Status::Captured, Some(Message::BoardCardMoved(*id, x, position.y - grab.y)))
+                }
+                None => (event::Status::Ignored, None),
+            },
+            _ => (event::Status::Ignored, None),
+        }
+    }
+
+    fn draw(&self, _dragging: &Self::State, renderer: &Renderer, _theme: &Theme, bounds: Rectangle, cursor: mouse::Cursor) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        frame.fill_rectangle(Point::ORIGIN, bounds.size(), Color::from_rgb8(196, 154, 108));
+        let string_color = Color::from_rgb8(190, 30, 30);
+        let string = || canvas::Stroke::default().with_color(string_color).with_width(2.0);
+        let center = |card: &board::Card| {
+            let (x, y) = card.center();
+            Point::new(x, y)
+        };
+
+        for connection in &self.board.connections {
+            if let (Some(from), Some(to)) = (self.board.card(connection.from), self.board.card(connection.to)) {
+                frame.stroke(&canvas::Path::line(center(from), center(to)), string());
+            }
+        }
+        if let Some(from) = self.connecting.and_then(|id| self.board.card(id))
+            && let Some(position) = cursor.position_in(bounds)
+        {
+            frame.stroke(&canvas::Path::line(center(from), position), string());
+        }
+
+        let card_size = Size::new(CARD_WIDTH, CARD_HEIGHT);
+        for card in &self.board.cards {
+            let top_left = Point::new(card.x, card.y);
+            frame.fill_rectangle(top_left + Vector::new(3.0, 3.0), card_size, Color::from_rgba8(90, 60, 30, 0.6));
+            frame.fill_rectangle(top_left, card_size, board_color(&card.content));
+            if Some(card.id) == self.selected || Some(card.id) == self.connecting {
+                frame.stroke(
+                    &canvas::Path::rectangle(top_left, card_size),
+                    canvas::Stroke::default().with_color(Color::from_rgb8(40, 90, 200)).with_width(3.0),
+                );
+            }
+            frame.fill(&canvas::Path::circle(Point::new(card.x + CARD_WIDTH / 2.0, card.y), 5.0), string_color);
+
+            let (title, detail) = self.texts.get(&card.id).cloned().unwrap_or_default();
+            let lines = [
+                (title, 14.0, Color::BLACK),
+                (detail, 12.0, Color::from_rgb(0.4, 0.4, 0.4)),
+                (card.note.clone(), 12.0, Color::from_rgb8(40, 40, 140)),
+            ];
+            for (index, (line, size, color)) in lines.into_iter().enumerate() {
+                frame.fill_text(canvas::Text {
+                    content: board::truncate(&line, 26),
+                    position: top_left + Vector::new(8.0, 10.0 + index as f32 * 24.0),
+                    color,
+                    size: size.into(),
+                    ..canvas::Text::default()
+                });
+            }
+        }
+
+        // Labels go on last so cards don't hide them
+        for connection in self.board.connections.iter().filter(|c| !c.label.trim().is_empty()) {
+            if let (Some(from), Some(to)) = (self.board.card(connection.from), self.board.card(connection.to)) {
+                let (from, to) = (center(from), center(to));
+                let middle = Point::new((from.x + to.x) / 2.0, (from.y + to.y) / 2.0);
+                let label = board::truncate(connection.label.trim(), 40);
+                let width = label.chars().count() as f32 * 7.0 + 10.0;
+                frame.fill_rectangle(middle - Vector::new(width / 2.0, 10.0), Size::new(width, 20.0), Color::WHITE);
+                frame.fill_text(canvas::Text {
+                    content: label,
+                    position: middle,
+                    color: string_color,
+                    size: 12.0.into(),
+                    horizontal_alignment: iced::alignment::Horizontal::Center,
+                    vertical_alignment: iced::alignment::Vertical::Center,
+                    ..canvas::Text::default()
+                });
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(&self, dragging: &Self::State, bounds: Rectangle, cursor: mouse::Cursor) -> mouse::Interaction {
+        if dragging.is_some() {
+            return mouse::Interaction::Grabbing;
+        }
+        match cursor.position_in(bounds) {
+            Some(position) if self.board.card_at(position.x, position.y).is_some() => {
+                if self.connecting.is_some() || !self.editable {
+                    mouse::Interaction::Pointer
+                } else {
+                    mouse::Interaction::Grab
+                }
+            }
+            _ => mouse::Interaction::default(),
+        }
+    }
+}
+
+fn shared_identifiers_panel(state: &AppState) -> Element<'_, Message> {
+    let mut panel = column![
+        text("Shared identifiers").size(16),
+        text("Information values recorded under more than one person, which may link them")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+    ]
+    .spacing(3);
+
+    if state.statistics.shared_identifiers.is_empty() {
+        panel = panel.push(
+            text("No shared values found")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+    for shared in &state.statistics.shared_identifiers {
+        let mut persons = row![].spacing(5);
+        for person in state.persons.iter().filter(|p| shared.persons.contains(&p.id)) {
+            persons = persons.push(
+                button(text(&person.name))
+                    .on_press(Message::PersonSelected(person.id))
+                    .style(theme::Button::Secondary)
+            );
+        }
+        panel = panel.push(
+            row![
+                text(&shared.info_type)
+                    .width(Length::FillPortion(1)),
+                text(&shared.value)
+                    .width(Length::FillPortion(2)),
+                persons,
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+
+    container(panel)
+        .width(Length::Fill)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+fn upcoming_tasks(state: &AppState) -> Element<'_, Message> {
+    let dates = state.settings.dates();
+    // Overdue tasks and anything due in the next week
+    let today = Local::now().date_naive();
+    let horizon = today + chrono::Duration::days(7);
+    let mut upcoming: Vec<(&Person, &Task)> = state.persons
+        .iter()
+        .flat_map(|person| person.tasks.iter().map(move |task| (person, task)))
+        .filter(|(_, task)| !task.done && task.due_date.is_some_and(|due| due <= horizon))
+        .collect();
+    upcoming.sort_by_key(|(_, task)| task.due_date);
+
+    let mut panel = column![text("Upcoming tasks").size(16)].spacing(3);
+    if upcoming.is_empty() {
+        panel = panel.push(
+            text("Nothing due in the next 7 days")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+    for (person, task) in upcoming {
+        let color = if task.is_due(today) {
+            Color::from_rgb(0.8, 0.0, 0.0)
+        } else {
+            Color::from_rgb(0.5, 0.5, 0.5)
+        };
+        panel = panel.push(
+            row![
+                text(task.due_date.map(|due| dates.date(due)).unwrap_or_default())
+                    .style(theme::Text::Color(color)),
+                text(&task.description)
+                    .width(Length::Fill),
+                button(text(&person.name))
+                    .on_press(Message::PersonSelected(person.id))
+                    .style(theme::Button::Secondary),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+
+    container(panel)
+        .width(Length::Fill)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+fn chat_import_dialog(dialog: &ChatImportDialog, dates: DateDisplay) -> Element<'_, Message> {
+    let selected_count = dialog.selected.iter().filter(|s| **s).count();
+
+    let mut content = column![
+        text(format!("Import {} Chat Export", dialog.export.format.label())).size(16),
+        text(format!("{} messages found, {} selected", dialog.export.messages.len(), selected_count))
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        row![
+            button("Select All")
+                .on_press(Message::ChatSelectAll(true)),
+            button("Select None")
+                .on_press(Message::ChatSelectAll(false)),
+            pick_list(dialog.export.senders(), None::<String>, Message::ChatSelectSender)
+                .placeholder("Select messages from..."),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center),
+        Space::with_height(5),
+    ]
+    .spacing(5);
+
+    let mut message_list = Column::new().spacing(2);
+    for (index, (message, selected)) in dialog.export.messages.iter().zip(&dialog.selected).enumerate() {
+        message_list = message_list.push(
+            row![
+                checkbox("", *selected)
+                    .on_toggle(move |checked| Message::ChatMessageToggled(index, checked)),
+                text(format!("{} {}", dates.typed_date(&message.date), message.time.as_deref().map(|time| dates.typed_time(time)).unwrap_or_default()))
+                    .width(Length::FillPortion(1)),
+                text(&message.sender)
+                    .width(Length::FillPortion(1)),
+                text(&message.text)
+                    .width(Length::FillPortion(4)),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+
+    content = content.push(
+        scrollable(message_list)
+            .height(Length::Fixed(400.0))
+    );
+
+    content = content.push(
+        row![
+            button("Cancel")
+                .on_press(Message::ChatImportCancelled),
+            Space::with_width(Length::Fill),
+            button(text(format!("Import {} as Quotes", selected_count)))
+                .on_press(Message::ChatImportConfirmed)
+                .style(theme::Button::Primary),
+        ]
+        .spacing(10)
+    );
+
+    container(content)
+        .width(Length::Fill)
+        .padding(10)
+        .into()
+}
+
+/// Quotes pasted one per line, with a preview of how each line was read
+fn quote_paste_dialog(dialog: &QuotePasteDialog, dates: DateDisplay) -> Element<'_, Message> {
+    let valid = dialog.lines.iter().filter(|line| line.quote.is_ok()).count();
+
+    let mut content = column![
+        text("Paste Quotes").size(16),
+        text("One quote per line, as \"text\", \"date | text\" or \"date | place | text\". A date may include a time, e.g. 2024-05-01 13:45")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        text_editor(&dialog.content)
+            .on_action(Message::QuotePasteEdited)
+            .height(Length::Fixed(200.0)),
+        row![
+            text_input("Date for lines without one", &dialog.default_date)
+                .on_input(Message::QuotePasteDefaultDateChanged),
+            text_input("Source for all quotes (optional)", &dialog.source)
+                .on_input(Message::QuotePasteSourceChanged),
+        ]
+        .spacing(5),
+        text(format!("{} lines, {} quotes to import", dialog.lines.len(), valid))
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+    ]
+    .spacing(5);
+
+    let mut preview = Column::new().spacing(2);
+    for line in &dialog.lines {
+        let row = match &line.quote {
+            Ok(quote) => row![
+                text(line.number.to_string())
+                    .width(Length::Fixed(30.0)),
+                text(format!("{} {}", dates.typed_date(&quote.date), quote.time.as_deref().map(|time| dates.typed_time(time)).unwrap_or_default()))
+                    .width(Length::FillPortion(1)),
+                text(quote.place.as_deref().unwrap_or(""))
+                    .width(Length::FillPortion(1)),
+                text(&quote.text)
+                    .width(Length::FillPortion(4)),
+            ],
+            Err(e) => row![
+                text(line.number.to_string())
+                    .width(Length::Fixed(30.0)),
+                text(format!("Skipped: {}", e))
+                    .style(theme::Text::Color(Color::from_rgb(0.8, 0.2, 0.2))),
+            ],
+        };
+        preview = preview.push(row.spacing(5));
+    }
+    content = content.push(
+        scrollable(preview)
+            .height(Length::Fixed(250.0))
+    );
+
+    content = content.push(
+        row![
+            button("Cancel")
+                .on_press(Message::QuotePasteCancelled),
+            Space::with_width(Length::Fill),
+            button(text(format!("Import {} Quotes", valid)))
+                .on_press_maybe((valid > 0).then_some(Message::QuotePasteConfirmed))
+                .style(theme::Button::Primary),
+        ]
+        .spacing(10)
+    );
+
+    container(content)
+        .width(Length::Fill)
+        .padding(10)
+        .into()
+}
+
+fn linked_evidence_picker(state: &AppState) -> Element<'_, Message> {
+    let choices: Vec<EvidenceChoice> = state.evidence_files
+        .iter()
+        .filter(|f| !state.new_quote_linked_evidence.contains(&f.id) && f.superseded_by.is_none())
+        .map(|f| EvidenceChoice { id: f.id, name: f.original_name.clone() })
+        .collect();
+
+    let mut picker = row![
+        pick_list(choices, None::<EvidenceChoice>, |choice| Message::AddQuoteLinkEvidence(choice.id))
+            .placeholder("Link evidence file (optional)"),
+    ]
+    .spacing(5)
+    .align_items(Alignment::Center);
+
+    for evidence_id in &state.new_quote_linked_evidence {
+        let name = evidence_name(state, *evidence_id).unwrap_or("Unknown file");
+        picker = picker.push(
+            button(text(format!("{} ✕", name)))
+                .on_press(Message::AddQuoteUnlinkEvidence(*evidence_id))
+                .style(theme::Button::Secondary)
+        );
+    }
+
+    picker.into()
+}
+
+fn linked_evidence_label(state: &AppState, linked_evidence: &[Uuid]) -> String {
+    if linked_evidence.is_empty() {
+        return "-".to_string();
+    }
+
+    linked_evidence
+        .iter()
+        .map(|id| evidence_name(state, *id).unwrap_or("Missing file"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn activity_view(state: &AppState) -> Element<'_, Message> {
+    let dates = state.settings.dates();
+    let mut content = column![
+        row![
+            text("Recent Activity")
+                .size(18)
+                .style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8))),
+            Space::with_width(Length::Fill),
+            button("Close")
+                .on_press(Message::ShowActivity(false)),
+        ]
+        .align_items(Alignment::Center),
+        Space::with_height(10),
+    ]
+    .spacing(5);
+
+    if state.activity.is_empty() {
+        content = content.push(
+            text("No activity recorded yet")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+
+    let mut entries = Column::new().spacing(2);
+    for entry in &state.activity {
+        // Link to the person while they still exist
+        let person: Element<Message> = match entry.person_id.filter(|id| state.persons.iter().any(|p| p.id == *id)) {
+            Some(person_id) => button(text(&entry.person_name))
+                .on_press(Message::PersonSelected(person_id))
+                .style(theme::Button::Secondary)
+                .into(),
+            None => text(&entry.person_name).into(),
+        };
+
+        entries = entries.push(
+            row![
+                text(dates.date_time(entry.timestamp))
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+                    .width(Length::Fixed(130.0)),
+                text(entry.action.label())
+                    .width(Length::Fixed(140.0)),
+                container(person)
+                    .width(Length::Fixed(150.0)),
+                text(&entry.details)
+                    .width(Length::Fill),
+                text(&entry.operator)
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+                    .width(Length::Fixed(100.0)),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+    content = content.push(entries);
+
+    container(scrollable(content))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+fn jobs_view(state: &AppState) -> Element<'_, Message> {
+    let dates = state.settings.dates();
+    let mut content = column![
+        row![
+            text("Background Jobs")
+                .size(18)
+                .style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8))),
+            Space::with_width(Length::Fill),
+            button("Clear Finished")
+                .on_press_maybe(state.jobs.jobs.iter().any(|job| job.status.is_finished()).then_some(Message::ClearFinishedJobs)),
+            button("Close")
+                .on_press(Message::ShowJobs(false)),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center),
+        text("Exports and imports run here one at a time. Jobs interrupted by closing the app start again the next time it opens.")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        Space::with_height(10),
+    ]
+    .spacing(5);
+
+    if state.jobs.jobs.is_empty() {
+        content = content.push(
+            text("No jobs yet")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+
+    let mut entries = Column::new().spacing(2);
+    // Newest first
+    for job in state.jobs.jobs.iter().rev() {
+        let status_color = match job.status {
+            JobStatus::Queued => Color::from_rgb(0.5, 0.5, 0.5),
+            JobStatus::Running => Color::from_rgb(0.2, 0.4, 0.9),
+            JobStatus::Completed => Color::from_rgb(0.0, 0.6, 0.0),
+            JobStatus::Failed(_) => Color::from_rgb(0.8, 0.0, 0.0),
+            JobStatus::Cancelled => Color::from_rgb(0.9, 0.5, 0.0),
+        };
+        let mut job_row = row![
+            text(dates.date_time(job.created_at))
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+                .width(Length::Fixed(130.0)),
+            text(job.status.label())
+                .style(theme::Text::Color(status_color))
+                .width(Length::Fixed(90.0)),
+            text(job.kind.label())
+                .width(Length::Fill),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center);
+
+        if let JobStatus::Failed(error) = &job.status {
+            job_row = job_row.push(
+                text(error)
+                    .style(theme::Text::Color(Color::from_rgb(0.8, 0.0, 0.0)))
+                    .width(Length::Fill)
+            );
+        }
+        if let Some(summary) = &job.summary {
+            job_row = job_row.push(
+                text(summary)
+                    .width(Length::Fill)
+            );
+        }
+        if !job.status.is_finished() {
+            job_row = job_row.push(
+                button("Cancel")
+                    .on_press(Message::CancelJob(job.id))
+                    .style(theme::Button::Destructive)
+            );
+        }
+        entries = entries.push(job_row);
+
+        for warning in &job.warnings {
+            entries = entries.push(
+                row![
+                    Space::with_width(Length::Fixed(225.0)),
+                    text(format!("⚠ {}", warning))
+                        .style(theme::Text::Color(Color::from_rgb(0.9, 0.5, 0.0))),
+                ]
+            );
+        }
+    }
+    content = content.push(entries);
+
+    container(scrollable(content))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+/// Case-wide list of every person's evidence, with filters, sortable columns and actions on a selection
+fn evidence_browser_view(state: &AppState) -> Element<'_, Message> {
+    let dates = state.settings.dates();
+    let browser = &state.evidence_browser;
+    let visible = browser.visible();
+
+    let categories: Vec<CategoryChoice> = browser.categories()
+        .into_iter()
+        .map(|evidence_type| CategoryChoice {
+            name: state.settings.display_name(&evidence_type),
+            evidence_type,
+        })
+        .collect();
+    let category = categories.iter()
+        .find(|c| Some(&c.evidence_type) == browser.category.as_ref())
+        .cloned();
+
+    let mut content = column![
+        row![
+            text("All Evidence")
+                .size(18)
+                .style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8))),
+            Space::with_width(Length::Fill),
+            button("Reload")
+                .on_press_maybe((!browser.loading).then_some(Message::ShowEvidenceBrowser(true))),
+            button("Close")
+                .on_press(Message::ShowEvidenceBrowser(false)),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center),
+        row![
+            text_input("Filter by person, file name or tag", &browser.filter)
+                .on_input(Message::BrowserFilterChanged),
+            pick_list(categories, category, |choice| Message::BrowserCategorySelected(choice.evidence_type))
+                .placeholder("Any category"),
+            pick_list(ReviewStatus::all().to_vec(), browser.review, Message::BrowserReviewFilterSelected)
+                .placeholder("Any review status"),
+            button("Clear Filters")
+                .on_press_maybe(
+                    (!browser.filter.is_empty() || browser.category.is_some() || browser.review.is_some())
+                        .then_some(Message::BrowserFiltersCleared)
+                ),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center),
+    ]
+    .spacing(5);
+
+    if browser.loading {
+        content = content.push(
+            text("Reading evidence...")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+
+    if !browser.selected.is_empty() {
+        content = content.push(
+            container(
+                row![
+                    text(format!("{} selected", browser.selected.len())),
+                    Space::with_width(Length::Fill),
+                    pick_list(ReviewStatus::all().to_vec(), None::<ReviewStatus>, Message::BrowserReviewStatusChanged)
+                        .placeholder("Mark as..."),
+                    button("Copy Paths")
+                        .on_press(Message::BrowserCopyPaths),
+                    button("Clear Selection")
+                        .on_press(Message::BrowserSelectAll(false)),
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center)
+            )
+            .padding(10)
+            .style(theme::Container::Box)
+        );
+    }
+
+    let total_size: u64 = visible.iter().map(|row| row.file.size).sum();
+    content = content.push(
+        text(format!("{} of {} files, {} KB", visible.len(), browser.rows.len(), total_size / 1024))
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+    );
+
+    let header = |column: BrowserColumn| {
+        let arrow = match (browser.sort == Some(column), browser.descending) {
+            (true, false) => " ▲",
+            (true, true) => " ▼",
+            (false, _) => "",
+        };
+        button(text(format!("{}{}", column.label(), arrow)))
+            .on_press(Message::BrowserSortClicked(column))
+            .style(theme::Button::Text)
+            .width(browser_column_width(column))
+    };
+    let all_selected = !visible.is_empty() && visible.iter().all(|row| browser.selected.contains(&row.file.id));
+    let mut header_row = row![
+        checkbox("", all_selected)
+            .on_toggle(Message::BrowserSelectAll),
+    ]
+    .spacing(5)
+    .align_items(Alignment::Center);
+    for column in BrowserColumn::ALL {
+        header_row = header_row.push(header(column));
+    }
+    content = content.push(header_row);
+
+    let mut rows = Column::new().spacing(2);
+    for &row in visible.iter().take(BROWSER_ROW_LIMIT) {
+        let file = &row.file;
+        let date = file.evidence_date.unwrap_or(file.created_at);
+        rows = rows.push(
+            row![
+                checkbox("", browser.selected.contains(&file.id))
+                    .on_toggle(move |selected| Message::BrowserRowToggled(file.id, selected)),
+                text(&row.person_name)
+                    .width(browser_column_width(BrowserColumn::Person)),
+                text(format!("{} {}", state.settings.icon(&file.file_type), row.category))
+                    .width(browser_column_width(BrowserColumn::Category)),
+                button(text(&file.original_name))
+                    .on_press(Message::BrowserOpenEvidence(row.person_id, file.id))
+                    .style(theme::Button::Text)
+                    .padding(0)
+                    .width(browser_column_width(BrowserColumn::Name)),
+                text(dates.date_time(date))
+                    .width(browser_column_width(BrowserColumn::Date)),
+                text(format!("{} KB", file.size / 1024))
+                    .width(browser_column_width(BrowserColumn::Size)),
+                text(row.tags.join(", "))
+                    .width(browser_column_width(BrowserColumn::Tags))
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+    if visible.len() > BROWSER_ROW_LIMIT {
+        rows = rows.push(
+            text(format!("Showing the first {} files; narrow the list with the filters to see the rest", BROWSER_ROW_LIMIT))
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+    if visible.is_empty() && !browser.loading {
+        rows = rows.push(
+            text("No evidence files found")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+    content = content.push(scrollable(rows).height(Length::Fill));
+
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+fn browser_column_width(column: BrowserColumn) -> Length {
+    match column {
+        BrowserColumn::Person => Length::FillPortion(2),
+        BrowserColumn::Category => Length::FillPortion(2),
+        BrowserColumn::Name => Length::FillPortion(4),
+        BrowserColumn::Date => Length::Fixed(130.0),
+        BrowserColumn::Size => Length::Fixed(80.0),
+        BrowserColumn::Tags => Length::FillPortion(2),
+    }
+}
+
+fn store_check_view(state: &AppState) -> Element<'_, Message> {
+    let dates = state.settings.dates();
+    let repairable = state.store_report.as_ref().map_or(0, |report| report.repairable());
+    let mut content = column![
+        row![
+            text("Store Check")
+                .size(18)
+                .style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8))),
+            Space::with_width(Length::Fill),
+            button("Verify Now")
+                .on_press_maybe((!state.store_checking).then_some(Message::VerifyStoreClicked)),
+            button(text(format!("Repair {}", repairable)))
+                .on_press_maybe((!state.store_checking && repairable > 0 && state.role == Some(Role::Admin)).then_some(Message::RepairStoreClicked)),
+            button("Close")
+                .on_press(Message::ShowStoreCheck(false)),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center),
+        text("Checks that every person's data and evidence index can be read, their folders exist, every indexed file is there with the hash recorded when it was added, and every file is in the index. A changed hash is never repaired.")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        Space::with_height(10),
+    ]
+    .spacing(5);
+
+    let Some(report) = &state.store_report else {
+        return container(content.push(text("Checking…")))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(10)
+            .style(theme::Container::Box)
+            .into();
+    };
+
+    content = content.push(
+        text(format!(
+            "{}Checked {} people and {} files at {}: {} {}",
+            if state.store_checking { "Checking again… " } else { "" },
+            report.people,
+            report.files,
+            dates.date_time(report.checked_at),
+            report.issues.len(),
+            if report.issues.len() == 1 { "issue" } else { "issues" },
+        ))
+    );
+
+    let mut entries = Column::new().spacing(2);
+    for issue in &report.issues {
+        let color = match issue.kind {
+            IssueKind::HashMismatch | IssueKind::UnreadablePerson | IssueKind::UnreadableIndex | IssueKind::FolderMismatch => Color::from_rgb(0.8, 0.0, 0.0),
+            _ => Color::from_rgb(0.9, 0.5, 0.0),
+        };
+        // Link to the person while they still exist
+        let person: Element<Message> = match issue.person_id.filter(|id| state.persons.iter().any(|p| p.id == *id)) {
+            Some(person_id) => button(text(&issue.person_name))
+                .on_press(Message::PersonSelected(person_id))
+                .style(theme::Button::Secondary)
+                .into(),
+            None => text(&issue.person_name).into(),
+        };
+
+        entries = entries.push(
+            row![
+                text(issue.kind.label())
+                    .style(theme::Text::Color(color))
+                    .width(Length::Fixed(170.0)),
+                container(person)
+                    .width(Length::Fixed(150.0)),
+                column![
+                    text(issue.path.display().to_string()),
+                    text(&issue.details)
+                        .size(12)
+                        .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+                ]
+                .width(Length::Fill),
+                text(issue.kind.repair_label().unwrap_or("Check by hand"))
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+                    .width(Length::Fixed(120.0)),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+    content = content.push(entries);
+
+    container(scrollable(content))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10)
+        .style(theme::Container::Box)
+        .into()
+}
+
+fn settings_view(state: &AppState) -> Element<'_, Message> {
+    let mut content = column![
+        row![
+            text("Settings")
+                .size(18)
+                .style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8))),
+            Space::with_width(Length::Fill),
+            button("Export Settings…")
+                .on_press(Message::ExportSettingsClicked),
+            button("Import Settings…")
+                .on_press(Message::ImportSettingsClicked),
+            button("Close")
+                .on_press(Message::ShowSettings(false)),
+        ]
+        .align_items(Alignment::Center),
+        Space::with_height(10),
+    ]
+    .spacing(5);
+
+    content = content.push(
+        column![
+            text("Operator").size(16),
+            text("Your name or initials, recorded on every activity log entry, including evidence added and exports, and filled in as \"Collected by\" in archive intake mode. Set it when several people share this computer.")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            text_input("Name or initials", &state.settings.operator)
+                .on_input(Message::OperatorChanged),
+        ]
+        .spacing(5)
+    );
+    content = content.push(Space::with_height(15));
+    content = content.push(
+        column![
+            text("Evidence Intake").size(16),
+            checkbox("Archive intake mode: record where each file came from when it's added", state.settings.intake_mode)
+                .on_toggle(Message::IntakeModeToggled),
+            checkbox("Move files into evidence instead of copying them", state.settings.move_into_evidence)
+                .on_toggle(Message::MoveIntoEvidenceToggled),
+            text(format!("Screenshots (pasted, or files named like \"Screenshot ...\") are renamed by capture time. Placeholders: {}", naming::PATTERN_HELP))
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            text_input(naming::DEFAULT_SCREENSHOT_PATTERN, &state.settings.screenshot_name_pattern)
+                .on_input(Message::ScreenshotPatternChanged),
+        ]
+        .spacing(5)
+    );
+    content = content.push(Space::with_height(15));
+    let mut report_templates = column![
+        text("Report Templates").size(16),
+        text(format!(
+            "Printed reports follow the template picked next to \"Print\". Add your own from a JSON file naming the sections in order ({}), with an optional title, header and footer using {}.",
+            reports::SECTION_NAMES,
+            reports::TEMPLATE_PLACEHOLDERS,
+        ))
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+    ]
+    .spacing(5);
+    for template in &state.settings.report_templates {
+        report_templates = report_templates.push(
+            row![
+                text(&template.name).width(Length::Fill),
+                button("Remove")
+                    .on_press(Message::RemoveReportTemplate(template.name.clone()))
+                    .style(theme::Button::Destructive),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+    report_templates = report_templates.push(
+        button("Add Template File…")
+            .on_press(Message::AddReportTemplateClicked)
+    );
+    content = content.push(report_templates);
+    content = content.push(Space::with_height(15));
+    let mut scale_steps = row![text("Interface scale")].spacing(5).align_items(Alignment::Center);
+    for step in UI_SCALE_STEPS {
+        scale_steps = scale_steps.push(
+            button(text(format!("{}%", step)))
+                .on_press(Message::UiScaleChanged(step))
+                .style(if state.settings.ui_scale() == step { theme::Button::Primary } else { theme::Button::Secondary })
+        );
+    }
+    content = content.push(
+        column![
+            text("Display").size(16),
+            scale_steps,
+            text("Ctrl + and Ctrl - (Cmd on macOS) also change the scale, and Ctrl 0 resets it. Text, thumbnails and previews all scale, for 4K monitors and projectors.")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            row![
+                text("Dates"),
+                pick_list(DateFormat::all().to_vec(), Some(state.settings.date_format), Message::DateFormatSelected),
+                text("Times"),
+                pick_list(TimeFormat::all().to_vec(), Some(state.settings.time_format), Message::TimeFormatSelected),
+                text("Show times in"),
+                pick_list(DisplayZone::all(), Some(state.settings.display_zone), Message::DisplayZoneSelected),
+                text(format!("e.g. {}", state.settings.dates().now()))
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center),
+            text("Used in lists, the timeline and printed reports. Dates typed as YYYY-MM-DD are shown this way too; CSV and JSON exports keep ISO 8601 for other programs. \"As recorded\" shows quotes at the UTC offset they were given in.")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        ]
+        .spacing(5)
+    );
+    content = content.push(Space::with_height(15));
+    let mut signing = column![
+        text("Signing").size(16),
+        checkbox("Sign exported .ema archives and printed reports", state.settings.sign_exports)
+            .on_toggle(Message::SignExportsToggled),
+        text("Signatures use an ed25519 key created for this installation. Imports check a signed archive against its signature and refuse it if anything was changed.")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+    ]
+    .spacing(5);
+    if let Some(fingerprint) = &state.signing_fingerprint {
+        signing = signing.push(
+            row![
+                text(format!("Key fingerprint: {}", fingerprint)),
+                button("Copy")
+                    .on_press(Message::CopyText(fingerprint.clone()))
+                    .style(theme::Button::Secondary),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+    content = content.push(signing);
+    content = content.push(Space::with_height(15));
+    let mut profiles = column![
+        text("Profiles").size(16),
+        text("With an admin passphrase set, the app asks at launch whether to open as Admin or as Viewer. Viewers can browse, print and export, but can't change people, evidence or settings.")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        row![
+            text_input("New admin passphrase", &state.new_admin_passphrase)
+                .secure(true)
+                .on_input(Message::NewAdminPassphraseChanged)
+                .on_submit(Message::SetAdminPassphrase),
+            button(if state.settings.admin_passphrase.is_some() { "Change Passphrase" } else { "Set Passphrase" })
+                .on_press_maybe((!state.new_admin_passphrase.trim().is_empty()).then_some(Message::SetAdminPassphrase)),
+        ]
+        .spacing(5),
+    ]
+    .spacing(5);
+    if state.settings.admin_passphrase.is_some() {
+        profiles = profiles.push(
+            button("Remove Passphrase")
+                .on_press(Message::RemoveAdminPassphrase)
+                .style(theme::Button::Destructive)
+        );
+    }
+    content = content.push(profiles);
+    content = content.push(Space::with_height(15));
+    let limit = |value: u64| if value == 0 { String::new() } else { value.to_string() };
+    content = content.push(
+        column![
+            text("Storage").size(16),
+            text("Adding evidence past these limits shows a warning and a system notification; the file is still added. Leave a field empty for no limit.")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            row![
+                text("Evidence per person (MB)").width(Length::Fixed(220.0)),
+                text_input("No limit", &limit(state.settings.person_quota_mb))
+                    .on_input(Message::PersonQuotaSizeChanged)
+                    .width(Length::Fixed(120.0)),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center),
+            row![
+                text("Files per person").width(Length::Fixed(220.0)),
+                text_input("No limit", &limit(state.settings.person_quota_files as u64))
+                    .on_input(Message::PersonQuotaFilesChanged)
+                    .width(Length::Fixed(120.0)),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center),
+            row![
+                text("Minimum free disk space (MB)").width(Length::Fixed(220.0)),
+                text_input("No minimum", &limit(state.settings.free_space_floor_mb))
+                    .on_input(Message::FreeSpaceFloorChanged)
+                    .width(Length::Fixed(120.0)),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center),
+            row![
+                text("Layout").width(Length::Fixed(220.0)),
+                pick_list(StorageLayout::all().to_vec(), Some(state.settings.storage_layout), Message::StorageLayoutSelected),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center),
+            text("The blob store keeps each file's content once, named by its SHA-256, and links it into the folder of everyone who holds it. Evidence stored before is moved into it with \"evidence-manager migrate-blobs\".")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        ]
+        .spacing(5)
+    );
+    content = content.push(Space::with_height(15));
+    content = content.push(
+        column![
+            text("Reminders").size(16),
+            checkbox("Show a system notification when a task is due", state.settings.task_notifications)
+                .on_toggle(Message::TaskNotificationsToggled),
+            checkbox("Show a system notification when an export or import finishes while the app is in the background", state.settings.job_notifications)
+                .on_toggle(Message::JobNotificationsToggled),
+        ]
+        .spacing(5)
+    );
+    content = content.push(Space::with_height(15));
+    content = content.push(
+        column![
+            text("Map").size(16),
+            text("Tile server for the map tab, with {z}, {x} and {y} placeholders. Downloaded tiles are cached for offline use.")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            text_input(map::DEFAULT_TILE_URL, &state.settings.map_tile_url)
+                .on_input(Message::MapTileUrlChanged),
+            text("Geocoding service for quote places, with a {query} placeholder. Leave empty to only use coordinates and places already in the local gazetteer; otherwise place names are sent to this service.")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            text_input(geocode::NOMINATIM_URL, &state.settings.geocoding_url)
+                .on_input(Message::GeocodingUrlChanged),
+        ]
+        .spacing(5)
+    );
+    content = content.push(Space::with_height(15));
+    content = content.push(
+        column![
+            text("Quick Capture").size(16),
+            text("A system-wide shortcut, e.g. Ctrl+Shift+E, that brings up the small quick capture window from anywhere. Takes effect the next time the app starts. Leave empty for none.")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            text_input("Shortcut", &state.settings.quick_capture_hotkey)
+                .on_input(Message::QuickCaptureHotkeyChanged),
+        ]
+        .spacing(5)
+    );
+    content = content.push(Space::with_height(15));
+    content = content.push(
+        column![
+            text("Plugins").size(16),
+            text("Rhai scripts (.rhai) in the plugins folder can act on events. on_evidence_added(file) may return #{ tags: [...], review_status: \"Flagged\", notes: \"...\" } to tag the person or mark the new file; before_export(people) may return a message to stop an export. Scripts can't read files or reach the network. New scripts start disabled.")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            plugin_list(state),
+            row![
+                button("Open Plugins Folder")
+                    .on_press(Message::OpenPluginsFolder),
+                button("Reload Plugins")
+                    .on_press(Message::ReloadPlugins),
+            ]
+            .spacing(5),
+        ]
+        .spacing(5)
+    );
+    content = content.push(Space::with_height(15));
+    content = content.push(
+        column![
+            text("Webhook").size(16),
+            text("Each person added, file added and export is posted as JSON to this URL, e.g. a chat or ticketing integration. With a secret set, the body's HMAC-SHA256 is sent in the X-Evidence-Manager-Signature header as \"sha256=<hex>\" so the receiver can check it came from here. Leave the URL empty to send nothing.")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            text_input("Webhook URL", &state.settings.webhook_url)
+                .on_input(Message::WebhookUrlChanged),
+            row![
+                text_input("Signing secret", &state.settings.webhook_secret)
+                    .secure(true)
+                    .on_input(Message::WebhookSecretChanged),
+                button("Send Test Event")
+                    .on_press_maybe((!state.settings.webhook_url.trim().is_empty()).then_some(Message::SendTestWebhook)),
+            ]
+            .spacing(5),
+        ]
+        .spacing(5)
+    );
+    content = content.push(Space::with_height(15));
+    content = content.push(
+        column![
+            text("Trusted Timestamping").size(16),
+            text("RFC 3161 timestamping authority, e.g. https://freetsa.org/tsr. When set, the SHA-256 of each file added is sent to it and the signed timestamp is kept with the file. Leave empty to turn timestamping off.")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            text_input("Timestamping authority URL", &state.settings.tsa_url)
+                .on_input(Message::TsaUrlChanged),
+        ]
+        .spacing(5)
+    );
+    content = content.push(Space::with_height(15));
+    content = content.push(
+        column![
+            text("Contact Normalization").size(16),
+            text("Phone numbers are stored in international (E.164) form and emails in lowercase next to the value as entered, so the same contact matches however it was typed")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+            row![
+                text_input("Default country calling code, e.g. 1 or 44", &state.settings.default_country_code)
+                    .on_input(Message::DefaultCountryCodeChanged),
+                button("Normalize Existing Entries")
+                    .on_press(Message::NormalizeAllInformation),
+            ]
+            .spacing(5),
+        ]
+        .spacing(5)
+    );
+    content = content.push(Space::with_height(15));
+    content = content.push(export_profiles_settings(state));
+    content = content.push(Space::with_height(15));
+    content = content.push(custom_fields_settings(state));
+    content = content.push(Space::with_height(15));
+    content = content.push(info_templates_settings(state));
+    content = content.push(Space::with_height(15));
+    content = content.push(checklist_templates_settings(state));
+    content = content.push(Space::with_height(15));
+    content = content.push(bulk_edit_settings(state));
+    content = content.push(Space::with_height(15));
+    content = content.push(categories_settings(state));
+    content = content.push(Space::with_height(15));
+    content = content.push(extension_mapping_settings(state));
+
+    container(scrollable(content))
         .width(Length::Fill)
+        .height(Length::Fill)
         .padding(10)
+        .style(theme::Container::Box)
         .into()
 }
 
-fn quotes_tab<'a>(state: &'a AppState, person: &'a Person) -> Element<'a, Message> {
-    let mut content = column![
-        text("Add Quote").size(16),
-        Space::with_height(5),
-    ];
+fn export_profiles_settings(state: &AppState) -> Element<'_, Message> {
+    let dates = state.settings.dates();
+    let draft = &state.new_export_profile;
+    let destination = if draft.destination.as_os_str().is_empty() {
+        "No folder chosen".to_string()
+    } else {
+        draft.destination.display().to_string()
+    };
+    let person_choices: Vec<PersonChoice> = state.persons
+        .iter()
+        .filter(|p| !draft.persons.contains(&p.id))
+        .map(|p| PersonChoice { id: p.id, name: p.name.clone() })
+        .collect();
+    let mut chosen_people = row![].spacing(5).align_items(Alignment::Center);
+    for person in state.persons.iter().filter(|p| draft.persons.contains(&p.id)) {
+        chosen_people = chosen_people.push(
+            button(text(format!("{} ×", person.name)))
+                .on_press(Message::NewExportProfilePersonRemoved(person.id))
+                .style(theme::Button::Secondary)
+        );
+    }
 
-    // Add quote form
-    content = content.push(
-        column![
-            text_input("Quote", &state.new_quote_text)
-                .on_input(Message::AddQuoteTextChanged),
-            text_input("Date", &state.new_quote_date)
-                .on_input(Message::AddQuoteDateChanged),
+    // Only editable once encryption is ticked
+    let mut passphrase = text_input("Passphrase", &draft.passphrase).secure(true);
+    if draft.encrypt {
+        passphrase = passphrase.on_input(Message::NewExportProfilePassphraseChanged);
+    }
+
+    let mut section = column![
+        text("Export Profiles").size(16),
+        text("Named .ema exports to run again with one click, or on a schedule while the app is open. Each run is a job, written to the profile's folder as <profile>_<date>_<time>.ema. Choose people and tags to include, or neither for everyone. Encrypted archives end in .ema.enc and split ones in .001, .002, ...; turn them back into a .ema with `evidence-manager unpack <file>` before importing.")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        row![
+            text_input("Profile name, e.g. Weekly backup", &draft.name)
+                .on_input(Message::NewExportProfileNameChanged),
+            button("Choose Folder…")
+                .on_press(Message::ChooseExportProfileDestination),
+            text(destination)
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center),
+        row![
+            pick_list(person_choices, None::<PersonChoice>, |choice| Message::NewExportProfilePersonAdded(choice.id))
+                .placeholder("Add person..."),
+            chosen_people,
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center),
+        text_input("Tags, e.g. case-42, urgent", &state.new_export_profile_tags)
+            .on_input(Message::NewExportProfileTagsChanged),
+        row![
+            checkbox("Encrypt", draft.encrypt)
+                .on_toggle(Message::NewExportProfileEncryptToggled),
+            passphrase,
+            checkbox("Thumbnails only", draft.thumbnails_only)
+                .on_toggle(Message::NewExportProfileThumbnailsToggled),
+            text_input("Split size in MB (empty for one file)", &state.new_export_profile_split)
+                .on_input(Message::NewExportProfileSplitChanged),
+            pick_list(ExportSchedule::all().to_vec(), Some(draft.schedule), Message::NewExportProfileScheduleSelected),
+            button("Add Profile")
+                .on_press(Message::AddExportProfileSubmitted)
+                .style(theme::Button::Primary),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center),
+    ]
+    .spacing(5);
+
+    if state.settings.export_profiles.is_empty() {
+        section = section.push(
+            text("No export profiles yet")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+
+    for profile in &state.settings.export_profiles {
+        let mut included: Vec<String> = state.persons
+            .iter()
+            .filter(|p| profile.persons.contains(&p.id))
+            .map(|p| p.name.clone())
+            .collect();
+        included.extend(profile.tags.iter().map(|tag| format!("#{}", tag)));
+        let mut details = vec![
+            if included.is_empty() { "Everyone".to_string() } else { included.join(", ") },
+            profile.schedule.label().to_string(),
+        ];
+        if profile.encrypt {
+            details.push("Encrypted".to_string());
+        }
+        if profile.split_size_mb > 0 {
+            details.push(format!("{} MB parts", profile.split_size_mb));
+        }
+        if profile.thumbnails_only {
+            details.push("Thumbnails only".to_string());
+        }
+        let last_run = profile.last_run
+            .map(|time| format!("Last run {}", dates.date_time(time)))
+            .unwrap_or_else(|| "Never run".to_string());
+        section = section.push(
             row![
-                text_input("Time (optional)", &state.new_quote_time)
-                    .on_input(Message::AddQuoteTimeChanged),
-                text_input("Place (optional)", &state.new_quote_place)
-                    .on_input(Message::AddQuotePlaceChanged),
+                column![
+                    text(&profile.name),
+                    text(format!("{} → {}", details.join(" · "), profile.destination.display()))
+                        .size(12)
+                        .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+                ]
+                .width(Length::FillPortion(3)),
+                text(last_run)
+                    .width(Length::FillPortion(1))
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+                button("Run Now")
+                    .on_press(Message::RunExportProfile(profile.name.clone())),
+                button("Delete")
+                    .on_press(Message::RemoveExportProfile(profile.name.clone()))
+                    .style(theme::Button::Destructive),
             ]
-            .spacing(5),
-            button("Add Quote")
-                .on_press(Message::AddQuoteSubmitted)
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+
+    section.into()
+}
+
+fn custom_fields_settings(state: &AppState) -> Element<'_, Message> {
+    let mut section = column![
+        text("Custom Fields").size(16),
+        text("Typed fields shown on every person's Information tab and included in the CSV export and printed summary")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        row![
+            text_input("Field name, e.g. Date of birth", &state.new_custom_field_name)
+                .on_input(Message::NewCustomFieldNameChanged)
+                .on_submit(Message::AddCustomFieldSubmitted),
+            pick_list(FieldType::all().to_vec(), Some(state.new_custom_field_type), Message::NewCustomFieldTypeSelected),
+            button("Add Field")
+                .on_press(Message::AddCustomFieldSubmitted)
                 .style(theme::Button::Primary),
         ]
-        .spacing(5)
-    );
+        .spacing(5),
+    ]
+    .spacing(5);
 
-    content = content.push(Space::with_height(10));
+    if state.settings.custom_fields.is_empty() {
+        section = section.push(
+            text("No custom fields yet")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
 
-    // Quotes table
-    if person.quotes.is_empty() {
-        content = content.push(
-            text("No quotes added yet")
+    for field in &state.settings.custom_fields {
+        section = section.push(
+            row![
+                text(&field.name)
+                    .width(Length::FillPortion(2)),
+                text(field.field_type.label())
+                    .width(Length::FillPortion(1)),
+                button("Delete")
+                    .on_press(Message::RemoveCustomField(field.name.clone()))
+                    .style(theme::Button::Destructive),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+
+    section.into()
+}
+
+fn info_templates_settings(state: &AppState) -> Element<'_, Message> {
+    let mut section = column![
+        text("Information Templates").size(16),
+        text("Choose a template when adding a person to start with empty information slots to fill in")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        row![
+            text_input("Template name, e.g. Standard intake", &state.new_template_name)
+                .on_input(Message::NewTemplateNameChanged),
+            text_input("Info types, e.g. Phone, Email, Address, DOB", &state.new_template_types)
+                .on_input(Message::NewTemplateTypesChanged)
+                .on_submit(Message::AddInfoTemplateSubmitted),
+            button("Add Template")
+                .on_press(Message::AddInfoTemplateSubmitted)
+                .style(theme::Button::Primary),
+        ]
+        .spacing(5),
+    ]
+    .spacing(5);
+
+    if state.settings.info_templates.is_empty() {
+        section = section.push(
+            text("No templates yet")
                 .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
         );
-    } else {
-        content = content.push(
-            text("Quotes")
-                .size(14)
-                .style(theme::Text::Color(Color::from_rgb(0.2, 0.2, 0.8)))
+    }
+
+    for template in &state.settings.info_templates {
+        section = section.push(
+            row![
+                text(&template.name)
+                    .width(Length::FillPortion(1)),
+                text(template.info_types.join(", "))
+                    .width(Length::FillPortion(3)),
+                button("Delete")
+                    .on_press(Message::RemoveInfoTemplate(template.name.clone()))
+                    .style(theme::Button::Destructive),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+
+    if !state.settings.info_templates.is_empty() {
+        section = section.push(
+            row![
+                text("Measure completeness against"),
+                pick_list(
+                    state.settings.info_templates.iter().map(|t| t.name.clone()).collect::<Vec<_>>(),
+                    state.settings.completeness_template().map(|t| t.name.clone()),
+                    Message::CompletenessTemplateSelected,
+                )
+                .placeholder("No template"),
+                button("Clear")
+                    .on_press_maybe(
+                        state.settings.completeness_template()
+                            .is_some()
+                            .then(|| Message::CompletenessTemplateSelected(String::new()))
+                    )
+                    .style(theme::Button::Secondary),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
         );
+    }
 
-        let mut quote_list = Column::new().spacing(2);
-        for quote in &person.quotes {
-            quote_list = quote_list.push(
+    section.into()
+}
+
+fn checklist_templates_settings(state: &AppState) -> Element<'_, Message> {
+    let mut section = column![
+        text("Checklist Templates").size(16),
+        text("Interview or intake questions that can be attached to a person from their Tasks tab and answered there")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        row![
+            text_input("Template name, e.g. Witness interview", &state.new_checklist_template_name)
+                .on_input(Message::NewChecklistTemplateNameChanged),
+            text_input("Questions separated by semicolons, e.g. Where were you?; Who else was there?", &state.new_checklist_template_questions)
+                .on_input(Message::NewChecklistTemplateQuestionsChanged)
+                .on_submit(Message::AddChecklistTemplateSubmitted),
+            button("Add Template")
+                .on_press(Message::AddChecklistTemplateSubmitted)
+                .style(theme::Button::Primary),
+        ]
+        .spacing(5),
+    ]
+    .spacing(5);
+
+    if state.settings.checklist_templates.is_empty() {
+        section = section.push(
+            text("No templates yet")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+
+    for template in &state.settings.checklist_templates {
+        section = section.push(
+            row![
+                text(&template.name)
+                    .width(Length::FillPortion(1)),
+                text(template.questions.join("; "))
+                    .width(Length::FillPortion(3)),
+                button("Delete")
+                    .on_press(Message::RemoveChecklistTemplate(template.name.clone()))
+                    .style(theme::Button::Destructive),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+
+    section.into()
+}
+
+fn bulk_edit_settings(state: &AppState) -> Element<'_, Message> {
+    let bulk_edit = &state.bulk_edit;
+    let changes = bulk_edit.preview(&state.persons);
+
+    let mut section = column![
+        text("Bulk Edit Information").size(16),
+        text("Find and replace across every person's information, e.g. to re-type \"ph.\" as \"Phone\". Review the preview before applying; every change is recorded in the audit log.")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        row![
+            pick_list(BulkEditField::all().to_vec(), Some(bulk_edit.field), Message::BulkEditFieldChanged),
+            text_input("Find", &bulk_edit.find)
+                .on_input(Message::BulkEditFindChanged),
+            text_input("Replace with", &bulk_edit.replace)
+                .on_input(Message::BulkEditReplaceChanged),
+            checkbox("Whole field", bulk_edit.whole_value)
+                .on_toggle(Message::BulkEditWholeValueToggled),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center),
+    ]
+    .spacing(5);
+
+    if !bulk_edit.find.is_empty() {
+        if changes.is_empty() {
+            section = section.push(
+                text("No entries match")
+                    .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+            );
+        }
+        let mut preview = Column::new().spacing(2);
+        for change in &changes {
+            let person_name = state.persons.iter()
+                .find(|p| p.id == change.person_id)
+                .map(|p| p.name.as_str())
+                .unwrap_or_default();
+            preview = preview.push(
                 row![
-                    text(&quote.quote)
-                        .width(Length::FillPortion(2)),
-                    text(&quote.date)
-                        .width(Length::FillPortion(1)),
-                    text(quote.time.as_deref().unwrap_or("-"))
-                        .width(Length::FillPortion(1)),
-                    text(quote.place.as_deref().unwrap_or("-"))
-                        .width(Length::FillPortion(1)),
-                    button("Delete")
-                        .on_press(Message::RemoveQuote(quote.id))
-                        .style(theme::Button::Destructive),
+                    text(person_name).width(Length::FillPortion(1)),
+                    text(&change.before).width(Length::FillPortion(2)),
+                    text("→"),
+                    text(&change.after).width(Length::FillPortion(2)),
                 ]
                 .spacing(5)
-                .align_items(Alignment::Center)
             );
         }
-        
-        content = content.push(
-            scrollable(quote_list)
-                .height(Length::Fixed(300.0))
+        section = section.push(scrollable(preview).height(Length::Fixed(150.0)));
+        section = section.push(
+            button(text(format!("Apply to {} entries", changes.len())))
+                .on_press_maybe((!changes.is_empty()).then_some(Message::ApplyBulkEdit))
+                .style(theme::Button::Primary)
         );
     }
 
-    container(content)
-        .width(Length::Fill)
-        .padding(10)
-        .into()
+    section.into()
+}
+
+fn categories_settings(state: &AppState) -> Element<'_, Message> {
+    let mut section = column![
+        text("Evidence Categories").size(16),
+        text("Custom categories get their own tab and folder in each person's evidence")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        row![
+            text_input("Name", &state.new_category_name)
+                .on_input(Message::NewCategoryNameChanged),
+            text_input("Folder (optional)", &state.new_category_folder)
+                .on_input(Message::NewCategoryFolderChanged),
+            text_input("Icon", &state.new_category_icon)
+                .on_input(Message::NewCategoryIconChanged)
+                .width(Length::Fixed(60.0)),
+            text_input("Extensions, e.g. csv, xlsx", &state.new_category_extensions)
+                .on_input(Message::NewCategoryExtensionsChanged),
+            button("Add Category")
+                .on_press(Message::AddCategorySubmitted)
+                .style(theme::Button::Primary),
+        ]
+        .spacing(5),
+    ]
+    .spacing(5);
+
+    if state.settings.custom_categories.is_empty() {
+        section = section.push(
+            text("No custom categories yet")
+                .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5)))
+        );
+    }
+
+    for category in &state.settings.custom_categories {
+        section = section.push(
+            row![
+                text(&category.icon),
+                text(&category.name)
+                    .width(Length::FillPortion(2)),
+                text(format!("{}/", category.folder_name))
+                    .width(Length::FillPortion(2)),
+                text(category.extensions.join(", "))
+                    .width(Length::FillPortion(2)),
+                button("Delete")
+                    .on_press(Message::RemoveCategory(category.folder_name.clone()))
+                    .style(theme::Button::Destructive),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+
+    section.into()
+}
+
+/// An evidence category offered in a pick list, shown by its display name
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryChoice {
+    pub evidence_type: EvidenceType,
+    pub name: String,
+}
+
+impl std::fmt::Display for CategoryChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+fn category_choices(settings: &Settings) -> Vec<CategoryChoice> {
+    settings.file_categories()
+        .into_iter()
+        .map(|evidence_type| CategoryChoice {
+            name: settings.display_name(&evidence_type),
+            evidence_type,
+        })
+        .collect()
+}
+
+fn extension_mapping_settings(state: &AppState) -> Element<'_, Message> {
+    let choices = category_choices(&state.settings);
+    let selected = choices.iter()
+        .find(|c| Some(&c.evidence_type) == state.new_mapping_type.as_ref())
+        .cloned();
+
+    let mut section = column![
+        text("File Type Mapping").size(16),
+        text("Choose which category files with a given extension are stored in")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        row![
+            text_input("Extension, e.g. heic", &state.new_mapping_extension)
+                .on_input(Message::NewMappingExtensionChanged),
+            pick_list(choices, selected, |choice| Message::NewMappingTypeSelected(choice.evidence_type))
+                .placeholder("Category"),
+            button("Add Mapping")
+                .on_press(Message::AddMappingSubmitted)
+                .style(theme::Button::Primary),
+        ]
+        .spacing(5),
+    ]
+    .spacing(5);
+
+    for (extension, evidence_type) in &state.settings.extension_mappings {
+        section = section.push(
+            row![
+                text(format!(".{}", extension))
+                    .width(Length::FillPortion(1)),
+                text(state.settings.display_name(evidence_type))
+                    .width(Length::FillPortion(2)),
+                button("Delete")
+                    .on_press(Message::RemoveMapping(extension.clone()))
+                    .style(theme::Button::Destructive),
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center)
+        );
+    }
+
+    section.into()
 }
 
 // Modal dialogs
@@ -458,11 +4922,20 @@ pub fn add_person_dialog(state: &AppState) -> Option<Element<'_, Message>> {
                 Space::with_height(10),
                 text_input("Name", &state.new_person_name)
                     .on_input(Message::AddPersonNameChanged),
+                pick_list(
+                    state.settings.info_templates.clone(),
+                    state.new_person_template.clone(),
+                    Message::AddPersonTemplateSelected,
+                )
+                .placeholder("Information template (optional)"),
                 Space::with_height(10),
                 row![
                     button("Cancel")
                         .on_press(Message::ShowAddPersonDialog(false)),
                     Space::with_width(Length::Fill),
+                    button("Add Several...")
+                        .on_press(Message::AddSeveralPersonsClicked)
+                        .style(theme::Button::Secondary),
                     button("Add")
                         .on_press(Message::AddPersonSubmitted)
                         .style(theme::Button::Primary),
@@ -475,4 +4948,123 @@ pub fn add_person_dialog(state: &AppState) -> Option<Element<'_, Message>> {
         .style(theme::Container::Box)
         .into()
     )
-}
\ No newline at end of file
+}
+
+fn bulk_add_dialog<'a>(state: &'a AppState, dialog: &'a BulkAddDialog) -> Element<'a, Message> {
+    let to_add = dialog.to_add().len();
+    let existing = dialog.names.iter().filter(|listed| listed.status == NameStatus::Existing).count();
+
+    let mut content = column![
+        text("Add Several People").size(18),
+        text("Paste one name per line. Each person gets their own folder, as when added one at a time")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        text_editor(&dialog.content)
+            .on_action(Message::BulkAddEdited)
+            .height(Length::Fixed(200.0)),
+        row![
+            pick_list(
+                state.settings.info_templates.clone(),
+                dialog.template.clone(),
+                Message::BulkAddTemplateSelected,
+            )
+            .placeholder("Information template (optional)"),
+            checkbox(format!("Also add the {} name(s) already in the case", existing), dialog.add_existing)
+                .on_toggle(Message::BulkAddExistingToggled),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+        text(format!("{} names, {} to add", dialog.names.len(), to_add))
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+    ]
+    .spacing(5);
+
+    let mut preview = Column::new().spacing(2);
+    for listed in &dialog.names {
+        let (note, color) = match listed.status {
+            NameStatus::New => ("New", Color::from_rgb(0.1, 0.65, 0.2)),
+            NameStatus::Existing if dialog.add_existing => ("Already in the case, added again", Color::from_rgb(0.8, 0.5, 0.1)),
+            NameStatus::Existing => ("Already in the case, skipped", Color::from_rgb(0.8, 0.2, 0.2)),
+            NameStatus::Repeated => ("Listed earlier, skipped", Color::from_rgb(0.8, 0.2, 0.2)),
+        };
+        preview = preview.push(
+            row![
+                text(listed.number.to_string())
+                    .width(Length::Fixed(30.0)),
+                text(&listed.name)
+                    .width(Length::FillPortion(3)),
+                text(note)
+                    .width(Length::FillPortion(2))
+                    .style(theme::Text::Color(color)),
+            ]
+            .spacing(5)
+        );
+    }
+    content = content.push(
+        scrollable(preview)
+            .height(Length::Fixed(250.0))
+    );
+
+    content = content.push(
+        row![
+            button("Cancel")
+                .on_press(Message::BulkAddCancelled),
+            Space::with_width(Length::Fill),
+            button(text(format!("Add {} People", to_add)))
+                .on_press_maybe((to_add > 0).then_some(Message::AddPersonsBulk))
+                .style(theme::Button::Primary),
+        ]
+        .spacing(10)
+    );
+
+    container(content)
+        .padding(20)
+        .style(theme::Container::Box)
+        .into()
+}
+
+fn lineup_dialog<'a>(state: &'a AppState, dialog: &'a LineupDialog) -> Element<'a, Message> {
+    let mut content = column![
+        text("Photo Lineup").size(18),
+        text("A numbered sheet with one photo of each ticked person: their earliest image evidence. Save it as .pdf or .png. The numbers and names are recorded in the activity log.")
+            .style(theme::Text::Color(Color::from_rgb(0.5, 0.5, 0.5))),
+        row![
+            pick_list(LineupOrder::all().to_vec(), Some(dialog.order), Message::LineupOrderSelected),
+            checkbox("Print names under the numbers", dialog.show_names)
+                .on_toggle(Message::LineupShowNamesToggled),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+    ]
+    .spacing(5);
+
+    let mut people = Column::new().spacing(2);
+    for person in state.filtered_persons.iter().filter_map(|id| state.persons.iter().find(|p| p.id == *id)) {
+        let person_id = person.id;
+        people = people.push(
+            checkbox(person.name.clone(), dialog.persons.contains(&person_id))
+                .on_toggle(move |included| Message::LineupPersonToggled(person_id, included))
+        );
+    }
+    content = content.push(
+        scrollable(people)
+            .height(Length::Fixed(250.0))
+    );
+
+    let count = dialog.persons.len();
+    content = content.push(
+        row![
+            button("Cancel")
+                .on_press(Message::LineupCancelled),
+            Space::with_width(Length::Fill),
+            button(text(format!("Export Lineup of {}…", count)))
+                .on_press_maybe((count > 0).then_some(Message::LineupSaveClicked))
+                .style(theme::Button::Primary),
+        ]
+        .spacing(10)
+    );
+
+    container(content)
+        .padding(20)
+        .style(theme::Container::Box)
+        .into()
+}
@@ -0,0 +1,89 @@
+use anyhow::{Result, Context, bail};
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+const CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+pub fn sha256_hex(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// Hashes a file without reading it into memory, for archives that may be large
+pub fn sha256_file(path: &Path) -> Result<String> {
+    Ok(to_hex(&sha256_file_digest(path)?))
+}
+
+/// As `sha256_file`, returning the raw digest
+pub fn sha256_file_digest(path: &Path) -> Result<[u8; 32]> {
+    let mut file = fs::File::open(path)
+        .context("Failed to open file for hashing")?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)
+        .context("Failed to read file for hashing")?;
+    Ok(hasher.finalize().into())
+}
+
+/// Copies everything from `reader` to `writer` in chunks, hashing it on the way. `progress` is
+/// called with the bytes copied so far after each chunk. Returns the hash of what was copied.
+pub fn copy_hashing(reader: &mut impl Read, writer: &mut impl Write, mut progress: impl FnMut(u64)) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut copied = 0u64;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        hasher.update(&buffer[..read]);
+        writer.write_all(&buffer[..read])?;
+        copied += read as u64;
+        progress(copied);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Checks a file against a hex digest written by another tool, e.g. a forensic collection
+/// tool's file list. MD5, SHA-1 or SHA-256 is picked by the digest's length, and a prefix such
+/// as "MD5:" is ignored. Returns the algorithm's name when the file matches.
+pub fn verify_file_digest(path: &Path, expected: &str) -> Result<&'static str> {
+    let expected = expected.trim().rsplit([':', '=']).next().unwrap_or_default().trim().to_lowercase();
+    let (algorithm, actual) = match expected.len() {
+        32 => ("MD5", file_digest::<Md5>(path)?),
+        40 => ("SHA-1", file_digest::<Sha1>(path)?),
+        64 => ("SHA-256", sha256_file(path)?),
+        _ => bail!("\"{}\" isn't an MD5, SHA-1 or SHA-256 hash", expected),
+    };
+    if actual != expected {
+        bail!("{} is {}, not {} as listed", algorithm, actual, expected);
+    }
+    Ok(algorithm)
+}
+
+fn file_digest<D: Digest + Write>(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path)
+        .context("Failed to open file for hashing")?;
+    let mut hasher = D::new();
+    io::copy(&mut file, &mut hasher)
+        .context("Failed to read file for hashing")?;
+    Ok(to_hex(&hasher.finalize()))
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn from_hex(text: &str) -> Result<Vec<u8>> {
+    if !text.len().is_multiple_of(2) || !text.is_ascii() {
+        bail!("Invalid hex");
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).context("Invalid hex"))
+        .collect()
+}
@@ -0,0 +1,198 @@
+use crate::paths;
+use anyhow::{Result, Context, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const JOURNAL_FILE: &str = "import_journal.jsonl";
+const STAGING_DIR: &str = ".import_staging";
+const BACKUP_DIR: &str = ".import_backup";
+
+/// One line of the journal file
+#[derive(Serialize, Deserialize)]
+enum JournalLine {
+    Started {
+        archive: PathBuf,
+        archive_sha256: String,
+        started_at: DateTime<Utc>,
+        total: usize,
+    },
+    // Written before a staged file is moved into place, so it can be rolled back
+    Writing {
+        entry: usize,
+        created: Option<PathBuf>, // Relative to the Evidence folder, when the file didn't exist before
+        #[serde(default)]
+        replaced: Option<PathBuf>, // Relative to the Evidence folder, when the file existed and is backed up
+    },
+    // The entry is extracted into the staging folder
+    Done {
+        entry: usize,
+    },
+}
+
+/// Record of an import in progress, kept next to the evidence until the import finishes or is
+/// rolled back. One left behind at startup means the app stopped mid-import.
+#[derive(Debug, Clone)]
+pub struct ImportJournal {
+    path: PathBuf,
+    pub archive: PathBuf,
+    pub archive_sha256: String,
+    pub started_at: DateTime<Utc>,
+    pub total: usize, // Files in the archive
+    pub completed: HashSet<usize>, // Archive entries extracted into the staging folder
+    pub created: Vec<PathBuf>, // Files the import added to the Evidence folder, relative to it
+    pub replaced: Vec<PathBuf>, // Files the import overwrote, relative to it, with their old version in the backup folder
+}
+
+impl ImportJournal {
+    /// The journal of an interrupted import, if there is one
+    pub fn load(evidence_dir: &Path) -> Result<Option<Self>> {
+        let path = evidence_dir.join(JOURNAL_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .context("Failed to read import journal")?;
+        let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+        let Some(Ok(JournalLine::Started { archive, archive_sha256, started_at, total })) = lines.next().map(serde_json::from_str) else {
+            bail!("The import journal is damaged");
+        };
+
+        let mut journal = Self {
+            path,
+            archive,
+            archive_sha256,
+            started_at,
+            total,
+            completed: HashSet::new(),
+            created: Vec::new(),
+            replaced: Vec::new(),
+        };
+        for line in lines {
+            // The last line may have been cut short by the crash
+            match serde_json::from_str(line) {
+                Ok(JournalLine::Writing { created, replaced, .. }) => {
+                    journal.created.extend(created);
+                    journal.replaced.extend(replaced);
+                }
+                Ok(JournalLine::Done { entry }) => {
+                    journal.completed.insert(entry);
+                }
+                _ => {}
+            }
+        }
+        Ok(Some(journal))
+    }
+
+    /// Starts a new journal for importing `archive`
+    pub fn begin(evidence_dir: &Path, archive: &Path, archive_sha256: String, total: usize) -> Result<Self> {
+        let journal = Self {
+            path: evidence_dir.join(JOURNAL_FILE),
+            archive: archive.to_path_buf(),
+            archive_sha256,
+            started_at: Utc::now(),
+            total,
+            completed: HashSet::new(),
+            created: Vec::new(),
+            replaced: Vec::new(),
+        };
+        let header = serde_json::to_string(&JournalLine::Started {
+            archive: journal.archive.clone(),
+            archive_sha256: journal.archive_sha256.clone(),
+            started_at: journal.started_at,
+            total,
+        })
+        .context("Failed to serialize import journal")?;
+        fs::write(&journal.path, format!("{}\n", header))
+            .context("Failed to write import journal")?;
+        Ok(journal)
+    }
+
+    /// Where the archive is extracted before its files are moved into the Evidence folder
+    pub fn staging_dir(&self) -> PathBuf {
+        self.path.with_file_name(STAGING_DIR)
+    }
+
+    /// Where files the import overwrites are kept until it finishes, so a roll back can restore them
+    pub fn backup_dir(&self) -> PathBuf {
+        self.path.with_file_name(BACKUP_DIR)
+    }
+
+    pub fn writing(&mut self, entry: usize, created: Option<&Path>, replaced: Option<&Path>) -> Result<()> {
+        if let Some(created) = created {
+            self.created.push(created.to_path_buf());
+        }
+        if let Some(replaced) = replaced {
+            self.replaced.push(replaced.to_path_buf());
+        }
+        self.append(&JournalLine::Writing {
+            entry,
+            created: created.map(Path::to_path_buf),
+            replaced: replaced.map(Path::to_path_buf),
+        })
+    }
+
+    pub fn done(&mut self, entry: usize) -> Result<()> {
+        self.completed.insert(entry);
+        self.append(&JournalLine::Done { entry })
+    }
+
+    fn append(&self, line: &JournalLine) -> Result<()> {
+        let line = serde_json::to_string(line)
+            .context("Failed to serialize import journal")?;
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(&self.path)
+            .context("Failed to open import journal")?;
+        writeln!(file, "{}", line)
+            .context("Failed to write import journal")?;
+        Ok(())
+    }
+
+    /// The import finished; its files stay. Whatever is left in the staging and backup folders goes.
+    pub fn finish(self) -> Result<()> {
+        let staging_dir = self.staging_dir();
+        if staging_dir.exists() {
+            fs::remove_dir_all(&staging_dir)
+                .context("Failed to remove import staging folder")?;
+        }
+        let backup_dir = self.backup_dir();
+        if backup_dir.exists() {
+            fs::remove_dir_all(&backup_dir)
+                .context("Failed to remove import backup folder")?;
+        }
+        fs::remove_file(&self.path)
+            .context("Failed to remove import journal")
+    }
+
+    /// Removes the files the import added, newest first, and puts back the ones it overwrote from
+    /// the backup folder, then removes the staging folder and the journal. Returns how many files
+    /// were removed or restored.
+    pub fn roll_back(self, evidence_dir: &Path) -> Result<usize> {
+        let mut undone = 0;
+        for relative in self.created.iter().rev() {
+            let path = paths::join_within(evidence_dir, relative)?;
+            if fs::remove_file(&path).is_ok() {
+                undone += 1;
+            }
+        }
+        // A file whose backup isn't there was never moved aside, so it's still the original
+        let backup_dir = self.backup_dir();
+        for relative in self.replaced.iter().rev() {
+            let backup = paths::join_within(&backup_dir, relative)?;
+            if !backup.exists() {
+                continue;
+            }
+            let path = paths::join_within(evidence_dir, relative)?;
+            fs::rename(&backup, &path)
+                .with_context(|| format!("Failed to restore {}", relative.display()))?;
+            undone += 1;
+        }
+        self.finish()?;
+        Ok(undone)
+    }
+}
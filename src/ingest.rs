@@ -0,0 +1,192 @@
+use crate::audit::{AuditAction, AuditLog};
+use crate::dates::UtcOffset;
+use crate::file_manager::{FileManager, Intake, IntakeContext};
+use crate::models::{EvidenceType, Person, Provenance};
+use crate::plugins::Plugins;
+use crate::profiles;
+use crate::settings::Settings;
+use crate::webhook::{self, Webhook};
+use anyhow::{Result, Context, bail};
+use chrono::Local;
+use serde::Deserialize;
+use serde_json::json;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// One line of input: a file or a quote for an existing person
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IngestRequest {
+    person: Uuid,
+    #[serde(default)]
+    file: Option<PathBuf>,
+    #[serde(default)]
+    category: Option<String>, // Folder name, e.g. "images"; by file type when absent
+    #[serde(default)]
+    quote: Option<IngestQuote>,
+    // Recorded as the file's provenance
+    #[serde(default)]
+    source_url: Option<String>,
+    #[serde(default)]
+    source_device: Option<String>,
+    #[serde(default)]
+    provided_by: Option<String>,
+    #[serde(default)]
+    obtained_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IngestQuote {
+    text: String,
+    #[serde(default)]
+    date: Option<String>, // Today when absent
+    #[serde(default)]
+    time: Option<String>,
+    #[serde(default)]
+    place: Option<String>,
+    #[serde(default)]
+    source: Option<String>,
+}
+
+/// `evidence-manager ingest [file]`: reads JSON lines from the file or stdin and adds each to the
+/// store, writing one JSON result line per input line. Returns the process exit code.
+pub fn run(input: Option<String>) -> i32 {
+    match ingest(input) {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(e) => {
+            eprintln!("ingest: {:#}", e);
+            2
+        }
+    }
+}
+
+/// Whether every line went in
+fn ingest(input: Option<String>) -> Result<bool> {
+    let settings = Settings::load().context("Failed to load settings")?;
+    profiles::require_admin(settings.admin_passphrase.as_ref())?;
+
+    let mut file_manager = FileManager::new()?;
+    file_manager.set_storage_layout(settings.storage_layout);
+    let mut audit_log = AuditLog::new(file_manager.get_evidence_dir());
+    audit_log.set_operator(&settings.operator);
+    audit_log.set_webhook(Webhook::new(&settings.webhook_url, &settings.webhook_secret));
+    let (plugins, errors) = Plugins::load(&settings);
+    for error in errors {
+        eprintln!("ingest: plugin not loaded: {}", error);
+    }
+    let mut ingestor = Ingestor {
+        persons: file_manager.load_all_persons()?,
+        plugins,
+        file_manager,
+        audit_log,
+        settings,
+    };
+
+    let reader: Box<dyn BufRead> = match input.as_deref() {
+        None | Some("-") => Box::new(BufReader::new(io::stdin())),
+        Some(path) => Box::new(BufReader::new(
+            File::open(path).with_context(|| format!("Failed to open {}", path))?
+        )),
+    };
+
+    let mut stdout = io::stdout();
+    let mut all_ok = true;
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.context("Failed to read input")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let result = serde_json::from_str::<IngestRequest>(&line)
+            .context("Invalid request")
+            .and_then(|request| ingestor.add(request));
+        let output = match result {
+            Ok(added) => json!({ "line": index + 1, "ok": true, "id": added.id, "added": added.details, "warnings": added.warnings }),
+            Err(e) => {
+                all_ok = false;
+                json!({ "line": index + 1, "ok": false, "error": format!("{:#}", e) })
+            }
+        };
+        writeln!(stdout, "{}", output).context("Failed to write output")?;
+    }
+    webhook::wait_for_deliveries();
+    Ok(all_ok)
+}
+
+struct Added {
+    id: Uuid, // The evidence file or quote
+    details: String,
+    warnings: Vec<String>,
+}
+
+struct Ingestor {
+    file_manager: FileManager,
+    audit_log: AuditLog,
+    settings: Settings,
+    plugins: Plugins,
+    persons: Vec<Person>,
+}
+
+impl Ingestor {
+    fn add(&mut self, request: IngestRequest) -> Result<Added> {
+        let Some(person) = self.persons.iter_mut().find(|p| p.id == request.person) else {
+            bail!("No person with ID {}", request.person);
+        };
+
+        match (request.file, request.quote) {
+            (Some(path), None) => {
+                if !path.is_file() {
+                    bail!("{} isn't a file", path.display());
+                }
+                let evidence_type = match &request.category {
+                    Some(folder_name) => self.settings.file_categories()
+                        .into_iter()
+                        .find(|t| t.folder_name() == folder_name)
+                        .with_context(|| format!("Unknown category \"{}\"", folder_name))?,
+                    None => self.settings.evidence_type_for_file(&path).unwrap_or(EvidenceType::Other),
+                };
+                let provenance = Provenance {
+                    source_device: request.source_device,
+                    source_url: request.source_url,
+                    provided_by: request.provided_by,
+                    obtained_at: request.obtained_at,
+                    collected_by: Some(self.settings.operator.trim().to_string()).filter(|name| !name.is_empty()),
+                    ..Provenance::default()
+                };
+                let context = IntakeContext { audit_log: &self.audit_log, plugins: &self.plugins, settings: &self.settings };
+                let file_manager = &self.file_manager;
+                let intake = file_manager.intake(&context, person, &path, evidence_type.clone(), " via ingest", |person| {
+                    file_manager.intake_file(person, &path, evidence_type, provenance, false, None)
+                })?;
+                match intake {
+                    Intake::Added { evidence_file, storage_warnings, warnings } => {
+                        let details = format!("{} ({})", evidence_file.original_name, evidence_file.file_type.folder_name());
+                        Ok(Added { id: evidence_file.id, details, warnings: storage_warnings.into_iter().chain(warnings).collect() })
+                    }
+                    // Held like a dropped file would be, to be accepted in the app
+                    Intake::Quarantined(held) => {
+                        bail!("{} looks like a {} and was quarantined; accept it in the app to add it", held.original_name, held.reason.to_lowercase());
+                    }
+                }
+            }
+            (None, Some(quote)) => {
+                if quote.text.trim().is_empty() {
+                    bail!("The quote is empty");
+                }
+                let date = quote.date.unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string());
+                let details = format!("\"{}\" ({})", quote.text, date);
+                // Taken as this computer's time, like the default date
+                let quote = person.add_quote(quote.text, date, quote.time, quote.place, quote.source, Vec::new());
+                quote.utc_offset = Some(UtcOffset::system_on(&quote.date, quote.time.as_deref()));
+                self.file_manager.save_person_data(person)?;
+                self.audit_log.record(Some(person), AuditAction::QuoteAdded, format!("{} via ingest", details))?;
+                let id = person.quotes.last().map(|q| q.id).unwrap_or_default();
+                Ok(Added { id, details, warnings: Vec::new() })
+            }
+            _ => bail!("Give either \"file\" or \"quote\""),
+        }
+    }
+}
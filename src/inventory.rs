@@ -0,0 +1,155 @@
+use crate::file_manager::FileManager;
+use crate::hashing;
+use crate::models::{EvidenceFile, Person, Provenance};
+use crate::settings::Settings;
+use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fs;
+use uuid::Uuid;
+
+/// Identifies the format to tools reading the file
+pub const SCHEMA: &str = "evidence-manager/inventory";
+
+/// Raised when a field is removed or changes meaning; new fields don't raise it
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A case as plain JSON for other tools to read, unlike the printed reports which are meant
+/// for people: the persons, their information and quotes, and every evidence file with its hash
+#[derive(Debug, Serialize)]
+pub struct Inventory {
+    pub schema: &'static str,
+    pub schema_version: u32,
+    pub generated_at: DateTime<Utc>,
+    pub persons: Vec<InventoryPerson>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InventoryPerson {
+    pub id: Uuid,
+    pub name: String,
+    pub role: String,
+    pub case_status: String,
+    pub priority: String,
+    pub tags: Vec<String>,
+    pub information: Vec<InventoryInfo>,
+    pub quotes: Vec<InventoryQuote>,
+    pub evidence: Vec<InventoryEvidence>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InventoryInfo {
+    pub info_type: String,
+    pub value: String,
+    pub normalized: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InventoryQuote {
+    pub id: Uuid,
+    pub text: String,
+    pub date: String,
+    pub time: Option<String>,
+    pub utc_offset: Option<String>, // Zone the date and time were given in, as "UTC+02:00"
+    pub place: Option<String>,
+    pub source: Option<String>,
+    pub linked_evidence: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InventoryEvidence {
+    pub id: Uuid,
+    pub path: String, // Relative to the evidence folder, with forward slashes
+    pub original_name: String,
+    pub category: String,
+    pub size: u64,
+    pub sha256: String,
+    pub intake_date: DateTime<Utc>,
+    pub evidence_date: Option<DateTime<Utc>>,
+    pub review_status: String,
+    pub previous_version: Option<Uuid>,
+    pub provenance: Option<Provenance>,
+}
+
+impl Inventory {
+    /// Hashes each file from disk rather than trusting the index, so the inventory matches what's stored
+    pub fn build(file_manager: &FileManager, settings: &Settings, evidence: &[(Person, Vec<EvidenceFile>)]) -> Result<Self> {
+        let evidence_dir = file_manager.get_evidence_dir();
+        let mut persons = Vec::new();
+        for (person, files) in evidence {
+            let mut inventory_evidence = Vec::new();
+            // Entries whose file is gone aren't stored evidence any more
+            for file in files.iter().filter(|f| f.file_path.exists()) {
+                // Streamed, so large videos don't have to fit in memory
+                let real_path = file_manager.resolve_evidence(&file.file_path)
+                    .with_context(|| format!("Failed to read {}", file.file_path.display()))?;
+                let size = fs::metadata(&real_path)
+                    .with_context(|| format!("Failed to read {}", file.file_path.display()))?
+                    .len();
+                let sha256 = hashing::sha256_file(&real_path)
+                    .with_context(|| format!("Failed to hash {}", file.file_path.display()))?;
+                inventory_evidence.push(InventoryEvidence {
+                    id: file.id,
+                    path: file.file_path.strip_prefix(evidence_dir)
+                        .unwrap_or(&file.file_path)
+                        .to_string_lossy()
+                        .replace('\\', "/"),
+                    original_name: file.original_name.clone(),
+                    category: settings.display_name(&file.file_type),
+                    size,
+                    sha256,
+                    intake_date: file.created_at,
+                    evidence_date: file.evidence_date,
+                    review_status: file.review_status.label().to_string(),
+                    previous_version: file.previous_version,
+                    provenance: file.provenance.clone(),
+                });
+            }
+
+            persons.push(InventoryPerson {
+                id: person.id,
+                name: person.name.clone(),
+                role: person.role.label().to_string(),
+                case_status: person.case_status.label().to_string(),
+                priority: person.priority.label().to_string(),
+                tags: person.tags.clone(),
+                information: person.information.iter()
+                    .map(|info| InventoryInfo {
+                        info_type: info.info_type.clone(),
+                        value: info.value.clone(),
+                        normalized: info.normalized.clone(),
+                    })
+                    .collect(),
+                quotes: person.quotes.iter()
+                    .map(|quote| InventoryQuote {
+                        id: quote.id,
+                        text: quote.quote.clone(),
+                        date: quote.date.clone(),
+                        time: quote.time.clone(),
+                        utc_offset: quote.utc_offset.map(|offset| offset.to_string()),
+                        place: quote.place.clone(),
+                        source: quote.source.clone(),
+                        linked_evidence: quote.linked_evidence.clone(),
+                    })
+                    .collect(),
+                evidence: inventory_evidence,
+            });
+        }
+
+        Ok(Self {
+            schema: SCHEMA,
+            schema_version: SCHEMA_VERSION,
+            generated_at: Utc::now(),
+            persons,
+        })
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .context("Failed to serialize inventory")
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.persons.iter().map(|person| person.evidence.len()).sum()
+    }
+}
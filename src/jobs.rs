@@ -0,0 +1,207 @@
+use crate::export_import::ExportOptions;
+use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use uuid::Uuid;
+
+// Finished jobs kept for the Jobs panel; older ones are dropped when the queue is saved
+const FINISHED_JOBS_KEPT: usize = 50;
+
+/// Long-running work done in the background, one job at a time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum JobKind {
+    ExportArchive {
+        path: PathBuf,
+        persons: Vec<Uuid>,
+        options: ExportOptions,
+    },
+    ExportSelection {
+        path: PathBuf,
+        person: Uuid,
+        files: Vec<Uuid>,
+        options: ExportOptions,
+        include_manifest: bool,
+    },
+    ImportArchive {
+        path: PathBuf,
+    },
+    ExportProfile {
+        name: String, // Looked up in settings when the job starts, so the passphrase isn't kept in the queue
+    },
+}
+
+impl JobKind {
+    pub fn label(&self) -> String {
+        match self {
+            JobKind::ExportArchive { path, persons, .. } => {
+                format!("Export {} {} to {}", persons.len(), if persons.len() == 1 { "person" } else { "people" }, file_name(path))
+            }
+            JobKind::ExportSelection { path, files, .. } => format!("Export {} files to {}", files.len(), file_name(path)),
+            JobKind::ImportArchive { path } => format!("Import {}", file_name(path)),
+            JobKind::ExportProfile { name } => format!("Export profile \"{}\"", name),
+        }
+    }
+
+    /// Imports change the store and export profiles change settings, so only the Admin profile
+    /// can queue them; plain exports are open to viewers too
+    pub fn needs_admin(&self) -> bool {
+        matches!(self, JobKind::ImportArchive { .. } | JobKind::ExportProfile { .. })
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed(String),
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn label(&self) -> &str {
+        match self {
+            JobStatus::Queued => "Queued",
+            JobStatus::Running => "Running",
+            JobStatus::Completed => "Completed",
+            JobStatus::Failed(_) => "Failed",
+            JobStatus::Cancelled => "Cancelled",
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self, JobStatus::Completed | JobStatus::Failed(_) | JobStatus::Cancelled)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: Uuid,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub summary: Option<String>, // What a finished job did
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// Files done out of the total, written by the worker thread and polled by the UI
+#[derive(Debug, Default)]
+pub struct JobProgress {
+    done: AtomicUsize,
+    total: AtomicUsize,
+}
+
+impl JobProgress {
+    pub fn set(&self, done: usize, total: usize) {
+        self.total.store(total, Ordering::Relaxed);
+        self.done.store(done, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> (usize, usize) {
+        (self.done.load(Ordering::Relaxed), self.total.load(Ordering::Relaxed))
+    }
+}
+
+/// Jobs in the order they were queued, kept in the config directory so work interrupted
+/// by closing the app is picked up again on the next start
+#[derive(Debug, Clone, Default)]
+pub struct JobQueue {
+    pub jobs: Vec<Job>,
+}
+
+impl JobQueue {
+    fn path() -> Result<PathBuf> {
+        let project_dirs = ProjectDirs::from("com", "Evidence-Manager", "Evidence-Manager")
+            .context("Failed to get user config directory")?;
+        Ok(project_dirs.config_dir().join("jobs.json"))
+    }
+
+    /// Loads the queue; jobs that were running when the app closed are queued again
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let json = fs::read_to_string(&path)
+            .context("Failed to read job queue")?;
+        let mut jobs: Vec<Job> = serde_json::from_str(&json)
+            .context("Failed to parse job queue")?;
+        for job in &mut jobs {
+            if job.status == JobStatus::Running {
+                job.status = JobStatus::Queued;
+            }
+        }
+        Ok(Self { jobs })
+    }
+
+    pub fn save(&mut self) -> Result<()> {
+        let finished = self.jobs.iter().filter(|job| job.status.is_finished()).count();
+        let mut excess = finished.saturating_sub(FINISHED_JOBS_KEPT);
+        self.jobs.retain(|job| {
+            let drop = excess > 0 && job.status.is_finished();
+            if drop {
+                excess -= 1;
+            }
+            !drop
+        });
+
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .context("Failed to create config directory")?;
+        }
+        let json = serde_json::to_string_pretty(&self.jobs)
+            .context("Failed to serialize job queue")?;
+        fs::write(&path, json)
+            .context("Failed to write job queue")?;
+        Ok(())
+    }
+
+    pub fn push(&mut self, kind: JobKind) -> Uuid {
+        let id = Uuid::new_v4();
+        self.jobs.push(Job {
+            id,
+            kind,
+            status: JobStatus::Queued,
+            created_at: Utc::now(),
+            finished_at: None,
+            summary: None,
+            warnings: Vec::new(),
+        });
+        id
+    }
+
+    pub fn get_mut(&mut self, id: Uuid) -> Option<&mut Job> {
+        self.jobs.iter_mut().find(|job| job.id == id)
+    }
+
+    /// The oldest queued job the current profile may run
+    pub fn next_queued(&self, admin: bool) -> Option<&Job> {
+        self.jobs.iter().find(|job| job.status == JobStatus::Queued && (admin || !job.kind.needs_admin()))
+    }
+
+    pub fn finish(&mut self, id: Uuid, status: JobStatus) {
+        if let Some(job) = self.get_mut(id) {
+            job.status = status;
+            job.finished_at = Some(Utc::now());
+        }
+    }
+
+    pub fn clear_finished(&mut self) {
+        self.jobs.retain(|job| !job.status.is_finished());
+    }
+}
@@ -0,0 +1,193 @@
+use crate::dates::DateDisplay;
+use crate::file_manager::FileManager;
+use crate::models::{EvidenceFile, EvidenceType, Person};
+use crate::reports;
+use crate::watermark::{self, GLYPH_HEIGHT, GLYPH_WIDTH};
+use anyhow::{Result, Context, anyhow, bail};
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+// Every photo is cropped to the same square so none stands out
+const PHOTO_SIZE: u32 = 300;
+const GAP: u32 = 20;
+const LABEL_HEIGHT: u32 = 70;
+const MAX_COLUMNS: usize = 4;
+
+/// The order people appear on the sheet, and so the numbers they get
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineupOrder {
+    ListOrder, // As in the people list
+    Name,
+    Random, // Shuffled afresh on every export
+}
+
+impl LineupOrder {
+    pub fn all() -> [LineupOrder; 3] {
+        [LineupOrder::ListOrder, LineupOrder::Name, LineupOrder::Random]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LineupOrder::ListOrder => "List order",
+            LineupOrder::Name => "By name",
+            LineupOrder::Random => "Random order",
+        }
+    }
+}
+
+impl std::fmt::Display for LineupOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// File formats the lineup sheet can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineupFormat {
+    Png,
+    Pdf,
+}
+
+impl LineupFormat {
+    /// PDF for files ending in .pdf, PNG otherwise
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("pdf") => LineupFormat::Pdf,
+            _ => LineupFormat::Png,
+        }
+    }
+}
+
+/// A numbered place on the sheet
+#[derive(Debug, Clone)]
+pub struct LineupEntry {
+    pub number: usize,
+    pub name: String,
+    pub photo: PathBuf,
+}
+
+/// A photo lineup: one photo of each person, numbered in the chosen order
+#[derive(Debug, Clone)]
+pub struct Lineup {
+    pub entries: Vec<LineupEntry>,
+    pub skipped: Vec<String>, // People without an image to show
+}
+
+impl Lineup {
+    /// Each person's photo is their earliest image evidence that hasn't been replaced by a newer
+    /// version. People with none are left off the sheet.
+    pub fn build(file_manager: &FileManager, persons: &[Person], order: LineupOrder) -> Result<Self> {
+        let mut chosen = Vec::new();
+        let mut skipped = Vec::new();
+        for person in persons {
+            let evidence_files = file_manager.load_evidence_index(person)?;
+            match profile_photo(&evidence_files) {
+                Some(file) => chosen.push((person.name.clone(), file.file_path.clone())),
+                None => skipped.push(person.name.clone()),
+            }
+        }
+
+        match order {
+            LineupOrder::ListOrder => {}
+            LineupOrder::Name => chosen.sort_by_key(|(name, _)| name.to_lowercase()),
+            LineupOrder::Random => shuffle(&mut chosen)?,
+        }
+        let entries = chosen
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, photo))| LineupEntry { number: index + 1, name, photo })
+            .collect();
+        Ok(Self { entries, skipped })
+    }
+
+    /// "1 Name, 2 Name, ...", to keep with the sheet when names aren't printed on it
+    pub fn key(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| format!("{} {}", entry.number, entry.name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Lays the photos out in rows with each number, and the name if asked, underneath
+    pub fn render_image(&self, file_manager: &FileManager, show_names: bool) -> Result<RgbaImage> {
+        if self.entries.is_empty() {
+            bail!("None of the people have an image to show");
+        }
+        let columns = ((self.entries.len() as f32).sqrt().ceil() as usize).clamp(1, MAX_COLUMNS);
+        let rows = self.entries.len().div_ceil(columns);
+        let cell_height = PHOTO_SIZE + LABEL_HEIGHT;
+        let mut canvas = RgbaImage::from_pixel(
+            columns as u32 * (PHOTO_SIZE + GAP) + GAP,
+            rows as u32 * (cell_height + GAP) + GAP,
+            Rgba([255, 255, 255, 255]),
+        );
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let x = GAP + (index % columns) as u32 * (PHOTO_SIZE + GAP);
+            let y = GAP + (index / columns) as u32 * (cell_height + GAP);
+            let content = file_manager.read_evidence(&entry.photo)
+                .with_context(|| format!("Failed to read the photo of {}", entry.name))?;
+            let picture = image::load_from_memory(&content)
+                .with_context(|| format!("Failed to decode the photo of {}", entry.name))?;
+            let photo = picture.resize_to_fill(PHOTO_SIZE, PHOTO_SIZE, FilterType::Triangle).to_rgba8();
+            imageops::overlay(&mut canvas, &photo, x as i64, y as i64);
+
+            let number = entry.number.to_string();
+            let number_width = number.len() as u32 * (GLYPH_WIDTH + 1) * 4;
+            watermark::draw_text(&mut canvas, &number, x + (PHOTO_SIZE.saturating_sub(number_width)) / 2, y + PHOTO_SIZE + 8, 4, Rgba([0, 0, 0, 255]));
+            if show_names {
+                let max_chars = (PHOTO_SIZE / ((GLYPH_WIDTH + 1) * 2)) as usize;
+                let name: String = entry.name.chars().take(max_chars).collect();
+                let name_width = name.chars().count() as u32 * (GLYPH_WIDTH + 1) * 2;
+                let name_y = y + PHOTO_SIZE + 16 + GLYPH_HEIGHT * 4;
+                watermark::draw_text(&mut canvas, &name, x + (PHOTO_SIZE.saturating_sub(name_width)) / 2, name_y, 2, Rgba([80, 80, 80, 255]));
+            }
+        }
+        Ok(canvas)
+    }
+
+    pub fn write(&self, file_manager: &FileManager, show_names: bool, format: LineupFormat, dates: DateDisplay) -> Result<Vec<u8>> {
+        let sheet = self.render_image(file_manager, show_names)?;
+        let mut output = Cursor::new(Vec::new());
+        match format {
+            LineupFormat::Png => {
+                sheet.write_to(&mut output, ImageFormat::Png)
+                    .context("Failed to write lineup image")?;
+                Ok(output.into_inner())
+            }
+            LineupFormat::Pdf => {
+                let (width, height) = sheet.dimensions();
+                // JPEG has no alpha channel
+                DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(sheet).to_rgb8())
+                    .write_to(&mut output, ImageFormat::Jpeg)
+                    .context("Failed to encode lineup image")?;
+                let subtitle = format!("{} photos    Printed: {}", self.entries.len(), dates.now());
+                reports::picture_pdf("Photo lineup", &subtitle, &output.into_inner(), width, height)
+            }
+        }
+    }
+}
+
+fn profile_photo(evidence_files: &[EvidenceFile]) -> Option<&EvidenceFile> {
+    evidence_files
+        .iter()
+        .filter(|file| file.file_type == EvidenceType::Image && file.superseded_by.is_none())
+        .min_by_key(|file| file.created_at)
+}
+
+// Fisher-Yates with the system's secure generator, so the order can't be predicted
+fn shuffle<T>(items: &mut [T]) -> Result<()> {
+    let random = SystemRandom::new();
+    for index in (1..items.len()).rev() {
+        let mut bytes = [0u8; 8];
+        random.fill(&mut bytes)
+            .map_err(|_| anyhow!("Failed to generate a random order"))?;
+        let other = (u64::from_le_bytes(bytes) % (index as u64 + 1)) as usize;
+        items.swap(index, other);
+    }
+    Ok(())
+}
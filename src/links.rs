@@ -0,0 +1,46 @@
+use crate::models::Person;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+// Shorter values ("n/a", initials) match too much to mean anything
+const MIN_IDENTIFIER_LENGTH: usize = 4;
+
+/// An information value recorded under more than one person, a potential link between them
+#[derive(Debug, Clone)]
+pub struct SharedIdentifier {
+    pub info_type: String,
+    pub value: String, // As first recorded
+    pub persons: Vec<Uuid>,
+}
+
+/// Finds values such as phone numbers, emails and addresses that appear under several persons.
+/// Values are compared in their normalized form, so "+1 (555) 010-2030" matches "15550102030"
+/// and "John@Example.com" matches "john@example.com".
+pub fn shared_identifiers(persons: &[Person]) -> Vec<SharedIdentifier> {
+    let mut by_value: BTreeMap<String, SharedIdentifier> = BTreeMap::new();
+    for person in persons {
+        for info in &person.information {
+            // Numbers saved before a default country code was set have no "+"
+            let key = info.match_value().trim_start_matches('+').to_string();
+            if key.chars().count() < MIN_IDENTIFIER_LENGTH {
+                continue;
+            }
+            let shared = by_value.entry(key).or_insert_with(|| SharedIdentifier {
+                info_type: info.info_type.clone(),
+                value: info.value.clone(),
+                persons: Vec::new(),
+            });
+            if !shared.persons.contains(&person.id) {
+                shared.persons.push(person.id);
+            }
+        }
+    }
+
+    let mut shared: Vec<SharedIdentifier> = by_value
+        .into_values()
+        .filter(|shared| shared.persons.len() > 1)
+        .collect();
+    // Values linking the most people first
+    shared.sort_by(|a, b| b.persons.len().cmp(&a.persons.len()).then_with(|| a.value.cmp(&b.value)));
+    shared
+}
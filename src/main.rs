@@ -3,17 +3,103 @@ mod file_manager;
 mod export_import;
 mod state;
 mod gui;
+mod watermark;
+mod chat_import;
+mod email;
+mod duplicates;
+mod naming;
+mod settings;
+mod statistics;
+mod audit;
+mod hashing;
+mod clipboard;
+mod reports;
+mod exif;
+mod map;
+mod geocode;
+mod query;
+mod links;
+mod bulk_edit;
+mod paths;
+mod jobs;
+mod sniff;
+mod signing;
+mod timestamp;
+mod profiles;
+mod import_journal;
+mod quick_capture;
+mod ingest;
+mod webhook;
+mod plugins;
+mod timeline;
+mod board;
+mod encryption;
+mod export_profiles;
+mod store_check;
+mod content_store;
+mod quarantine;
+mod device_import;
+mod recorder;
+mod notes;
+mod quote_paste;
+mod drafts;
+mod session;
+mod inventory;
+mod file_list_import;
+mod completeness;
+mod name_list;
+mod evidence_browser;
+mod lineup;
+mod dates;
 
-use iced::{Application, Settings};
+use iced::{Application, Point, Settings, Size};
+use iced::window::Position;
 use state::AppState;
 
+const MIN_WINDOW_SIZE: Size = Size::new(800.0, 600.0);
+
 fn main() -> iced::Result {
+    // `ingest` adds files and quotes sent by scripts, `unpack` turns a split or encrypted export
+    // back into a plain .ema, `verify` checks the store, `rebuild-index` regenerates evidence
+    // indexes from disk and `migrate-blobs` moves evidence into the blob store; none of them open
+    // the window
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        Some("ingest") => std::process::exit(ingest::run(args.next())),
+        Some("unpack") => std::process::exit(export_profiles::unpack(args.next(), args.next())),
+        Some("verify") => std::process::exit(store_check::run(args.next())),
+        Some("rebuild-index") => std::process::exit(store_check::run_rebuild(args.next())),
+        Some("migrate-blobs") => std::process::exit(content_store::run_migrate()),
+        _ => {}
+    }
+
+    // The window opens at the size and place it had when the app last closed
+    let settings = settings::Settings::load().unwrap_or_default();
+    let layout = settings.layout;
+    // Kept registered until the app exits
+    let hotkey = match settings.quick_capture_hotkey.trim() {
+        "" => None,
+        shortcut => Some(quick_capture::Hotkey::register(shortcut)),
+    };
+    let hotkey_id = match &hotkey {
+        Some(Ok(hotkey)) => Ok(Some(hotkey.id())),
+        Some(Err(e)) => Err(format!("{:#}", e)),
+        None => Ok(None),
+    };
     AppState::run(Settings {
         window: iced::window::Settings {
-            size: iced::Size::new(1200.0, 800.0),
-            min_size: Some(iced::Size::new(800.0, 600.0)),
+            size: Size::new(
+                layout.window_width.max(MIN_WINDOW_SIZE.width),
+                layout.window_height.max(MIN_WINDOW_SIZE.height),
+            ),
+            position: layout.window_position
+                .map_or(Position::Default, |(x, y)| Position::Specific(Point::new(x as f32, y as f32))),
+            // Small enough for quick capture; the window only opens this small on request
+            min_size: Some(quick_capture::WINDOW_SIZE),
+            // Closing goes through the app so the layout can be saved first
+            exit_on_close_request: false,
             ..Default::default()
         },
-        ..Default::default()
+        ..Settings::with_flags(hotkey_id)
     })
 }
@@ -0,0 +1,203 @@
+use crate::hashing;
+use crate::models::Coordinates;
+use crate::watermark::{self, GLYPH_HEIGHT, GLYPH_WIDTH};
+use anyhow::{Result, Context};
+use directories::ProjectDirs;
+use image::{Rgba, RgbaImage};
+use std::f64::consts::PI;
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::Duration;
+use uuid::Uuid;
+
+pub const DEFAULT_TILE_URL: &str = "https://tile.openstreetmap.org/{z}/{x}/{y}.png";
+pub const MAP_WIDTH: u32 = 768;
+pub const MAP_HEIGHT: u32 = 512;
+
+const TILE_SIZE: u32 = 256;
+const MAX_ZOOM: u32 = 16;
+const MARKER_RADIUS: i64 = 9;
+// Web Mercator can't show the poles
+const MAX_LATITUDE: f64 = 85.0511;
+
+/// What a map marker links back to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MapTarget {
+    Evidence(Uuid),
+    Quote(Uuid),
+}
+
+#[derive(Debug, Clone)]
+pub struct MapPoint {
+    pub label: String,
+    pub coordinates: Coordinates,
+    pub target: MapTarget,
+}
+
+pub struct RenderedMap {
+    pub image: RgbaImage,
+    pub missing_tiles: usize, // Tiles that were neither cached nor downloadable
+}
+
+/// Stitches map tiles around the points and draws numbered markers matching their order
+pub fn render_map(points: &[MapPoint], tile_url: &str) -> Result<RenderedMap> {
+    anyhow::ensure!(!points.is_empty(), "No locations to show");
+    let zoom = fit_zoom(points);
+    let world_points: Vec<(f64, f64)> = points.iter().map(|p| world_pixel(p.coordinates, zoom)).collect();
+
+    let (min_x, max_x, min_y, max_y) = bounds(&world_points);
+    let left = ((min_x + max_x) / 2.0 - MAP_WIDTH as f64 / 2.0).floor() as i64;
+    let top = ((min_y + max_y) / 2.0 - MAP_HEIGHT as f64 / 2.0).floor() as i64;
+
+    let mut canvas = RgbaImage::from_pixel(MAP_WIDTH, MAP_HEIGHT, Rgba([220, 220, 220, 255]));
+    let tiles = TileSource::new(tile_url)?;
+    let tile_count = 1i64 << zoom;
+    let mut missing_tiles = 0;
+
+    for tile_y in top.div_euclid(TILE_SIZE as i64)..=(top + MAP_HEIGHT as i64).div_euclid(TILE_SIZE as i64) {
+        if !(0..tile_count).contains(&tile_y) {
+            continue;
+        }
+        for tile_x in left.div_euclid(TILE_SIZE as i64)..=(left + MAP_WIDTH as i64).div_euclid(TILE_SIZE as i64) {
+            let wrapped_x = tile_x.rem_euclid(tile_count);
+            match tiles.tile(zoom, wrapped_x as u32, tile_y as u32) {
+                Some(tile) => image::imageops::overlay(
+                    &mut canvas,
+                    &tile,
+                    tile_x * TILE_SIZE as i64 - left,
+                    tile_y * TILE_SIZE as i64 - top,
+                ),
+                None => missing_tiles += 1,
+            }
+        }
+    }
+
+    for (index, (x, y)) in world_points.iter().enumerate() {
+        draw_marker(&mut canvas, *x as i64 - left, *y as i64 - top, index + 1);
+    }
+
+    Ok(RenderedMap { image: canvas, missing_tiles })
+}
+
+/// Position in pixels on the whole-world map at the given zoom
+fn world_pixel(coordinates: Coordinates, zoom: u32) -> (f64, f64) {
+    let size = (TILE_SIZE as f64) * (1u64 << zoom) as f64;
+    let latitude = coordinates.latitude.clamp(-MAX_LATITUDE, MAX_LATITUDE).to_radians();
+    let x = (coordinates.longitude + 180.0) / 360.0 * size;
+    let y = (1.0 - (latitude.tan() + 1.0 / latitude.cos()).ln() / PI) / 2.0 * size;
+    (x, y)
+}
+
+fn bounds(points: &[(f64, f64)]) -> (f64, f64, f64, f64) {
+    points.iter().fold(
+        (f64::MAX, f64::MIN, f64::MAX, f64::MIN),
+        |(min_x, max_x, min_y, max_y), (x, y)| (min_x.min(*x), max_x.max(*x), min_y.min(*y), max_y.max(*y)),
+    )
+}
+
+/// Closest zoom at which every point fits, leaving room for the markers
+fn fit_zoom(points: &[MapPoint]) -> u32 {
+    let margin = (MARKER_RADIUS * 4) as f64;
+    (0..=MAX_ZOOM)
+        .rev()
+        .find(|zoom| {
+            let world_points: Vec<(f64, f64)> = points.iter().map(|p| world_pixel(p.coordinates, *zoom)).collect();
+            let (min_x, max_x, min_y, max_y) = bounds(&world_points);
+            max_x - min_x <= MAP_WIDTH as f64 - margin && max_y - min_y <= MAP_HEIGHT as f64 - margin
+        })
+        .unwrap_or(0)
+}
+
+fn draw_marker(canvas: &mut RgbaImage, center_x: i64, center_y: i64, number: usize) {
+    let (width, height) = canvas.dimensions();
+    for dy in -MARKER_RADIUS..=MARKER_RADIUS {
+        for dx in -MARKER_RADIUS..=MARKER_RADIUS {
+            let distance = dx * dx + dy * dy;
+            let (x, y) = (center_x + dx, center_y + dy);
+            if distance > MARKER_RADIUS * MARKER_RADIUS || x < 0 || y < 0 || x >= width as i64 || y >= height as i64 {
+                continue;
+            }
+            let color = if distance > (MARKER_RADIUS - 2) * (MARKER_RADIUS - 2) {
+                Rgba([255, 255, 255, 255])
+            } else {
+                Rgba([200, 30, 30, 255])
+            };
+            canvas.put_pixel(x as u32, y as u32, color);
+        }
+    }
+
+    let label = number.to_string();
+    let text_width = label.len() as i64 * (GLYPH_WIDTH as i64 + 1) - 1;
+    let text_x = center_x - text_width / 2;
+    let text_y = center_y - GLYPH_HEIGHT as i64 / 2;
+    if text_x >= 0 && text_y >= 0 {
+        watermark::draw_text(canvas, &label, text_x as u32, text_y as u32, 1, Rgba([255, 255, 255, 255]));
+    }
+}
+
+/// Downloads tiles on first use and keeps them on disk so the map keeps working offline
+struct TileSource {
+    url_template: String,
+    cache_dir: PathBuf,
+    agent: ureq::Agent,
+    offline: std::cell::Cell<bool>,
+}
+
+impl TileSource {
+    fn new(url_template: &str) -> Result<Self> {
+        let project_dirs = ProjectDirs::from("com", "Evidence-Manager", "Evidence-Manager")
+            .context("Failed to get user cache directory")?;
+        // Separate caches per tile server so switching providers doesn't mix styles
+        let provider = &hashing::sha256_hex(url_template.as_bytes())[..12];
+
+        Ok(Self {
+            url_template: url_template.to_string(),
+            cache_dir: project_dirs.cache_dir().join("tiles").join(provider),
+            agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(5)).build(),
+            offline: std::cell::Cell::new(false),
+        })
+    }
+
+    fn tile(&self, zoom: u32, x: u32, y: u32) -> Option<RgbaImage> {
+        let path = self.cache_dir.join(zoom.to_string()).join(x.to_string()).join(format!("{}.png", y));
+        let content = match fs::read(&path) {
+            Ok(content) => content,
+            Err(_) => {
+                let content = self.download(zoom, x, y)?;
+                if let Some(parent) = path.parent() {
+                    let _ = fs::create_dir_all(parent);
+                }
+                let _ = fs::write(&path, &content);
+                content
+            }
+        };
+        image::load_from_memory(&content).ok().map(|tile| tile.to_rgba8())
+    }
+
+    fn download(&self, zoom: u32, x: u32, y: u32) -> Option<Vec<u8>> {
+        // After one failure assume we're offline rather than waiting on every tile
+        if self.offline.get() {
+            return None;
+        }
+        let url = self.url_template
+            .replace("{z}", &zoom.to_string())
+            .replace("{x}", &x.to_string())
+            .replace("{y}", &y.to_string());
+
+        let response = self.agent.get(&url)
+            .set("User-Agent", concat!("Evidence-Manager/", env!("CARGO_PKG_VERSION")))
+            .call();
+        match response {
+            Ok(response) => {
+                let mut content = Vec::new();
+                response.into_reader().read_to_end(&mut content).ok()?;
+                Some(content)
+            }
+            Err(_) => {
+                self.offline.set(true);
+                None
+            }
+        }
+    }
+}
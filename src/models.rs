@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, Utc};
+use crate::dates::UtcOffset;
+use crate::timestamp::TrustedTimestamp;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,16 +17,361 @@ pub struct Person {
     pub information: Vec<PersonInfo>,
     #[serde(default)] // Backward compatibility
     pub quotes: Vec<Quote>,
+    #[serde(default)]
+    pub case_status: CaseStatus,
+    #[serde(default)]
+    pub priority: Priority,
+    #[serde(default)]
+    pub tasks: Vec<Task>,
+    #[serde(default)]
+    pub custom_fields: BTreeMap<String, FieldValue>, // Keyed by the field name defined in settings
+    #[serde(default)]
+    pub pinned: bool, // Listed first in the sidebar
+    #[serde(default)]
+    pub legal_hold: bool, // Existing entries can't be changed or deleted, only added to
+    #[serde(default)]
+    pub role: CaseRole,
+    #[serde(default)]
+    pub color_label: Option<ColorLabel>, // Dot shown next to the name, e.g. to tell victims from suspects
+    #[serde(default)]
+    pub checklists: Vec<Checklist>,
+}
+
+/// A follow-up reminder attached to a person
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Task {
+    pub id: Uuid,
+    pub description: String,
+    pub due_date: Option<NaiveDate>,
+    pub done: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Task {
+    /// Open and due today or earlier
+    pub fn is_due(&self, today: NaiveDate) -> bool {
+        !self.done && self.due_date.is_some_and(|due| due <= today)
+    }
+}
+
+/// Interview or intake questions attached to a person, copied from a template in Settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checklist {
+    pub id: Uuid,
+    pub name: String,
+    pub items: Vec<ChecklistItem>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    pub id: Uuid,
+    pub question: String,
+    pub answer: String, // Empty until answered
+}
+
+impl Checklist {
+    pub fn answered(&self) -> usize {
+        self.items.iter().filter(|item| !item.answer.trim().is_empty()).count()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.answered() == self.items.len()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CaseStatus {
+    #[default]
+    Active,
+    OnHold,
+    Closed,
+}
+
+impl CaseStatus {
+    pub fn all() -> [CaseStatus; 3] {
+        [CaseStatus::Active, CaseStatus::OnHold, CaseStatus::Closed]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CaseStatus::Active => "Active",
+            CaseStatus::OnHold => "On hold",
+            CaseStatus::Closed => "Closed",
+        }
+    }
+}
+
+impl std::fmt::Display for CaseStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// The kind of value a custom field holds, which decides its editor and validation
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FieldType {
+    Date,
+    Number,
+    Boolean,
+    Url,
+}
+
+impl FieldType {
+    pub fn all() -> [FieldType; 4] {
+        [FieldType::Date, FieldType::Number, FieldType::Boolean, FieldType::Url]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FieldType::Date => "Date",
+            FieldType::Number => "Number",
+            FieldType::Boolean => "Yes/No",
+            FieldType::Url => "URL",
+        }
+    }
+}
+
+impl std::fmt::Display for FieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum FieldValue {
+    Date(NaiveDate),
+    Number(f64),
+    Boolean(bool),
+    Url(String),
+}
+
+impl FieldValue {
+    /// Validates text typed into a field's editor
+    pub fn parse(field_type: FieldType, text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        match field_type {
+            FieldType::Date => NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .map(FieldValue::Date)
+                .map_err(|_| format!("\"{}\" is not a date (YYYY-MM-DD)", text)),
+            FieldType::Number => text.parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .map(FieldValue::Number)
+                .ok_or_else(|| format!("\"{}\" is not a number", text)),
+            FieldType::Boolean => match text.to_lowercase().as_str() {
+                "yes" | "true" => Ok(FieldValue::Boolean(true)),
+                "no" | "false" => Ok(FieldValue::Boolean(false)),
+                _ => Err(format!("\"{}\" is not yes or no", text)),
+            },
+            FieldType::Url => {
+                if (text.starts_with("http://") || text.starts_with("https://")) && !text.contains(char::is_whitespace) {
+                    Ok(FieldValue::Url(text.to_string()))
+                } else {
+                    Err(format!("\"{}\" is not a web address (http:// or https://)", text))
+                }
+            }
+        }
+    }
+
+    pub fn field_type(&self) -> FieldType {
+        match self {
+            FieldValue::Date(_) => FieldType::Date,
+            FieldValue::Number(_) => FieldType::Number,
+            FieldValue::Boolean(_) => FieldType::Boolean,
+            FieldValue::Url(_) => FieldType::Url,
+        }
+    }
+}
+
+impl std::fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldValue::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            FieldValue::Number(number) => write!(f, "{}", number),
+            FieldValue::Boolean(value) => write!(f, "{}", if *value { "Yes" } else { "No" }),
+            FieldValue::Url(url) => write!(f, "{}", url),
+        }
+    }
+}
+
+// Declared from lowest to highest so sorting follows urgency
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Urgent,
+}
+
+impl Priority {
+    pub fn all() -> [Priority; 4] {
+        [Priority::Low, Priority::Normal, Priority::High, Priority::Urgent]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Priority::Low => "Low",
+            Priority::Normal => "Normal",
+            Priority::High => "High",
+            Priority::Urgent => "Urgent",
+        }
+    }
+}
+
+impl std::fmt::Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// How a person relates to the investigation
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CaseRole {
+    #[default]
+    Unassigned,
+    Victim,
+    Suspect,
+    Witness,
+    Other,
+}
+
+impl CaseRole {
+    pub fn all() -> [CaseRole; 5] {
+        [CaseRole::Unassigned, CaseRole::Victim, CaseRole::Suspect, CaseRole::Witness, CaseRole::Other]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CaseRole::Unassigned => "No role",
+            CaseRole::Victim => "Victim",
+            CaseRole::Suspect => "Suspect",
+            CaseRole::Witness => "Witness",
+            CaseRole::Other => "Other",
+        }
+    }
+}
+
+impl std::fmt::Display for CaseRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// A color a person can be marked with; what each means is up to the user
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ColorLabel {
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+}
+
+impl ColorLabel {
+    pub fn all() -> [ColorLabel; 6] {
+        [ColorLabel::Red, ColorLabel::Orange, ColorLabel::Yellow, ColorLabel::Green, ColorLabel::Blue, ColorLabel::Purple]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColorLabel::Red => "Red",
+            ColorLabel::Orange => "Orange",
+            ColorLabel::Yellow => "Yellow",
+            ColorLabel::Green => "Green",
+            ColorLabel::Blue => "Blue",
+            ColorLabel::Purple => "Purple",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PersonInfo {
     pub id: Uuid,
     pub info_type: String,
-    pub value: String,
+    pub value: String, // As entered
+    #[serde(default)]
+    pub normalized: Option<String>, // E.164 phone number or lowercased email, used for matching
     pub created_at: DateTime<Utc>,
 }
 
+impl PersonInfo {
+    /// The form values are compared in: the normalized contact if there is one,
+    /// otherwise the value ignoring case and spacing
+    pub fn match_value(&self) -> String {
+        self.normalized.clone()
+            .or_else(|| normalize_contact(&self.value, ""))
+            .unwrap_or_else(|| self.value.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase())
+    }
+
+    /// A link for values that can be acted on: web addresses, emails and phone numbers
+    pub fn link(&self) -> Option<String> {
+        let value = self.value.trim();
+        if value.contains(char::is_whitespace) && !looks_like_phone(value) {
+            return None;
+        }
+
+        if value.starts_with("http://") || value.starts_with("https://") {
+            Some(value.to_string())
+        } else if value.starts_with("www.") {
+            Some(format!("https://{}", value))
+        } else if let Some((local, domain)) = value.split_once('@')
+            && !local.is_empty()
+            && domain.contains('.')
+            && !domain.contains('@')
+            && !domain.starts_with('.')
+            && !domain.ends_with('.')
+        {
+            Some(format!("mailto:{}", value))
+        } else if looks_like_phone(value) {
+            let number = self.normalized.clone().unwrap_or_else(|| {
+                let digits: String = value.chars().filter(char::is_ascii_digit).collect();
+                let prefix = if value.starts_with('+') { "+" } else { "" };
+                format!("{}{}", prefix, digits)
+            });
+            Some(format!("tel:{}", number))
+        } else {
+            None
+        }
+    }
+}
+
+/// Normalizes phone numbers to E.164 and emails to lowercase, so the same contact written
+/// differently still matches. Numbers without an international prefix get `default_country_code`
+/// (digits only, e.g. "44"), replacing a leading trunk 0; without one they are kept as bare digits.
+pub fn normalize_contact(value: &str, default_country_code: &str) -> Option<String> {
+    let value = value.trim();
+    if let Some((local, domain)) = value.split_once('@')
+        && !local.is_empty()
+        && domain.contains('.')
+        && !value.contains(char::is_whitespace)
+    {
+        return Some(value.to_lowercase());
+    }
+    if !looks_like_phone(value) {
+        return None;
+    }
+
+    let digits: String = value.chars().filter(char::is_ascii_digit).collect();
+    let country_code: String = default_country_code.chars().filter(char::is_ascii_digit).collect();
+    if value.starts_with('+') {
+        Some(format!("+{}", digits))
+    } else if let Some(international) = digits.strip_prefix("00") {
+        Some(format!("+{}", international))
+    } else if country_code.is_empty() {
+        Some(digits)
+    } else {
+        Some(format!("+{}{}", country_code, digits.strip_prefix('0').unwrap_or(&digits)))
+    }
+}
+
+fn looks_like_phone(value: &str) -> bool {
+    value.chars().all(|c| c.is_ascii_digit() || " +-().".contains(c))
+        && value.chars().filter(char::is_ascii_digit).count() >= 7
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Quote {
     pub id: Uuid,
@@ -32,7 +380,25 @@ pub struct Quote {
     pub date: String,
     pub time: Option<String>,
     pub place: Option<String>,
+    #[serde(default)]
+    pub source: Option<String>, // Witness, platform, or document reference
+    #[serde(default)]
+    pub linked_evidence: Vec<Uuid>,
+    #[serde(default)]
+    pub coordinates: Option<Coordinates>, // Resolved from `place`
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub utc_offset: Option<UtcOffset>, // Zone the date and time were given in; None when it wasn't recorded
+}
+
+impl Quote {
+    /// When it was said, if the date is YYYY-MM-DD, the time HH:MM and the zone is known
+    pub fn instant(&self) -> Option<DateTime<FixedOffset>> {
+        let offset = self.utc_offset?;
+        let date = NaiveDate::parse_from_str(self.date.trim(), "%Y-%m-%d").ok()?;
+        let time = NaiveTime::parse_from_str(self.time.as_deref()?.trim(), "%H:%M").ok()?;
+        date.and_time(time).and_local_timezone(offset.fixed()).single()
+    }
 }
 
 impl Person {
@@ -47,6 +413,15 @@ impl Person {
             tags: Vec::new(),
             information: Vec::new(),
             quotes: Vec::new(),
+            case_status: CaseStatus::Active,
+            priority: Priority::Normal,
+            tasks: Vec::new(),
+            custom_fields: BTreeMap::new(),
+            pinned: false,
+            legal_hold: false,
+            role: CaseRole::Unassigned,
+            color_label: None,
+            checklists: Vec::new(),
         }
     }
 
@@ -54,8 +429,14 @@ impl Person {
         self.updated_at = Utc::now();
     }
 
+    /// Names come from imported data too, so separators and dot-only names can't reach the file system
     pub fn folder_name(&self) -> String {
-        self.name.replace(' ', "_")
+        let folder_name = self.name.replace([' ', '/', '\\'], "_");
+        if folder_name.chars().all(|c| c == '.') {
+            folder_name.replace('.', "_")
+        } else {
+            folder_name
+        }
     }
 
     pub fn add_information(&mut self, info_type: String, value: String) {
@@ -63,35 +444,105 @@ impl Person {
             id: Uuid::new_v4(),
             info_type,
             value,
+            normalized: None,
             created_at: Utc::now(),
         };
         self.information.push(info);
         self.update_timestamp();
     }
 
+    /// Recomputes the normalized form of every information value; returns how many changed
+    pub fn normalize_information(&mut self, default_country_code: &str) -> usize {
+        let mut changed = 0;
+        for info in &mut self.information {
+            let normalized = normalize_contact(&info.value, default_country_code);
+            if info.normalized != normalized {
+                info.normalized = normalized;
+                changed += 1;
+            }
+        }
+        changed
+    }
+
     pub fn remove_information(&mut self, info_id: Uuid) {
         self.information.retain(|info| info.id != info_id);
         self.update_timestamp();
     }
 
-    pub fn add_quote(&mut self, quote: String, date: String, time: Option<String>, place: Option<String>) {
+    /// Returns the new quote, for details such as the zone it was given in
+    pub fn add_quote(&mut self, quote: String, date: String, time: Option<String>, place: Option<String>, source: Option<String>, linked_evidence: Vec<Uuid>) -> &mut Quote {
         let new_quote = Quote {
             id: Uuid::new_v4(),
             person_id: self.id,
             quote,
             date,
             time,
+            coordinates: place.as_deref().and_then(Coordinates::parse),
             place,
+            source,
+            linked_evidence,
             created_at: Utc::now(),
+            utc_offset: None,
         };
-        self.quotes.push(new_quote);
         self.update_timestamp();
+        self.quotes.push(new_quote);
+        self.quotes.last_mut().expect("the quote was just added")
     }
 
     pub fn remove_quote(&mut self, quote_id: Uuid) {
         self.quotes.retain(|quote| quote.id != quote_id);
         self.update_timestamp();
     }
+
+    pub fn add_task(&mut self, description: String, due_date: Option<NaiveDate>) {
+        let task = Task {
+            id: Uuid::new_v4(),
+            description,
+            due_date,
+            done: false,
+            created_at: Utc::now(),
+        };
+        self.tasks.push(task);
+        self.update_timestamp();
+    }
+
+    pub fn add_checklist(&mut self, name: String, questions: &[String]) {
+        let checklist = Checklist {
+            id: Uuid::new_v4(),
+            name,
+            items: questions
+                .iter()
+                .map(|question| ChecklistItem { id: Uuid::new_v4(), question: question.clone(), answer: String::new() })
+                .collect(),
+            created_at: Utc::now(),
+        };
+        self.checklists.push(checklist);
+        self.update_timestamp();
+    }
+
+    pub fn set_checklist_answer(&mut self, item_id: Uuid, answer: String) {
+        if let Some(item) = self.checklists.iter_mut().flat_map(|c| c.items.iter_mut()).find(|i| i.id == item_id) {
+            item.answer = answer;
+        }
+        self.update_timestamp();
+    }
+
+    pub fn remove_checklist(&mut self, checklist_id: Uuid) {
+        self.checklists.retain(|checklist| checklist.id != checklist_id);
+        self.update_timestamp();
+    }
+
+    pub fn set_task_done(&mut self, task_id: Uuid, done: bool) {
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) {
+            task.done = done;
+        }
+        self.update_timestamp();
+    }
+
+    pub fn remove_task(&mut self, task_id: Uuid) {
+        self.tasks.retain(|task| task.id != task_id);
+        self.update_timestamp();
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -104,6 +555,155 @@ pub struct EvidenceFile {
     pub size: u64,
     pub created_at: DateTime<Utc>,
     pub notes: String,
+    #[serde(default)]
+    pub email: Option<EmailMetadata>,
+    #[serde(default)]
+    pub linked_evidence: Vec<Uuid>, // e.g. attachments extracted from an email
+    #[serde(default)]
+    pub perceptual_hash: Option<u64>,
+    #[serde(default)]
+    pub provenance: Option<Provenance>,
+    #[serde(default)]
+    pub review_status: ReviewStatus,
+    #[serde(default)]
+    pub evidence_date: Option<DateTime<Utc>>, // When the evidence itself was made, e.g. a screenshot's capture time
+    #[serde(default)]
+    pub timestamp: Option<TrustedTimestamp>,
+    #[serde(default)]
+    pub sha256: Option<String>, // Content hash recorded at intake, checked by the store verification
+    #[serde(default)]
+    pub previous_version: Option<Uuid>, // The file this one replaced
+    #[serde(default)]
+    pub superseded_by: Option<Uuid>, // The newer version; superseded files only show in the version history
+}
+
+impl EvidenceFile {
+    pub fn new(person_id: Uuid, file_path: PathBuf, file_type: EvidenceType, original_name: String, size: u64, created_at: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            person_id,
+            file_path,
+            file_type,
+            original_name,
+            size,
+            created_at,
+            notes: String::new(),
+            email: None,
+            linked_evidence: Vec::new(),
+            perceptual_hash: None,
+            provenance: None,
+            review_status: ReviewStatus::New,
+            evidence_date: None,
+            timestamp: None,
+            sha256: None,
+            previous_version: None,
+            superseded_by: None,
+        }
+    }
+
+    /// The files this one replaced, newest first
+    pub fn previous_versions<'a>(&self, files: &'a [EvidenceFile]) -> Vec<&'a EvidenceFile> {
+        let mut versions: Vec<&EvidenceFile> = Vec::new();
+        let mut previous = self.previous_version;
+        // A hand-edited index could loop back on itself
+        while let Some(id) = previous.filter(|_| versions.len() < files.len()) {
+            let Some(file) = files.iter().find(|f| f.id == id) else {
+                break;
+            };
+            versions.push(file);
+            previous = file.previous_version;
+        }
+        versions
+    }
+}
+
+/// How far a piece of evidence has got through triage
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ReviewStatus {
+    #[default]
+    New,
+    Reviewed,
+    Flagged,
+    Irrelevant,
+}
+
+impl ReviewStatus {
+    pub fn all() -> [ReviewStatus; 4] {
+        [ReviewStatus::New, ReviewStatus::Reviewed, ReviewStatus::Flagged, ReviewStatus::Irrelevant]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReviewStatus::New => "New",
+            ReviewStatus::Reviewed => "Reviewed",
+            ReviewStatus::Flagged => "Flagged",
+            ReviewStatus::Irrelevant => "Irrelevant",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ReviewStatus::New => "●",
+            ReviewStatus::Reviewed => "✓",
+            ReviewStatus::Flagged => "⚑",
+            ReviewStatus::Irrelevant => "✕",
+        }
+    }
+}
+
+impl std::fmt::Display for ReviewStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Coordinates {
+    /// Parses decimal degrees written as "latitude, longitude", e.g. "51.5074, -0.1278"
+    pub fn parse(text: &str) -> Option<Self> {
+        let (latitude, longitude) = text.split_once(',')?;
+        let latitude: f64 = latitude.trim().parse().ok()?;
+        let longitude: f64 = longitude.trim().parse().ok()?;
+        ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude))
+            .then_some(Self { latitude, longitude })
+    }
+
+    /// Great-circle distance in kilometres
+    pub fn distance_km(&self, other: &Coordinates) -> f64 {
+        const EARTH_RADIUS_KM: f64 = 6371.0;
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let delta_lat = lat2 - lat1;
+        let delta_lon = (other.longitude - self.longitude).to_radians();
+        let a = (delta_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (delta_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
+/// Where a piece of evidence came from, recorded when it is taken in
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Provenance {
+    pub source_device: Option<String>,
+    pub source_url: Option<String>,
+    pub provided_by: Option<String>,
+    pub obtained_at: Option<String>, // As entered, e.g. "2024-03-01"
+    pub collected_by: Option<String>,
+    pub original_path: String,
+    pub original_modified: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub source_hash: Option<String>, // Digest listed by the tool the file came from, checked at intake, e.g. "MD5 9e10…"
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EmailMetadata {
+    pub from: String,
+    pub to: String,
+    pub date: Option<String>,
+    pub subject: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -113,6 +713,8 @@ pub enum EvidenceType {
     Video,
     Document,
     Quote,
+    Other, // Files with no recognised extension
+    Custom(String), // Folder name of a user-defined category
 }
 
 impl EvidenceType {
@@ -122,18 +724,48 @@ impl EvidenceType {
             "jpg" | "jpeg" | "png" | "gif" | "bmp" | "tiff" | "webp" => Some(EvidenceType::Image),
             "mp3" | "wav" | "flac" | "aac" | "ogg" | "m4a" => Some(EvidenceType::Audio),
             "mp4" | "avi" | "mov" | "wmv" | "flv" | "webm" | "mkv" => Some(EvidenceType::Video),
-            "pdf" | "doc" | "docx" | "txt" | "rtf" => Some(EvidenceType::Document),
+            "pdf" | "doc" | "docx" | "txt" | "md" | "rtf" | "eml" => Some(EvidenceType::Document),
             _ => None,
         }
     }
 
-    pub fn folder_name(&self) -> &'static str {
+    pub fn built_in() -> [EvidenceType; 6] {
+        [EvidenceType::Image, EvidenceType::Audio, EvidenceType::Video, EvidenceType::Document, EvidenceType::Other, EvidenceType::Quote]
+    }
+
+    pub fn folder_name(&self) -> &str {
         match self {
             EvidenceType::Image => "images",
             EvidenceType::Audio => "audio",
             EvidenceType::Video => "videos",
             EvidenceType::Document => "documents",
             EvidenceType::Quote => "quotes",
+            EvidenceType::Other => "other",
+            EvidenceType::Custom(folder_name) => folder_name,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            EvidenceType::Image => "Image",
+            EvidenceType::Audio => "Audio",
+            EvidenceType::Video => "Video",
+            EvidenceType::Document => "Document",
+            EvidenceType::Quote => "Quote",
+            EvidenceType::Other => "Other",
+            EvidenceType::Custom(folder_name) => folder_name,
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            EvidenceType::Image => "🖼",
+            EvidenceType::Audio => "🎵",
+            EvidenceType::Video => "🎬",
+            EvidenceType::Document => "📄",
+            EvidenceType::Quote => "💬",
+            EvidenceType::Other => "📦",
+            EvidenceType::Custom(_) => "📁",
         }
     }
 }
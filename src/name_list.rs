@@ -0,0 +1,44 @@
+use crate::models::Person;
+
+/// Whether a pasted name would add someone new
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameStatus {
+    New,
+    Existing, // Someone with this name is already in the case
+    Repeated, // The name is listed more than once; only its first line counts
+}
+
+/// One non-empty pasted line
+#[derive(Debug, Clone)]
+pub struct ListedName {
+    pub number: usize, // 1-based, as counted in the pasted text
+    pub name: String,
+    pub status: NameStatus,
+}
+
+/// Reads one name per line, comparing them with the existing persons and each other without
+/// regard to case or extra spaces
+pub fn parse(pasted: &str, persons: &[Person]) -> Vec<ListedName> {
+    let key = |name: &str| name.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let existing: Vec<String> = persons.iter().map(|person| key(&person.name)).collect();
+    let mut seen: Vec<String> = Vec::new();
+
+    pasted
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let name = line.split_whitespace().collect::<Vec<_>>().join(" ");
+            let name_key = key(&name);
+            let status = if seen.contains(&name_key) {
+                NameStatus::Repeated
+            } else if existing.contains(&name_key) {
+                NameStatus::Existing
+            } else {
+                NameStatus::New
+            };
+            seen.push(name_key);
+            ListedName { number: index + 1, name, status }
+        })
+        .collect()
+}
@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+
+/// Values available to file name patterns such as `{date}_{person}_{counter}.{ext}`
+pub struct NameContext<'a> {
+    pub date: DateTime<Utc>,
+    pub person: &'a str,
+    pub name: &'a str, // Current file stem
+    pub ext: &'a str,
+    pub counter: usize,
+    pub counter_width: usize,
+}
+
+pub const PATTERN_HELP: &str = "{date} {time} {person} {name} {counter} {ext}";
+
+pub const DEFAULT_SCREENSHOT_PATTERN: &str = "{date}_{time}_screenshot.{ext}";
+
+pub const RECORDING_PATTERN: &str = "{date}_{time}_recording.{ext}";
+
+/// Names screenshot tools give by default, e.g. "Screenshot 2024-05-01 at 13.45.01.png"
+pub fn is_screenshot_name(file_name: &str) -> bool {
+    let name = file_name.to_lowercase();
+    ["screenshot", "screen shot", "bildschirmfoto", "capture d’écran", "capture d'écran", "schermafbeelding", "snip"]
+        .iter()
+        .any(|marker| name.contains(marker))
+}
+
+pub fn render_pattern(pattern: &str, context: &NameContext) -> String {
+    let rendered = pattern
+        .replace("{date}", &context.date.format("%Y-%m-%d").to_string())
+        .replace("{time}", &context.date.format("%H%M%S").to_string())
+        .replace("{person}", context.person)
+        .replace("{name}", context.name)
+        .replace("{counter}", &format!("{:0width$}", context.counter, width = context.counter_width))
+        .replace("{ext}", context.ext);
+
+    sanitize_file_name(&rendered)
+}
+
+/// Replaces characters that aren't allowed in file names on any supported platform
+pub fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+
+    let sanitized = sanitized.trim().trim_matches('.').to_string();
+    if sanitized.is_empty() {
+        "unnamed".to_string()
+    } else {
+        sanitized
+    }
+}
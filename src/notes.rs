@@ -0,0 +1,36 @@
+use crate::models::{EvidenceFile, EvidenceType};
+use crate::naming;
+use chrono::{DateTime, Utc};
+use std::path::Path;
+
+const NOTE_EXTENSIONS: [&str; 2] = ["md", "txt"];
+
+/// Largest note opened in the editor; anything bigger is better off in its own application
+pub const MAX_NOTE_SIZE: u64 = 1024 * 1024;
+
+/// Whether a document can be opened in the note editor
+pub fn is_note(file: &EvidenceFile) -> bool {
+    file.file_type == EvidenceType::Document
+        && file.size <= MAX_NOTE_SIZE
+        && has_note_extension(&file.file_path)
+}
+
+fn has_note_extension(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| NOTE_EXTENSIONS.iter().any(|note| ext.eq_ignore_ascii_case(note)))
+}
+
+/// The name a new note starts with, e.g. "2024-05-01_134501_note.md"
+pub fn default_name(now: DateTime<Utc>) -> String {
+    now.format("%Y-%m-%d_%H%M%S_note.md").to_string()
+}
+
+/// The file name a note is saved under: the name as typed, as Markdown unless it ends in .txt
+pub fn file_name(name: &str) -> String {
+    let name = naming::sanitize_file_name(name);
+    if has_note_extension(Path::new(&name)) {
+        name
+    } else {
+        format!("{}.md", name)
+    }
+}
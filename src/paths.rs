@@ -0,0 +1,32 @@
+use anyhow::{Result, bail};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Whether `name` can be used as a single file or folder name without leaving its parent
+pub fn is_plain_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(components.next(), Some(Component::Normal(_))) && components.next().is_none() && !name.contains(['/', '\\'])
+}
+
+/// Whether `path` is relative and only descends, e.g. `images/photo.jpg` but not `../x` or `/etc/x`
+pub fn is_contained(path: &Path) -> bool {
+    path.components().next().is_some() && path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// Joins a path from an untrusted source (an archive entry, an index file) onto `root`.
+/// Absolute paths and `..` are refused, as are symlinks already on disk along the way,
+/// which could otherwise redirect a write outside of `root`.
+pub fn join_within(root: &Path, relative: &Path) -> Result<PathBuf> {
+    if !is_contained(relative) {
+        bail!("\"{}\" points outside the Evidence folder", relative.display());
+    }
+
+    let mut path = root.to_path_buf();
+    for component in relative.components() {
+        path.push(component);
+        if fs::symlink_metadata(&path).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+            bail!("\"{}\" is a symbolic link", relative.display());
+        }
+    }
+    Ok(path)
+}
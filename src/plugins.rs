@@ -0,0 +1,239 @@
+use crate::audit::{AuditAction, AuditLog};
+use crate::file_manager::FileManager;
+use crate::models::{EvidenceFile, Person, ReviewStatus};
+use crate::settings::Settings;
+use anyhow::{Result, Context, anyhow, bail};
+use directories::ProjectDirs;
+use rhai::{AST, Array, Dynamic, Engine, Map, Scope};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const EXTENSION: &str = "rhai";
+
+// Called when a file is added; may return #{ tags: [...], review_status: "...", notes: "..." }
+const EVIDENCE_ADDED: &str = "on_evidence_added";
+// Called before an .ema export; returning a string stops the export with that message
+const BEFORE_EXPORT: &str = "before_export";
+
+/// A script in the plugins folder
+#[derive(Debug, Clone)]
+pub struct PluginFile {
+    pub name: String, // File name, e.g. "tag_whatsapp.rhai"
+    pub enabled: bool,
+}
+
+/// Plugin scripts live in the config directory, not next to the evidence, since they're code
+pub fn plugins_dir() -> Result<PathBuf> {
+    let project_dirs = ProjectDirs::from("com", "Evidence-Manager", "Evidence-Manager")
+        .context("Failed to get user config directory")?;
+    Ok(project_dirs.config_dir().join("plugins"))
+}
+
+/// Every script in the plugins folder, by name
+pub fn list(settings: &Settings) -> Result<Vec<PluginFile>> {
+    let dir = plugins_dir()?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut plugins: Vec<PluginFile> = fs::read_dir(&dir)
+        .context("Failed to read plugins folder")?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == EXTENSION))
+        .filter_map(|path| path.file_name().map(|name| name.to_string_lossy().to_string()))
+        .map(|name| PluginFile { enabled: settings.enabled_plugins.contains(&name), name })
+        .collect();
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(plugins)
+}
+
+/// What a plugin asked to change about a newly added file
+#[derive(Debug, Default)]
+struct EvidenceChanges {
+    tags: Vec<String>, // Added to the person
+    review_status: Option<ReviewStatus>,
+    notes: Option<String>, // Appended to the file's notes
+}
+
+/// The enabled scripts, compiled. Scripts run sandboxed: they can't reach files or the network,
+/// and are stopped once they run too long or build values that are too large.
+#[derive(Clone)]
+pub struct Plugins {
+    engine: Arc<Engine>,
+    scripts: Vec<(String, Arc<AST>)>,
+}
+
+impl Default for Plugins {
+    fn default() -> Self {
+        Self { engine: Arc::new(sandboxed_engine()), scripts: Vec::new() }
+    }
+}
+
+impl Plugins {
+    /// Compiles the enabled scripts; ones that fail to load are left out and reported
+    pub fn load(settings: &Settings) -> (Self, Vec<String>) {
+        let mut plugins = Self::default();
+        let mut errors = Vec::new();
+        let enabled = match list(settings) {
+            Ok(files) => files.into_iter().filter(|file| file.enabled).collect(),
+            Err(e) => {
+                errors.push(e.to_string());
+                Vec::new()
+            }
+        };
+        let Ok(dir) = plugins_dir() else {
+            return (plugins, errors);
+        };
+        for file in enabled {
+            let compiled = fs::read_to_string(dir.join(&file.name))
+                .map_err(|e| e.to_string())
+                .and_then(|script| plugins.engine.compile(&script).map_err(|e| e.to_string()));
+            match compiled {
+                Ok(ast) => plugins.scripts.push((file.name, Arc::new(ast))),
+                Err(e) => errors.push(format!("{}: {}", file.name, e)),
+            }
+        }
+        (plugins, errors)
+    }
+
+    pub fn count(&self) -> usize {
+        self.scripts.len()
+    }
+
+    /// Runs `on_evidence_added` in each script and applies what they ask for: tags go on the
+    /// person, review status and notes on the file. Returns warnings for scripts that failed.
+    pub fn evidence_added(&self, file_manager: &FileManager, audit_log: &AuditLog, person: &mut Person, file: &mut EvidenceFile) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for (name, ast) in self.with_function(EVIDENCE_ADDED) {
+            let result = self.call(ast, EVIDENCE_ADDED, evidence_map(person, file))
+                .and_then(evidence_changes)
+                .and_then(|changes| apply(file_manager, audit_log, name, person, file, changes));
+            if let Err(e) = result {
+                warnings.push(format!("Plugin {}: {:#}", name, e));
+            }
+        }
+        warnings
+    }
+
+    /// Runs `before_export` in each script; the first objection stops the export
+    pub fn before_export(&self, persons: &[Person]) -> Result<()> {
+        let people: Array = persons.iter().map(|person| Dynamic::from_map(person_map(person))).collect();
+        for (name, ast) in self.with_function(BEFORE_EXPORT) {
+            let result = self.call(ast, BEFORE_EXPORT, people.clone())
+                .with_context(|| format!("Plugin {} failed", name))?;
+            if let Some(objection) = result.into_string().ok().filter(|objection| !objection.trim().is_empty()) {
+                bail!("Plugin {} stopped the export: {}", name, objection);
+            }
+        }
+        Ok(())
+    }
+
+    fn with_function<'a>(&'a self, function: &'a str) -> impl Iterator<Item = (&'a str, &'a AST)> {
+        self.scripts
+            .iter()
+            .filter(move |(_, ast)| ast.iter_functions().any(|f| f.name == function && f.params.len() == 1))
+            .map(|(name, ast)| (name.as_str(), ast.as_ref()))
+    }
+
+    fn call(&self, ast: &AST, function: &str, argument: impl Into<Dynamic>) -> Result<Dynamic> {
+        self.engine.call_fn::<Dynamic>(&mut Scope::new(), ast, function, (argument.into(),))
+            .map_err(|e| anyhow!("{}", e))
+    }
+}
+
+fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(1_000_000);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(1024 * 1024);
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+    engine.disable_symbol("eval");
+    engine.on_print(|message| eprintln!("plugin: {}", message));
+    engine.on_debug(|message, _, _| eprintln!("plugin: {}", message));
+    engine
+}
+
+fn person_map(person: &Person) -> Map {
+    let mut map = Map::new();
+    map.insert("id".into(), person.id.to_string().into());
+    map.insert("name".into(), person.name.clone().into());
+    map.insert("tags".into(), person.tags.iter().cloned().map(Dynamic::from).collect::<Array>().into());
+    map.insert("case_status".into(), person.case_status.label().into());
+    map.insert("priority".into(), person.priority.label().into());
+    map.insert("legal_hold".into(), person.legal_hold.into());
+    map
+}
+
+fn evidence_map(person: &Person, file: &EvidenceFile) -> Map {
+    let mut map = Map::new();
+    map.insert("name".into(), file.original_name.clone().into());
+    map.insert("category".into(), file.file_type.folder_name().into());
+    map.insert("size".into(), (file.size as i64).into());
+    map.insert("source_url".into(), file.provenance.as_ref().and_then(|p| p.source_url.clone()).unwrap_or_default().into());
+    map.insert("person".into(), person_map(person).into());
+    map
+}
+
+/// Reads the map `on_evidence_added` returned; returning nothing changes nothing
+fn evidence_changes(result: Dynamic) -> Result<EvidenceChanges> {
+    if result.is_unit() {
+        return Ok(EvidenceChanges::default());
+    }
+    let map = result.try_cast::<Map>()
+        .context("on_evidence_added must return a map or nothing")?;
+    let mut changes = EvidenceChanges::default();
+    if let Some(tags) = map.get("tags") {
+        let tags = tags.clone().into_array().map_err(|_| anyhow!("tags must be an array of strings"))?;
+        for tag in tags {
+            changes.tags.push(tag.into_string().map_err(|_| anyhow!("tags must be an array of strings"))?);
+        }
+    }
+    if let Some(status) = map.get("review_status") {
+        let status = status.clone().into_string().map_err(|_| anyhow!("review_status must be a string"))?;
+        changes.review_status = Some(
+            ReviewStatus::all()
+                .into_iter()
+                .find(|s| s.label().eq_ignore_ascii_case(&status))
+                .with_context(|| format!("Unknown review status \"{}\"", status))?
+        );
+    }
+    if let Some(notes) = map.get("notes") {
+        changes.notes = Some(notes.clone().into_string().map_err(|_| anyhow!("notes must be a string"))?);
+    }
+    Ok(changes)
+}
+
+fn apply(file_manager: &FileManager, audit_log: &AuditLog, plugin: &str, person: &mut Person, file: &mut EvidenceFile, changes: EvidenceChanges) -> Result<()> {
+    let new_tags: Vec<String> = changes.tags
+        .into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty() && !person.tags.contains(tag))
+        .collect();
+    if !new_tags.is_empty() {
+        person.tags.extend(new_tags.iter().cloned());
+        person.update_timestamp();
+        file_manager.save_person_data(person)?;
+        audit_log.record(Some(person), AuditAction::PersonUpdated, format!("Tagged {} by plugin {}", new_tags.join(", "), plugin))?;
+    }
+
+    let mut details = Vec::new();
+    if let Some(status) = changes.review_status {
+        file.review_status = status;
+        details.push(format!("marked as {}", status.label()));
+    }
+    if let Some(notes) = changes.notes.filter(|notes| !notes.trim().is_empty()) {
+        if !file.notes.is_empty() {
+            file.notes.push('\n');
+        }
+        file.notes.push_str(notes.trim());
+        details.push("notes added".to_string());
+    }
+    if !details.is_empty() {
+        file_manager.update_evidence_metadata(person, file)?;
+        audit_log.record(Some(person), AuditAction::EvidenceUpdated, format!("{} {} by plugin {}", file.original_name, details.join(", "), plugin))?;
+    }
+    Ok(())
+}
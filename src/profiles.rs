@@ -0,0 +1,78 @@
+use crate::hashing;
+use anyhow::{Result, anyhow, bail};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+
+const PBKDF2_ITERATIONS: u32 = 100_000;
+const SALT_LENGTH: usize = 16;
+const HASH_LENGTH: usize = 32;
+
+/// Environment variable holding the admin passphrase for commands run without the window
+pub const PASSPHRASE_VARIABLE: &str = "EVIDENCE_MANAGER_PASSPHRASE";
+
+/// Who is using the app: admins can change anything, viewers can browse and export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Admin,
+    Viewer,
+}
+
+impl Role {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Role::Admin => "Admin",
+            Role::Viewer => "Viewer",
+        }
+    }
+}
+
+/// The admin passphrase as a salted PBKDF2-SHA256 hash, kept in the settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Passphrase {
+    pub salt: String, // Hex
+    pub hash: String, // Hex
+    pub iterations: u32,
+}
+
+impl Passphrase {
+    pub fn new(passphrase: &str) -> Result<Self> {
+        if passphrase.trim().is_empty() {
+            bail!("The passphrase can't be empty");
+        }
+        let mut salt = [0u8; SALT_LENGTH];
+        SystemRandom::new().fill(&mut salt)
+            .map_err(|_| anyhow!("Failed to generate a salt"))?;
+        let mut hash = [0u8; HASH_LENGTH];
+        pbkdf2::derive(pbkdf2::PBKDF2_HMAC_SHA256, iterations(PBKDF2_ITERATIONS)?, &salt, passphrase.as_bytes(), &mut hash);
+        Ok(Self {
+            salt: hashing::to_hex(&salt),
+            hash: hashing::to_hex(&hash),
+            iterations: PBKDF2_ITERATIONS,
+        })
+    }
+
+    pub fn matches(&self, passphrase: &str) -> bool {
+        let (Ok(salt), Ok(hash), Ok(iterations)) = (hashing::from_hex(&self.salt), hashing::from_hex(&self.hash), iterations(self.iterations)) else {
+            return false;
+        };
+        pbkdf2::verify(pbkdf2::PBKDF2_HMAC_SHA256, iterations, &salt, passphrase.as_bytes(), &hash).is_ok()
+    }
+}
+
+/// Commands that change the store take the Admin profile, as in the app: once a passphrase is
+/// set, it has to be given in `PASSPHRASE_VARIABLE`
+pub fn require_admin(passphrase: Option<&Passphrase>) -> Result<()> {
+    if let Some(passphrase) = passphrase {
+        let given = std::env::var(PASSPHRASE_VARIABLE).unwrap_or_default();
+        if !passphrase.matches(&given) {
+            bail!("An admin passphrase is set; put it in {}", PASSPHRASE_VARIABLE);
+        }
+    }
+    Ok(())
+}
+
+fn iterations(count: u32) -> Result<NonZeroU32> {
+    NonZeroU32::new(count).ok_or_else(|| anyhow!("Invalid passphrase iteration count"))
+}
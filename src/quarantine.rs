@@ -0,0 +1,208 @@
+use crate::hashing;
+use crate::models::{EvidenceType, Person};
+use crate::sniff;
+use anyhow::{Result, Context, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+// Each held file sits in a folder named by its ID, under its own name, outside every person's
+// folder until it's accepted
+const QUARANTINE_DIR: &str = ".quarantine";
+const LIST_FILE: &str = "quarantine.json";
+
+const SHORTCUT_EXTENSIONS: [&str; 5] = ["lnk", "url", "desktop", "webloc", "scf"];
+const EXECUTABLE_EXTENSIONS: [&str; 15] = [
+    "exe", "dll", "com", "scr", "msi", "msp", "cpl", "pif", "app", "apk", "jar", "appimage", "deb", "rpm", "pkg",
+];
+const SCRIPT_EXTENSIONS: [&str; 18] = [
+    "bat", "cmd", "ps1", "psm1", "vbs", "vbe", "js", "jse", "wsf", "wsh", "hta", "sh", "bash", "command", "py", "pl", "scpt", "reg",
+];
+
+/// A file held back from intake because it could run code when opened
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantinedFile {
+    pub id: Uuid,
+    pub person_id: Uuid,
+    pub original_name: String,
+    pub original_path: String,
+    pub original_modified: Option<DateTime<Utc>>,
+    pub evidence_type: EvidenceType,
+    pub reason: String, // What it looks like, e.g. "Windows executable"
+    pub sha256: String, // Of the held copy, shown before accepting it
+    pub size: u64,
+    pub quarantined_at: DateTime<Utc>,
+}
+
+/// Why a file should be quarantined rather than taken in: executables, scripts and shortcuts,
+/// by extension or by their first bytes whatever the extension says
+pub fn reason(path: &Path) -> Option<&'static str> {
+    reason_by_extension(path).or_else(|| sniff::detect_executable_file(path))
+}
+
+/// As `reason`, for content held in memory, such as an email attachment
+pub fn reason_for_content(name: &str, content: &[u8]) -> Option<&'static str> {
+    reason_by_extension(Path::new(name)).or_else(|| sniff::detect_executable(content))
+}
+
+fn reason_by_extension(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if SHORTCUT_EXTENSIONS.contains(&extension.as_str()) {
+        return Some("Shortcut");
+    }
+    if EXECUTABLE_EXTENSIONS.contains(&extension.as_str()) {
+        return Some("Executable or installer");
+    }
+    if SCRIPT_EXTENSIONS.contains(&extension.as_str()) {
+        return Some("Script");
+    }
+    None
+}
+
+pub struct Quarantine {
+    dir: PathBuf,
+}
+
+impl Quarantine {
+    pub fn new(evidence_dir: &Path) -> Self {
+        Self { dir: evidence_dir.join(QUARANTINE_DIR) }
+    }
+
+    /// Everything held, oldest first
+    pub fn list(&self) -> Result<Vec<QuarantinedFile>> {
+        let path = self.dir.join(LIST_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let json = fs::read_to_string(&path)
+            .context("Failed to read quarantine list")?;
+        serde_json::from_str(&json)
+            .context("Failed to parse quarantine list")
+    }
+
+    fn save(&self, files: &[QuarantinedFile]) -> Result<()> {
+        let json = serde_json::to_string_pretty(files)
+            .context("Failed to serialize quarantine list")?;
+        fs::write(self.dir.join(LIST_FILE), json)
+            .context("Failed to write quarantine list")
+    }
+
+    /// Copies a file into quarantine for a person; the original is left untouched
+    pub fn hold(&self, person: &Person, source_path: &Path, evidence_type: EvidenceType, reason: &str) -> Result<QuarantinedFile> {
+        if !fs::metadata(source_path).context("Failed to read source file")?.is_file() {
+            bail!("\"{}\" is not a regular file", source_path.display());
+        }
+        let original_name = source_path.file_name()
+            .context("Source file has no name")?
+            .to_string_lossy()
+            .to_string();
+        let id = Uuid::new_v4();
+        let held = self.held_path(id, &original_name)?;
+        fs::copy(source_path, &held)
+            .context("Failed to copy file into quarantine")?;
+
+        self.record(QuarantinedFile {
+            id,
+            person_id: person.id,
+            original_name,
+            original_path: source_path.display().to_string(),
+            original_modified: fs::metadata(source_path).and_then(|m| m.modified()).ok().map(DateTime::<Utc>::from),
+            evidence_type,
+            reason: reason.to_string(),
+            sha256: String::new(),
+            size: 0,
+            quarantined_at: Utc::now(),
+        })
+    }
+
+    /// Writes in-memory content into quarantine for a person. `origin` says where it came from,
+    /// e.g. the email it was attached to.
+    pub fn hold_bytes(&self, person: &Person, file_name: &str, content: &[u8], evidence_type: EvidenceType, reason: &str, origin: String) -> Result<QuarantinedFile> {
+        // Never trust names from inside a file to stay within the folder
+        let original_name = Path::new(file_name)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| "unnamed".to_string());
+        let id = Uuid::new_v4();
+        let held = self.held_path(id, &original_name)?;
+        fs::write(&held, content)
+            .context("Failed to write file into quarantine")?;
+
+        self.record(QuarantinedFile {
+            id,
+            person_id: person.id,
+            original_name,
+            original_path: origin,
+            original_modified: None,
+            evidence_type,
+            reason: reason.to_string(),
+            sha256: String::new(),
+            size: 0,
+            quarantined_at: Utc::now(),
+        })
+    }
+
+    /// Creates the folder for a newly held file and returns where its copy goes
+    fn held_path(&self, id: Uuid, original_name: &str) -> Result<PathBuf> {
+        let folder = self.dir.join(id.to_string());
+        fs::create_dir_all(&folder)
+            .context("Failed to create quarantine folder")?;
+        Ok(folder.join(original_name))
+    }
+
+    /// Protects a copy just written into quarantine, records its hash and size and adds it to the list
+    fn record(&self, mut file: QuarantinedFile) -> Result<QuarantinedFile> {
+        let held = self.path(&file);
+        // Read-only, so it isn't changed by accident while it waits
+        let mut permissions = fs::metadata(&held).context("Failed to read quarantined file")?.permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(&held, permissions)
+            .context("Failed to protect quarantined file")?;
+
+        file.sha256 = hashing::sha256_file(&held)?;
+        file.size = fs::metadata(&held).context("Failed to read quarantined file")?.len();
+        let mut files = self.list()?;
+        files.push(file.clone());
+        self.save(&files)?;
+        Ok(file)
+    }
+
+    /// Where the held copy is
+    pub fn path(&self, file: &QuarantinedFile) -> PathBuf {
+        self.dir.join(file.id.to_string()).join(&file.original_name)
+    }
+
+    /// Fails if the held copy no longer has the hash shown when it was quarantined
+    pub fn check(&self, file: &QuarantinedFile) -> Result<()> {
+        if hashing::sha256_file(&self.path(file))? != file.sha256 {
+            bail!("{} has changed in quarantine", file.original_name);
+        }
+        Ok(())
+    }
+
+    /// Deletes the held copy and drops it from the list, once accepted or discarded
+    pub fn release(&self, id: Uuid) -> Result<()> {
+        let folder = self.dir.join(id.to_string());
+        if folder.exists() {
+            // Read-only files can't be removed on Windows
+            for entry in fs::read_dir(&folder).context("Failed to read quarantine folder")?.flatten() {
+                if let Ok(metadata) = entry.metadata() {
+                    let mut permissions = metadata.permissions();
+                    #[allow(clippy::permissions_set_readonly_false)]
+                    permissions.set_readonly(false);
+                    let _ = fs::set_permissions(entry.path(), permissions);
+                }
+            }
+            fs::remove_dir_all(&folder)
+                .context("Failed to delete quarantined file")?;
+        }
+        let mut files = self.list()?;
+        files.retain(|f| f.id != id);
+        self.save(&files)
+    }
+}
@@ -0,0 +1,323 @@
+use crate::models::{CaseRole, CaseStatus, EvidenceFile, Person, Priority, ReviewStatus};
+use chrono::{Datelike, Duration, Local, NaiveDate};
+
+/// A parsed search such as `type:phone value:555* person:"John" after:2023-01-01 tag:verified`.
+/// Every term must match. Dates may also be relative: `today`, `week`, `month`, `year` or `30d`. Text matches ignore case and look for a substring, unless the
+/// pattern contains `*`, in which case it must match the whole text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Query {
+    names: Vec<String>, // Bare words and person:
+    info_types: Vec<String>,
+    info_values: Vec<String>,
+    tags: Vec<String>,
+    quotes: Vec<String>,
+    status: Option<CaseStatus>,
+    priority: Option<Priority>,
+    role: Option<CaseRole>,
+    evidence_types: Vec<String>,
+    review: Option<ReviewStatus>,
+    after: Option<NaiveDate>,
+    before: Option<NaiveDate>,
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut query = Query::default();
+        for token in tokenize(input) {
+            let Some((field, value)) = token.split_once(':').filter(|(field, _)| !field.is_empty() && !field.contains(' ')) else {
+                query.names.push(token.to_lowercase());
+                continue;
+            };
+            if value.is_empty() {
+                return Err(format!("Missing value after \"{}:\"", field));
+            }
+            let value = value.to_lowercase();
+            match field.to_lowercase().as_str() {
+                "person" | "name" => query.names.push(value),
+                "type" => query.info_types.push(value),
+                "value" => query.info_values.push(value),
+                "tag" => query.tags.push(value),
+                "quote" => query.quotes.push(value),
+                "status" => {
+                    query.status = Some(
+                        CaseStatus::all().into_iter()
+                            .find(|s| matches_text(&value, s.label()))
+                            .ok_or_else(|| format!("Unknown status \"{}\"", value))?,
+                    );
+                }
+                "priority" => {
+                    query.priority = Some(
+                        Priority::all().into_iter()
+                            .find(|p| matches_text(&value, p.label()))
+                            .ok_or_else(|| format!("Unknown priority \"{}\"", value))?,
+                    );
+                }
+                "role" => {
+                    query.role = Some(
+                        CaseRole::all().into_iter()
+                            .find(|r| matches_text(&value, r.label()))
+                            .ok_or_else(|| format!("Unknown role \"{}\"", value))?,
+                    );
+                }
+                "evidence" => query.evidence_types.push(value),
+                "review" => {
+                    query.review = Some(
+                        ReviewStatus::all().into_iter()
+                            .find(|s| matches_text(&value, s.label()))
+                            .ok_or_else(|| format!("Unknown review status \"{}\"", value))?,
+                    );
+                }
+                "after" => query.after = Some(parse_date(&value)?),
+                "before" => query.before = Some(parse_date(&value)?),
+                other => return Err(format!("Unknown search field \"{}\"", other)),
+            }
+        }
+        Ok(query)
+    }
+
+    /// Whether matching needs the person's evidence files, which aren't kept in memory for everyone
+    pub fn uses_evidence(&self) -> bool {
+        !self.evidence_types.is_empty() || self.review.is_some()
+    }
+
+    pub fn matches(&self, person: &Person, evidence_files: &[EvidenceFile]) -> bool {
+        if !self.names.iter().all(|pattern| matches_text(pattern, &person.name))
+            || !self.tags.iter().all(|pattern| person.tags.iter().any(|tag| matches_text(pattern, tag)))
+            || !self.quotes.iter().all(|pattern| person.quotes.iter().any(|quote| matches_text(pattern, &quote.quote)))
+            || self.status.is_some_and(|status| person.case_status != status)
+            || self.priority.is_some_and(|priority| person.priority != priority)
+            || self.role.is_some_and(|role| person.role != role)
+        {
+            return false;
+        }
+
+        // Evidence terms take the dates for themselves: "flagged images added this month"
+        if self.uses_evidence()
+            && !evidence_files.iter().any(|file| {
+                self.evidence_types.iter().all(|pattern| {
+                    matches_text(pattern, file.file_type.label()) || matches_text(pattern, file.file_type.folder_name())
+                })
+                    && self.review.is_none_or(|review| file.review_status == review)
+                    && self.in_date_range(file.created_at.date_naive())
+            })
+        {
+            return false;
+        }
+
+        // Type, value and date terms must all hold for the same information entry,
+        // so `type:phone value:555*` doesn't match a phone number plus an unrelated 555 value
+        if !self.info_types.is_empty() || !self.info_values.is_empty() {
+            return person.information.iter().any(|info| {
+                self.info_types.iter().all(|pattern| matches_text(pattern, &info.info_type))
+                    && self.info_values.iter().all(|pattern| {
+                        matches_text(pattern, &info.value) || matches_text(pattern, &info.match_value())
+                    })
+                    && (self.uses_evidence() || self.in_date_range(info.created_at.date_naive()))
+            });
+        }
+
+        if self.uses_evidence() || (self.after.is_none() && self.before.is_none()) {
+            return true;
+        }
+        // Otherwise any dated record of the person counts
+        std::iter::once(person.created_at.date_naive())
+            .chain(person.information.iter().map(|info| info.created_at.date_naive()))
+            .chain(person.quotes.iter().filter_map(|quote| NaiveDate::parse_from_str(quote.date.trim(), "%Y-%m-%d").ok()))
+            .any(|date| self.in_date_range(date))
+    }
+
+    fn in_date_range(&self, date: NaiveDate) -> bool {
+        self.after.is_none_or(|after| date >= after) && self.before.is_none_or(|before| date <= before)
+    }
+}
+
+/// Relative dates are resolved on every parse, so saved searches move along with the calendar
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    let today = Local::now().date_naive();
+    match value {
+        "today" => return Ok(today),
+        "week" => return Ok(today - Duration::days(today.weekday().num_days_from_monday() as i64)),
+        "month" => return Ok(today.with_day(1).unwrap_or(today)),
+        "year" => return Ok(today.with_ordinal(1).unwrap_or(today)),
+        _ => {}
+    }
+    if let Some(days) = value.strip_suffix('d').and_then(|days| days.parse::<i64>().ok()) {
+        return Ok(today - Duration::days(days));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("\"{}\" is not a date (YYYY-MM-DD, today, week, month, year or e.g. 30d)", value))
+}
+
+/// Splits on whitespace, keeping double-quoted phrases together and dropping the quotes
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in input.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// `pattern` is already lowercase
+fn matches_text(pattern: &str, text: &str) -> bool {
+    let text = text.to_lowercase();
+    if !pattern.contains('*') {
+        return text.contains(pattern);
+    }
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !text.starts_with(first) || text.len() < first.len() + last.len() || !text.ends_with(last) {
+        return false;
+    }
+    // Middle parts must appear in order between the fixed start and end
+    let mut remaining = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match remaining.find(part) {
+            Some(index) => remaining = &remaining[index + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EvidenceType;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn person() -> Person {
+        let mut person = Person::new("John Smith".to_string());
+        person.tags.push("Verified".to_string());
+        person.case_status = CaseStatus::OnHold;
+        person.add_information("Phone".to_string(), "555-0142".to_string());
+        person.add_information("Email".to_string(), "john.smith@example.com".to_string());
+        person.add_quote("I was home all night".to_string(), "2020-05-01".to_string(), Some("21:30".to_string()), None, None, Vec::new());
+        person
+    }
+
+    fn flagged_image(person: &Person) -> EvidenceFile {
+        let mut file = EvidenceFile::new(person.id, PathBuf::from("John_Smith/images/door.jpg"), EvidenceType::Image, "door.jpg".to_string(), 2048, Utc::now());
+        file.review_status = ReviewStatus::Flagged;
+        file
+    }
+
+    fn matches(query: &str, person: &Person, evidence_files: &[EvidenceFile]) -> bool {
+        Query::parse(query).unwrap().matches(person, evidence_files)
+    }
+
+    #[test]
+    fn tokenize_keeps_quoted_phrases() {
+        assert_eq!(
+            tokenize(r#"  person:"John Smith"   tag:verified "home all" "#),
+            ["person:John Smith", "tag:verified", "home all"],
+        );
+        assert!(tokenize("   ").is_empty());
+    }
+
+    #[test]
+    fn parse_reads_each_field() {
+        let query = Query::parse(r#"John person:"J Smith" type:Phone value:555* tag:VERIFIED quote:home status:"on hold" priority:normal evidence:image review:flagged after:2020-01-01 before:2020-12-31"#).unwrap();
+        assert_eq!(query.names, ["john", "j smith"]);
+        assert_eq!(query.info_types, ["phone"]);
+        assert_eq!(query.info_values, ["555*"]);
+        assert_eq!(query.tags, ["verified"]);
+        assert_eq!(query.quotes, ["home"]);
+        assert_eq!(query.status, Some(CaseStatus::OnHold));
+        assert_eq!(query.priority, Some(Priority::Normal));
+        assert_eq!(query.evidence_types, ["image"]);
+        assert_eq!(query.review, Some(ReviewStatus::Flagged));
+        assert_eq!(query.after, NaiveDate::from_ymd_opt(2020, 1, 1));
+        assert_eq!(query.before, NaiveDate::from_ymd_opt(2020, 12, 31));
+        assert!(query.uses_evidence());
+        assert_eq!(Query::parse("").unwrap(), Query::default());
+    }
+
+    #[test]
+    fn parse_reports_bad_terms() {
+        assert_eq!(Query::parse("tag:").unwrap_err(), "Missing value after \"tag:\"");
+        assert_eq!(Query::parse("colour:red").unwrap_err(), "Unknown search field \"colour\"");
+        assert_eq!(Query::parse("status:archived").unwrap_err(), "Unknown status \"archived\"");
+        assert!(Query::parse("after:2020-13-01").is_err());
+        assert!(Query::parse("before:yesterday").is_err());
+        // A phrase with a space before its colon isn't a field
+        assert_eq!(Query::parse(r#""meeting at: noon""#).unwrap().names, ["meeting at: noon"]);
+    }
+
+    #[test]
+    fn relative_dates() {
+        let today = Local::now().date_naive();
+        assert_eq!(parse_date("today"), Ok(today));
+        assert_eq!(parse_date("30d"), Ok(today - Duration::days(30)));
+        assert_eq!(parse_date("month").unwrap().day(), 1);
+        assert_eq!(parse_date("year").unwrap().ordinal(), 1);
+        assert_eq!(parse_date("week").unwrap().weekday(), chrono::Weekday::Mon);
+    }
+
+    #[test]
+    fn wildcards_match_the_whole_text() {
+        assert!(matches_text("smith", "John Smith"));
+        assert!(matches_text("john*", "John Smith"));
+        assert!(!matches_text("smith*", "John Smith"));
+        assert!(matches_text("*smith", "John Smith"));
+        assert!(matches_text("j*n*h", "John Smith"));
+        assert!(!matches_text("j*x*h", "John Smith"));
+        // The fixed start and end can't overlap
+        assert!(!matches_text("ab*ba", "aba"));
+        assert!(matches_text("*", ""));
+    }
+
+    #[test]
+    fn matches_person_details() {
+        let person = person();
+        assert!(matches("smith", &person, &[]));
+        assert!(matches("tag:verif* status:\"on hold\"", &person, &[]));
+        assert!(matches("quote:\"home all night\"", &person, &[]));
+        assert!(!matches("smith status:closed", &person, &[]));
+        assert!(!matches("tag:unverified", &person, &[]));
+    }
+
+    #[test]
+    fn type_and_value_must_match_the_same_entry() {
+        let person = person();
+        assert!(matches("type:phone value:555*", &person, &[]));
+        assert!(matches("type:email value:*@example.com", &person, &[]));
+        assert!(!matches("type:email value:555*", &person, &[]));
+    }
+
+    #[test]
+    fn evidence_terms_need_a_matching_file() {
+        let person = person();
+        let files = [flagged_image(&person)];
+        assert!(matches("evidence:image review:flagged", &person, &files));
+        assert!(matches("evidence:images", &person, &files));
+        assert!(!matches("evidence:image review:reviewed", &person, &files));
+        assert!(!matches("evidence:image", &person, &[]));
+        // Dates then apply to the file, added just now
+        assert!(matches("evidence:image after:30d", &person, &files));
+        assert!(!matches("evidence:image before:2020-12-31", &person, &files));
+    }
+
+    #[test]
+    fn dates_match_any_dated_record() {
+        let person = person();
+        // Only the quote is from 2020
+        assert!(matches("after:2020-04-01 before:2020-06-01", &person, &[]));
+        assert!(!matches("before:2019-12-31", &person, &[]));
+        assert!(matches("after:30d", &person, &[]));
+    }
+}
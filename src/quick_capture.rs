@@ -0,0 +1,42 @@
+use anyhow::{Result, Context};
+use global_hotkey::hotkey::HotKey;
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+use iced::Size;
+
+/// The window shrinks to this while quick capture is open
+pub const WINDOW_SIZE: Size = Size::new(420.0, 300.0);
+
+/// The system-wide shortcut that brings up quick capture; registered for as long as it's kept
+pub struct Hotkey {
+    _manager: GlobalHotKeyManager,
+    id: u32,
+}
+
+impl Hotkey {
+    /// Registers a shortcut such as "Ctrl+Shift+E". Must be called on the main thread, before
+    /// the window opens.
+    pub fn register(shortcut: &str) -> Result<Self> {
+        let hotkey: HotKey = shortcut.parse()
+            .with_context(|| format!("\"{}\" isn't a valid shortcut", shortcut))?;
+        let manager = GlobalHotKeyManager::new()
+            .context("Failed to set up global shortcuts")?;
+        manager.register(hotkey)
+            .with_context(|| format!("Failed to register {}; another app may be using it", shortcut))?;
+        Ok(Self { _manager: manager, id: hotkey.id() })
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+/// Whether the shortcut with this id was pressed since the last check
+pub fn hotkey_pressed(id: u32) -> bool {
+    let mut pressed = false;
+    while let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
+        if event.id == id && event.state == HotKeyState::Pressed {
+            pressed = true;
+        }
+    }
+    pressed
+}
@@ -0,0 +1,61 @@
+use chrono::NaiveTime;
+
+/// A quote read from one pasted line
+#[derive(Debug, Clone)]
+pub struct PastedQuote {
+    pub date: String,
+    pub time: Option<String>, // HH:MM
+    pub place: Option<String>,
+    pub text: String,
+}
+
+/// One non-empty pasted line: the quote it holds, or why it can't be used
+#[derive(Debug, Clone)]
+pub struct PastedLine {
+    pub number: usize, // 1-based, as counted in the pasted text
+    pub quote: Result<PastedQuote, String>,
+}
+
+/// Reads one quote per line, written as `text`, `date | text` or `date | place | text`. Lines
+/// without a date get `default_date`; a date may carry a time, e.g. "2024-05-01 13:45".
+pub fn parse(pasted: &str, default_date: &str) -> Vec<PastedLine> {
+    pasted
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| PastedLine { number: index + 1, quote: parse_line(line, default_date) })
+        .collect()
+}
+
+fn parse_line(line: &str, default_date: &str) -> Result<PastedQuote, String> {
+    let parts: Vec<&str> = line.splitn(3, '|').map(str::trim).collect();
+    let (date, place, text) = match parts.as_slice() {
+        [text] => ("", "", *text),
+        [date, text] => (*date, "", *text),
+        [date, place, text] => (*date, *place, *text),
+        _ => unreachable!("splitn(3) gives one to three parts"),
+    };
+    if text.is_empty() {
+        return Err("No quote text".to_string());
+    }
+
+    let (date, time) = match date.rsplit_once(' ') {
+        Some((day, time)) if is_time(time) => (day.trim(), Some(time.to_string())),
+        _ => (date, None),
+    };
+    let date = if date.is_empty() { default_date.trim() } else { date };
+    if date.is_empty() {
+        return Err("No date, and no date to use for lines without one".to_string());
+    }
+
+    Ok(PastedQuote {
+        date: date.to_string(),
+        time,
+        place: Some(place.to_string()).filter(|place| !place.is_empty()),
+        text: text.to_string(),
+    })
+}
+
+fn is_time(value: &str) -> bool {
+    NaiveTime::parse_from_str(value, "%H:%M").is_ok() || NaiveTime::parse_from_str(value, "%H:%M:%S").is_ok()
+}
@@ -0,0 +1,130 @@
+use anyhow::{Result, Context, anyhow};
+use chrono::{DateTime, Utc};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SizedSample};
+use std::fs;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+type WavWriter = hound::WavWriter<BufWriter<fs::File>>;
+
+/// A microphone recording in progress. Audio is written to a WAV file in the system's temp
+/// folder, which becomes evidence once the recording is stopped.
+pub struct Recording {
+    pub started_at: DateTime<Utc>,
+    pub path: PathBuf,
+    stop: Arc<AtomicBool>,
+    // The audio stream can't leave the thread it was opened on, so it lives and ends there
+    thread: JoinHandle<Result<()>>,
+}
+
+impl Recording {
+    /// Starts recording from the default microphone into a file with the given name. Returns
+    /// once the microphone is open, so a missing or busy one is reported straight away.
+    pub fn start(file_name: &str) -> Result<Self> {
+        let path = std::env::temp_dir().join(file_name);
+        let stop = Arc::new(AtomicBool::new(false));
+        let (ready, opened) = mpsc::channel();
+
+        let thread = thread::spawn({
+            let path = path.clone();
+            let stop = stop.clone();
+            move || {
+                let (stream, writer) = match open_microphone(&path) {
+                    Ok(opened) => {
+                        let _ = ready.send(Ok(()));
+                        opened
+                    }
+                    Err(e) => {
+                        let _ = ready.send(Err(e));
+                        return Ok(());
+                    }
+                };
+                while !stop.load(Ordering::Relaxed) {
+                    thread::sleep(Duration::from_millis(100));
+                }
+                drop(stream);
+                let writer = writer.lock()
+                    .map_err(|_| anyhow!("The recording was interrupted"))?
+                    .take();
+                if let Some(writer) = writer {
+                    writer.finalize().context("Failed to finish recording")?;
+                }
+                Ok(())
+            }
+        });
+
+        opened.recv()
+            .map_err(|_| anyhow!("The recorder stopped unexpectedly"))??;
+        Ok(Self { started_at: Utc::now(), path, stop, thread })
+    }
+
+    /// Ends the recording and returns the finished file
+    pub fn stop(self) -> Result<PathBuf> {
+        self.stop.store(true, Ordering::Relaxed);
+        self.thread.join()
+            .map_err(|_| anyhow!("The recorder stopped unexpectedly"))??;
+        Ok(self.path)
+    }
+
+    /// Ends the recording and deletes what was recorded
+    pub fn discard(self) {
+        if let Ok(path) = self.stop() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+fn open_microphone(path: &Path) -> Result<(cpal::Stream, Arc<Mutex<Option<WavWriter>>>)> {
+    let device = cpal::default_host()
+        .default_input_device()
+        .context("No microphone found")?;
+    let config = device.default_input_config()
+        .context("Failed to read the microphone's settings")?;
+
+    // Whatever the microphone delivers is stored as 16-bit PCM, which every player can open
+    let spec = hound::WavSpec {
+        channels: config.channels(),
+        sample_rate: config.sample_rate().0,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let writer = Arc::new(Mutex::new(Some(
+        hound::WavWriter::create(path, spec).context("Failed to create recording file")?
+    )));
+
+    let stream_config = config.config();
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::I16 => input_stream::<i16>(&device, &stream_config, writer.clone()),
+        cpal::SampleFormat::U16 => input_stream::<u16>(&device, &stream_config, writer.clone()),
+        cpal::SampleFormat::I32 => input_stream::<i32>(&device, &stream_config, writer.clone()),
+        cpal::SampleFormat::F32 => input_stream::<f32>(&device, &stream_config, writer.clone()),
+        format => Err(anyhow!("The microphone's sample format ({}) isn't supported", format)),
+    }?;
+    stream.play().context("Failed to start recording")?;
+    Ok((stream, writer))
+}
+
+fn input_stream<T>(device: &cpal::Device, config: &cpal::StreamConfig, writer: Arc<Mutex<Option<WavWriter>>>) -> Result<cpal::Stream>
+where
+    T: SizedSample,
+    i16: FromSample<T>,
+{
+    device.build_input_stream(
+        config,
+        move |samples: &[T], _: &cpal::InputCallbackInfo| {
+            if let Ok(mut writer) = writer.lock() && let Some(writer) = writer.as_mut() {
+                for &sample in samples {
+                    let _ = writer.write_sample(sample.to_sample::<i16>());
+                }
+            }
+        },
+        |e| eprintln!("Recording error: {}", e),
+        None,
+    )
+    .context("Failed to open the microphone")
+}
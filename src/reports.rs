@@ -0,0 +1,799 @@
+use crate::models::{CaseRole, EvidenceFile, Person};
+use crate::dates::DateDisplay;
+use crate::settings::Settings;
+use crate::board::{Board, Card, CardContent};
+use crate::links;
+use crate::audit::{self, AuditEntry};
+use crate::export_import::csv_field;
+use anyhow::{Result, Context, bail};
+use chrono::Local;
+use lopdf::content::{Content, Operation};
+use lopdf::{Dictionary, Object, Stream};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+// A4 in points
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+const BODY_SIZE: f32 = 9.0;
+const FOOTER_SIZE: f32 = 8.0;
+
+/// One printed line; tables are lines with several cells at fixed offsets
+struct Line {
+    cells: Vec<(f32, String)>,
+    size: f32,
+    bold: bool,
+}
+
+impl Line {
+    fn text(text: impl Into<String>, size: f32, bold: bool) -> Self {
+        Self { cells: vec![(0.0, text.into())], size, bold }
+    }
+
+    fn row(cells: Vec<(f32, String)>, bold: bool) -> Self {
+        Self { cells, size: BODY_SIZE, bold }
+    }
+
+    fn blank() -> Self {
+        Self { cells: Vec::new(), size: BODY_SIZE, bold: false }
+    }
+}
+
+/// Placeholders available in a template's title, header and footer
+pub const TEMPLATE_PLACEHOLDERS: &str = "{name} {role} {status} {priority} {date}";
+
+/// Section names as written in template files
+pub const SECTION_NAMES: &str = "information, custom_fields, tags, notes, quotes, tasks, checklists, evidence";
+
+/// Parts of a report, printed in the order a template lists them
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportSection {
+    Information,
+    CustomFields,
+    Tags,
+    Notes,
+    Quotes,
+    Tasks,
+    Checklists,
+    Evidence,
+}
+
+/// Which sections a printed report has and in what order, with optional branding.
+/// Built in, or loaded from a JSON file such as
+/// `{"name": "Court bundle", "header": "Smith & Co", "sections": ["information", "evidence"]}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub title: String, // Empty means "Person summary: {name}"
+    #[serde(default)]
+    pub header: String, // Printed above the title, e.g. an organisation name
+    #[serde(default)]
+    pub footer: String, // Printed at the foot of every page, e.g. "Confidential"
+    pub sections: Vec<ReportSection>,
+}
+
+impl ReportTemplate {
+    pub fn built_in() -> Vec<ReportTemplate> {
+        vec![
+            ReportTemplate {
+                name: "Full dossier".to_string(),
+                title: "Dossier: {name}".to_string(),
+                header: String::new(),
+                footer: String::new(),
+                sections: vec![
+                    ReportSection::Information,
+                    ReportSection::CustomFields,
+                    ReportSection::Tags,
+                    ReportSection::Notes,
+                    ReportSection::Quotes,
+                    ReportSection::Tasks,
+                    ReportSection::Checklists,
+                    ReportSection::Evidence,
+                ],
+            },
+            ReportTemplate {
+                name: "Summary sheet".to_string(),
+                title: String::new(),
+                header: String::new(),
+                footer: String::new(),
+                sections: vec![ReportSection::Information, ReportSection::CustomFields, ReportSection::Tasks],
+            },
+            ReportTemplate {
+                name: "Evidence inventory".to_string(),
+                title: "Evidence inventory: {name}".to_string(),
+                header: String::new(),
+                footer: String::new(),
+                sections: vec![ReportSection::Evidence],
+            },
+        ]
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .context("Failed to read report template")?;
+        let template: ReportTemplate = serde_json::from_str(&json)
+            .context("Failed to parse report template")?;
+        if template.name.trim().is_empty() {
+            bail!("The template has no name");
+        }
+        if template.sections.is_empty() {
+            bail!("The template has no sections");
+        }
+        Ok(template)
+    }
+
+    fn fill(&self, text: &str, person: &Person, printed: &str) -> String {
+        text.replace("{name}", &person.name)
+            .replace("{role}", person.role.label())
+            .replace("{status}", person.case_status.label())
+            .replace("{priority}", person.priority.label())
+            .replace("{date}", printed)
+    }
+}
+
+/// Printable one-person report with the sections the template asks for
+pub fn person_summary_pdf(person: &Person, evidence_files: &[EvidenceFile], settings: &Settings, template: &ReportTemplate) -> Result<Vec<u8>> {
+    let dates = settings.dates();
+    let printed = dates.now();
+    let title = if template.title.trim().is_empty() { "Person summary: {name}" } else { template.title.as_str() };
+
+    let mut lines = Vec::new();
+    if !template.header.trim().is_empty() {
+        lines.push(Line::text(template.fill(&template.header, person, &printed), 11.0, true));
+    }
+    lines.push(Line::text(template.fill(title, person, &printed), 16.0, true));
+    lines.push(Line::text(
+        format!(
+            "Role: {}    Status: {}    Priority: {}    Printed: {}",
+            person.role.label(),
+            person.case_status.label(),
+            person.priority.label(),
+            printed,
+        ),
+        BODY_SIZE,
+        false,
+    ));
+
+    for section in &template.sections {
+        lines.push(Line::blank());
+        match section {
+            ReportSection::Information => information_lines(&mut lines, person),
+            ReportSection::CustomFields => custom_field_lines(&mut lines, person, settings),
+            ReportSection::Tags => {
+                lines.push(Line::text("Tags", 12.0, true));
+                let tags = if person.tags.is_empty() { "No tags".to_string() } else { person.tags.join(", ") };
+                for chunk in wrap(&tags, 100) {
+                    lines.push(Line::text(chunk, BODY_SIZE, false));
+                }
+            }
+            ReportSection::Notes => {
+                lines.push(Line::text("Notes", 12.0, true));
+                let notes = if person.notes.trim().is_empty() { "No notes" } else { person.notes.as_str() };
+                for chunk in wrap(notes, 100) {
+                    lines.push(Line::text(chunk, BODY_SIZE, false));
+                }
+            }
+            ReportSection::Quotes => quote_lines(&mut lines, person, dates),
+            ReportSection::Tasks => task_lines(&mut lines, person, dates),
+            ReportSection::Checklists => checklist_lines(&mut lines, person),
+            ReportSection::Evidence => evidence_lines(&mut lines, evidence_files, settings),
+        }
+    }
+
+    render(&lines, &template.fill(&template.footer, person, &printed))
+}
+
+fn information_lines(lines: &mut Vec<Line>, person: &Person) {
+    lines.push(Line::text("Information", 12.0, true));
+    if person.information.is_empty() {
+        lines.push(Line::text("No information recorded", BODY_SIZE, false));
+    }
+    for info in &person.information {
+        for (index, chunk) in wrap(&info.value, 75).into_iter().enumerate() {
+            let label = if index == 0 { info.info_type.clone() } else { String::new() };
+            lines.push(Line::row(vec![(0.0, truncate(&label, 25)), (140.0, chunk)], false));
+        }
+    }
+}
+
+fn custom_field_lines(lines: &mut Vec<Line>, person: &Person, settings: &Settings) {
+    lines.push(Line::text("Custom fields", 12.0, true));
+    if settings.custom_fields.is_empty() {
+        lines.push(Line::text("No custom fields defined", BODY_SIZE, false));
+    }
+    for field in &settings.custom_fields {
+        let value = person.custom_fields.get(&field.name)
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        lines.push(Line::row(vec![(0.0, truncate(&field.name, 25)), (140.0, truncate(&value, 75))], false));
+    }
+}
+
+fn quote_lines(lines: &mut Vec<Line>, person: &Person, dates: DateDisplay) {
+    lines.push(Line::text("Quotes", 12.0, true));
+    if person.quotes.is_empty() {
+        lines.push(Line::text("No quotes recorded", BODY_SIZE, false));
+    }
+    for quote in &person.quotes {
+        let mut heading = dates.quote_line(quote);
+        if let Some(place) = &quote.place {
+            heading.push_str(&format!(", {}", place));
+        }
+        if let Some(source) = &quote.source {
+            heading.push_str(&format!(" (source: {})", source));
+        }
+        lines.push(Line::text(heading, BODY_SIZE, true));
+        for chunk in wrap(&format!("\"{}\"", quote.quote), 100) {
+            lines.push(Line::row(vec![(10.0, chunk)], false));
+        }
+    }
+}
+
+fn task_lines(lines: &mut Vec<Line>, person: &Person, dates: DateDisplay) {
+    lines.push(Line::text("Tasks", 12.0, true));
+    if person.tasks.is_empty() {
+        lines.push(Line::text("No tasks", BODY_SIZE, false));
+    }
+    for task in &person.tasks {
+        let due = task.due_date.map(|date| dates.date(date)).unwrap_or_default();
+        lines.push(Line::row(
+            vec![
+                (0.0, if task.done { "Done" } else { "Open" }.to_string()),
+                (50.0, truncate(&task.description, 70)),
+                (440.0, due),
+            ],
+            false,
+        ));
+    }
+}
+
+fn checklist_lines(lines: &mut Vec<Line>, person: &Person) {
+    lines.push(Line::text("Checklists", 12.0, true));
+    if person.checklists.is_empty() {
+        lines.push(Line::text("No checklists", BODY_SIZE, false));
+    }
+    for checklist in &person.checklists {
+        lines.push(Line::text(
+            format!("{} ({} of {} answered)", checklist.name, checklist.answered(), checklist.items.len()),
+            BODY_SIZE,
+            true,
+        ));
+        // Each answer goes under its question, indented
+        for item in &checklist.items {
+            for chunk in wrap(&item.question, 100) {
+                lines.push(Line::text(chunk, BODY_SIZE, false));
+            }
+            let answer = if item.answer.trim().is_empty() { "(not answered)" } else { item.answer.as_str() };
+            for chunk in wrap(answer, 95) {
+                lines.push(Line::row(vec![(20.0, chunk)], false));
+            }
+        }
+    }
+}
+
+fn evidence_lines(lines: &mut Vec<Line>, evidence_files: &[EvidenceFile], settings: &Settings) {
+    lines.push(Line::text(format!("Evidence inventory ({} files)", evidence_files.len()), 12.0, true));
+    lines.push(Line::row(
+        vec![
+            (0.0, "Category".to_string()),
+            (80.0, "File".to_string()),
+            (310.0, "Size".to_string()),
+            (370.0, "Date".to_string()),
+            (440.0, "Review".to_string()),
+        ],
+        true,
+    ));
+    // Evidence date where known, otherwise when the file was added
+    let mut evidence_files: Vec<&EvidenceFile> = evidence_files.iter().collect();
+    evidence_files.sort_by_key(|f| f.evidence_date.unwrap_or(f.created_at));
+    for file in evidence_files {
+        let name = file.file_path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| file.original_name.clone());
+        lines.push(Line::row(
+            vec![
+                (0.0, truncate(&settings.display_name(&file.file_type), 14)),
+                (80.0, truncate(&name, 45)),
+                (310.0, format!("{} KB", file.size / 1024)),
+                (370.0, settings.dates().date(file.evidence_date.unwrap_or(file.created_at).with_timezone(&Local).date_naive())),
+                (440.0, file.review_status.label().to_string()),
+            ],
+            false,
+        ));
+    }
+}
+
+/// Lays the lines out over as many pages as needed, with `footer` and the page number at the foot of each
+fn render(lines: &[Line], footer: &str) -> Result<Vec<u8>> {
+    let mut document = lopdf::Document::with_version("1.5");
+    let pages_id = document.new_object_id();
+
+    let regular = document.add_object(font_dictionary("Helvetica"));
+    let bold = document.add_object(font_dictionary("Helvetica-Bold"));
+    let resources = document.add_object(Dictionary::from_iter([
+        ("Font", Object::Dictionary(Dictionary::from_iter([
+            ("F1", Object::Reference(regular)),
+            ("F2", Object::Reference(bold)),
+        ]))),
+    ]));
+
+    // Split into pages by height
+    let mut pages: Vec<Vec<Operation>> = vec![Vec::new()];
+    let mut y = PAGE_HEIGHT - MARGIN;
+    for line in lines {
+        let height = line.size * 1.5;
+        if y - height < MARGIN {
+            pages.push(Vec::new());
+            y = PAGE_HEIGHT - MARGIN;
+        }
+        y -= height;
+
+        let operations = pages.last_mut().expect("there is always a current page");
+        let font = if line.bold { "F2" } else { "F1" };
+        for (x, text) in &line.cells {
+            operations.extend([
+                Operation::new("BT", vec![]),
+                Operation::new("Tf", vec![font.into(), line.size.into()]),
+                Operation::new("Td", vec![(MARGIN + x).into(), y.into()]),
+                Operation::new("Tj", vec![Object::string_literal(win_ansi(text))]),
+                Operation::new("ET", vec![]),
+            ]);
+        }
+    }
+
+    let page_count = pages.len();
+    let mut kids = Vec::new();
+    for (index, mut operations) in pages.into_iter().enumerate() {
+        let mut footer_text = format!("Page {} of {}", index + 1, page_count);
+        if !footer.trim().is_empty() {
+            footer_text = format!("{}    {}", footer.trim(), footer_text);
+        }
+        operations.extend([
+            Operation::new("BT", vec![]),
+            Operation::new("Tf", vec!["F1".into(), FOOTER_SIZE.into()]),
+            Operation::new("Td", vec![MARGIN.into(), (MARGIN / 2.0).into()]),
+            Operation::new("Tj", vec![Object::string_literal(win_ansi(&footer_text))]),
+            Operation::new("ET", vec![]),
+        ]);
+
+        let content = Content { operations }.encode()
+            .context("Failed to encode report page")?;
+        let content_id = document.add_object(Stream::new(Dictionary::new(), content));
+        let page_id = document.add_object(Dictionary::from_iter([
+            ("Type", Object::Name(b"Page".to_vec())),
+            ("Parent", Object::Reference(pages_id)),
+            ("Contents", Object::Reference(content_id)),
+            ("Resources", Object::Reference(resources)),
+            ("MediaBox", Object::Array(vec![0.into(), 0.into(), PAGE_WIDTH.into(), PAGE_HEIGHT.into()])),
+        ]));
+        kids.push(Object::Reference(page_id));
+    }
+
+    document.objects.insert(pages_id, Object::Dictionary(Dictionary::from_iter([
+        ("Type", Object::Name(b"Pages".to_vec())),
+        ("Kids", Object::Array(kids)),
+        ("Count", Object::Integer(page_count as i64)),
+    ])));
+    let catalog_id = document.add_object(Dictionary::from_iter([
+        ("Type", Object::Name(b"Catalog".to_vec())),
+        ("Pages", Object::Reference(pages_id)),
+    ]));
+    document.trailer.set("Root", Object::Reference(catalog_id));
+
+    let mut output = Vec::new();
+    document.save_to(&mut output)
+        .context("Failed to write report PDF")?;
+    Ok(output)
+}
+
+/// One page with a title and a JPEG picture of `width` x `height` pixels scaled to fit below it
+pub fn picture_pdf(title: &str, subtitle: &str, jpeg: &[u8], width: u32, height: u32) -> Result<Vec<u8>> {
+    let mut document = lopdf::Document::with_version("1.5");
+    let pages_id = document.new_object_id();
+
+    let regular = document.add_object(font_dictionary("Helvetica"));
+    let bold = document.add_object(font_dictionary("Helvetica-Bold"));
+    let picture = document.add_object(Stream::new(
+        Dictionary::from_iter([
+            ("Type", Object::Name(b"XObject".to_vec())),
+            ("Subtype", Object::Name(b"Image".to_vec())),
+            ("Width", Object::Integer(width as i64)),
+            ("Height", Object::Integer(height as i64)),
+            ("ColorSpace", Object::Name(b"DeviceRGB".to_vec())),
+            ("BitsPerComponent", Object::Integer(8)),
+            ("Filter", Object::Name(b"DCTDecode".to_vec())),
+        ]),
+        jpeg.to_vec(),
+    ));
+    let resources = document.add_object(Dictionary::from_iter([
+        ("Font", Object::Dictionary(Dictionary::from_iter([
+            ("F1", Object::Reference(regular)),
+            ("F2", Object::Reference(bold)),
+        ]))),
+        ("XObject", Object::Dictionary(Dictionary::from_iter([
+            ("Im1", Object::Reference(picture)),
+        ]))),
+    ]));
+
+    // Below the two heading lines, as large as the page allows without stretching
+    let top = PAGE_HEIGHT - MARGIN - 40.0;
+    let scale = ((PAGE_WIDTH - 2.0 * MARGIN) / width as f32).min((top - MARGIN) / height as f32);
+    let (shown_width, shown_height) = (width as f32 * scale, height as f32 * scale);
+    let operations = vec![
+        Operation::new("BT", vec![]),
+        Operation::new("Tf", vec!["F2".into(), 16.0.into()]),
+        Operation::new("Td", vec![MARGIN.into(), (PAGE_HEIGHT - MARGIN - 16.0).into()]),
+        Operation::new("Tj", vec![Object::string_literal(win_ansi(title))]),
+        Operation::new("ET", vec![]),
+        Operation::new("BT", vec![]),
+        Operation::new("Tf", vec!["F1".into(), BODY_SIZE.into()]),
+        Operation::new("Td", vec![MARGIN.into(), (PAGE_HEIGHT - MARGIN - 32.0).into()]),
+        Operation::new("Tj", vec![Object::string_literal(win_ansi(subtitle))]),
+        Operation::new("ET", vec![]),
+        Operation::new("q", vec![]),
+        Operation::new("cm", vec![
+            shown_width.into(), 0.into(), 0.into(), shown_height.into(),
+            ((PAGE_WIDTH - shown_width) / 2.0).into(), (top - shown_height).into(),
+        ]),
+        Operation::new("Do", vec!["Im1".into()]),
+        Operation::new("Q", vec![]),
+    ];
+
+    let content = Content { operations }.encode()
+        .context("Failed to encode report page")?;
+    let content_id = document.add_object(Stream::new(Dictionary::new(), content));
+    let page_id = document.add_object(Dictionary::from_iter([
+        ("Type", Object::Name(b"Page".to_vec())),
+        ("Parent", Object::Reference(pages_id)),
+        ("Contents", Object::Reference(content_id)),
+        ("Resources", Object::Reference(resources)),
+        ("MediaBox", Object::Array(vec![0.into(), 0.into(), PAGE_WIDTH.into(), PAGE_HEIGHT.into()])),
+    ]));
+    document.objects.insert(pages_id, Object::Dictionary(Dictionary::from_iter([
+        ("Type", Object::Name(b"Pages".to_vec())),
+        ("Kids", Object::Array(vec![Object::Reference(page_id)])),
+        ("Count", Object::Integer(1)),
+    ])));
+    let catalog_id = document.add_object(Dictionary::from_iter([
+        ("Type", Object::Name(b"Catalog".to_vec())),
+        ("Pages", Object::Reference(pages_id)),
+    ]));
+    document.trailer.set("Root", Object::Reference(catalog_id));
+
+    let mut output = Vec::new();
+    document.save_to(&mut output)
+        .context("Failed to write report PDF")?;
+    Ok(output)
+}
+
+fn font_dictionary(base_font: &str) -> Dictionary {
+    Dictionary::from_iter([
+        ("Type", Object::Name(b"Font".to_vec())),
+        ("Subtype", Object::Name(b"Type1".to_vec())),
+        ("BaseFont", Object::Name(base_font.as_bytes().to_vec())),
+        ("Encoding", Object::Name(b"WinAnsiEncoding".to_vec())),
+    ])
+}
+
+/// File formats the custody log can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustodyLogFormat {
+    Csv,
+    Pdf,
+}
+
+impl CustodyLogFormat {
+    /// PDF for files ending in .pdf, CSV otherwise
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("pdf") => CustodyLogFormat::Pdf,
+            _ => CustodyLogFormat::Csv,
+        }
+    }
+}
+
+/// The audit log entries of a case, each with whether it passed hash-chain verification, to go
+/// with a formal evidence submission
+#[derive(Debug)]
+pub struct CustodyLog {
+    pub names: Vec<String>,
+    pub entries: Vec<(AuditEntry, bool)>,
+    pub chain_intact: bool, // Every entry in the whole log verified, not only this case's
+}
+
+impl CustodyLog {
+    /// Verifies the whole log, then keeps the entries about the given people
+    pub fn build(log: Vec<AuditEntry>, persons: &[Person]) -> Self {
+        let verified = audit::verify_chain(&log);
+        let chain_intact = verified.iter().all(|ok| *ok);
+        let entries = log.into_iter()
+            .zip(verified)
+            .filter(|(entry, _)| entry.person_id.is_some_and(|id| persons.iter().any(|p| p.id == id)))
+            .collect();
+        Self {
+            names: persons.iter().map(|p| p.name.clone()).collect(),
+            entries,
+            chain_intact,
+        }
+    }
+
+    /// Times in the PDF are shown with `dates`; the CSV keeps RFC 3339
+    pub fn write(&self, format: CustodyLogFormat, dates: DateDisplay) -> Result<Vec<u8>> {
+        match format {
+            CustodyLogFormat::Csv => Ok(self.to_csv().into_bytes()),
+            CustodyLogFormat::Pdf => self.to_pdf(dates),
+        }
+    }
+
+    fn chain_status(&self) -> String {
+        let failed = self.entries.iter().filter(|(_, verified)| !verified).count();
+        match (self.chain_intact, failed) {
+            (true, _) => "intact, every entry in the audit log verified".to_string(),
+            (false, 0) => "broken elsewhere in the audit log; the entries listed here verified".to_string(),
+            (false, failed) => format!("BROKEN, {} of the entries listed here failed verification", failed),
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = String::from("timestamp,person,action,details,operator,previous_hash,hash,verified\n");
+        for (entry, verified) in &self.entries {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                entry.timestamp.to_rfc3339(),
+                csv_field(&entry.person_name),
+                csv_field(entry.action.label()),
+                csv_field(&entry.details),
+                csv_field(&entry.operator),
+                entry.previous_hash,
+                entry.hash,
+                verified,
+            ));
+        }
+        csv
+    }
+
+    fn to_pdf(&self, dates: DateDisplay) -> Result<Vec<u8>> {
+        let printed = dates.now();
+        let mut lines = vec![
+            Line::text("Chain of custody", 16.0, true),
+            Line::text(truncate(&self.names.join(", "), 100), 11.0, true),
+            Line::text(format!("Printed: {}    Entries: {}", printed, self.entries.len()), BODY_SIZE, false),
+            Line::text(format!("Hash chain: {}", self.chain_status()), BODY_SIZE, true),
+            Line::blank(),
+            Line::row(
+                vec![
+                    (0.0, "Time".to_string()),
+                    (75.0, "Person".to_string()),
+                    (160.0, "Action".to_string()),
+                    (260.0, "Operator".to_string()),
+                    (330.0, "Details".to_string()),
+                    (455.0, "Verified".to_string()),
+                ],
+                true,
+            ),
+        ];
+        for (entry, verified) in &self.entries {
+            let details = wrap(&entry.details, 28);
+            lines.push(Line::row(
+                vec![
+                    (0.0, dates.date_time(entry.timestamp)),
+                    (75.0, truncate(&entry.person_name, 18)),
+                    (160.0, truncate(entry.action.label(), 22)),
+                    (260.0, truncate(&entry.operator, 14)),
+                    (330.0, details[0].clone()),
+                    (455.0, if *verified { "Yes" } else { "FAILED" }.to_string()),
+                ],
+                false,
+            ));
+            for chunk in &details[1..] {
+                lines.push(Line::row(vec![(330.0, chunk.clone())], false));
+            }
+            lines.push(Line::row(vec![(10.0, format!("SHA-256 {}", entry.hash))], false));
+        }
+        let head = self.entries.last().map(|(entry, _)| format!("Last entry {}", &entry.hash[..entry.hash.len().min(16)])).unwrap_or_default();
+        render(&lines, &head)
+    }
+}
+
+/// File formats the relationship graph can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    GraphMl, // Gephi, yEd, Cytoscape
+    Dot, // Graphviz
+}
+
+impl GraphFormat {
+    /// DOT for files ending in .dot or .gv, GraphML otherwise
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_ascii_lowercase()).as_deref() {
+            Some("dot") | Some("gv") => GraphFormat::Dot,
+            _ => GraphFormat::GraphMl,
+        }
+    }
+}
+
+/// A person, or an identifier recorded under several people
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    pub id: String,
+    pub label: String,
+    pub kind: &'static str, // "person" or "identifier"
+    pub detail: String, // Case status for people, information type for identifiers
+}
+
+#[derive(Debug, Clone)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    pub kind: &'static str, // "shares" (person to identifier) or "board" (a string between two people)
+    pub label: String,
+}
+
+/// People and what links them, for analysis in graph tools
+#[derive(Debug, Default)]
+pub struct RelationshipGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl RelationshipGraph {
+    /// The people, the identifiers they share, and strings tied between their cards on evidence boards
+    pub fn build(persons: &[Person], boards: &[Board]) -> Self {
+        let mut graph = Self::default();
+        let person_node = |id: &Uuid| format!("p-{}", id);
+        for person in persons {
+            graph.nodes.push(GraphNode {
+                id: person_node(&person.id),
+                label: person.name.clone(),
+                kind: "person",
+                detail: match person.role {
+                    CaseRole::Unassigned => person.case_status.label().to_string(),
+                    role => format!("{}, {}", role.label(), person.case_status.label()),
+                },
+            });
+        }
+
+        for (index, shared) in links::shared_identifiers(persons).into_iter().enumerate() {
+            let id = format!("i-{}", index + 1);
+            for person in &shared.persons {
+                graph.edges.push(GraphEdge {
+                    source: person_node(person),
+                    target: id.clone(),
+                    kind: "shares",
+                    label: shared.info_type.clone(),
+                });
+            }
+            graph.nodes.push(GraphNode { id, label: shared.value, kind: "identifier", detail: shared.info_type });
+        }
+
+        // Only strings with a listed person at both ends
+        let listed = |card: Option<&Card>| match card.map(|card| &card.content) {
+            Some(CardContent::Person(id)) if persons.iter().any(|p| p.id == *id) => Some(*id),
+            _ => None,
+        };
+        for board in boards {
+            for connection in &board.connections {
+                if let (Some(from), Some(to)) = (listed(board.card(connection.from)), listed(board.card(connection.to))) {
+                    graph.edges.push(GraphEdge {
+                        source: person_node(&from),
+                        target: person_node(&to),
+                        kind: "board",
+                        label: connection.label.trim().to_string(),
+                    });
+                }
+            }
+        }
+        graph
+    }
+
+    pub fn write(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::GraphMl => self.to_graphml(),
+            GraphFormat::Dot => self.to_dot(),
+        }
+    }
+
+    fn to_graphml(&self) -> String {
+        let mut xml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+            "  <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+            "  <key id=\"detail\" for=\"node\" attr.name=\"detail\" attr.type=\"string\"/>\n",
+            "  <key id=\"edge_kind\" for=\"edge\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+            "  <key id=\"edge_label\" for=\"edge\" attr.name=\"label\" attr.type=\"string\"/>\n",
+            "  <graph id=\"relationships\" edgedefault=\"undirected\">\n",
+        ));
+        for node in &self.nodes {
+            xml.push_str(&format!(
+                "    <node id=\"{}\"><data key=\"label\">{}</data><data key=\"kind\">{}</data><data key=\"detail\">{}</data></node>\n",
+                escape_xml(&node.id), escape_xml(&node.label), node.kind, escape_xml(&node.detail)
+            ));
+        }
+        for edge in &self.edges {
+            xml.push_str(&format!(
+                "    <edge source=\"{}\" target=\"{}\"><data key=\"edge_kind\">{}</data><data key=\"edge_label\">{}</data></edge>\n",
+                escape_xml(&edge.source), escape_xml(&edge.target), edge.kind, escape_xml(&edge.label)
+            ));
+        }
+        xml.push_str("  </graph>\n</graphml>\n");
+        xml
+    }
+
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("graph relationships {\n");
+        for node in &self.nodes {
+            let shape = if node.kind == "person" { "box" } else { "ellipse" };
+            dot.push_str(&format!(
+                "  \"{}\" [label=\"{}\", shape={}, tooltip=\"{}\"];\n",
+                escape_dot(&node.id), escape_dot(&node.label), shape, escape_dot(&node.detail)
+            ));
+        }
+        for edge in &self.edges {
+            let style = if edge.kind == "board" { "solid" } else { "dashed" };
+            dot.push_str(&format!(
+                "  \"{}\" -- \"{}\" [label=\"{}\", style={}];\n",
+                escape_dot(&edge.source), escape_dot(&edge.target), escape_dot(&edge.label), style
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// The standard fonts only cover Latin-1, so anything else is replaced
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| if (c as u32) < 256 && !c.is_control() { c as u8 } else { b'?' })
+        .collect()
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        let kept: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+        format!("{}...", kept)
+    }
+}
+
+/// Word-wraps to roughly `width` characters, which fits the page at body size
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut current = String::new();
+        for word in paragraph.split_whitespace() {
+            if !current.is_empty() && current.chars().count() + word.chars().count() + 1 > width {
+                lines.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        lines.push(current);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
@@ -0,0 +1,63 @@
+use crate::gui::EvidenceTab;
+use anyhow::{Result, Context};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// What was open in the app, written every few seconds while it runs and removed when it
+/// closes normally. Finding one at launch means the app crashed or was killed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub selected_person: Option<Uuid>,
+    pub selected_tab: Option<EvidenceTab>,
+    pub person_list_scroll: f32, // How far down each list was, from 0.0 at the top to 1.0 at the bottom
+    pub tab_scroll: f32,
+}
+
+impl Session {
+    fn path() -> Result<PathBuf> {
+        let project_dirs = ProjectDirs::from("com", "Evidence-Manager", "Evidence-Manager")
+            .context("Failed to get user config directory")?;
+        Ok(project_dirs.config_dir().join("session.json"))
+    }
+
+    /// The snapshot left by a session that didn't close properly, if there is one
+    pub fn load() -> Result<Option<Self>> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let json = fs::read_to_string(&path)
+            .context("Failed to read session")?;
+        let session = serde_json::from_str(&json)
+            .context("Failed to parse session")?;
+        Ok(Some(session))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .context("Failed to create config directory")?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .context("Failed to serialize session")?;
+        fs::write(&path, json)
+            .context("Failed to write session")?;
+        Ok(())
+    }
+
+    /// Removes the snapshot once the app closes normally
+    pub fn clear() -> Result<()> {
+        let path = Self::path()?;
+        if path.exists() {
+            fs::remove_file(&path)
+                .context("Failed to remove session")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,507 @@
+use crate::dates::{DateDisplay, DateFormat, DisplayZone, TimeFormat};
+use crate::map;
+use crate::naming;
+use crate::paths;
+use crate::sniff;
+use crate::gui::{EvidenceTab, PersonGrouping};
+use crate::models::{CaseStatus, ColorLabel, EvidenceType, FieldType, Person};
+use crate::reports::ReportTemplate;
+use crate::profiles::Passphrase;
+use anyhow::{Result, Context, bail};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// A user-defined evidence category stored in its own folder next to the built-in ones
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomCategory {
+    pub name: String,
+    pub folder_name: String,
+    pub icon: String,
+    pub extensions: Vec<String>,
+}
+
+impl CustomCategory {
+    pub fn evidence_type(&self) -> EvidenceType {
+        EvidenceType::Custom(self.folder_name.clone())
+    }
+}
+
+/// A typed field shown for every person, in addition to free-form information
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomField {
+    pub name: String,
+    pub field_type: FieldType,
+}
+
+/// Information types pre-created, with empty values, on people added from this template
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InfoTemplate {
+    pub name: String,
+    pub info_types: Vec<String>,
+}
+
+impl std::fmt::Display for InfoTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Questions attached, with empty answers, to a person as an interview or intake checklist
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChecklistTemplate {
+    pub name: String,
+    pub questions: Vec<String>,
+}
+
+impl std::fmt::Display for ChecklistTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// A named search and filter combination listed in the sidebar as a smart view
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query: String,
+    pub case_status: Option<CaseStatus>,
+    pub sort_by_priority: bool,
+    #[serde(default)]
+    pub color_label: Option<ColorLabel>,
+}
+
+/// How often an export profile runs on its own while the app is open
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportSchedule {
+    #[default]
+    Manual, // Only with "Run Now"
+    Hourly,
+    Daily,
+    Weekly,
+}
+
+impl ExportSchedule {
+    pub fn all() -> [ExportSchedule; 4] {
+        [ExportSchedule::Manual, ExportSchedule::Hourly, ExportSchedule::Daily, ExportSchedule::Weekly]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportSchedule::Manual => "Manual",
+            ExportSchedule::Hourly => "Hourly",
+            ExportSchedule::Daily => "Daily",
+            ExportSchedule::Weekly => "Weekly",
+        }
+    }
+
+    fn interval(&self) -> Option<chrono::Duration> {
+        match self {
+            ExportSchedule::Manual => None,
+            ExportSchedule::Hourly => Some(chrono::Duration::hours(1)),
+            ExportSchedule::Daily => Some(chrono::Duration::days(1)),
+            ExportSchedule::Weekly => Some(chrono::Duration::weeks(1)),
+        }
+    }
+}
+
+impl std::fmt::Display for ExportSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// How evidence content is kept on disk
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StorageLayout {
+    #[default]
+    Blobs, // Stored once under Evidence/.blobs/<sha256> and hard-linked into each person's folder
+    PersonFolders, // Each person's folder holds its own copy of every file
+}
+
+impl StorageLayout {
+    pub fn all() -> [StorageLayout; 2] {
+        [StorageLayout::Blobs, StorageLayout::PersonFolders]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StorageLayout::Blobs => "Blob store (each file stored once)",
+            StorageLayout::PersonFolders => "Person folders (a copy per person)",
+        }
+    }
+}
+
+impl std::fmt::Display for StorageLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// A named .ema export that can be run again on demand or on a schedule
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ExportProfile {
+    pub name: String,
+    pub destination: PathBuf, // Folder the archive is written to, named after the profile and the time
+    #[serde(default)]
+    pub persons: Vec<Uuid>,
+    #[serde(default)]
+    pub tags: Vec<String>, // People with any of these tags are included too; no people and no tags means everyone
+    #[serde(default)]
+    pub encrypt: bool,
+    #[serde(default, skip_serializing)]
+    pub passphrase: String, // For encryption; held only while the app runs and asked for after a restart
+    #[serde(default)]
+    pub passphrase_check: Option<Passphrase>, // Salted hash of the passphrase, to catch a mistyped one
+    #[serde(default)]
+    pub split_size_mb: u64, // Archive is cut into parts of this size; 0 means one file
+    #[serde(default)]
+    pub thumbnails_only: bool, // Previews of the images instead of the evidence files, for triage
+    #[serde(default)]
+    pub schedule: ExportSchedule,
+    #[serde(default)]
+    pub last_run: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ExportProfile {
+    pub fn includes(&self, person: &Person) -> bool {
+        (self.persons.is_empty() && self.tags.is_empty())
+            || self.persons.contains(&person.id)
+            || person.tags.iter().any(|tag| self.tags.contains(tag))
+    }
+
+    /// Whether a scheduled run is due; a profile that never ran is due straight away
+    pub fn is_due(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        match (self.schedule.interval(), self.last_run) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(interval), Some(last_run)) => now - last_run >= interval,
+        }
+    }
+}
+
+/// Interface scale steps in percent, stepped through with Ctrl +/-
+pub const UI_SCALE_STEPS: [u16; 8] = [75, 90, 100, 110, 125, 150, 175, 200];
+
+const BUNDLE_FORMAT: &str = "evidence-manager-settings";
+const BUNDLE_VERSION: u32 = 1;
+
+/// Settings as written to a file for moving them to another machine
+#[derive(Serialize, Deserialize)]
+struct SettingsBundle {
+    format: String,
+    version: u32,
+    exported_at: chrono::DateTime<chrono::Utc>,
+    settings: Settings,
+}
+
+/// Application preferences, kept separate from evidence data
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub custom_categories: Vec<CustomCategory>,
+    #[serde(default)]
+    pub extension_mappings: BTreeMap<String, EvidenceType>, // Lowercase extension without the dot
+    #[serde(default)]
+    pub intake_mode: bool, // Ask for provenance whenever evidence is added
+    #[serde(default)]
+    pub move_into_evidence: bool, // Move added files rather than copying them, unless switched off for a drop
+    #[serde(default)]
+    pub task_notifications: bool,
+    #[serde(default)]
+    pub job_notifications: bool, // Announce finished exports and imports while the app is in the background
+    #[serde(default)]
+    pub map_tile_url: String, // Empty means the OpenStreetMap tile server
+    #[serde(default)]
+    pub geocoding_url: String, // Empty keeps place lookups to the local gazetteer
+    #[serde(default)]
+    pub tsa_url: String, // RFC 3161 timestamping authority; empty means evidence isn't timestamped
+    #[serde(default)]
+    pub webhook_url: String, // Sent person added, evidence added and export events; empty means none
+    #[serde(default)]
+    pub webhook_secret: String, // Key for the HMAC-SHA256 signature on webhook payloads
+    #[serde(default)]
+    pub operator: String, // Name or initials recorded with each change, for workstations shared by several people
+    #[serde(default)]
+    pub admin_passphrase: Option<Passphrase>, // Unset means no profiles: the app always opens as Admin
+    #[serde(default)]
+    pub person_quota_mb: u64, // Warn when a person's evidence grows past this; 0 means no limit
+    #[serde(default)]
+    pub person_quota_files: usize, // 0 means no limit
+    #[serde(default)]
+    pub free_space_floor_mb: u64, // Warn when adding evidence leaves less free disk space than this; 0 means no check
+    #[serde(default)]
+    pub storage_layout: StorageLayout,
+    #[serde(default)]
+    pub saved_searches: Vec<SavedSearch>,
+    #[serde(default)]
+    pub info_templates: Vec<InfoTemplate>,
+    #[serde(default)]
+    pub checklist_templates: Vec<ChecklistTemplate>,
+    #[serde(default)]
+    pub custom_fields: Vec<CustomField>,
+    #[serde(default)]
+    pub default_country_code: String, // Calling code for phone numbers entered without one, e.g. "44"
+    #[serde(default)]
+    pub screenshot_name_pattern: String, // Empty means naming::DEFAULT_SCREENSHOT_PATTERN
+    #[serde(default)]
+    pub layout: Layout,
+    #[serde(default)]
+    pub ui_scale: u16, // Percent; 0 means 100%
+    #[serde(default)]
+    pub report_templates: Vec<ReportTemplate>, // Loaded from template files, in addition to the built-in ones
+    #[serde(default)]
+    pub report_template: String, // Name of the template used for printing; empty means the first built-in one
+    #[serde(default)]
+    pub quick_capture_hotkey: String, // Global shortcut for quick capture, e.g. "Ctrl+Shift+E"; empty means none
+    #[serde(default)]
+    pub enabled_plugins: Vec<String>, // File names in the plugins folder; scripts only run once enabled here
+    #[serde(default)]
+    pub sign_exports: bool, // Sign .ema archives and printed reports with this installation's key
+    #[serde(default)]
+    pub export_profiles: Vec<ExportProfile>,
+    #[serde(default)]
+    pub completeness_template: String, // Info template whose types each person should have filled in; empty means none
+    #[serde(default)]
+    pub date_format: DateFormat,
+    #[serde(default)]
+    pub time_format: TimeFormat,
+    #[serde(default)]
+    pub display_zone: DisplayZone,
+}
+
+/// Window and panel layout, and what was open, restored on the next launch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Layout {
+    pub window_width: f32,
+    pub window_height: f32,
+    pub window_position: Option<(i32, i32)>, // None leaves placement to the platform
+    pub sidebar_width: f32,
+    pub selected_person: Option<Uuid>,
+    pub selected_tab: Option<EvidenceTab>,
+    pub person_grouping: PersonGrouping,
+    pub collapsed_groups: Vec<String>, // PersonGrouping::group_key of each collapsed sidebar group
+    pub group_information: bool, // Information tab rows in sections by info type
+    pub collapsed_info_types: Vec<String>, // Lowercase info types whose section is collapsed
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            window_width: 1200.0,
+            window_height: 800.0,
+            window_position: None,
+            sidebar_width: 300.0,
+            selected_person: None,
+            selected_tab: None,
+            person_grouping: PersonGrouping::None,
+            collapsed_groups: Vec::new(),
+            group_information: false,
+            collapsed_info_types: Vec::new(),
+        }
+    }
+}
+
+impl Settings {
+    // Stored in the platform config directory rather than next to the evidence
+    fn settings_path() -> Result<PathBuf> {
+        let project_dirs = ProjectDirs::from("com", "Evidence-Manager", "Evidence-Manager")
+            .context("Failed to get user config directory")?;
+        Ok(project_dirs.config_dir().join("settings.json"))
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::settings_path()?;
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let json = fs::read_to_string(&path)
+            .context("Failed to read settings file")?;
+
+        let mut settings: Self = serde_json::from_str(&json)
+            .context("Failed to parse settings file")?;
+        // Export profile passphrases used to be saved as they were typed; saving again leaves only their hashes
+        let mut plain_passphrases = false;
+        for profile in settings.export_profiles.iter_mut().filter(|p| !p.passphrase.is_empty() && p.passphrase_check.is_none()) {
+            profile.passphrase_check = Some(Passphrase::new(&profile.passphrase)?);
+            plain_passphrases = true;
+        }
+        if plain_passphrases {
+            settings.save()?;
+        }
+        Ok(settings)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::settings_path()?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .context("Failed to create settings directory")?;
+        }
+
+        let json = serde_json::to_string_pretty(self)
+            .context("Failed to serialize settings")?;
+
+        fs::write(&path, json)
+            .context("Failed to write settings file")?;
+
+        Ok(())
+    }
+
+    pub fn screenshot_name_pattern(&self) -> &str {
+        if self.screenshot_name_pattern.trim().is_empty() {
+            naming::DEFAULT_SCREENSHOT_PATTERN
+        } else {
+            self.screenshot_name_pattern.trim()
+        }
+    }
+
+    pub fn dates(&self) -> DateDisplay {
+        DateDisplay { date: self.date_format, time: self.time_format, zone: self.display_zone }
+    }
+
+    pub fn ui_scale(&self) -> u16 {
+        if self.ui_scale == 0 {
+            100
+        } else {
+            self.ui_scale.clamp(UI_SCALE_STEPS[0], UI_SCALE_STEPS[UI_SCALE_STEPS.len() - 1])
+        }
+    }
+
+    /// The next scale step above or below the current one, staying at the ends
+    pub fn next_ui_scale(&self, larger: bool) -> u16 {
+        let current = self.ui_scale();
+        let next = if larger {
+            UI_SCALE_STEPS.iter().find(|step| **step > current)
+        } else {
+            UI_SCALE_STEPS.iter().rev().find(|step| **step < current)
+        };
+        next.copied().unwrap_or(current)
+    }
+
+    /// Built-in report templates followed by the user's own
+    pub fn all_report_templates(&self) -> Vec<ReportTemplate> {
+        let mut templates = ReportTemplate::built_in();
+        templates.extend(self.report_templates.iter().cloned());
+        templates
+    }
+
+    /// The template chosen for printing, falling back to the first built-in one if it was removed
+    pub fn report_template(&self) -> ReportTemplate {
+        let mut templates = self.all_report_templates();
+        let index = templates.iter().position(|t| t.name == self.report_template).unwrap_or(0);
+        templates.swap_remove(index)
+    }
+
+    /// The info template people's completeness is measured against, if one is chosen and still exists
+    pub fn completeness_template(&self) -> Option<&InfoTemplate> {
+        self.info_templates.iter().find(|t| t.name == self.completeness_template)
+    }
+
+    pub fn tile_url(&self) -> &str {
+        if self.map_tile_url.trim().is_empty() {
+            map::DEFAULT_TILE_URL
+        } else {
+            self.map_tile_url.trim()
+        }
+    }
+
+    /// Writes the settings as a versioned bundle, separate from any evidence
+    pub fn export_bundle(&self, path: &Path) -> Result<()> {
+        let bundle = SettingsBundle {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            exported_at: chrono::Utc::now(),
+            // The admin passphrase protects this machine only; the webhook secret isn't written to files that get passed around,
+            // and export profiles name this machine's folders and people
+            settings: Settings { admin_passphrase: None, webhook_secret: String::new(), export_profiles: Vec::new(), ..self.clone() },
+        };
+        let json = serde_json::to_string_pretty(&bundle)
+            .context("Failed to serialize settings")?;
+        fs::write(path, json)
+            .context("Failed to write settings bundle")?;
+        Ok(())
+    }
+
+    pub fn import_bundle(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .context("Failed to read settings bundle")?;
+        let bundle: SettingsBundle = serde_json::from_str(&json)
+            .context("Not an Evidence Manager settings file")?;
+
+        if bundle.format != BUNDLE_FORMAT {
+            bail!("Not an Evidence Manager settings file");
+        }
+        // Older bundles load through serde defaults; newer ones may hold settings this version can't keep
+        if bundle.version > BUNDLE_VERSION {
+            bail!("Settings were exported by a newer version of Evidence Manager");
+        }
+        // Category folders are created inside each person's folder
+        if let Some(category) = bundle.settings.custom_categories.iter().find(|c| !paths::is_plain_name(&c.folder_name) || c.folder_name.starts_with('.')) {
+            bail!("Category \"{}\" has an unsafe folder name \"{}\"", category.name, category.folder_name);
+        }
+        Ok(bundle.settings)
+    }
+
+    pub fn category(&self, folder_name: &str) -> Option<&CustomCategory> {
+        self.custom_categories.iter().find(|c| c.folder_name == folder_name)
+    }
+
+    /// Explicit mappings win, then custom category extensions, then the built-in mapping
+    /// The category an extension was explicitly given, through a mapping or a custom category
+    fn user_type_for_extension(&self, ext: &str) -> Option<EvidenceType> {
+        let ext = ext.to_lowercase();
+        if let Some(mapped) = self.extension_mappings.get(&ext) {
+            return Some(mapped.clone());
+        }
+
+        self.custom_categories
+            .iter()
+            .find(|c| c.extensions.iter().any(|e| e.eq_ignore_ascii_case(&ext)))
+            .map(CustomCategory::evidence_type)
+    }
+
+    /// Classifies a file by its extension, except that the file's content decides over the built-in
+    /// extension list, so a photo saved as "IMG_0001" or "scan.txt" still counts as an image.
+    /// Mappings and categories the user set up always win.
+    pub fn evidence_type_for_file(&self, path: &Path) -> Option<EvidenceType> {
+        let ext = path.extension().map(|ext| ext.to_string_lossy().to_string()).unwrap_or_default();
+        self.user_type_for_extension(&ext)
+            .or_else(|| sniff::detect_file(path))
+            .or_else(|| EvidenceType::from_extension(&ext))
+    }
+
+    /// Every category a file can be stored in: the built-ins (except quotes) and custom categories
+    pub fn file_categories(&self) -> Vec<EvidenceType> {
+        EvidenceType::built_in()
+            .into_iter()
+            .filter(|t| *t != EvidenceType::Quote)
+            .chain(self.custom_categories.iter().map(CustomCategory::evidence_type))
+            .collect()
+    }
+
+    pub fn display_name(&self, evidence_type: &EvidenceType) -> String {
+        match evidence_type {
+            EvidenceType::Custom(folder_name) => self.category(folder_name)
+                .map(|c| c.name.clone())
+                .unwrap_or_else(|| folder_name.clone()),
+            built_in => built_in.label().to_string(),
+        }
+    }
+
+    pub fn icon(&self, evidence_type: &EvidenceType) -> String {
+        match evidence_type {
+            EvidenceType::Custom(folder_name) => self.category(folder_name)
+                .map(|c| c.icon.clone())
+                .unwrap_or_else(|| "📁".to_string()),
+            built_in => built_in.icon().to_string(),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::hashing;
+use anyhow::{Result, Context, anyhow, bail};
+use chrono::{DateTime, Utc};
+use directories::ProjectDirs;
+use ring::rand::SystemRandom;
+use ring::signature::{self, Ed25519KeyPair, KeyPair, UnparsedPublicKey};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the signature entry at the top of a signed .ema archive
+pub const ARCHIVE_SIGNATURE_ENTRY: &str = "signature.json";
+
+const ALGORITHM: &str = "ed25519";
+
+/// This installation's ed25519 key, created on first use and kept in the config directory
+pub struct SigningKey {
+    key_pair: Ed25519KeyPair,
+}
+
+impl SigningKey {
+    fn path() -> Result<PathBuf> {
+        let project_dirs = ProjectDirs::from("com", "Evidence-Manager", "Evidence-Manager")
+            .context("Failed to get user config directory")?;
+        Ok(project_dirs.config_dir().join("signing_key.pk8"))
+    }
+
+    pub fn load_or_create() -> Result<Self> {
+        let path = Self::path()?;
+        if path.exists() {
+            let pkcs8 = fs::read(&path)
+                .context("Failed to read signing key")?;
+            let key_pair = Ed25519KeyPair::from_pkcs8(&pkcs8)
+                .map_err(|e| anyhow!("Failed to parse signing key: {}", e))?;
+            return Ok(Self { key_pair });
+        }
+
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+            .map_err(|_| anyhow!("Failed to generate signing key"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .context("Failed to create config directory")?;
+        }
+        fs::write(&path, pkcs8.as_ref())
+            .context("Failed to write signing key")?;
+        // Only the owner may read the private key
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o600))
+                .context("Failed to restrict signing key permissions")?;
+        }
+
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref())
+            .map_err(|e| anyhow!("Failed to parse signing key: {}", e))?;
+        Ok(Self { key_pair })
+    }
+
+    pub fn fingerprint(&self) -> String {
+        fingerprint(self.key_pair.public_key().as_ref())
+    }
+}
+
+/// Short form of a public key for comparing by eye, e.g. "3f2a 9c41 07be 51d0 8e6a 2c13 f4b9 d702"
+pub fn fingerprint(public_key: &[u8]) -> String {
+    let digest = hashing::sha256_hex(public_key);
+    digest.as_bytes()[..32]
+        .chunks(4)
+        .map(|group| String::from_utf8_lossy(group).to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A signature over the SHA-256 hashes of a set of files, stored in an archive or next to a report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Signature {
+    pub algorithm: String,
+    pub public_key: String, // Hex
+    pub fingerprint: String,
+    pub signed_at: DateTime<Utc>,
+    pub files: BTreeMap<String, String>, // Path to SHA-256
+    pub signature: String, // Hex, over the signing time and the file list
+}
+
+impl Signature {
+    pub fn create(key: &SigningKey, files: BTreeMap<String, String>) -> Self {
+        let signed_at = Utc::now();
+        let signature = key.key_pair.sign(&signed_message(signed_at, &files));
+        Self {
+            algorithm: ALGORITHM.to_string(),
+            public_key: hashing::to_hex(key.key_pair.public_key().as_ref()),
+            fingerprint: key.fingerprint(),
+            signed_at,
+            files,
+            signature: hashing::to_hex(signature.as_ref()),
+        }
+    }
+
+    /// Checks the signature over the listed hashes; comparing them with the actual files is up to the caller
+    pub fn verify(&self) -> Result<()> {
+        if self.algorithm != ALGORITHM {
+            bail!("Unsupported signature algorithm \"{}\"", self.algorithm);
+        }
+        let public_key = hashing::from_hex(&self.public_key)?;
+        if fingerprint(&public_key) != self.fingerprint {
+            bail!("The signature's fingerprint doesn't belong to its public key");
+        }
+        UnparsedPublicKey::new(&signature::ED25519, &public_key)
+            .verify(&signed_message(self.signed_at, &self.files), &hashing::from_hex(&self.signature)?)
+            .map_err(|_| anyhow!("The signature doesn't match the signed file list"))
+    }
+}
+
+/// Writes a detached signature for a file next to it, named "<file>.sig.json"
+pub fn sign_detached(key: &SigningKey, path: &Path, content: &[u8]) -> Result<PathBuf> {
+    let file_name = path.file_name()
+        .context("File has no name")?
+        .to_string_lossy()
+        .to_string();
+    let signature = Signature::create(key, BTreeMap::from([(file_name.clone(), hashing::sha256_hex(content))]));
+    let json = serde_json::to_string_pretty(&signature)
+        .context("Failed to serialize signature")?;
+    let signature_path = path.with_file_name(format!("{}.sig.json", file_name));
+    fs::write(&signature_path, json)
+        .context("Failed to write signature")?;
+    Ok(signature_path)
+}
+
+/// The exact bytes that are signed: the signing time, then one "hash  path" line per file in path order
+fn signed_message(signed_at: DateTime<Utc>, files: &BTreeMap<String, String>) -> Vec<u8> {
+    let mut message = format!("{}\n", signed_at.to_rfc3339());
+    for (path, hash) in files {
+        message.push_str(&format!("{}  {}\n", hash, path));
+    }
+    message.into_bytes()
+}
@@ -0,0 +1,103 @@
+use crate::models::EvidenceType;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+// Every signature below sits within the first few bytes
+const HEADER_LENGTH: u64 = 32;
+
+/// Recognizes common media and document formats by their magic numbers, for files whose
+/// extension is missing or wrong. Formats that can't be told apart this way (plain text,
+/// zip-based office files) return `None` and are left to the extension.
+pub fn detect(content: &[u8]) -> Option<EvidenceType> {
+    let starts = |signature: &[u8]| content.starts_with(signature);
+    let at = |offset: usize, signature: &[u8]| content.get(offset..offset + signature.len()) == Some(signature);
+
+    if starts(b"\xFF\xD8\xFF")
+        || starts(b"\x89PNG\r\n\x1A\n")
+        || starts(b"GIF87a")
+        || starts(b"GIF89a")
+        || starts(b"II*\0")
+        || starts(b"MM\0*")
+        || (starts(b"RIFF") && at(8, b"WEBP"))
+        // BMP's two-byte signature is only trusted with its reserved bytes zeroed
+        || (starts(b"BM") && at(6, b"\0\0\0\0"))
+    {
+        return Some(EvidenceType::Image);
+    }
+
+    // ISO media files (MP4, MOV, M4A, HEIC) name their flavour after "ftyp"
+    if at(4, b"ftyp") {
+        let brand = content.get(8..12)?;
+        return match brand {
+            b"heic" | b"heix" | b"mif1" | b"msf1" | b"avif" => Some(EvidenceType::Image),
+            b"M4A " | b"M4B " => Some(EvidenceType::Audio),
+            _ => Some(EvidenceType::Video),
+        };
+    }
+
+    if starts(b"\x1A\x45\xDF\xA3")
+        || starts(b"FLV")
+        || (starts(b"RIFF") && at(8, b"AVI "))
+        || starts(b"\x30\x26\xB2\x75\x8E\x66\xCF\x11")
+    {
+        return Some(EvidenceType::Video);
+    }
+
+    if starts(b"ID3")
+        || starts(b"fLaC")
+        || starts(b"OggS")
+        || (starts(b"RIFF") && at(8, b"WAVE"))
+        // MP3 and AAC frames begin with an 11-bit sync word
+        || (content.len() > 1 && content[0] == 0xFF && content[1] & 0xE0 == 0xE0)
+    {
+        return Some(EvidenceType::Audio);
+    }
+
+    if starts(b"%PDF-") || starts(b"{\\rtf") || starts(b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1") {
+        return Some(EvidenceType::Document);
+    }
+
+    None
+}
+
+/// Reads just the start of the file to detect its type
+pub fn detect_file(path: &Path) -> Option<EvidenceType> {
+    let mut header = Vec::new();
+    fs::File::open(path)
+        .and_then(|file| file.take(HEADER_LENGTH).read_to_end(&mut header))
+        .ok()?;
+    detect(&header)
+}
+
+/// Recognizes programs and shortcuts by their magic numbers, so renaming one doesn't get it past
+/// intake. Returns what it looks like.
+pub fn detect_executable(content: &[u8]) -> Option<&'static str> {
+    let starts = |signature: &[u8]| content.starts_with(signature);
+
+    if starts(b"MZ") {
+        return Some("Windows executable");
+    }
+    if starts(b"\x7FELF") {
+        return Some("Linux executable");
+    }
+    if starts(b"\xFE\xED\xFA\xCE") || starts(b"\xFE\xED\xFA\xCF") || starts(b"\xCE\xFA\xED\xFE") || starts(b"\xCF\xFA\xED\xFE") {
+        return Some("macOS executable");
+    }
+    if starts(b"L\0\0\0\x01\x14\x02\0") {
+        return Some("Windows shortcut");
+    }
+    if starts(b"#!") {
+        return Some("Script");
+    }
+    None
+}
+
+/// Reads just the start of the file to tell whether it's a program or shortcut
+pub fn detect_executable_file(path: &Path) -> Option<&'static str> {
+    let mut header = Vec::new();
+    fs::File::open(path)
+        .and_then(|file| file.take(HEADER_LENGTH).read_to_end(&mut header))
+        .ok()?;
+    detect_executable(&header)
+}
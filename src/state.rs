@@ -1,22 +1,108 @@
-use crate::models::{Person, EvidenceFile, EvidenceType};
-use crate::file_manager::FileManager;
-use crate::export_import::ExportImportManager;
-use crate::gui::EvidenceTab;
+use crate::models::{Person, EvidenceFile, EvidenceType, Coordinates, CaseRole, CaseStatus, ColorLabel, FieldType, FieldValue, Priority, Provenance, ReviewStatus};
+use crate::file_manager::{FileManager, Intake, IntakeContext};
+use crate::export_import::{CancellationToken, ExportImportManager, ExportOptions, ImportReport, ProgressCallback};
+use crate::watermark::Watermark;
+use crate::chat_import::{self, ChatExport};
+use crate::email::{self, ParsedEmail};
+use crate::duplicates::{self, DuplicatePair};
+use crate::gui::{self, EvidenceSort, EvidenceTab, PersonGrouping};
+use crate::settings::{ChecklistTemplate, CustomCategory, CustomField, ExportProfile, ExportSchedule, InfoTemplate, SavedSearch, Settings, StorageLayout};
+use crate::naming;
+use crate::exif;
+use crate::geocode;
+use crate::query::Query;
+use crate::bulk_edit::{BulkEdit, BulkEditField};
+use crate::map::{self, MapPoint, MapTarget};
+use crate::reports::{self, CustodyLog, CustodyLogFormat, ReportTemplate};
+use crate::signing::{self, SigningKey};
+use crate::timestamp;
+use crate::profiles::{Passphrase, Role};
+use crate::clipboard;
+use crate::audit::{AuditAction, AuditEntry, AuditLog};
+use crate::jobs::{JobKind, JobProgress, JobQueue, JobStatus};
+use crate::import_journal::ImportJournal;
+use crate::quick_capture;
+use crate::webhook::{self, Webhook};
+use crate::plugins::{self, PluginFile, Plugins};
+use crate::timeline::Timeline;
+use crate::board::{self, Board, CardContent};
+use crate::export_profiles;
+use crate::store_check::{self, RepairSummary, StoreReport};
+use crate::content_store::{ContentStore, Reference};
+use crate::quarantine::{self, Quarantine, QuarantinedFile};
+use crate::device_import::{self, DeviceImportSummary, DeviceItem};
+use crate::file_list_import::{self, ColumnMapping, FileList, FileListImportSummary, FileListRow, MappedField};
+use crate::recorder::Recording;
+use crate::notes;
+use crate::quote_paste::{self, PastedLine};
+use crate::name_list::{self, ListedName, NameStatus};
+use crate::evidence_browser::{BrowserColumn, BrowserRow, EvidenceBrowser};
+use crate::lineup::{Lineup, LineupFormat, LineupOrder};
+use crate::dates::{DateFormat, DisplayZone, QuoteZone, TimeFormat, UtcOffset};
+use crate::drafts::{Draft, DraftForm, Drafts};
+use crate::session::Session;
+use crate::statistics::Statistics;
+use crate::inventory::Inventory;
 use iced::{
-    Application, Command, Element, Theme, executor, Subscription,
+    Application, Command, Element, Event, Theme, event, executor, keyboard, mouse, time, window, Subscription,
+    widget::{image, scrollable, text_editor},
 };
+use chrono::{Local, NaiveDate};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 use std::path::PathBuf;
+use std::time::Duration;
 use uuid::Uuid;
 use anyhow::Result;
 
+const ACTIVITY_FEED_LENGTH: usize = 200;
+const SIDEBAR_MIN_WIDTH: f32 = 200.0;
+const SIDEBAR_MAX_WIDTH: f32 = 600.0;
+
 #[derive(Debug, Clone)]
 pub enum Message {
     // Person management
     PersonSelected(Uuid),
     AddPersonClicked,
     AddPersonNameChanged(String),
+    AddPersonTemplateSelected(InfoTemplate),
     AddPersonSubmitted,
+    AddSeveralPersonsClicked,
+    BulkAddEdited(text_editor::Action),
+    BulkAddTemplateSelected(InfoTemplate),
+    BulkAddExistingToggled(bool),
+    AddPersonsBulk,
+    BulkAddCancelled,
+    PersonsAdded(Vec<Person>, Vec<String>), // The persons added, and the names that failed with why
+    CaseStatusChanged(CaseStatus),
+    PriorityChanged(Priority),
+    CaseRoleChanged(CaseRole),
+    PersonSaved(Result<(), String>),
+    CaseStatusFilterChanged(Option<CaseStatus>),
+    ColorLabelFilterChanged(Option<ColorLabel>),
+    SortByPriorityToggled(bool),
+    PersonGroupingChanged(PersonGrouping),
+    PersonGroupToggled(String),
+    JumpToLetter(char),
+    InfoGroupingToggled(bool),
+    InfoTypeGroupToggled(String), // Lowercase info type
     DeletePerson(Uuid),
+    PersonActionsToggled(Option<Uuid>),
+    PersonNameDraftChanged(String),
+    RenamePerson(Uuid),
+    PersonRenamed(Uuid, Result<Person, String>),
+    TogglePersonPinned(Uuid),
+    SetColorLabel(Uuid, Option<ColorLabel>),
+    CopyPersonId(Uuid),
+    PersonTagDraftChanged(String),
+    AddPersonTag(Uuid),
+    RemovePersonTag(Uuid, String),
+    OpenPersonFolder(Uuid),
+    RebuildIndex(Uuid),
+    IndexRebuilt(Uuid, Result<String, String>),
+    PlaceLegalHold(Uuid),
+    ReleaseLegalHoldClicked(Option<Uuid>),
+    ReleaseLegalHoldConfirmed(Uuid),
     
     // Information management
     AddInfoTypeChanged(String),
@@ -28,9 +114,80 @@ pub enum Message {
     AddQuoteTextChanged(String),
     AddQuoteDateChanged(String),
     AddQuoteTimeChanged(String),
+    AddQuoteZoneSelected(QuoteZone),
     AddQuotePlaceChanged(String),
+    AddQuoteSourceChanged(String),
+    AddQuoteLinkEvidence(Uuid),
+    AddQuoteUnlinkEvidence(Uuid),
     AddQuoteSubmitted,
     RemoveQuote(Uuid),
+    LocateQuotePlaces,
+    QuotePlacesLocated(Result<(Uuid, Vec<(Uuid, Coordinates)>), String>),
+    QuoteNearPlaceChanged(String),
+    QuoteNearRadiusChanged(String),
+    QuoteNearSubmitted,
+    QuoteNearResolved(Result<Option<Coordinates>, String>),
+    ClearQuoteNear,
+    QuoteFilterChanged(String),
+    
+    // Chat export import
+    ImportChatClicked,
+    ChatFileSelected(PathBuf),
+    ChatParsed(Result<ChatExport, String>),
+    ChatMessageToggled(usize, bool),
+    ChatSelectAll(bool),
+    ChatSelectSender(String),
+    ChatImportConfirmed,
+    ChatImportCancelled,
+    ChatImportComplete(Result<usize, String>),
+    
+    // Pasted quotes
+    PasteQuotesClicked,
+    QuotePasteEdited(text_editor::Action),
+    QuotePasteDefaultDateChanged(String),
+    QuotePasteSourceChanged(String),
+    QuotePasteConfirmed,
+    QuotePasteCancelled,
+    SaveDrafts,
+    RestoreDraft(Uuid, DraftForm),
+    DiscardDraft(Uuid, DraftForm),
+    PersonListScrolled(f32),
+    TabScrolled(f32),
+    SaveSession,
+    
+    // Device import
+    ImportFromDeviceClicked,
+    DevicesFound(Vec<PathBuf>),
+    DeviceSelected(PathBuf),
+    ChooseDeviceFolder,
+    DeviceScanned(PathBuf, Result<Vec<DeviceItem>, String>),
+    DeviceThumbnailLoaded(PathBuf, Option<image::Handle>),
+    DeviceItemToggled(usize, bool),
+    DeviceSelectAll(bool),
+    DeviceImportConfirmed,
+    DeviceImportCancelled,
+    DeviceImportComplete(Result<DeviceImportSummary, String>),
+    
+    // File list import
+    ImportFileListClicked,
+    FileListSelected(PathBuf),
+    FileListLoaded(Result<FileList, String>),
+    FileListColumnSelected(MappedField, Option<usize>),
+    FileListImportConfirmed,
+    FileListImportCancelled,
+    FileListImportComplete(Result<FileListImportSummary, String>),
+    
+    // Task management
+    NewTaskDescriptionChanged(String),
+    NewTaskDueChanged(String),
+    AddTaskSubmitted,
+    TaskDoneToggled(Uuid, bool),
+    RemoveTask(Uuid),
+    CheckDueTasks,
+    AttachChecklist(ChecklistTemplate),
+    ChecklistAnswerChanged(Uuid, String), // Item, typed answer
+    ChecklistAnswerSubmitted(Uuid),
+    RemoveChecklist(Uuid),
     
     // Tab navigation
     TabChanged(EvidenceTab),
@@ -38,53 +195,826 @@ pub enum Message {
     // File operations
     SelectFileClicked,
     FileSelected(PathBuf),
-    FileAddedSuccessfully,
+    FileAddedSuccessfully(EvidenceFile, Vec<String>), // With any storage limit warnings
+    FileCopied(Uuid, Result<Intake, String>), // A copy shown in the status bar finishing
+    MoveFilesToggled(bool),
+    UncategorizedFileCategorySelected(EvidenceType),
+    CancelUncategorizedFile,
+    FileQuarantined(Result<QuarantinedFile, String>),
+    AcceptQuarantinedClicked(Option<Uuid>), // None cancels
+    AcceptQuarantinedConfirmed(Uuid),
+    QuarantinedAccepted(Uuid, Result<EvidenceFile, String>),
+    DiscardQuarantined(Uuid),
+    QuarantinedDiscarded(Uuid, Result<(), String>),
+    IntakeSourceDeviceChanged(String),
+    IntakeSourceUrlChanged(String),
+    IntakeProvidedByChanged(String),
+    IntakeObtainedAtChanged(String),
+    IntakeCollectedByChanged(String),
+    IntakeSubmitted,
+    CancelIntake,
+    PreviewEmail(Uuid),
+    EmailPreviewLoaded(Result<ParsedEmail, String>),
+    CloseEmailPreview,
+    PreviewImage(Uuid),
+    ImagePreviewLoaded(Uuid, Result<Vec<u8>, String>),
+    CloseImagePreview,
+    FindDuplicatesClicked,
+    DuplicatesFound(Result<(Vec<(Uuid, u64)>, Vec<DuplicatePair>), String>), // Newly computed perceptual hashes and the pairs found
+    DismissDuplicate(Uuid, Uuid),
+    CloseDuplicateReview,
+    EvidenceSelectionToggled(Uuid, bool),
+    ClearEvidenceSelection,
+    LoadMap,
+    MapLoaded(Result<MapView, String>),
+    OpenMapPoint(MapTarget),
+    BoardCardAdded(CardContent),
+    BoardCardPressed(Uuid),
+    BoardBackgroundPressed,
+    BoardCardMoved(Uuid, f32, f32),
+    BoardCardDropped,
+    BoardCardRemoved(Uuid),
+    BoardNoteChanged(Uuid, String),
+    BoardConnectClicked(Option<Uuid>), // None stops connecting
+    BoardConnectionLabelChanged(Uuid, String),
+    BoardConnectionRemoved(Uuid),
+    ExportBoardClicked,
+    ExportBoardFileSelected(PathBuf),
+    BoardExported(Result<usize, String>),
+    EvidenceActionsToggled(Option<Uuid>),
+    CopyEvidencePath(Uuid),
+    CopyEvidenceFile(Uuid),
+    EvidenceFileCopied(Result<(), String>),
+    OpenEvidence(Uuid),
+    OpenEvidenceFolder(Uuid),
+    ExportEvidenceFile(Uuid),
+    DeleteEvidence(Uuid),
+    EvidenceDeleted(Result<(), String>),
+    ReplaceEvidenceClicked(Uuid),
+    EvidenceVersionSelected(Uuid, PathBuf),
+    ShareEvidenceWith(Uuid, Uuid), // File, person to add it to
+    EvidenceShared(Result<String, String>),
+    ReviewStatusChanged(Uuid, ReviewStatus),
+    SelectedReviewStatusChanged(ReviewStatus),
+    ReviewStatusSaved(Result<(), String>),
+    EvidenceDateDraftChanged(String),
+    SaveEvidenceDate(Uuid),
+    EvidenceDateSaved(Result<(), String>),
+    TimestampEvidence(Uuid),
+    EvidenceTimestamped(Result<usize, String>),
+    CheckTimestamp(Uuid),
+    TimestampChecked(Result<String, String>),
+    ReviewFilterChanged(Option<ReviewStatus>),
+    EvidenceTypeFilterToggled(EvidenceType),
+    EvidenceTypeFilterCleared,
+    EvidenceSortChanged(EvidenceSort),
+    RenamePatternChanged(String),
+    BatchRenameClicked,
+    BatchRenameComplete(Result<usize, String>),
     ImportClicked,
     ExportClicked,
-    ExportPersonClicked,
+    ExportPersonClicked(Uuid),
     ImportFileSelected(PathBuf),
     ExportFileSelected(PathBuf),
-    ExportPersonFileSelected(PathBuf),
+    ExportPersonFileSelected(Uuid, PathBuf),
+    ExportEachPersonClicked,
+    ExportEachPersonFolderSelected(PathBuf),
+    RunExportProfile(String),
+    CheckScheduledExports,
+    ExportSelectionClicked,
+    PrintPersonClicked,
+    PrintSummaryOpened(Result<PathBuf, String>),
+    ExportSelectionFileSelected(PathBuf),
+    ExportSelectionManifestToggled(bool),
     
     // Async operations
-    ImportComplete(Result<Vec<Person>, String>),
-    ExportComplete(Result<(), String>),
+    JobFinished(Uuid, Result<JobOutcome, String>),
+    JobNotificationClosed(Result<bool, String>), // Whether the notification was clicked
+    CancelJob(Uuid),
+    ClearFinishedJobs,
+    JobProgressTick,
+    ShowJobs(bool),
+    ResumeInterruptedImport,
+    RollBackInterruptedImport,
+    InterruptedImportRolledBack(Result<usize, String>),
+    WindowResized(u32, u32),
+    WindowMoved(i32, i32),
+    WindowCloseRequested(window::Id),
+    WindowFocused(bool),
+    QuickCaptureOpened,
+    QuickCaptureClosed,
+    QuickCaptureHidden,
+    QuickCaptureTextChanged(String),
+    QuickCaptureQuoteSubmitted,
+    QuickCaptureHotkeyChanged(String),
+    CheckQuickCaptureHotkey,
+    SidebarDragStarted,
+    SidebarDragged(f32),
+    SidebarDragEnded,
     PersonAdded(Result<Person, String>),
-    PersonDeleted(Result<(), String>),
+    PersonDeleted(Uuid, Result<(), String>),
     InfoAdded(Result<(), String>),
     InfoRemoved(Result<(), String>),
     QuoteAdded(Result<(), String>),
     QuoteRemoved(Result<(), String>),
     
     // UI state
+    ShowDashboard,
+    ShowActivity(bool),
+    ShowStoreCheck(bool),
+    ShowEvidenceBrowser(bool),
+    EvidenceBrowserLoaded(Result<Vec<BrowserRow>, String>),
+    BrowserFilterChanged(String),
+    BrowserCategorySelected(EvidenceType),
+    BrowserReviewFilterSelected(ReviewStatus),
+    BrowserFiltersCleared,
+    BrowserSortClicked(BrowserColumn),
+    BrowserRowToggled(Uuid, bool),
+    BrowserSelectAll(bool), // Ticks every listed row, or clears the selection
+    BrowserReviewStatusChanged(ReviewStatus),
+    BrowserCopyPaths,
+    BrowserOpenEvidence(Uuid, Uuid), // Person and file
+    VerifyStoreClicked,
+    StoreVerified(Result<StoreReport, String>),
+    RepairStoreClicked,
+    StoreRepaired(Result<(RepairSummary, StoreReport), String>),
     SearchQueryChanged(String),
     ShowAddPersonDialog(bool),
     ShowImportDialog(bool),
     ShowExportDialog(bool),
+    WatermarkExportsToggled(bool),
+    MetadataOnlyExportsToggled(bool),
+    ThumbnailsOnlyExportsToggled(bool),
+    WatermarkCaseNameChanged(String),
+    
+    // Settings
+    ShowSettings(bool),
+    NewCategoryNameChanged(String),
+    NewCategoryFolderChanged(String),
+    NewCategoryIconChanged(String),
+    NewCategoryExtensionsChanged(String),
+    AddCategorySubmitted,
+    RemoveCategory(String),
+    NewMappingExtensionChanged(String),
+    NewMappingTypeSelected(EvidenceType),
+    AddMappingSubmitted,
+    RemoveMapping(String),
+    IntakeModeToggled(bool),
+    MoveIntoEvidenceToggled(bool),
+    SignExportsToggled(bool),
+    TaskNotificationsToggled(bool),
+    JobNotificationsToggled(bool),
+    UiScaleChanged(u16),
+    DateFormatSelected(DateFormat),
+    TimeFormatSelected(TimeFormat),
+    DisplayZoneSelected(DisplayZone),
+    ZoomIn,
+    ZoomOut,
+    MapTileUrlChanged(String),
+    GeocodingUrlChanged(String),
+    TsaUrlChanged(String),
+    WebhookUrlChanged(String),
+    WebhookSecretChanged(String),
+    SendTestWebhook,
+    PluginToggled(String, bool),
+    ReloadPlugins,
+    OpenPluginsFolder,
+    TestWebhookSent(Result<(), String>),
+    OperatorChanged(String),
+    PersonQuotaSizeChanged(String),
+    PersonQuotaFilesChanged(String),
+    StorageLayoutSelected(StorageLayout),
+    FreeSpaceFloorChanged(String),
+    SavedSearchNameChanged(String),
+    NewExportProfileNameChanged(String),
+    ChooseExportProfileDestination,
+    ExportProfileDestinationChosen(PathBuf),
+    NewExportProfilePersonAdded(Uuid),
+    NewExportProfilePersonRemoved(Uuid),
+    NewExportProfileTagsChanged(String),
+    NewExportProfileEncryptToggled(bool),
+    NewExportProfileThumbnailsToggled(bool),
+    NewExportProfilePassphraseChanged(String),
+    NewExportProfileSplitChanged(String),
+    NewExportProfileScheduleSelected(ExportSchedule),
+    AddExportProfileSubmitted,
+    RemoveExportProfile(String),
+    ExportProfilePassphraseChanged(String),
+    ExportProfilePassphraseSubmitted,
+    ExportProfilePassphraseCancelled,
+    NewTemplateNameChanged(String),
+    NewCustomFieldNameChanged(String),
+    NewCustomFieldTypeSelected(FieldType),
+    AddCustomFieldSubmitted,
+    RemoveCustomField(String),
+    CustomFieldDraftChanged(String, String),
+    CustomFieldSubmitted(String),
+    CustomFieldToggled(String, bool),
+    OpenUrl(String),
+    PasteScreenshotClicked,
+    StartRecording,
+    StopRecording,
+    DiscardRecording,
+    RecordingTick,
+    RecordingSaved(Result<(EvidenceFile, Vec<String>), String>),
+    NewNoteClicked,
+    EditNote(Uuid),
+    NoteLoaded(Uuid, Result<String, String>),
+    NoteNameChanged(String),
+    NoteEdited(text_editor::Action),
+    SaveNote,
+    CloseNote,
+    NoteSaved(Result<EvidenceFile, String>),
+    ScreenshotPatternChanged(String),
+    ReportTemplateSelected(String),
+    AddReportTemplateClicked,
+    ReportTemplateLoaded(Result<ReportTemplate, String>),
+    RemoveReportTemplate(String),
+    ExportSettingsClicked,
+    ImportSettingsClicked,
+    SettingsImported(Result<Settings, String>),
+    DefaultCountryCodeChanged(String),
+    NormalizeAllInformation,
+    InformationNormalized(Result<usize, String>),
+    CopyText(String),
+    ExportCsvClicked,
+    ExportCsvFileSelected(PathBuf),
+    CsvExported(Result<(), String>),
+    ExportHashManifestClicked(Option<Uuid>), // One person, or everyone in the current list
+    ExportHashManifestFileSelected(Option<Uuid>, PathBuf),
+    HashManifestExported(Result<usize, String>),
+    ExportCustodyLogClicked(Option<Uuid>), // One person, or everyone in the current list
+    ExportCustodyLogFileSelected(Option<Uuid>, PathBuf),
+    CustodyLogExported(Result<(usize, bool), String>), // Entries written, and whether the hash chain verified
+    ExportInventoryClicked(Option<Uuid>), // One person, or everyone in the current list
+    ExportInventoryFileSelected(Option<Uuid>, PathBuf),
+    InventoryExported(Result<(usize, bool), String>), // Files listed, and whether the inventory was signed
+    ExportTimelineClicked(Option<Uuid>), // One person, or everyone in the current list
+    ExportTimelineFileSelected(Option<Uuid>, PathBuf),
+    TimelineExported(Result<usize, String>),
+    ExportGraphClicked,
+    ExportGraphFileSelected(PathBuf),
+    GraphExported(Result<(usize, usize), String>),
+    ExportLineupClicked,
+    LineupPersonToggled(Uuid, bool),
+    LineupOrderSelected(LineupOrder),
+    LineupShowNamesToggled(bool),
+    LineupCancelled,
+    LineupSaveClicked,
+    LineupFileSelected(PathBuf),
+    LineupExported(Result<(usize, Vec<String>), String>), // Photos on the sheet, and the people left off for want of one
+    NewTemplateTypesChanged(String),
+    AddInfoTemplateSubmitted,
+    RemoveInfoTemplate(String),
+    CompletenessTemplateSelected(String), // Empty stops measuring information types
+    ShowCompleteness(bool),
+    NewChecklistTemplateNameChanged(String),
+    NewChecklistTemplateQuestionsChanged(String),
+    AddChecklistTemplateSubmitted,
+    RemoveChecklistTemplate(String),
+    InfoDraftChanged(Uuid, String),
+    InfoDraftSubmitted(Uuid),
+    BulkEditFieldChanged(BulkEditField),
+    BulkEditFindChanged(String),
+    BulkEditReplaceChanged(String),
+    BulkEditWholeValueToggled(bool),
+    ApplyBulkEdit,
+    BulkEditApplied(Result<usize, String>),
+    SaveSearch,
+    ApplySavedSearch(String),
+    RemoveSavedSearch(String),
+    
+    // Profiles
+    ProfilePassphraseChanged(String),
+    OpenAsAdmin,
+    OpenAsViewer,
+    SwitchProfile,
+    NewAdminPassphraseChanged(String),
+    SetAdminPassphrase,
+    RemoveAdminPassphrase,
     
     // Status
     StatusMessage(String),
 }
 
+impl Message {
+    /// Whether the message changes the store or the settings, which viewers can't do. Anything
+    /// not listed in `is_read_only` counts, so a new message is refused to viewers until it's
+    /// added there.
+    pub fn is_mutating(&self) -> bool {
+        !self.is_read_only()
+    }
+    
+    /// Messages a viewer may send: browsing, searching and previews, exports and reports,
+    /// typing into forms, view preferences, and the results of work already started
+    fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Message::PersonSelected(_)
+                | Message::AddPersonClicked
+                | Message::AddPersonNameChanged(_)
+                | Message::AddPersonTemplateSelected(_)
+                | Message::BulkAddEdited(_)
+                | Message::BulkAddTemplateSelected(_)
+                | Message::BulkAddExistingToggled(_)
+                | Message::BulkAddCancelled
+                | Message::PersonsAdded(..)
+                | Message::PersonSaved(_)
+                | Message::CaseStatusFilterChanged(_)
+                | Message::ColorLabelFilterChanged(_)
+                | Message::SortByPriorityToggled(_)
+                | Message::PersonGroupingChanged(_)
+                | Message::PersonGroupToggled(_)
+                | Message::JumpToLetter(_)
+                | Message::InfoGroupingToggled(_)
+                | Message::InfoTypeGroupToggled(_)
+                | Message::PersonActionsToggled(_)
+                | Message::PersonNameDraftChanged(_)
+                | Message::PersonRenamed(..)
+                | Message::CopyPersonId(_)
+                | Message::PersonTagDraftChanged(_)
+                | Message::OpenPersonFolder(_)
+                | Message::IndexRebuilt(..)
+                | Message::AddInfoTypeChanged(_)
+                | Message::AddInfoValueChanged(_)
+                | Message::AddQuoteTextChanged(_)
+                | Message::AddQuoteDateChanged(_)
+                | Message::AddQuoteTimeChanged(_)
+                | Message::AddQuoteZoneSelected(_)
+                | Message::AddQuotePlaceChanged(_)
+                | Message::AddQuoteSourceChanged(_)
+                | Message::AddQuoteLinkEvidence(_)
+                | Message::AddQuoteUnlinkEvidence(_)
+                | Message::QuotePlacesLocated(_)
+                | Message::QuoteNearPlaceChanged(_)
+                | Message::QuoteNearRadiusChanged(_)
+                | Message::QuoteNearSubmitted
+                | Message::QuoteNearResolved(_)
+                | Message::ClearQuoteNear
+                | Message::QuoteFilterChanged(_)
+                | Message::ChatFileSelected(_)
+                | Message::ChatParsed(_)
+                | Message::ChatMessageToggled(..)
+                | Message::ChatSelectAll(_)
+                | Message::ChatSelectSender(_)
+                | Message::ChatImportCancelled
+                | Message::ChatImportComplete(_)
+                | Message::QuotePasteEdited(_)
+                | Message::QuotePasteDefaultDateChanged(_)
+                | Message::QuotePasteSourceChanged(_)
+                | Message::QuotePasteCancelled
+                | Message::SaveDrafts
+                | Message::RestoreDraft(..)
+                | Message::DiscardDraft(..)
+                | Message::PersonListScrolled(_)
+                | Message::TabScrolled(_)
+                | Message::SaveSession
+                | Message::DevicesFound(_)
+                | Message::DeviceSelected(_)
+                | Message::ChooseDeviceFolder
+                | Message::DeviceScanned(..)
+                | Message::DeviceThumbnailLoaded(..)
+                | Message::DeviceItemToggled(..)
+                | Message::DeviceSelectAll(_)
+                | Message::DeviceImportCancelled
+                | Message::DeviceImportComplete(_)
+                | Message::FileListSelected(_)
+                | Message::FileListLoaded(_)
+                | Message::FileListColumnSelected(..)
+                | Message::FileListImportCancelled
+                | Message::FileListImportComplete(_)
+                | Message::NewTaskDescriptionChanged(_)
+                | Message::NewTaskDueChanged(_)
+                | Message::CheckDueTasks
+                | Message::ChecklistAnswerChanged(..)
+                | Message::TabChanged(_)
+                | Message::FileAddedSuccessfully(..)
+                | Message::FileCopied(..)
+                | Message::MoveFilesToggled(_)
+                | Message::CancelUncategorizedFile
+                | Message::FileQuarantined(_)
+                | Message::AcceptQuarantinedClicked(_)
+                | Message::QuarantinedAccepted(..)
+                | Message::QuarantinedDiscarded(..)
+                | Message::IntakeSourceDeviceChanged(_)
+                | Message::IntakeSourceUrlChanged(_)
+                | Message::IntakeProvidedByChanged(_)
+                | Message::IntakeObtainedAtChanged(_)
+                | Message::IntakeCollectedByChanged(_)
+                | Message::CancelIntake
+                | Message::PreviewEmail(_)
+                | Message::EmailPreviewLoaded(_)
+                | Message::CloseEmailPreview
+                | Message::PreviewImage(_)
+                | Message::ImagePreviewLoaded(..)
+                | Message::CloseImagePreview
+                | Message::DuplicatesFound(_)
+                | Message::DismissDuplicate(..)
+                | Message::CloseDuplicateReview
+                | Message::EvidenceSelectionToggled(..)
+                | Message::ClearEvidenceSelection
+                | Message::LoadMap
+                | Message::MapLoaded(_)
+                | Message::OpenMapPoint(_)
+                | Message::BoardCardPressed(_)
+                | Message::BoardBackgroundPressed
+                | Message::ExportBoardClicked
+                | Message::ExportBoardFileSelected(_)
+                | Message::BoardExported(_)
+                | Message::EvidenceActionsToggled(_)
+                | Message::CopyEvidencePath(_)
+                | Message::CopyEvidenceFile(_)
+                | Message::EvidenceFileCopied(_)
+                | Message::OpenEvidence(_)
+                | Message::OpenEvidenceFolder(_)
+                | Message::ExportEvidenceFile(_)
+                | Message::EvidenceDeleted(_)
+                | Message::EvidenceShared(_)
+                | Message::ReviewStatusSaved(_)
+                | Message::EvidenceDateDraftChanged(_)
+                | Message::EvidenceDateSaved(_)
+                | Message::EvidenceTimestamped(_)
+                | Message::CheckTimestamp(_)
+                | Message::TimestampChecked(_)
+                | Message::ReviewFilterChanged(_)
+                | Message::EvidenceTypeFilterToggled(_)
+                | Message::EvidenceTypeFilterCleared
+                | Message::EvidenceSortChanged(_)
+                | Message::RenamePatternChanged(_)
+                | Message::BatchRenameComplete(_)
+                | Message::ExportClicked
+                | Message::ExportPersonClicked(_)
+                | Message::ExportFileSelected(_)
+                | Message::ExportPersonFileSelected(..)
+                | Message::ExportEachPersonClicked
+                | Message::ExportEachPersonFolderSelected(_)
+                | Message::CheckScheduledExports
+                | Message::ExportSelectionClicked
+                | Message::PrintPersonClicked
+                | Message::PrintSummaryOpened(_)
+                | Message::ExportSelectionFileSelected(_)
+                | Message::ExportSelectionManifestToggled(_)
+                | Message::JobFinished(..)
+                | Message::JobNotificationClosed(_)
+                | Message::CancelJob(_)
+                | Message::ClearFinishedJobs
+                | Message::JobProgressTick
+                | Message::ShowJobs(_)
+                | Message::InterruptedImportRolledBack(_)
+                | Message::WindowResized(..)
+                | Message::WindowMoved(..)
+                | Message::WindowCloseRequested(_)
+                | Message::WindowFocused(_)
+                | Message::QuickCaptureOpened
+                | Message::QuickCaptureClosed
+                | Message::QuickCaptureHidden
+                | Message::QuickCaptureTextChanged(_)
+                | Message::CheckQuickCaptureHotkey
+                | Message::SidebarDragStarted
+                | Message::SidebarDragged(_)
+                | Message::SidebarDragEnded
+                | Message::PersonAdded(_)
+                | Message::PersonDeleted(..)
+                | Message::InfoAdded(_)
+                | Message::InfoRemoved(_)
+                | Message::QuoteAdded(_)
+                | Message::QuoteRemoved(_)
+                | Message::ShowDashboard
+                | Message::ShowActivity(_)
+                | Message::ShowStoreCheck(_)
+                | Message::ShowEvidenceBrowser(_)
+                | Message::EvidenceBrowserLoaded(_)
+                | Message::BrowserFilterChanged(_)
+                | Message::BrowserCategorySelected(_)
+                | Message::BrowserReviewFilterSelected(_)
+                | Message::BrowserFiltersCleared
+                | Message::BrowserSortClicked(_)
+                | Message::BrowserRowToggled(..)
+                | Message::BrowserSelectAll(_)
+                | Message::BrowserCopyPaths
+                | Message::BrowserOpenEvidence(..)
+                | Message::VerifyStoreClicked
+                | Message::StoreVerified(_)
+                | Message::StoreRepaired(_)
+                | Message::SearchQueryChanged(_)
+                | Message::ShowAddPersonDialog(_)
+                | Message::ShowImportDialog(_)
+                | Message::ShowExportDialog(_)
+                | Message::WatermarkExportsToggled(_)
+                | Message::MetadataOnlyExportsToggled(_)
+                | Message::ThumbnailsOnlyExportsToggled(_)
+                | Message::WatermarkCaseNameChanged(_)
+                | Message::ShowSettings(_)
+                | Message::NewCategoryNameChanged(_)
+                | Message::NewCategoryFolderChanged(_)
+                | Message::NewCategoryIconChanged(_)
+                | Message::NewCategoryExtensionsChanged(_)
+                | Message::NewMappingExtensionChanged(_)
+                | Message::NewMappingTypeSelected(_)
+                | Message::UiScaleChanged(_)
+                | Message::ZoomIn
+                | Message::ZoomOut
+                | Message::SendTestWebhook
+                | Message::ReloadPlugins
+                | Message::OpenPluginsFolder
+                | Message::TestWebhookSent(_)
+                | Message::SavedSearchNameChanged(_)
+                | Message::NewExportProfileNameChanged(_)
+                | Message::ChooseExportProfileDestination
+                | Message::ExportProfileDestinationChosen(_)
+                | Message::NewExportProfilePersonAdded(_)
+                | Message::NewExportProfilePersonRemoved(_)
+                | Message::NewExportProfileTagsChanged(_)
+                | Message::NewExportProfileEncryptToggled(_)
+                | Message::NewExportProfileThumbnailsToggled(_)
+                | Message::NewExportProfilePassphraseChanged(_)
+                | Message::NewExportProfileSplitChanged(_)
+                | Message::NewExportProfileScheduleSelected(_)
+                | Message::NewTemplateNameChanged(_)
+                | Message::NewCustomFieldNameChanged(_)
+                | Message::NewCustomFieldTypeSelected(_)
+                | Message::CustomFieldDraftChanged(..)
+                | Message::OpenUrl(_)
+                | Message::DiscardRecording
+                | Message::RecordingTick
+                | Message::RecordingSaved(_)
+                | Message::NoteLoaded(..)
+                | Message::NoteNameChanged(_)
+                | Message::NoteEdited(_)
+                | Message::CloseNote
+                | Message::NoteSaved(_)
+                | Message::ReportTemplateSelected(_)
+                | Message::ReportTemplateLoaded(_)
+                | Message::ExportSettingsClicked
+                | Message::InformationNormalized(_)
+                | Message::CopyText(_)
+                | Message::ExportCsvClicked
+                | Message::ExportCsvFileSelected(_)
+                | Message::CsvExported(_)
+                | Message::ExportHashManifestClicked(_)
+                | Message::ExportHashManifestFileSelected(..)
+                | Message::HashManifestExported(_)
+                | Message::ExportCustodyLogClicked(_)
+                | Message::ExportCustodyLogFileSelected(..)
+                | Message::CustodyLogExported(_)
+                | Message::ExportInventoryClicked(_)
+                | Message::ExportInventoryFileSelected(..)
+                | Message::InventoryExported(_)
+                | Message::ExportTimelineClicked(_)
+                | Message::ExportTimelineFileSelected(..)
+                | Message::TimelineExported(_)
+                | Message::ExportGraphClicked
+                | Message::ExportGraphFileSelected(_)
+                | Message::GraphExported(_)
+                | Message::ExportLineupClicked
+                | Message::LineupPersonToggled(..)
+                | Message::LineupOrderSelected(_)
+                | Message::LineupShowNamesToggled(_)
+                | Message::LineupCancelled
+                | Message::LineupSaveClicked
+                | Message::LineupFileSelected(_)
+                | Message::LineupExported(_)
+                | Message::NewTemplateTypesChanged(_)
+                | Message::ShowCompleteness(_)
+                | Message::NewChecklistTemplateNameChanged(_)
+                | Message::NewChecklistTemplateQuestionsChanged(_)
+                | Message::InfoDraftChanged(..)
+                | Message::BulkEditFieldChanged(_)
+                | Message::BulkEditFindChanged(_)
+                | Message::BulkEditReplaceChanged(_)
+                | Message::BulkEditWholeValueToggled(_)
+                | Message::BulkEditApplied(_)
+                | Message::ApplySavedSearch(_)
+                | Message::ProfilePassphraseChanged(_)
+                | Message::OpenAsAdmin
+                | Message::OpenAsViewer
+                | Message::SwitchProfile
+                | Message::NewAdminPassphraseChanged(_)
+                | Message::StatusMessage(_)
+        )
+    }
+    
+    /// Whether the message changes what's typed into a form that's kept as a draft
+    pub fn edits_draft(&self) -> bool {
+        matches!(
+            self,
+            Message::AddQuoteTextChanged(_)
+                | Message::AddQuoteDateChanged(_)
+                | Message::AddQuoteTimeChanged(_)
+                | Message::AddQuotePlaceChanged(_)
+                | Message::AddQuoteSourceChanged(_)
+                | Message::AddQuoteSubmitted
+                | Message::NewNoteClicked
+                | Message::NoteNameChanged(_)
+                | Message::NoteEdited(_)
+                | Message::CloseNote
+                | Message::NoteSaved(_)
+                | Message::PasteQuotesClicked
+                | Message::QuotePasteEdited(_)
+                | Message::QuotePasteDefaultDateChanged(_)
+                | Message::QuotePasteSourceChanged(_)
+                | Message::QuotePasteConfirmed
+                | Message::QuotePasteCancelled
+        )
+    }
+}
+
+/// State of the guided chat import: the parsed export and which messages become quotes
+pub struct ChatImportDialog {
+    pub export: ChatExport,
+    pub selected: Vec<bool>,
+}
+
+/// Quotes pasted as text, one per line, with how each line reads for the preview
+pub struct QuotePasteDialog {
+    pub content: text_editor::Content,
+    pub default_date: String, // For lines that don't give a date
+    pub source: String, // Given to every quote, optional
+    pub lines: Vec<PastedLine>,
+}
+
+impl QuotePasteDialog {
+    fn reparse(&mut self) {
+        self.lines = quote_paste::parse(&self.content.text(), &self.default_date);
+    }
+}
+
+/// State of adding several people at once from a pasted list of names, one per line
+pub struct BulkAddDialog {
+    pub content: text_editor::Content,
+    pub template: Option<InfoTemplate>,
+    pub add_existing: bool, // Also add names someone in the case already has
+    pub names: Vec<ListedName>,
+}
+
+impl BulkAddDialog {
+    fn reparse(&mut self, persons: &[Person]) {
+        self.names = name_list::parse(&self.content.text(), persons);
+    }
+
+    /// The names that will be added
+    pub fn to_add(&self) -> Vec<String> {
+        self.names
+            .iter()
+            .filter(|listed| listed.status == NameStatus::New || (listed.status == NameStatus::Existing && self.add_existing))
+            .map(|listed| listed.name.clone())
+            .collect()
+    }
+}
+
+/// Which people a photo lineup shows and how
+pub struct LineupDialog {
+    pub persons: Vec<Uuid>, // Ticked, out of the current list
+    pub order: LineupOrder,
+    pub show_names: bool, // Print names under the numbers; off for sheets shown to witnesses
+}
+
+/// State of the device import wizard: the attached devices, and the files on the chosen one
+/// with which of them to take in
+pub struct DeviceImportDialog {
+    pub volumes: Vec<PathBuf>,
+    pub volume: Option<PathBuf>,
+    pub items: Vec<DeviceItem>,
+    pub selected: Vec<bool>,
+    pub thumbnails: HashMap<PathBuf, image::Handle>,
+    pub scanning: bool,
+}
+
+/// A collection tool's file list, with the columns picked for each evidence detail and the rows
+/// as those columns read
+pub struct FileListDialog {
+    pub list: FileList,
+    pub mapping: ColumnMapping,
+    pub rows: Vec<FileListRow>,
+}
+
+impl FileListDialog {
+    fn remap(&mut self) {
+        self.rows = self.list.mapped_rows(&self.mapping);
+    }
+}
+
+/// The rendered map for the selected person, with markers numbered in `points` order
+#[derive(Debug, Clone)]
+pub struct MapView {
+    pub image: image::Handle,
+    pub points: Vec<MapPoint>,
+    pub missing_tiles: usize,
+}
+
+/// The job currently being worked on, which the status bar offers to cancel
+pub struct RunningJob {
+    pub id: Uuid,
+    pub cancel: CancellationToken,
+    progress: Arc<JobProgress>,
+    pub shown_progress: (usize, usize), // Files done and total as of the last refresh
+}
+
+/// A file being copied into the store, shown in the status bar until it's in
+pub struct FileCopy {
+    pub id: Uuid,
+    pub name: String,
+    progress: Arc<JobProgress>,
+    pub shown_progress: (u64, u64), // Bytes done and total as of the last refresh
+}
+
+/// What a finished job hands back to the UI
+#[derive(Debug, Clone)]
+pub enum JobOutcome {
+    Exported(Vec<String>), // Warnings, e.g. files that went out without their watermark
+    Imported(ImportReport),
+}
+
+/// A text note being written in the Documents tab, new or an existing .md or .txt document
+pub struct NoteEditor {
+    pub name: String,
+    pub content: text_editor::Content,
+    pub editing: Option<Uuid>, // The document being edited; saving adds a new version of it
+}
+
+/// A file waiting for its provenance to be recorded before it is copied in
+pub struct IntakeDialog {
+    pub path: PathBuf,
+    pub evidence_type: EvidenceType,
+}
+
 pub struct AppState {
     // Backend
     file_manager: FileManager,
     export_import_manager: ExportImportManager,
+    audit_log: AuditLog,
+    
+    pub settings: Settings,
     
     // Data
     pub persons: Vec<Person>,
     pub selected_person: Option<Uuid>,
     pub evidence_files: Vec<EvidenceFile>,
+    pub shared_content: BTreeMap<String, Vec<Reference>>, // Entries holding each stored piece of content, by hash
+    pub selected_evidence: Vec<Uuid>,
+    pub evidence_actions: Option<Uuid>, // Row whose right-click actions are open
+    pub evidence_date_draft: String, // Evidence date being edited in the open actions row
+    pub person_actions: Option<Uuid>, // Sidebar person whose right-click actions are open
+    pub person_name_draft: String,
+    pub person_tag_draft: String,
+    pub sidebar_dragging: bool,
+    pub quick_capture: bool, // The window is shrunk to the quick capture panel
+    pub quick_capture_text: String,
+    pub quick_capture_hotkey: Option<u32>, // Id of the registered global shortcut
+    pub plugins: Plugins,
+    pub plugin_files: Vec<PluginFile>, // Listed in Settings
+    pub confirm_hold_release: Option<Uuid>, // Person whose legal hold release is waiting for confirmation
+    pub show_completeness: bool, // Checklist of what the open person's record is missing
+    pub signing_fingerprint: Option<String>, // This installation's signing key, once it exists
+    pub map_view: Option<MapView>,
+    pub map_loading: bool,
+    pub board: Board, // The selected person's evidence board
+    pub board_selected: Option<Uuid>, // Card whose details are shown beside the board
+    pub board_connecting: Option<Uuid>, // Card a string is being tied from; the next card clicked ends it
+    pub statistics: Statistics,
+    pub evidence_counts: HashMap<Uuid, usize>, // Files per person for the sidebar, kept from the last scan
+    pub activity: Vec<AuditEntry>,
+    pub store_report: Option<StoreReport>, // From the last store verification
+    pub store_checking: bool, // A verification or repair is running
     
     // UI State
     pub current_tab: EvidenceTab,
     pub search_query: String,
+    pub search_error: Option<String>, // Why the search query couldn't be parsed
+    pub new_saved_search_name: String,
+    pub bulk_edit: BulkEdit,
+    pub case_status_filter: Option<CaseStatus>,
+    pub color_label_filter: Option<ColorLabel>,
+    pub sort_by_priority: bool,
+    pub review_filter: Option<ReviewStatus>,
+    pub evidence_type_filter: Vec<EvidenceType>, // Categories shown in the All Evidence tab; empty shows every one
+    pub evidence_sort: EvidenceSort,
     pub filtered_persons: Vec<Uuid>,
     
     // Dialog states
     pub show_add_person_dialog: bool,
+    pub bulk_add: Option<BulkAddDialog>,
+    pub lineup: Option<LineupDialog>,
     pub show_import_dialog: bool,
     pub show_export_dialog: bool,
+    pub chat_import: Option<ChatImportDialog>,
+    pub quote_paste: Option<QuotePasteDialog>,
+    pub device_import: Option<DeviceImportDialog>,
+    pub file_list_import: Option<FileListDialog>,
+    pub show_settings: bool,
+    pub show_activity: bool,
+    pub show_store_check: bool,
+    pub show_evidence_browser: bool,
+    pub evidence_browser: EvidenceBrowser,
+    pub email_preview: Option<ParsedEmail>,
+    pub image_preview: Option<(Uuid, image::Handle)>,
+    pub uncategorized_file: Option<PathBuf>, // Dropped file waiting for a category
+    pub quarantined: Vec<QuarantinedFile>, // Executables, scripts and shortcuts held back from intake
+    pub quarantine_confirm: Option<Uuid>, // Quarantined file whose acceptance is being confirmed
+    pub intake: Option<IntakeDialog>,
+    pub note_editor: Option<NoteEditor>,
+    pub move_files: bool, // Whether the next files added are moved in; starts from the setting
+    pub duplicate_review: Option<Vec<DuplicatePair>>,
     
     // Form fields
     pub new_person_name: String,
@@ -93,53 +1023,759 @@ pub struct AppState {
     pub new_quote_text: String,
     pub new_quote_date: String,
     pub new_quote_time: String,
+    pub new_quote_zone: QuoteZone, // Zone the typed date and time are in, also used for pasted and chat quotes
     pub new_quote_place: String,
+    pub new_quote_source: String,
+    pub new_quote_linked_evidence: Vec<Uuid>,
+    pub quote_near_place: String,
+    pub quote_near_radius: String,
+    pub quote_near: Option<(Coordinates, f64)>, // Centre and radius in km of the proximity filter
+    pub quote_filter: String, // Typed into the Quotes tab's search box
+    pub new_task_description: String,
+    pub new_task_due: String,
+    pub intake_source_device: String,
+    pub intake_source_url: String,
+    pub intake_provided_by: String,
+    pub intake_obtained_at: String,
+    pub intake_collected_by: String,
+    
+    pub rename_pattern: String,
+    pub new_category_name: String,
+    pub new_category_folder: String,
+    pub new_category_icon: String,
+    pub new_category_extensions: String,
+    pub new_person_template: Option<InfoTemplate>,
+    pub new_template_name: String,
+    pub new_template_types: String,
+    pub info_drafts: HashMap<Uuid, String>, // Values typed into empty information slots
+    pub checklist_drafts: HashMap<Uuid, String>, // Unsaved answers by checklist item
+    pub new_checklist_template_name: String,
+    pub new_checklist_template_questions: String, // Separated by semicolons
+    pub new_custom_field_name: String,
+    pub new_export_profile: ExportProfile, // Profile being set up in Settings
+    pub new_export_profile_tags: String, // Comma-separated
+    pub new_export_profile_split: String, // MB
+    pub export_passphrase_prompt: Option<(String, String)>, // Encrypted profile waiting for its passphrase, and what's typed
+    pub new_custom_field_type: FieldType,
+    pub custom_field_drafts: HashMap<String, String>, // Unsaved editor text by field name
+    pub new_mapping_extension: String,
+    pub new_mapping_type: Option<EvidenceType>,
+    
+    // Export options
+    pub watermark_exports: bool,
+    pub metadata_only_exports: bool, // .ema archives leave out the evidence files themselves
+    pub thumbnails_only_exports: bool, // As metadata only, with small previews of the images
+    pub watermark_case_name: String,
+    pub export_selection_manifest: bool,
+    
+    pub notified_tasks: Vec<Uuid>, // Due tasks already announced this session
+    pub window_focused: bool, // Finished jobs are only announced while the app is in the background
     
     // Status
     pub status_message: String,
     pub status_timeout: f32,
+    
+    // Background jobs
+    pub jobs: JobQueue,
+    pub running_job: Option<RunningJob>,
+    pub file_copies: Vec<FileCopy>,
+    pub recording: Option<(Uuid, Recording)>, // Microphone recording in progress and the person it's for
+    pub show_jobs: bool,
+    pub interrupted_import: Option<ImportJournal>, // Left by an import the app stopped in the middle of
+    
+    // Drafts of partly filled forms
+    pub drafts: Drafts,
+    pub restorable_drafts: Vec<Draft>, // Left from the last session, waiting to be restored or discarded
+    pub drafts_changed: bool, // Something was typed since the drafts were last saved
+    
+    // Session snapshot, for picking up again after a crash
+    pub person_list_scroll: f32,
+    pub tab_scroll: f32,
+    pub saved_session: Session, // As last written to the session file
+    
+    // Profiles
+    pub role: Option<Role>, // None until a profile is picked at launch
+    pub profile_passphrase: String,
+    pub new_admin_passphrase: String,
 }
 
 impl AppState {
     pub fn new() -> Result<Self> {
-        let file_manager = FileManager::new()?;
-        let export_import_manager = ExportImportManager::new(file_manager.clone());
+        let mut file_manager = FileManager::new()?;
+        let mut audit_log = AuditLog::new(file_manager.get_evidence_dir());
         let persons = file_manager.load_all_persons().unwrap_or_default();
+        let settings = Settings::load().unwrap_or_else(|e| {
+            eprintln!("Failed to load settings, using defaults: {}", e);
+            Settings::default()
+        });
+        file_manager.set_storage_layout(settings.storage_layout);
+        let export_import_manager = ExportImportManager::new(file_manager.clone());
+        audit_log.set_operator(&settings.operator);
+        audit_log.set_webhook(Webhook::new(&settings.webhook_url, &settings.webhook_secret));
+        let intake_collected_by = settings.operator.clone();
+        let move_files = settings.move_into_evidence;
+        // Without a passphrase there's only one profile
+        let role = settings.admin_passphrase.is_none().then_some(Role::Admin);
+        let interrupted_import = ImportJournal::load(file_manager.get_evidence_dir()).unwrap_or_else(|e| {
+            eprintln!("Failed to load import journal: {}", e);
+            None
+        });
+        let mut drafts = Drafts::load().unwrap_or_else(|e| {
+            eprintln!("Failed to load drafts: {}", e);
+            Drafts::default()
+        });
+        drafts.retain_persons(|id| persons.iter().any(|p| p.id == id));
+        let restorable_drafts = drafts.drafts.clone();
+        let quarantined = Quarantine::new(file_manager.get_evidence_dir()).list().unwrap_or_else(|e| {
+            eprintln!("Failed to load quarantine list: {}", e);
+            Vec::new()
+        });
         
-        Ok(Self {
+        let mut state = Self {
             file_manager,
             export_import_manager,
+            audit_log,
+            settings,
             persons,
             selected_person: None,
             evidence_files: Vec::new(),
+            shared_content: BTreeMap::new(),
+            selected_evidence: Vec::new(),
+            evidence_actions: None,
+            evidence_date_draft: String::new(),
+            person_actions: None,
+            person_name_draft: String::new(),
+            person_tag_draft: String::new(),
+            sidebar_dragging: false,
+            quick_capture: false,
+            quick_capture_text: String::new(),
+            quick_capture_hotkey: None,
+            plugins: Plugins::default(),
+            plugin_files: Vec::new(),
+            confirm_hold_release: None,
+            show_completeness: false,
+            signing_fingerprint: None,
+            map_view: None,
+            map_loading: false,
+            board: Board::default(),
+            board_selected: None,
+            board_connecting: None,
+            statistics: Statistics::default(),
+            evidence_counts: HashMap::new(),
+            activity: Vec::new(),
+            store_report: None,
+            store_checking: false,
             current_tab: EvidenceTab::Information,
             search_query: String::new(),
+            search_error: None,
+            new_saved_search_name: String::new(),
+            bulk_edit: BulkEdit {
+                field: BulkEditField::Type,
+                find: String::new(),
+                replace: String::new(),
+                whole_value: true,
+            },
+            case_status_filter: None,
+            color_label_filter: None,
+            sort_by_priority: false,
+            review_filter: None,
+            evidence_type_filter: Vec::new(),
+            evidence_sort: EvidenceSort::default(),
             filtered_persons: Vec::new(),
             show_add_person_dialog: false,
+            bulk_add: None,
+            lineup: None,
             show_import_dialog: false,
             show_export_dialog: false,
+            chat_import: None,
+            quote_paste: None,
+            device_import: None,
+            file_list_import: None,
+            show_settings: false,
+            show_activity: false,
+            show_store_check: false,
+            show_evidence_browser: false,
+            evidence_browser: EvidenceBrowser::default(),
+            email_preview: None,
+            image_preview: None,
+            uncategorized_file: None,
+            quarantined,
+            quarantine_confirm: None,
+            intake: None,
+            note_editor: None,
+            move_files,
+            duplicate_review: None,
             new_person_name: String::new(),
             new_info_type: String::new(),
             new_info_value: String::new(),
             new_quote_text: String::new(),
             new_quote_date: String::new(),
             new_quote_time: String::new(),
+            new_quote_zone: QuoteZone::System,
             new_quote_place: String::new(),
+            new_quote_source: String::new(),
+            new_quote_linked_evidence: Vec::new(),
+            quote_near_place: String::new(),
+            quote_near_radius: "10".to_string(),
+            quote_near: None,
+            quote_filter: String::new(),
+            new_task_description: String::new(),
+            new_task_due: String::new(),
+            intake_source_device: String::new(),
+            intake_source_url: String::new(),
+            intake_provided_by: String::new(),
+            intake_obtained_at: String::new(),
+            intake_collected_by,
+            rename_pattern: "{date}_{person}_{counter}.{ext}".to_string(),
+            new_category_name: String::new(),
+            new_category_folder: String::new(),
+            new_category_icon: String::new(),
+            new_category_extensions: String::new(),
+            new_person_template: None,
+            new_template_name: String::new(),
+            new_template_types: String::new(),
+            info_drafts: HashMap::new(),
+            checklist_drafts: HashMap::new(),
+            new_checklist_template_name: String::new(),
+            new_checklist_template_questions: String::new(),
+            new_custom_field_name: String::new(),
+            new_export_profile: ExportProfile::default(),
+            new_export_profile_tags: String::new(),
+            new_export_profile_split: String::new(),
+            export_passphrase_prompt: None,
+            new_custom_field_type: FieldType::Date,
+            custom_field_drafts: HashMap::new(),
+            new_mapping_extension: String::new(),
+            new_mapping_type: None,
+            watermark_exports: false,
+            metadata_only_exports: false,
+            thumbnails_only_exports: false,
+            watermark_case_name: String::new(),
+            export_selection_manifest: true,
             status_message: String::new(),
+            notified_tasks: Vec::new(),
+            window_focused: true,
             status_timeout: 0.0,
-        })
+            jobs: JobQueue::load().unwrap_or_else(|e| {
+                eprintln!("Failed to load job queue: {}", e);
+                JobQueue::default()
+            }),
+            running_job: None,
+            file_copies: Vec::new(),
+            recording: None,
+            show_jobs: false,
+            interrupted_import,
+            drafts,
+            restorable_drafts,
+            drafts_changed: false,
+            person_list_scroll: 0.0,
+            tab_scroll: 0.0,
+            saved_session: Session::default(),
+            role,
+            profile_passphrase: String::new(),
+            new_admin_passphrase: String::new(),
+        };
+        // An interrupted import waits for the user to resume or roll it back instead of restarting on its own
+        if let Some(journal) = &state.interrupted_import {
+            for job in &mut state.jobs.jobs {
+                if job.status == JobStatus::Queued && matches!(&job.kind, JobKind::ImportArchive { path } if *path == journal.archive) {
+                    job.status = JobStatus::Failed("Interrupted".to_string());
+                }
+            }
+        }
+        state.refresh_statistics();
+        
+        Ok(state)
     }
     
     fn update_filtered_persons(&mut self) {
-        if self.search_query.is_empty() {
-            self.filtered_persons = self.persons.iter().map(|p| p.id).collect();
-        } else {
-            self.filtered_persons = self.persons
-                .iter()
-                .filter(|p| p.name.to_lowercase().contains(&self.search_query.to_lowercase()))
-                .map(|p| p.id)
-                .collect();
+        let query = match Query::parse(&self.search_query) {
+            Ok(query) => {
+                self.search_error = None;
+                Some(query)
+            }
+            Err(e) => {
+                self.search_error = Some(e);
+                None
+            }
+        };
+        // Evidence is only read from disk for everyone when the query asks about it
+        let uses_evidence = query.as_ref().is_some_and(Query::uses_evidence);
+        let evidence_for = |person: &Person| -> Vec<EvidenceFile> {
+            if !uses_evidence {
+                Vec::new()
+            } else if Some(person.id) == self.selected_person {
+                self.evidence_files.clone()
+            } else {
+                self.file_manager.scan_person_evidence(person, &self.settings).unwrap_or_default()
+            }
+        };
+        let mut persons: Vec<&Person> = self.persons
+            .iter()
+            .filter(|p| query.as_ref().is_some_and(|q| q.matches(p, &evidence_for(p))))
+            .filter(|p| self.case_status_filter.is_none_or(|status| p.case_status == status))
+            .filter(|p| self.color_label_filter.is_none_or(|color| p.color_label == Some(color)))
+            .collect();
+        
+        if self.sort_by_priority {
+            // Stable sort keeps the usual order within each priority
+            persons.sort_by_key(|p| std::cmp::Reverse(p.priority));
+        }
+        // Pinned people stay on top whatever the sort
+        persons.sort_by_key(|p| !p.pinned);
+        
+        self.filtered_persons = persons.iter().map(|p| p.id).collect();
+    }
+    
+    fn checklist_question(&self, item_id: Uuid) -> String {
+        self.persons
+            .iter()
+            .flat_map(|p| &p.checklists)
+            .flat_map(|c| &c.items)
+            .find(|i| i.id == item_id)
+            .map(|i| i.question.clone())
+            .unwrap_or_default()
+    }
+    
+    fn task_description(&self, task_id: Uuid) -> String {
+        self.persons
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .find(|t| t.id == task_id)
+            .map(|t| t.description.clone())
+            .unwrap_or_default()
+    }
+    
+    /// Applies a change to the selected person and saves it in the background
+    fn update_selected_person(&mut self, action: AuditAction, details: String, change: impl FnOnce(&mut Person)) -> Command<Message> {
+        match self.selected_person {
+            Some(person_id) => self.update_person(person_id, action, details, change),
+            None => Command::none(),
+        }
+    }
+    
+    /// Applies a change to any person and saves it in the background
+    fn update_person(&mut self, person_id: Uuid, action: AuditAction, details: String, change: impl FnOnce(&mut Person)) -> Command<Message> {
+        if action.changes_existing() && self.refuse_on_hold(Some(person_id)) {
+            return Command::none();
+        }
+        let Some(person) = self.persons.iter_mut().find(|p| p.id == person_id) else {
+            return Command::none();
+        };
+        change(person);
+        person.update_timestamp();
+        
+        let person_clone = person.clone();
+        let file_manager = self.file_manager.clone();
+        let audit_log = self.audit_log.clone();
+        self.person_changed(person_clone.id);
+        
+        Command::perform(
+            async move {
+                file_manager.save_person_data(&person_clone).map_err(|e| e.to_string())?;
+                audit_log.record(Some(&person_clone), action, details).map_err(|e| e.to_string())
+            },
+            Message::PersonSaved
+        )
+    }
+    
+    /// Whether the person is under legal hold, in which case the change being made to their existing entries is refused
+    fn refuse_on_hold(&mut self, person_id: Option<Uuid>) -> bool {
+        let Some(person) = self.persons.iter().find(|p| Some(p.id) == person_id && p.legal_hold) else {
+            return false;
+        };
+        self.update_status(format!("{} is under legal hold: existing entries can't be changed or deleted", person.name));
+        true
+    }
+    
+    /// Replaces a person with what a background task saved for them, then refreshes everything derived from them
+    fn reload_person(&mut self, person_id: Uuid) {
+        if let Some(person) = self.persons.iter_mut().find(|p| p.id == person_id)
+            && let Ok(updated_person) = self.file_manager.load_person_data(
+                &self.file_manager.get_evidence_dir().join(person.folder_name())
+            )
+        {
+            *person = updated_person;
+        }
+        self.person_changed(person_id);
+    }
+    
+    /// Keeps the person list, search results, counters and map in step with a changed person
+    fn person_changed(&mut self, person_id: Uuid) {
+        if self.selected_person == Some(person_id) {
+            // Quotes place markers on the map too; it is drawn again when next opened
+            self.map_view = None;
+        }
+        self.persons.sort_by(|a, b| a.name.cmp(&b.name));
+        self.update_filtered_persons();
+    }
+    
+    /// Selected evidence files, in the order they are listed
+    pub fn selected_evidence_files(&self) -> Vec<EvidenceFile> {
+        self.evidence_files
+            .iter()
+            .filter(|f| self.selected_evidence.contains(&f.id))
+            .cloned()
+            .collect()
+    }
+    
+    fn export_options(&self) -> ExportOptions {
+        ExportOptions {
+            watermark: if self.watermark_exports {
+                Some(Watermark::new(self.watermark_case_name.clone()))
+            } else {
+                None
+            },
+            sign: self.settings.sign_exports,
+            metadata_only: self.metadata_only_exports,
+            thumbnails_only: self.thumbnails_only_exports,
+        }
+    }
+    
+    /// The selected person's form fields as they're kept in a draft, or None when the form's
+    /// text is empty and there's nothing worth restoring. The text comes first, as "text".
+    fn draft_fields(&self, form: DraftForm) -> Option<BTreeMap<String, String>> {
+        let fields = match form {
+            DraftForm::Quote => vec![
+                ("text", self.new_quote_text.clone()),
+                ("date", self.new_quote_date.clone()),
+                ("time", self.new_quote_time.clone()),
+                ("place", self.new_quote_place.clone()),
+                ("source", self.new_quote_source.clone()),
+            ],
+            DraftForm::Note => {
+                let editor = self.note_editor.as_ref()?;
+                vec![
+                    ("text", editor.content.text()),
+                    ("name", editor.name.clone()),
+                    ("editing", editor.editing.map(|id| id.to_string()).unwrap_or_default()),
+                ]
+            }
+            DraftForm::PastedQuotes => {
+                let dialog = self.quote_paste.as_ref()?;
+                vec![
+                    ("text", dialog.content.text()),
+                    ("default_date", dialog.default_date.clone()),
+                    ("source", dialog.source.clone()),
+                ]
+            }
+        };
+        if fields[0].1.trim().is_empty() {
+            return None;
+        }
+        Some(fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect())
+    }
+    
+    /// Puts a draft's text back into the selected person's form, and returns the tab the form is on
+    fn fill_draft(&mut self, draft: &Draft) -> EvidenceTab {
+        self.drafts_changed = true;
+        match draft.form {
+            DraftForm::Quote => {
+                self.new_quote_text = draft.field("text");
+                self.new_quote_date = draft.field("date");
+                self.new_quote_time = draft.field("time");
+                self.new_quote_place = draft.field("place");
+                self.new_quote_source = draft.field("source");
+                EvidenceTab::Quotes
+            }
+            DraftForm::Note => {
+                // A note that was edited into a newer version since is restored as a new note
+                let editing = draft.field("editing").parse::<Uuid>().ok()
+                    .filter(|id| self.evidence_files.iter().any(|f| f.id == *id));
+                self.note_editor = Some(NoteEditor {
+                    name: draft.field("name"),
+                    content: text_editor::Content::with_text(&draft.field("text")),
+                    editing,
+                });
+                EvidenceTab::Documents
+            }
+            DraftForm::PastedQuotes => {
+                let mut dialog = QuotePasteDialog {
+                    content: text_editor::Content::with_text(&draft.field("text")),
+                    default_date: draft.field("default_date"),
+                    source: draft.field("source"),
+                    lines: Vec::new(),
+                };
+                dialog.reparse();
+                self.quote_paste = Some(dialog);
+                EvidenceTab::Quotes
+            }
+        }
+    }
+    
+    /// Keeps what's typed into the selected person's forms in the drafts file
+    fn save_drafts(&mut self) {
+        self.drafts_changed = false;
+        let Some(person_id) = self.selected_person else {
+            return;
+        };
+        for form in DraftForm::ALL {
+            match self.draft_fields(form) {
+                Some(fields) => self.drafts.set(person_id, form, fields),
+                // A draft still offered for restoring isn't dropped because the form is empty
+                None if self.restorable_drafts.iter().any(|d| d.person_id == person_id && d.form == form) => {}
+                None => self.drafts.remove(person_id, form),
+            }
+        }
+        if let Err(e) = self.drafts.save() {
+            eprintln!("Failed to save drafts: {}", e);
+        }
+    }
+    
+    /// Reopens the person and tab that were showing when the app last closed
+    fn restore_layout(&mut self) {
+        let layout = self.settings.layout.clone();
+        self.reopen(layout.selected_person, layout.selected_tab);
+    }
+    
+    fn reopen(&mut self, person_id: Option<Uuid>, tab: Option<EvidenceTab>) {
+        if let Some(person_id) = person_id.filter(|id| self.persons.iter().any(|p| p.id == *id)) {
+            self.selected_person = Some(person_id);
+            self.refresh_evidence_files();
+            self.load_board();
+        }
+        // The map is only drawn on request, and a custom category may have been removed since
+        if let Some(tab) = tab.filter(|tab| *tab != EvidenceTab::Map && EvidenceTab::all(&self.settings).contains(tab)) {
+            self.current_tab = tab;
+        }
+    }
+    
+    /// What's open now, as written to the session file
+    fn session(&self) -> Session {
+        Session {
+            selected_person: self.selected_person,
+            selected_tab: Some(self.current_tab.clone()),
+            person_list_scroll: self.person_list_scroll,
+            tab_scroll: self.tab_scroll,
+        }
+    }
+    
+    /// Goes back to where the last session was if the app didn't close properly, with the
+    /// drafts typed for the person that was open put back in their forms
+    fn restore_session(&mut self) -> Command<Message> {
+        let session = match Session::load() {
+            Ok(Some(session)) => session,
+            Ok(None) => return Command::none(),
+            Err(e) => {
+                eprintln!("Failed to load session: {}", e);
+                return Command::none();
+            }
+        };
+        self.reopen(session.selected_person, session.selected_tab.clone());
+        if let Some(person_id) = self.selected_person {
+            let (drafts, others): (Vec<_>, Vec<_>) = std::mem::take(&mut self.restorable_drafts)
+                .into_iter()
+                .partition(|draft| draft.person_id == person_id);
+            self.restorable_drafts = others;
+            for draft in &drafts {
+                self.fill_draft(draft);
+            }
+        }
+        self.person_list_scroll = session.person_list_scroll;
+        self.tab_scroll = session.tab_scroll;
+        self.saved_session = session;
+        self.update_status("Restored the session from before the app closed unexpectedly".to_string());
+        Command::batch([
+            scrollable::snap_to(scrollable::Id::new(gui::PERSON_LIST_SCROLL), scrollable::RelativeOffset { x: 0.0, y: self.person_list_scroll }),
+            scrollable::snap_to(scrollable::Id::new(gui::TAB_SCROLL), scrollable::RelativeOffset { x: 0.0, y: self.tab_scroll }),
+        ])
+    }
+    
+    /// Reads the selected person's board, or starts an empty one
+    fn load_board(&mut self) {
+        self.board_selected = None;
+        self.board_connecting = None;
+        let board = match self.persons.iter().find(|p| Some(p.id) == self.selected_person) {
+            Some(person) => self.file_manager.load_board(person),
+            None => Ok(Board::default()),
+        };
+        self.board = board.unwrap_or_else(|e| {
+            self.update_status(format!("Failed to load board: {}", e));
+            Board::default()
+        });
+    }
+    
+    fn save_board(&mut self) {
+        let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person) else {
+            return;
+        };
+        if let Err(e) = self.file_manager.save_board(person, &self.board) {
+            self.update_status(format!("Failed to save board: {}", e));
+        }
+    }
+    
+    fn save_settings(&mut self) {
+        if let Err(e) = self.settings.save() {
+            self.update_status(format!("Failed to save settings: {}", e));
+        }
+    }
+    
+    /// Queues background work; it starts right away unless another job is running. Imports and
+    /// export profiles are refused here for viewers rather than left queued.
+    fn enqueue_job(&mut self, kind: JobKind) -> Command<Message> {
+        let label = kind.label();
+        if kind.needs_admin() && self.role != Some(Role::Admin) {
+            self.update_status(format!("Read-only: the Viewer profile can't run \"{}\"", label));
+            return Command::none();
+        }
+        self.jobs.push(kind);
+        self.save_jobs();
+        if self.running_job.is_some() {
+            self.update_status(format!("Queued: {}", label));
+        }
+        self.start_next_job()
+    }
+    
+    /// Switches how evidence is stored from now on, for imports as well as files added here
+    fn apply_storage_layout(&mut self) {
+        self.file_manager.set_storage_layout(self.settings.storage_layout);
+        self.export_import_manager = ExportImportManager::new(self.file_manager.clone());
+    }
+    
+    fn save_jobs(&mut self) {
+        if let Err(e) = self.jobs.save() {
+            self.update_status(format!("Failed to save job queue: {}", e));
+        }
+    }
+    
+    /// Picks up jobs left queued or running when the app last closed. Nothing starts until a
+    /// profile is chosen, and imports and export profiles wait for the Admin profile.
+    fn resume_jobs(&mut self) -> Command<Message> {
+        let Some(role) = self.role else {
+            return Command::none();
+        };
+        let resumed = self.jobs.jobs.iter()
+            .filter(|job| job.status == JobStatus::Queued && (role == Role::Admin || !job.kind.needs_admin()))
+            .count();
+        if resumed == 0 || self.running_job.is_some() {
+            return Command::none();
+        }
+        self.update_status(format!("Resuming {} interrupted job(s)", resumed));
+        self.start_next_job()
+    }
+    
+    /// Starts the oldest queued job the current profile may run, if nothing is running
+    fn start_next_job(&mut self) -> Command<Message> {
+        let Some(role) = self.role else {
+            return Command::none();
+        };
+        if self.running_job.is_some() {
+            return Command::none();
+        }
+        let Some(job) = self.jobs.next_queued(role == Role::Admin).cloned() else {
+            return Command::none();
+        };
+        let cancel = CancellationToken::default();
+        let progress = Arc::new(JobProgress::default());
+        self.running_job = Some(RunningJob { id: job.id, cancel: cancel.clone(), progress: progress.clone(), shown_progress: (0, 0) });
+        let report: ProgressCallback = Box::new(move |done, total| progress.set(done, total));
+        if let Some(queued) = self.jobs.get_mut(job.id) {
+            queued.status = JobStatus::Running;
+        }
+        self.save_jobs();
+        
+        let job_id = job.id;
+        let export_import_manager = self.export_import_manager.clone();
+        let file_manager = self.file_manager.clone();
+        let audit_log = self.audit_log.clone();
+        let plugins = self.plugins.clone();
+        match job.kind {
+            JobKind::ExportArchive { path, persons, options } => {
+                let persons: Vec<Person> = self.persons.iter().filter(|p| persons.contains(&p.id)).cloned().collect();
+                Command::perform(
+                    run_blocking(move || {
+                        plugins.before_export(&persons).map_err(|e| format!("{:#}", e))?;
+                        let warnings = export_import_manager.export_to_ema(&path, &persons, &options, &cancel, Some(report)).map_err(|e| e.to_string())?;
+                        let mut target = path.display().to_string();
+                        if options.thumbnails_only {
+                            target.push_str(", thumbnails only");
+                        } else if options.metadata_only {
+                            target.push_str(", metadata only");
+                        }
+                        // A single person's export belongs in their own history
+                        if let [person] = persons.as_slice() {
+                            audit_log.record(Some(person), AuditAction::Exported, target).map_err(|e| e.to_string())?;
+                        } else {
+                            let details = format!("{} ({} people)", target, persons.len());
+                            audit_log.record(None, AuditAction::Exported, details).map_err(|e| e.to_string())?;
+                        }
+                        Ok(JobOutcome::Exported(warnings))
+                    }),
+                    move |result| Message::JobFinished(job_id, result)
+                )
+            }
+            JobKind::ExportSelection { path, person, files, options, include_manifest } => {
+                let Some(person) = self.persons.iter().find(|p| p.id == person).cloned() else {
+                    return Command::perform(
+                        async { Err("The person no longer exists".to_string()) },
+                        move |result| Message::JobFinished(job_id, result)
+                    );
+                };
+                Command::perform(
+                    run_blocking(move || {
+                        plugins.before_export(std::slice::from_ref(&person)).map_err(|e| format!("{:#}", e))?;
+                        let files: Vec<EvidenceFile> = file_manager.load_evidence_index(&person)
+                            .map_err(|e| e.to_string())?
+                            .into_iter()
+                            .filter(|f| files.contains(&f.id))
+                            .collect();
+                        let warnings = export_import_manager.export_selection(&path, &files, &options, include_manifest, &cancel, Some(report)).map_err(|e| e.to_string())?;
+                        let details = format!("{} ({} files)", path.display(), files.len());
+                        audit_log.record(Some(&person), AuditAction::Exported, details).map_err(|e| e.to_string())?;
+                        Ok(JobOutcome::Exported(warnings))
+                    }),
+                    move |result| Message::JobFinished(job_id, result)
+                )
+            }
+            JobKind::ExportProfile { name } => {
+                let Some(profile) = self.settings.export_profiles.iter().find(|p| p.name == name).cloned() else {
+                    return Command::perform(
+                        async { Err("The export profile no longer exists".to_string()) },
+                        move |result| Message::JobFinished(job_id, result)
+                    );
+                };
+                let persons: Vec<Person> = self.persons.iter().filter(|p| profile.includes(p)).cloned().collect();
+                let options = ExportOptions {
+                    sign: self.settings.sign_exports,
+                    thumbnails_only: profile.thumbnails_only,
+                    ..ExportOptions::default()
+                };
+                Command::perform(
+                    run_blocking(move || {
+                        plugins.before_export(&persons).map_err(|e| format!("{:#}", e))?;
+                        let (written, warnings) = export_profiles::run(&profile, &persons, &export_import_manager, &options, &cancel, Some(report)).map_err(|e| format!("{:#}", e))?;
+                        let files: Vec<String> = written.iter().map(|path| path.display().to_string()).collect();
+                        let details = format!("Profile \"{}\": {} ({} people)", profile.name, files.join(", "), persons.len());
+                        audit_log.record(None, AuditAction::Exported, details).map_err(|e| e.to_string())?;
+                        Ok(JobOutcome::Exported(warnings))
+                    }),
+                    move |result| Message::JobFinished(job_id, result)
+                )
+            }
+            JobKind::ImportArchive { path } => {
+                Command::perform(
+                    run_blocking(move || {
+                        let import_report = export_import_manager.import_from_ema(&path, &cancel, Some(report)).map_err(|e| e.to_string())?;
+                        let details = format!("{} ({})", path.display(), import_report.summary());
+                        audit_log.record(None, AuditAction::Imported, details).map_err(|e| e.to_string())?;
+                        Ok(JobOutcome::Imported(import_report))
+                    }),
+                    move |result| Message::JobFinished(job_id, result)
+                )
+            }
+        }
+    }
+    
+    /// Compiles the enabled plugin scripts again, e.g. after one was enabled or edited.
+    /// Returns whether they all loaded.
+    fn load_plugins(&mut self) -> bool {
+        let (plugins, errors) = Plugins::load(&self.settings);
+        self.plugins = plugins;
+        self.plugin_files = plugins::list(&self.settings).unwrap_or_default();
+        if !errors.is_empty() {
+            self.update_status(format!("Some plugins didn't load: {}", errors.join("; ")));
         }
+        errors.is_empty()
     }
     
     fn update_status(&mut self, message: String) {
@@ -148,17 +1784,160 @@ impl AppState {
     }
     
     
+    fn add_file(&mut self, path: PathBuf, evidence_type: EvidenceType) -> Command<Message> {
+        // Anything that could run when opened goes straight to quarantine, without asking where it came from
+        if self.settings.intake_mode && quarantine::reason(&path).is_none() {
+            self.intake = Some(IntakeDialog { path, evidence_type });
+            return Command::none();
+        }
+        self.store_file(path, evidence_type, None)
+    }
+
+    fn store_file(&mut self, path: PathBuf, evidence_type: EvidenceType, provenance: Option<Provenance>) -> Command<Message> {
+        let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+            return Command::none();
+        };
+        let file_manager = self.file_manager.clone();
+        let audit_log = self.audit_log.clone();
+        let is_screenshot = evidence_type == EvidenceType::Image
+            && path.file_name().is_some_and(|name| naming::is_screenshot_name(&name.to_string_lossy()));
+        let screenshot_pattern = self.settings.screenshot_name_pattern().to_string();
+        let settings = self.settings.clone();
+        let plugins = self.plugins.clone();
+        let move_files = self.move_files;
+        let copy_id = Uuid::new_v4();
+        let progress = Arc::new(JobProgress::default());
+        self.file_copies.push(FileCopy {
+            id: copy_id,
+            name: path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default(),
+            progress: progress.clone(),
+            shown_progress: (0, 0),
+        });
+        
+        Command::perform(
+            run_blocking(move || {
+                let mut person = person;
+                let context = IntakeContext { audit_log: &audit_log, plugins: &plugins, settings: &settings };
+                let report = move |done: u64, total: u64| progress.set(done as usize, total as usize);
+                let mut not_removed = None;
+                let mut intake = file_manager.intake(&context, &mut person, &path, evidence_type.clone(), "", |person| {
+                    let evidence_file = if is_screenshot {
+                        file_manager.intake_screenshot(person, &path, &screenshot_pattern, provenance)
+                    } else {
+                        match provenance {
+                            Some(provenance) => file_manager.intake_file(person, &path, evidence_type, provenance, move_files, Some(&report)),
+                            None if move_files => file_manager.move_file_to_evidence(person, &path, evidence_type, Some(&report)),
+                            None => file_manager.copy_file_to_evidence(person, &path, evidence_type, Some(&report)),
+                        }
+                    }?;
+                    if move_files {
+                        // Screenshots are read in rather than copied, so they're removed here
+                        if is_screenshot {
+                            let _ = std::fs::remove_file(&path);
+                        }
+                        if path.exists() {
+                            not_removed = Some(format!("{} was added, but the original couldn't be removed", evidence_file.original_name));
+                        }
+                    }
+                    Ok(evidence_file)
+                })
+                .map_err(|e| e.to_string())?;
+                if let Intake::Added { storage_warnings, warnings, .. } = &mut intake {
+                    notify_storage_warnings(storage_warnings);
+                    warnings.extend(not_removed);
+                }
+                Ok(intake)
+            }),
+            move |result| Message::FileCopied(copy_id, result)
+        )
+    }
+
+    /// Re-scans every person so the dashboard reflects files added or changed on disk
+    pub fn refresh_statistics(&mut self) {
+        let evidence_files: Vec<EvidenceFile> = self.persons
+            .iter()
+            .filter_map(|person| self.file_manager.scan_person_evidence(person, &self.settings).ok())
+            .flatten()
+            .collect();
+        self.evidence_counts.clear();
+        for file in &evidence_files {
+            *self.evidence_counts.entry(file.person_id).or_default() += 1;
+        }
+        self.statistics = Statistics::collect(&self.persons, &evidence_files, &self.settings);
+    }
+
+    /// Updates the review status in memory right away and saves it in the background
+    fn set_review_status(&mut self, evidence_ids: &[Uuid], status: ReviewStatus) -> Command<Message> {
+        let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+            return Command::none();
+        };
+        if self.refuse_on_hold(Some(person.id)) {
+            return Command::none();
+        }
+        
+        let mut changed = Vec::new();
+        for file in self.evidence_files.iter_mut().filter(|f| evidence_ids.contains(&f.id)) {
+            file.review_status = status;
+            changed.push(file.clone());
+        }
+        // Smart views can filter on review status
+        self.update_filtered_persons();
+        
+        let file_manager = self.file_manager.clone();
+        let audit_log = self.audit_log.clone();
+        Command::perform(
+            async move {
+                for file in &changed {
+                    file_manager.update_evidence_metadata(&person, file).map_err(|e| e.to_string())?;
+                    let details = format!("{} marked as {}", file.original_name, status.label());
+                    audit_log.record(Some(&person), AuditAction::EvidenceUpdated, details).map_err(|e| e.to_string())?;
+                }
+                Ok(())
+            },
+            Message::ReviewStatusSaved
+        )
+    }
+
+    /// Obtains a trusted timestamp for each file from the configured TSA and stores it with the file's metadata
+    fn timestamp_evidence(&self, person: Person, files: Vec<EvidenceFile>) -> Command<Message> {
+        let tsa_url = self.settings.tsa_url.trim().to_string();
+        let file_manager = self.file_manager.clone();
+        let audit_log = self.audit_log.clone();
+        Command::perform(
+            run_blocking(move || {
+                for mut file in files.iter().cloned() {
+                    let digest = file_manager.evidence_digest(&file.file_path).map_err(|e| e.to_string())?;
+                    let timestamp = timestamp::request(&tsa_url, digest)
+                        .map_err(|e| format!("{}: {:#}", file.original_name, e))?;
+                    let details = format!("{} timestamped by {} at {}", file.original_name, tsa_url, timestamp.time.to_rfc3339());
+                    file.timestamp = Some(timestamp);
+                    file_manager.update_evidence_metadata(&person, &file).map_err(|e| e.to_string())?;
+                    audit_log.record(Some(&person), AuditAction::EvidenceUpdated, details).map_err(|e| e.to_string())?;
+                }
+                Ok(files.len())
+            }),
+            Message::EvidenceTimestamped
+        )
+    }
+
     pub fn refresh_evidence_files(&mut self) {
         if let Some(person_id) = self.selected_person {
             if let Some(person) = self.persons.iter().find(|p| p.id == person_id) {
-                match self.file_manager.scan_person_evidence(person) {
+                match self.file_manager.scan_person_evidence(person, &self.settings) {
                     Ok(files) => self.evidence_files = files,
-                    Err(_) => self.evidence_files.clear(),
+                    Err(e) => {
+                        self.evidence_files.clear();
+                        let message = format!("Couldn't load {}'s evidence: {}. Rebuild Index can recover it", person.name, e);
+                        self.update_status(message);
+                    }
                 }
+                self.evidence_counts.insert(person_id, self.evidence_files.len());
             }
         } else {
             self.evidence_files.clear();
         }
+        self.shared_content = ContentStore::new(self.file_manager.get_evidence_dir()).references().unwrap_or_default();
+        self.update_filtered_persons();
     }
 }
 
@@ -166,13 +1945,25 @@ impl Application for AppState {
     type Executor = executor::Default;
     type Message = Message;
     type Theme = Theme;
-    type Flags = ();
+    type Flags = Result<Option<u32>, String>; // The quick capture shortcut, if one is set
 
-    fn new(_flags: ()) -> (Self, Command<Message>) {
+    fn new(hotkey: Self::Flags) -> (Self, Command<Message>) {
         match Self::new() {
             Ok(mut state) => {
+                match hotkey {
+                    Ok(id) => state.quick_capture_hotkey = id,
+                    Err(e) => state.update_status(format!("Quick capture shortcut unavailable: {}", e)),
+                }
+                state.load_plugins();
                 state.update_filtered_persons();
-                (state, Command::none())
+                state.restore_layout();
+                let restore_session = state.restore_session();
+                if state.settings.sign_exports {
+                    state.signing_fingerprint = SigningKey::load_or_create().ok().map(|key| key.fingerprint());
+                }
+                // Without an admin passphrase the profile is Admin already; otherwise this waits for it
+                let command = state.resume_jobs();
+                (state, Command::batch([restore_session, command]))
             }
             Err(e) => {
                 eprintln!("Failed to initialize application: {}", e);
@@ -186,10 +1977,54 @@ impl Application for AppState {
     }
 
     fn update(&mut self, message: Message) -> Command<Message> {
+        if self.role != Some(Role::Admin) && message.is_mutating() {
+            self.update_status("Read-only: the Viewer profile can't make changes".to_string());
+            return Command::none();
+        }
+        if message.edits_draft() {
+            self.drafts_changed = true;
+        }
+        
         match message {
             Message::PersonSelected(id) => {
+                // What's typed so far is kept as the previous person's draft
+                if self.drafts_changed {
+                    self.save_drafts();
+                }
                 self.selected_person = Some(id);
+                self.tab_scroll = 0.0;
+                self.show_settings = false;
+                self.show_activity = false;
+                self.show_jobs = false;
+                self.show_store_check = false;
+                self.show_evidence_browser = false;
+                self.new_quote_linked_evidence.clear();
+                self.chat_import = None;
+                self.quote_paste = None;
+                self.device_import = None;
+                self.file_list_import = None;
+                self.email_preview = None;
+                self.image_preview = None;
+                self.duplicate_review = None;
+                self.uncategorized_file = None;
+                self.intake = None;
+                self.note_editor = None;
+                self.review_filter = None;
+                self.evidence_type_filter.clear();
+                self.custom_field_drafts.clear();
+                self.checklist_drafts.clear();
+                self.selected_evidence.clear();
+                self.evidence_actions = None;
+                self.map_view = None;
+                self.quote_near = None;
+                self.quote_filter.clear();
+                if self.current_tab == EvidenceTab::Map {
+                    self.current_tab = EvidenceTab::Information;
+                }
+                // Re-read from disk, so quotes added by the ingest command aren't overwritten
+                self.reload_person(id);
                 self.refresh_evidence_files();
+                self.load_board();
                 Command::none()
             }
             
@@ -206,14 +2041,22 @@ impl Application for AppState {
             Message::AddPersonSubmitted => {
                 if !self.new_person_name.trim().is_empty() {
                     let name = self.new_person_name.trim().to_string();
+                    let template = self.new_person_template.take();
                     self.new_person_name.clear();
                     self.show_add_person_dialog = false;
                     
                     let file_manager = self.file_manager.clone();
+                    let audit_log = self.audit_log.clone();
                     Command::perform(
                         async move {
-                            let person = Person::new(name);
-                            file_manager.save_person_data(&person).map(|_| person).map_err(|e| e.to_string())
+                            let mut person = Person::new(name);
+                            // Empty slots to be filled in on the Information tab
+                            for info_type in template.map(|t| t.info_types).unwrap_or_default() {
+                                person.add_information(info_type, String::new());
+                            }
+                            file_manager.save_person_data(&person).map_err(|e| e.to_string())?;
+                            audit_log.record(Some(&person), AuditAction::PersonAdded, "").map_err(|e| e.to_string())?;
+                            Ok(person)
                         },
                         Message::PersonAdded
                     )
@@ -222,6 +2065,99 @@ impl Application for AppState {
                 }
             }
             
+            Message::AddSeveralPersonsClicked => {
+                self.show_add_person_dialog = false;
+                self.bulk_add = Some(BulkAddDialog {
+                    content: text_editor::Content::new(),
+                    template: self.new_person_template.take(),
+                    add_existing: false,
+                    names: Vec::new(),
+                });
+                Command::none()
+            }
+            
+            Message::BulkAddEdited(action) => {
+                if let Some(dialog) = &mut self.bulk_add {
+                    let is_edit = action.is_edit();
+                    dialog.content.perform(action);
+                    if is_edit {
+                        dialog.reparse(&self.persons);
+                    }
+                }
+                Command::none()
+            }
+            
+            Message::BulkAddTemplateSelected(template) => {
+                if let Some(dialog) = &mut self.bulk_add {
+                    dialog.template = Some(template);
+                }
+                Command::none()
+            }
+            
+            Message::BulkAddExistingToggled(add_existing) => {
+                if let Some(dialog) = &mut self.bulk_add {
+                    dialog.add_existing = add_existing;
+                }
+                Command::none()
+            }
+            
+            Message::BulkAddCancelled => {
+                self.bulk_add = None;
+                Command::none()
+            }
+            
+            Message::AddPersonsBulk => {
+                let Some(dialog) = &self.bulk_add else {
+                    return Command::none();
+                };
+                let names = dialog.to_add();
+                if names.is_empty() {
+                    self.update_status("No new names to add".to_string());
+                    return Command::none();
+                }
+                let info_types = dialog.template.clone().map(|t| t.info_types).unwrap_or_default();
+                self.bulk_add = None;
+                self.update_status(format!("Adding {} person(s)...", names.len()));
+                
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                Command::perform(
+                    run_blocking(move || {
+                        let mut added = Vec::new();
+                        let mut failed = Vec::new();
+                        for name in names {
+                            let mut person = Person::new(name.clone());
+                            for info_type in &info_types {
+                                person.add_information(info_type.clone(), String::new());
+                            }
+                            let saved = file_manager.save_person_data(&person)
+                                .and_then(|_| audit_log.record(Some(&person), AuditAction::PersonAdded, "From a pasted list of names"));
+                            match saved {
+                                Ok(_) => added.push(person),
+                                Err(e) => failed.push(format!("{}: {:#}", name, e)),
+                            }
+                        }
+                        Ok((added, failed))
+                    }),
+                    |result| match result {
+                        Ok((added, failed)) => Message::PersonsAdded(added, failed),
+                        Err(e) => Message::PersonsAdded(Vec::new(), vec![e]),
+                    }
+                )
+            }
+            
+            Message::PersonsAdded(added, failed) => {
+                let mut status = format!("{} person(s) added", added.len());
+                if !failed.is_empty() {
+                    status.push_str(&format!(", {} failed ({})", failed.len(), failed.join(", ")));
+                }
+                self.persons.extend(added);
+                self.persons.sort_by(|a, b| a.name.cmp(&b.name));
+                self.update_filtered_persons();
+                self.update_status(status);
+                Command::none()
+            }
+            
             Message::PersonAdded(result) => {
                 match result {
                     Ok(person) => {
@@ -238,35 +2174,40 @@ impl Application for AppState {
             }
             
             Message::DeletePerson(id) => {
+                if self.refuse_on_hold(Some(id)) {
+                    return Command::none();
+                }
                 if let Some(person) = self.persons.iter().find(|p| p.id == id) {
                     let person_clone = person.clone();
                     let file_manager = self.file_manager.clone();
+                    let audit_log = self.audit_log.clone();
                     
                     Command::perform(
                         async move {
-                            file_manager.delete_person(&person_clone).map_err(|e| e.to_string())
+                            file_manager.delete_person(&person_clone).map_err(|e| e.to_string())?;
+                            audit_log.record(Some(&person_clone), AuditAction::PersonDeleted, "").map_err(|e| e.to_string())
                         },
-                        Message::PersonDeleted
+                        move |result| Message::PersonDeleted(id, result)
                     )
                 } else {
                     Command::none()
                 }
             }
             
-            Message::PersonDeleted(result) => {
+            Message::PersonDeleted(person_id, result) => {
                 match result {
                     Ok(()) => {
-                        if let Some(id) = self.selected_person {
-                            // Store the person ID before removing
-                            let person_id_to_remove = id;
-                            self.persons.retain(|p| p.id != person_id_to_remove);
-                            if self.selected_person == Some(person_id_to_remove) {
-                                self.selected_person = None;
-                                self.evidence_files.clear();
-                            }
-                            self.update_filtered_persons();
-                            self.update_status("Person successfully deleted".to_string());
+                        self.persons.retain(|p| p.id != person_id);
+                        if self.selected_person == Some(person_id) {
+                            self.selected_person = None;
+                            self.evidence_files.clear();
+                        }
+                        if self.person_actions == Some(person_id) {
+                            self.person_actions = None;
                         }
+                        self.update_filtered_persons();
+                        self.refresh_statistics();
+                        self.update_status("Person successfully deleted".to_string());
                     }
                     Err(e) => {
                         self.update_status(format!("Failed to delete person: {}", e));
@@ -292,7 +2233,9 @@ impl Application for AppState {
                             let person_clone = person.clone();
                             let info_type = self.new_info_type.trim().to_string();
                             let info_value = self.new_info_value.trim().to_string();
+                            let country_code = self.settings.default_country_code.clone();
                             let file_manager = self.file_manager.clone();
+                            let audit_log = self.audit_log.clone();
                             
                             self.new_info_type.clear();
                             self.new_info_value.clear();
@@ -300,8 +2243,11 @@ impl Application for AppState {
                             Command::perform(
                                 async move {
                                     let mut person = person_clone;
+                                    let details = format!("{}: {}", info_type, info_value);
                                     person.add_information(info_type, info_value);
-                                    file_manager.save_person_data(&person).map_err(|e| e.to_string())
+                                    person.normalize_information(&country_code);
+                                    file_manager.save_person_data(&person).map_err(|e| e.to_string())?;
+                                    audit_log.record(Some(&person), AuditAction::InformationAdded, details).map_err(|e| e.to_string())
                                 },
                                 Message::InfoAdded
                             )
@@ -320,16 +2266,8 @@ impl Application for AppState {
                 match result {
                     Ok(()) => {
                         self.update_status("Information successfully added".to_string());
-                        // Refresh the person data
                         if let Some(person_id) = self.selected_person {
-                            if let Some(person) = self.persons.iter_mut().find(|p| p.id == person_id) {
-                                // Reload person data to get updated information
-                                if let Ok(updated_person) = self.file_manager.load_person_data(
-                                    &self.file_manager.get_evidence_dir().join(person.folder_name())
-                                ) {
-                                    *person = updated_person;
-                                }
-                            }
+                            self.reload_person(person_id);
                         }
                     }
                     Err(e) => {
@@ -340,16 +2278,25 @@ impl Application for AppState {
             }
             
             Message::RemoveInfo(info_id) => {
+                if self.refuse_on_hold(self.selected_person) {
+                    return Command::none();
+                }
                 if let Some(person_id) = self.selected_person {
                     if let Some(person) = self.persons.iter().find(|p| p.id == person_id) {
                         let person_clone = person.clone();
                         let file_manager = self.file_manager.clone();
+                        let audit_log = self.audit_log.clone();
+                        let details = person.information.iter()
+                            .find(|info| info.id == info_id)
+                            .map(|info| format!("{}: {}", info.info_type, info.value))
+                            .unwrap_or_default();
                         
                         Command::perform(
                             async move {
                                 let mut person = person_clone;
                                 person.remove_information(info_id);
-                                file_manager.save_person_data(&person).map_err(|e| e.to_string())
+                                file_manager.save_person_data(&person).map_err(|e| e.to_string())?;
+                                audit_log.record(Some(&person), AuditAction::InformationRemoved, details).map_err(|e| e.to_string())
                             },
                             Message::InfoRemoved
                         )
@@ -365,15 +2312,8 @@ impl Application for AppState {
                 match result {
                     Ok(()) => {
                         self.update_status("Information successfully removed".to_string());
-                        // Refresh the person data
                         if let Some(person_id) = self.selected_person {
-                            if let Some(person) = self.persons.iter_mut().find(|p| p.id == person_id) {
-                                if let Ok(updated_person) = self.file_manager.load_person_data(
-                                    &self.file_manager.get_evidence_dir().join(person.folder_name())
-                                ) {
-                                    *person = updated_person;
-                                }
-                            }
+                            self.reload_person(person_id);
                         }
                     }
                     Err(e) => {
@@ -398,12 +2338,34 @@ impl Application for AppState {
                 Command::none()
             }
             
+            Message::AddQuoteZoneSelected(zone) => {
+                self.new_quote_zone = zone;
+                Command::none()
+            }
+            
             Message::AddQuotePlaceChanged(value) => {
                 self.new_quote_place = value;
                 Command::none()
             }
             
-            Message::AddQuoteSubmitted => {
+            Message::AddQuoteSourceChanged(value) => {
+                self.new_quote_source = value;
+                Command::none()
+            }
+            
+            Message::AddQuoteLinkEvidence(evidence_id) => {
+                if !self.new_quote_linked_evidence.contains(&evidence_id) {
+                    self.new_quote_linked_evidence.push(evidence_id);
+                }
+                Command::none()
+            }
+            
+            Message::AddQuoteUnlinkEvidence(evidence_id) => {
+                self.new_quote_linked_evidence.retain(|id| *id != evidence_id);
+                Command::none()
+            }
+            
+            Message::AddQuoteSubmitted => {
                 if !self.new_quote_text.trim().is_empty() && !self.new_quote_date.trim().is_empty() {
                     if let Some(person_id) = self.selected_person {
                         if let Some(person) = self.persons.iter().find(|p| p.id == person_id) {
@@ -420,18 +2382,30 @@ impl Application for AppState {
                             } else {
                                 Some(self.new_quote_place.trim().to_string())
                             };
+                            let quote_source = if self.new_quote_source.trim().is_empty() {
+                                None
+                            } else {
+                                Some(self.new_quote_source.trim().to_string())
+                            };
+                            let linked_evidence = std::mem::take(&mut self.new_quote_linked_evidence);
+                            let quote_zone = self.new_quote_zone;
                             let file_manager = self.file_manager.clone();
+                            let audit_log = self.audit_log.clone();
                             
                             self.new_quote_text.clear();
                             self.new_quote_date.clear();
                             self.new_quote_time.clear();
                             self.new_quote_place.clear();
+                            self.new_quote_source.clear();
                             
                             Command::perform(
                                 async move {
                                     let mut person = person_clone;
-                                    person.add_quote(quote_text, quote_date, quote_time, quote_place);
-                                    file_manager.save_person_data(&person).map_err(|e| e.to_string())
+                                    let details = format!("\"{}\" ({})", quote_text, quote_date);
+                                    let quote = person.add_quote(quote_text, quote_date, quote_time, quote_place, quote_source, linked_evidence);
+                                    quote.utc_offset = Some(quote_zone.offset_on(&quote.date, quote.time.as_deref()));
+                                    file_manager.save_person_data(&person).map_err(|e| e.to_string())?;
+                                    audit_log.record(Some(&person), AuditAction::QuoteAdded, details).map_err(|e| e.to_string())
                                 },
                                 Message::QuoteAdded
                             )
@@ -450,16 +2424,10 @@ impl Application for AppState {
                 match result {
                     Ok(()) => {
                         self.update_status("Quote successfully added".to_string());
-                        // Refresh the person data
                         if let Some(person_id) = self.selected_person {
-                            if let Some(person) = self.persons.iter_mut().find(|p| p.id == person_id) {
-                                if let Ok(updated_person) = self.file_manager.load_person_data(
-                                    &self.file_manager.get_evidence_dir().join(person.folder_name())
-                                ) {
-                                    *person = updated_person;
-                                }
-                            }
+                            self.reload_person(person_id);
                         }
+                        return self.update(Message::LocateQuotePlaces);
                     }
                     Err(e) => {
                         self.update_status(format!("Failed to add quote: {}", e));
@@ -468,109 +2436,139 @@ impl Application for AppState {
                 Command::none()
             }
             
-            Message::RemoveQuote(quote_id) => {
-                if let Some(person_id) = self.selected_person {
-                    if let Some(person) = self.persons.iter().find(|p| p.id == person_id) {
-                        let person_clone = person.clone();
-                        let file_manager = self.file_manager.clone();
-                        
-                        Command::perform(
-                            async move {
-                                let mut person = person_clone;
-                                person.remove_quote(quote_id);
-                                file_manager.save_person_data(&person).map_err(|e| e.to_string())
-                            },
-                            Message::QuoteRemoved
-                        )
-                    } else {
-                        Command::none()
-                    }
-                } else {
-                    Command::none()
+            Message::LocateQuotePlaces => {
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person) else {
+                    return Command::none();
+                };
+                let person_id = person.id;
+                let places: Vec<(Uuid, String)> = person.quotes
+                    .iter()
+                    .filter(|quote| quote.coordinates.is_none())
+                    .filter_map(|quote| quote.place.clone().map(|place| (quote.id, place)))
+                    .collect();
+                if places.is_empty() {
+                    return Command::none();
                 }
+                let provider_url = self.settings.geocoding_url.clone();
+                
+                Command::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            let mut located = Vec::new();
+                            for (quote_id, place) in places {
+                                if let Some(coordinates) = geocode::geocode(&place, &provider_url).map_err(|e| e.to_string())? {
+                                    located.push((quote_id, coordinates));
+                                }
+                            }
+                            Ok((person_id, located))
+                        })
+                        .await
+                        .map_err(|e| e.to_string())?
+                    },
+                    Message::QuotePlacesLocated
+                )
             }
             
-            Message::QuoteRemoved(result) => {
+            Message::QuotePlacesLocated(result) => {
                 match result {
-                    Ok(()) => {
-                        self.update_status("Quote successfully removed".to_string());
-                        // Refresh the person data
-                        if let Some(person_id) = self.selected_person {
-                            if let Some(person) = self.persons.iter_mut().find(|p| p.id == person_id) {
-                                if let Ok(updated_person) = self.file_manager.load_person_data(
-                                    &self.file_manager.get_evidence_dir().join(person.folder_name())
-                                ) {
-                                    *person = updated_person;
+                    Ok((person_id, located)) => {
+                        if located.is_empty() || self.selected_person != Some(person_id) {
+                            return Command::none();
+                        }
+                        self.map_view = None;
+                        let details = format!("Located {} quote place(s)", located.len());
+                        self.update_status(details.clone());
+                        self.update_selected_person(AuditAction::QuoteUpdated, details, |person| {
+                            for (quote_id, coordinates) in located {
+                                if let Some(quote) = person.quotes.iter_mut().find(|q| q.id == quote_id) {
+                                    quote.coordinates = Some(coordinates);
                                 }
                             }
-                        }
+                        })
                     }
                     Err(e) => {
-                        self.update_status(format!("Failed to remove quote: {}", e));
+                        self.update_status(format!("Failed to locate quote places: {}", e));
+                        Command::none()
                     }
                 }
+            }
+            
+            Message::QuoteNearPlaceChanged(value) => {
+                self.quote_near_place = value;
                 Command::none()
             }
             
-            Message::TabChanged(tab) => {
-                self.current_tab = tab;
+            Message::QuoteNearRadiusChanged(value) => {
+                self.quote_near_radius = value;
                 Command::none()
             }
             
-            Message::SelectFileClicked => {
-                if let Some(_person_id) = self.selected_person {
-                    Command::perform(
-                        async {
-                            rfd::FileDialog::new()
-                                .add_filter("All Files", &["*"])
-                                .add_filter("Images", &["jpg", "jpeg", "png", "gif", "bmp", "tiff", "webp"])
-                                .add_filter("Audio", &["mp3", "wav", "flac", "aac", "ogg", "m4a"])
-                                .add_filter("Videos", &["mp4", "avi", "mov", "wmv", "flv", "webm", "mkv"])
-                                .add_filter("Documents", &["pdf", "doc", "docx", "txt", "rtf"])
-                                .pick_file()
-                        },
-                        |path| {
-                            if let Some(path) = path {
-                                Message::FileSelected(path)
-                            } else {
-                                Message::StatusMessage("File selection cancelled".to_string())
-                            }
+            Message::QuoteNearSubmitted => {
+                let place = self.quote_near_place.trim().to_string();
+                if place.is_empty() {
+                    return Command::none();
+                }
+                let provider_url = self.settings.geocoding_url.clone();
+                Command::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || geocode::geocode(&place, &provider_url).map_err(|e| e.to_string()))
+                            .await
+                            .map_err(|e| e.to_string())?
+                    },
+                    Message::QuoteNearResolved
+                )
+            }
+            
+            Message::QuoteNearResolved(result) => {
+                match result {
+                    Ok(Some(center)) => {
+                        match self.quote_near_radius.trim().parse::<f64>() {
+                            Ok(radius) if radius > 0.0 => self.quote_near = Some((center, radius)),
+                            _ => self.update_status("Radius must be a positive number of kilometres".to_string()),
                         }
-                    )
-                } else {
-                    Command::perform(
-                        async { Message::StatusMessage("Please select a person before adding files".to_string()) },
-                        |msg| msg
-                    )
+                    }
+                    Ok(None) => {
+                        self.update_status(format!("Couldn't find \"{}\"; enter coordinates or set a geocoding service in Settings", self.quote_near_place.trim()));
+                    }
+                    Err(e) => {
+                        self.update_status(format!("Failed to look up place: {}", e));
+                    }
                 }
+                Command::none()
             }
             
-            Message::FileSelected(path) => {
+            Message::ClearQuoteNear => {
+                self.quote_near = None;
+                Command::none()
+            }
+            
+            Message::QuoteFilterChanged(value) => {
+                self.quote_filter = value;
+                Command::none()
+            }
+            
+            Message::RemoveQuote(quote_id) => {
+                if self.refuse_on_hold(self.selected_person) {
+                    return Command::none();
+                }
                 if let Some(person_id) = self.selected_person {
                     if let Some(person) = self.persons.iter().find(|p| p.id == person_id) {
                         let person_clone = person.clone();
                         let file_manager = self.file_manager.clone();
+                        let audit_log = self.audit_log.clone();
+                        let details = person.quotes.iter()
+                            .find(|quote| quote.id == quote_id)
+                            .map(|quote| format!("\"{}\" ({})", quote.quote, quote.date))
+                            .unwrap_or_default();
                         
                         Command::perform(
                             async move {
-                                if let Some(extension) = path.extension() {
-                                    let ext_str = extension.to_string_lossy();
-                                    
-                                    if let Some(evidence_type) = EvidenceType::from_extension(&ext_str) {
-                                        file_manager.copy_file_to_evidence(&person_clone, &path, evidence_type).map_err(|e| e.to_string())
-                                    } else {
-                                        Err(format!("Unsupported file type: {}", ext_str))
-                                    }
-                                } else {
-                                    Err("File has no extension".to_string())
-                                }
+                                let mut person = person_clone;
+                                person.remove_quote(quote_id);
+                                file_manager.save_person_data(&person).map_err(|e| e.to_string())?;
+                                audit_log.record(Some(&person), AuditAction::QuoteRemoved, details).map_err(|e| e.to_string())
                             },
-                            |result| {
-                                match result {
-                                    Ok(_) => Message::FileAddedSuccessfully,
-                                    Err(e) => Message::StatusMessage(format!("Failed to add file: {}", e)),
-                                }
-                            }
+                            Message::QuoteRemoved
                         )
                     } else {
                         Command::none()
@@ -580,186 +2578,4663 @@ impl Application for AppState {
                 }
             }
             
-            Message::FileAddedSuccessfully => {
-                self.update_status("File successfully added".to_string());
-                self.refresh_evidence_files();
+            Message::QuoteRemoved(result) => {
+                match result {
+                    Ok(()) => {
+                        self.update_status("Quote successfully removed".to_string());
+                        if let Some(person_id) = self.selected_person {
+                            self.reload_person(person_id);
+                        }
+                    }
+                    Err(e) => {
+                        self.update_status(format!("Failed to remove quote: {}", e));
+                    }
+                }
                 Command::none()
             }
             
-            Message::ImportClicked => {
+            Message::ImportChatClicked => {
                 Command::perform(
                     async {
                         rfd::FileDialog::new()
-                            .add_filter("Evidence Manager Archive", &["ema"])
+                            .add_filter("Chat Exports", &["txt", "json"])
                             .pick_file()
                     },
                     |path| {
                         if let Some(path) = path {
-                            Message::ImportFileSelected(path)
+                            Message::ChatFileSelected(path)
                         } else {
-                            Message::ShowImportDialog(false)
+                            Message::StatusMessage("Chat import cancelled".to_string())
                         }
                     }
                 )
             }
             
-            Message::ExportClicked => {
+            Message::ChatFileSelected(path) => {
                 Command::perform(
-                    async {
-                        rfd::FileDialog::new()
-                            .add_filter("Evidence Manager Archive", &["ema"])
-                            .set_file_name("evidence_export.ema")
-                            .save_file()
+                    async move {
+                        chat_import::parse_chat_export(&path).map_err(|e| e.to_string())
                     },
-                    |path| {
-                        if let Some(path) = path {
-                            Message::ExportFileSelected(path)
-                        } else {
-                            Message::ShowExportDialog(false)
-                        }
-                    }
+                    Message::ChatParsed
                 )
             }
             
-            Message::ExportPersonClicked => {
-                if let Some(person_id) = self.selected_person {
-                    if let Some(person) = self.persons.iter().find(|p| p.id == person_id) {
-                        let person_name = person.name.clone();
-                        Command::perform(
-                            async move {
-                                rfd::FileDialog::new()
-                                    .add_filter("Evidence Manager Archive", &["ema"])
-                                    .set_file_name(format!("{}.ema", person_name.replace(" ", "_")))
-                                    .save_file()
-                            },
-                            |path| {
-                                if let Some(path) = path {
-                                    Message::ExportPersonFileSelected(path)
-                                } else {
-                                    Message::StatusMessage("Export cancelled".to_string())
-                                }
-                            }
-                        )
-                    } else {
-                        Command::none()
+            Message::ChatParsed(result) => {
+                match result {
+                    Ok(export) => {
+                        let selected = vec![false; export.messages.len()];
+                        self.chat_import = Some(ChatImportDialog { export, selected });
+                    }
+                    Err(e) => {
+                        self.update_status(format!("Failed to read chat export: {}", e));
                     }
-                } else {
-                    Command::perform(
-                        async { Message::StatusMessage("No person selected for export".to_string()) },
-                        |msg| msg
-                    )
                 }
+                Command::none()
             }
             
-            Message::ImportFileSelected(path) => {
-                self.show_import_dialog = false;
-                let export_import_manager = self.export_import_manager.clone();
-                
-                Command::perform(
-                    async move {
-                        export_import_manager.import_from_ema(&path, None).map_err(|e| e.to_string())
-                    },
-                    Message::ImportComplete
-                )
+            Message::ChatMessageToggled(index, checked) => {
+                if let Some(dialog) = &mut self.chat_import && let Some(selected) = dialog.selected.get_mut(index) {
+                    *selected = checked;
+                }
+                Command::none()
             }
             
-            Message::ExportFileSelected(path) => {
-                self.show_export_dialog = false;
-                let export_import_manager = self.export_import_manager.clone();
-                let persons = self.persons.clone();
-                
-                Command::perform(
-                    async move {
-                        export_import_manager.export_to_ema(&path, &persons, None).map_err(|e| e.to_string())
-                    },
-                    Message::ExportComplete
-                )
+            Message::ChatSelectAll(checked) => {
+                if let Some(dialog) = &mut self.chat_import {
+                    dialog.selected.iter_mut().for_each(|s| *s = checked);
+                }
+                Command::none()
             }
             
-            Message::ExportPersonFileSelected(path) => {
-                if let Some(person_id) = self.selected_person {
-                    if let Some(person) = self.persons.iter().find(|p| p.id == person_id) {
-                        let export_import_manager = self.export_import_manager.clone();
-                        let person_clone = person.clone();
-                        
-                        Command::perform(
-                            async move {
-                                export_import_manager.export_to_ema(&path, &[person_clone], None).map_err(|e| e.to_string())
-                            },
-                            Message::ExportComplete
-                        )
-                    } else {
-                        Command::none()
+            Message::ChatSelectSender(sender) => {
+                if let Some(dialog) = &mut self.chat_import {
+                    for (selected, message) in dialog.selected.iter_mut().zip(&dialog.export.messages) {
+                        *selected = message.sender == sender;
                     }
-                } else {
-                    Command::none()
                 }
+                Command::none()
             }
             
-            Message::ImportComplete(result) => {
-                match result {
-                    Ok(imported_persons) => {
-                        self.persons.extend(imported_persons);
-                        self.persons.sort_by(|a, b| a.name.cmp(&b.name));
-                        self.update_filtered_persons();
-                        self.update_status(".ema successfully imported".to_string());
-                    }
-                    Err(e) => {
-                        self.update_status(format!("Failed to import evidence: {}", e));
-                    }
-                }
+            Message::ChatImportCancelled => {
+                self.chat_import = None;
                 Command::none()
             }
             
-            Message::ExportComplete(result) => {
+            Message::ChatImportConfirmed => {
+                let Some(dialog) = self.chat_import.take() else {
+                    return Command::none();
+                };
+                let Some(person) = self.selected_person.and_then(|id| self.persons.iter().find(|p| p.id == id)) else {
+                    return Command::none();
+                };
+                
+                let messages: Vec<_> = dialog.export.messages
+                    .into_iter()
+                    .zip(dialog.selected)
+                    .filter(|(_, selected)| *selected)
+                    .map(|(message, _)| message)
+                    .collect();
+                if messages.is_empty() {
+                    self.update_status("No messages selected for import".to_string());
+                    return Command::none();
+                }
+                
+                let person_clone = person.clone();
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                let path = dialog.export.path;
+                let format = dialog.export.format;
+                let zone = self.new_quote_zone;
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let mut person = person_clone;
+                        // Keep the original export alongside the quotes taken from it
+                        let evidence = file_manager.copy_file_to_evidence(&person, &path, EvidenceType::Document, None)
+                            .map_err(|e| e.to_string())?;
+                        let count = messages.len();
+                        for message in messages {
+                            let source = format!("{} chat export ({})", format.label(), message.sender);
+                            let quote = person.add_quote(message.text, message.date, message.time, None, Some(source), vec![evidence.id]);
+                            // Discord timestamps carry their offset; the chosen zone stands in for the others'
+                            quote.utc_offset = Some(message.utc_offset.unwrap_or_else(|| zone.offset_on(&quote.date, quote.time.as_deref())));
+                        }
+                        file_manager.save_person_data(&person).map_err(|e| e.to_string())?;
+                        let details = format!("{} quotes from {} chat export {}", count, format.label(), evidence.original_name);
+                        audit_log.record(Some(&person), AuditAction::QuoteAdded, details).map_err(|e| e.to_string())?;
+                        Ok(count)
+                    }),
+                    Message::ChatImportComplete
+                )
+            }
+            
+            Message::ChatImportComplete(result) => {
                 match result {
-                    Ok(()) => {
-                        self.update_status(".ema successfully exported".to_string());
+                    Ok(count) => {
+                        self.update_status(format!("{} quotes imported from chat export", count));
+                        if let Some(person_id) = self.selected_person {
+                            self.reload_person(person_id);
+                        }
+                        // The chat export itself was added as a document
+                        self.refresh_evidence_files();
                     }
                     Err(e) => {
-                        self.update_status(format!("Failed to export evidence: {}", e));
+                        self.update_status(format!("Failed to import chat export: {}", e));
                     }
                 }
                 Command::none()
             }
             
-            Message::SearchQueryChanged(query) => {
-                self.search_query = query;
-                self.update_filtered_persons();
+            Message::PasteQuotesClicked => {
+                self.quote_paste = Some(QuotePasteDialog {
+                    content: text_editor::Content::new(),
+                    default_date: Local::now().format("%Y-%m-%d").to_string(),
+                    source: String::new(),
+                    lines: Vec::new(),
+                });
                 Command::none()
             }
             
-            Message::ShowAddPersonDialog(show) => {
-                self.show_add_person_dialog = show;
-                if !show {
-                    self.new_person_name.clear();
+            Message::QuotePasteEdited(action) => {
+                if let Some(dialog) = &mut self.quote_paste {
+                    let is_edit = action.is_edit();
+                    dialog.content.perform(action);
+                    if is_edit {
+                        dialog.reparse();
+                    }
                 }
                 Command::none()
             }
             
-            Message::ShowImportDialog(show) => {
-                self.show_import_dialog = show;
+            Message::QuotePasteDefaultDateChanged(date) => {
+                if let Some(dialog) = &mut self.quote_paste {
+                    dialog.default_date = date;
+                    dialog.reparse();
+                }
                 Command::none()
             }
             
-            Message::ShowExportDialog(show) => {
-                self.show_export_dialog = show;
+            Message::QuotePasteSourceChanged(source) => {
+                if let Some(dialog) = &mut self.quote_paste {
+                    dialog.source = source;
+                }
                 Command::none()
             }
             
-            Message::StatusMessage(message) => {
-                self.update_status(message);
+            Message::QuotePasteCancelled => {
+                self.quote_paste = None;
                 Command::none()
             }
             
-        }
-    }
-
-    fn view(&self) -> Element<'_, Message> {
-        crate::gui::view(self)
-    }
-
-    fn subscription(&self) -> Subscription<Message> {
-        Subscription::none()
-    }
-}
\ No newline at end of file
+            Message::QuotePasteConfirmed => {
+                let Some(dialog) = &self.quote_paste else {
+                    return Command::none();
+                };
+                let quotes: Vec<_> = dialog.lines.iter().filter_map(|line| line.quote.clone().ok()).collect();
+                if quotes.is_empty() {
+                    self.update_status("No quotes to import".to_string());
+                    return Command::none();
+                }
+                let source = non_empty(dialog.source.clone());
+                let zone = self.new_quote_zone;
+                self.quote_paste = None;
+                
+                let count = quotes.len();
+                self.update_status(format!("{} quotes imported", count));
+                let details = format!("{} quotes pasted as text", count);
+                self.update_selected_person(AuditAction::QuoteAdded, details, |person| {
+                    for quote in quotes {
+                        let quote = person.add_quote(quote.text, quote.date, quote.time, quote.place, source.clone(), Vec::new());
+                        quote.utc_offset = Some(zone.offset_on(&quote.date, quote.time.as_deref()));
+                    }
+                })
+            }
+            
+            Message::ImportFromDeviceClicked => {
+                if self.selected_person.is_none() {
+                    self.update_status("Please select a person before importing from a device".to_string());
+                    return Command::none();
+                }
+                Command::perform(
+                    run_blocking(|| Ok(device_import::volumes())),
+                    |result| Message::DevicesFound(result.unwrap_or_default())
+                )
+            }
+            
+            Message::DevicesFound(volumes) => {
+                // With a single device attached there's nothing to choose
+                let only_volume = (volumes.len() == 1).then(|| volumes[0].clone());
+                self.device_import = Some(DeviceImportDialog {
+                    volumes,
+                    volume: None,
+                    items: Vec::new(),
+                    selected: Vec::new(),
+                    thumbnails: HashMap::new(),
+                    scanning: false,
+                });
+                match only_volume {
+                    Some(volume) => self.update(Message::DeviceSelected(volume)),
+                    None => Command::none(),
+                }
+            }
+            
+            Message::ChooseDeviceFolder => {
+                Command::perform(
+                    async {
+                        rfd::FileDialog::new().pick_folder()
+                    },
+                    |folder| {
+                        if let Some(folder) = folder {
+                            Message::DeviceSelected(folder)
+                        } else {
+                            Message::StatusMessage("Device selection cancelled".to_string())
+                        }
+                    }
+                )
+            }
+            
+            Message::DeviceSelected(volume) => {
+                let Some(dialog) = &mut self.device_import else {
+                    return Command::none();
+                };
+                dialog.volume = Some(volume.clone());
+                dialog.items.clear();
+                dialog.selected.clear();
+                dialog.thumbnails.clear();
+                dialog.scanning = true;
+                if !dialog.volumes.contains(&volume) {
+                    dialog.volumes.push(volume.clone());
+                }
+                Command::perform(
+                    run_blocking({
+                        let volume = volume.clone();
+                        move || device_import::scan(&volume).map_err(|e| e.to_string())
+                    }),
+                    move |result| Message::DeviceScanned(volume, result)
+                )
+            }
+            
+            Message::DeviceScanned(volume, result) => {
+                let Some(dialog) = &mut self.device_import else {
+                    return Command::none();
+                };
+                // A scan of a device the user has since moved away from
+                if dialog.volume.as_ref() != Some(&volume) {
+                    return Command::none();
+                }
+                dialog.scanning = false;
+                match result {
+                    Ok(items) => {
+                        let pictures: Vec<PathBuf> = items
+                            .iter()
+                            .filter(|item| self.settings.evidence_type_for_file(&item.path) == Some(EvidenceType::Image))
+                            .map(|item| item.path.clone())
+                            .collect();
+                        dialog.selected = vec![false; items.len()];
+                        dialog.items = items;
+                        Command::batch(pictures.into_iter().map(|path| {
+                            Command::perform(
+                                run_blocking({
+                                    let path = path.clone();
+                                    move || Ok(device_import::thumbnail(&path, device_import::THUMBNAIL_SIZE))
+                                }),
+                                move |result| {
+                                    let handle = result.ok().flatten()
+                                        .map(|thumbnail| image::Handle::from_pixels(thumbnail.width(), thumbnail.height(), thumbnail.into_raw()));
+                                    Message::DeviceThumbnailLoaded(path, handle)
+                                }
+                            )
+                        }))
+                    }
+                    Err(e) => {
+                        self.update_status(format!("Failed to read device: {}", e));
+                        Command::none()
+                    }
+                }
+            }
+            
+            Message::DeviceThumbnailLoaded(path, handle) => {
+                if let Some(dialog) = &mut self.device_import
+                    && let Some(handle) = handle
+                    && dialog.items.iter().any(|item| item.path == path)
+                {
+                    dialog.thumbnails.insert(path, handle);
+                }
+                Command::none()
+            }
+            
+            Message::DeviceItemToggled(index, checked) => {
+                if let Some(dialog) = &mut self.device_import && let Some(selected) = dialog.selected.get_mut(index) {
+                    *selected = checked;
+                }
+                Command::none()
+            }
+            
+            Message::DeviceSelectAll(checked) => {
+                if let Some(dialog) = &mut self.device_import {
+                    dialog.selected.iter_mut().for_each(|s| *s = checked);
+                }
+                Command::none()
+            }
+            
+            Message::DeviceImportCancelled => {
+                self.device_import = None;
+                Command::none()
+            }
+            
+            Message::DeviceImportConfirmed => {
+                let Some(dialog) = &self.device_import else {
+                    return Command::none();
+                };
+                let Some(volume) = dialog.volume.clone() else {
+                    return Command::none();
+                };
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+                    return Command::none();
+                };
+                let items: Vec<DeviceItem> = dialog.items
+                    .iter()
+                    .zip(&dialog.selected)
+                    .filter(|(_, selected)| **selected)
+                    .map(|(item, _)| item.clone())
+                    .collect();
+                if items.is_empty() {
+                    self.update_status("No files selected for import".to_string());
+                    return Command::none();
+                }
+                self.device_import = None;
+                self.update_status(format!("Importing {} file(s) from {}...", items.len(), device_import::label(&volume)));
+                
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                let plugins = self.plugins.clone();
+                let settings = self.settings.clone();
+                let device = device_import::label(&volume);
+                let size = items.iter().map(|item| item.size).sum();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let mut person = person;
+                        let warnings = storage_warnings(&file_manager, &person, size, &settings);
+                        let mut summary = device_import::import(&file_manager, &audit_log, &plugins, &settings, &mut person, &device, &items)
+                            .map_err(|e| e.to_string())?;
+                        summary.warnings.splice(0..0, warnings);
+                        Ok(summary)
+                    }),
+                    Message::DeviceImportComplete
+                )
+            }
+            
+            Message::DeviceImportComplete(result) => {
+                match result {
+                    Ok(summary) => {
+                        if summary.warnings.is_empty() {
+                            self.update_status(format!("Imported from device: {}", summary.describe()));
+                        } else {
+                            self.update_status(format!("Imported from device: {}. Warning: {}", summary.describe(), summary.warnings.join("; ")));
+                        }
+                        self.quarantined.extend(summary.quarantined);
+                        // Plugins may have tagged the person
+                        if let Some(person_id) = self.selected_person {
+                            self.reload_person(person_id);
+                        }
+                        self.refresh_evidence_files();
+                        if self.settings.tsa_url.trim().is_empty() || summary.added.is_empty() {
+                            return Command::none();
+                        }
+                        let person_id = summary.added[0].person_id;
+                        match self.persons.iter().find(|p| p.id == person_id).cloned() {
+                            Some(person) => self.timestamp_evidence(person, summary.added),
+                            None => Command::none(),
+                        }
+                    }
+                    Err(e) => {
+                        self.update_status(format!("Failed to import from device: {}", e));
+                        Command::none()
+                    }
+                }
+            }
+            
+            Message::ImportFileListClicked => {
+                if self.selected_person.is_none() {
+                    self.update_status("Please select a person before importing a file list".to_string());
+                    return Command::none();
+                }
+                Command::perform(
+                    async {
+                        rfd::FileDialog::new()
+                            .add_filter("File list", &["csv", "tsv", "txt"])
+                            .pick_file()
+                    },
+                    |path| {
+                        if let Some(path) = path {
+                            Message::FileListSelected(path)
+                        } else {
+                            Message::StatusMessage("Import cancelled".to_string())
+                        }
+                    }
+                )
+            }
+            
+            Message::FileListSelected(path) => {
+                Command::perform(
+                    run_blocking(move || FileList::load(&path).map_err(|e| e.to_string())),
+                    Message::FileListLoaded
+                )
+            }
+            
+            Message::FileListLoaded(result) => {
+                match result {
+                    Ok(list) => {
+                        let mut dialog = FileListDialog { mapping: list.guess_mapping(), list, rows: Vec::new() };
+                        dialog.remap();
+                        self.file_list_import = Some(dialog);
+                    }
+                    Err(e) => self.update_status(format!("Failed to read file list: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::FileListColumnSelected(field, column) => {
+                if let Some(dialog) = &mut self.file_list_import {
+                    match column {
+                        Some(column) => dialog.mapping.insert(field, column),
+                        None => dialog.mapping.remove(&field),
+                    };
+                    dialog.remap();
+                }
+                Command::none()
+            }
+            
+            Message::FileListImportCancelled => {
+                self.file_list_import = None;
+                Command::none()
+            }
+            
+            Message::FileListImportConfirmed => {
+                let Some(dialog) = self.file_list_import.take() else {
+                    return Command::none();
+                };
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+                    return Command::none();
+                };
+                self.update_status(format!("Importing {} listed file(s)...", dialog.rows.len()));
+                
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                let plugins = self.plugins.clone();
+                let settings = self.settings.clone();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let mut person = person;
+                        let mut summary = file_list_import::import(&file_manager, &audit_log, &plugins, &settings, &mut person, &dialog.list, &dialog.rows)
+                            .map_err(|e| e.to_string())?;
+                        notify_storage_warnings(&summary.storage_warnings);
+                        let storage_warnings = std::mem::take(&mut summary.storage_warnings);
+                        summary.warnings.splice(0..0, storage_warnings);
+                        Ok(summary)
+                    }),
+                    Message::FileListImportComplete
+                )
+            }
+            
+            Message::FileListImportComplete(result) => {
+                match result {
+                    Ok(summary) => {
+                        if summary.warnings.is_empty() {
+                            self.update_status(format!("Imported file list: {}", summary.describe()));
+                        } else {
+                            self.update_status(format!("Imported file list: {}. Warning: {}", summary.describe(), summary.warnings.join("; ")));
+                        }
+                        self.quarantined.extend(summary.quarantined);
+                        // Plugins may have tagged the person
+                        if let Some(person_id) = self.selected_person {
+                            self.reload_person(person_id);
+                        }
+                        self.refresh_evidence_files();
+                        if self.settings.tsa_url.trim().is_empty() || summary.added.is_empty() {
+                            return Command::none();
+                        }
+                        let person_id = summary.added[0].person_id;
+                        match self.persons.iter().find(|p| p.id == person_id).cloned() {
+                            Some(person) => self.timestamp_evidence(person, summary.added),
+                            None => Command::none(),
+                        }
+                    }
+                    Err(e) => {
+                        self.update_status(format!("Failed to import file list: {}", e));
+                        Command::none()
+                    }
+                }
+            }
+            
+            Message::TabChanged(tab) => {
+                let load_map = tab == EvidenceTab::Map && self.map_view.is_none();
+                self.current_tab = tab;
+                self.tab_scroll = 0.0;
+                self.selected_evidence.clear();
+                self.evidence_actions = None;
+                if load_map {
+                    self.update(Message::LoadMap)
+                } else {
+                    Command::none()
+                }
+            }
+            
+            Message::LoadMap => {
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+                    return Command::none();
+                };
+                let evidence_files = self.evidence_files.clone();
+                let tile_url = self.settings.tile_url().to_string();
+                self.map_loading = true;
+                
+                Command::perform(
+                    async move {
+                        // Reading images and downloading tiles both block
+                        tokio::task::spawn_blocking(move || {
+                            let points = map_points(&person, &evidence_files);
+                            if points.is_empty() {
+                                return Ok(MapView { image: image::Handle::from_pixels(1, 1, vec![0; 4]), points, missing_tiles: 0 });
+                            }
+                            let rendered = map::render_map(&points, &tile_url).map_err(|e| e.to_string())?;
+                            let (width, height) = rendered.image.dimensions();
+                            Ok(MapView {
+                                image: image::Handle::from_pixels(width, height, rendered.image.into_raw()),
+                                points,
+                                missing_tiles: rendered.missing_tiles,
+                            })
+                        })
+                        .await
+                        .map_err(|e| e.to_string())?
+                    },
+                    Message::MapLoaded
+                )
+            }
+            
+            Message::MapLoaded(result) => {
+                self.map_loading = false;
+                match result {
+                    Ok(map_view) => {
+                        if map_view.missing_tiles > 0 {
+                            self.update_status(format!("{} map tiles unavailable offline", map_view.missing_tiles));
+                        }
+                        self.map_view = Some(map_view);
+                    }
+                    Err(e) => self.update_status(format!("Failed to load map: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::BoardCardAdded(content) => {
+                let id = self.board.add_card(content);
+                self.board_selected = Some(id);
+                self.save_board();
+                Command::none()
+            }
+            
+            Message::BoardCardPressed(id) => {
+                if let Some(from) = self.board_connecting.take()
+                    && self.board.connect(from, id)
+                {
+                    self.save_board();
+                }
+                self.board_selected = Some(id);
+                Command::none()
+            }
+            
+            Message::BoardBackgroundPressed => {
+                self.board_selected = None;
+                self.board_connecting = None;
+                Command::none()
+            }
+            
+            Message::BoardCardMoved(id, x, y) => {
+                // Saved once the card is let go
+                self.board.move_card(id, x, y);
+                Command::none()
+            }
+            
+            Message::BoardCardDropped => {
+                self.save_board();
+                Command::none()
+            }
+            
+            Message::BoardCardRemoved(id) => {
+                self.board.remove_card(id);
+                self.board_selected = None;
+                self.board_connecting = None;
+                self.save_board();
+                Command::none()
+            }
+            
+            Message::BoardNoteChanged(id, note) => {
+                if let Some(card) = self.board.card_mut(id) {
+                    card.note = note;
+                    self.save_board();
+                }
+                Command::none()
+            }
+            
+            Message::BoardConnectClicked(from) => {
+                self.board_connecting = from;
+                Command::none()
+            }
+            
+            Message::BoardConnectionLabelChanged(id, label) => {
+                if let Some(connection) = self.board.connections.iter_mut().find(|c| c.id == id) {
+                    connection.label = label;
+                    self.save_board();
+                }
+                Command::none()
+            }
+            
+            Message::BoardConnectionRemoved(id) => {
+                self.board.connections.retain(|c| c.id != id);
+                self.save_board();
+                Command::none()
+            }
+            
+            Message::ExportBoardClicked => {
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person) else {
+                    return Command::none();
+                };
+                let file_name = format!("{}_board.png", person.name.replace(" ", "_"));
+                Command::perform(
+                    async move {
+                        rfd::FileDialog::new()
+                            .add_filter("PNG image", &["png"])
+                            .set_file_name(file_name)
+                            .save_file()
+                    },
+                    |path| {
+                        if let Some(path) = path {
+                            Message::ExportBoardFileSelected(path)
+                        } else {
+                            Message::StatusMessage("Export cancelled".to_string())
+                        }
+                    }
+                )
+            }
+            
+            Message::ExportBoardFileSelected(path) => {
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+                    return Command::none();
+                };
+                let board = self.board.clone();
+                let persons = self.persons.clone();
+                let evidence_files = self.evidence_files.clone();
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let image = board::render_image(&board, &person, &persons, &evidence_files, &file_manager);
+                        image.save(&path).map_err(|e| e.to_string())?;
+                        let details = format!("Board of {} cards to {}", board.cards.len(), path.display());
+                        audit_log.record(Some(&person), AuditAction::Exported, details).map_err(|e| e.to_string())?;
+                        Ok(board.cards.len())
+                    }),
+                    Message::BoardExported
+                )
+            }
+            
+            Message::BoardExported(result) => {
+                match result {
+                    Ok(count) => self.update_status(format!("Board of {} {} exported", count, if count == 1 { "card" } else { "cards" })),
+                    Err(e) => self.update_status(format!("Failed to export board: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::OpenMapPoint(target) => {
+                match target {
+                    MapTarget::Evidence(evidence_id) => {
+                        if let Some(file) = self.evidence_files.iter().find(|f| f.id == evidence_id) {
+                            self.current_tab = EvidenceTab::for_evidence_type(&file.file_type);
+                            // Ticking the file makes it easy to spot in the list
+                            self.selected_evidence = vec![evidence_id];
+                        }
+                    }
+                    MapTarget::Quote(_) => self.current_tab = EvidenceTab::Quotes,
+                }
+                Command::none()
+            }
+            
+            Message::SelectFileClicked => {
+                if let Some(_person_id) = self.selected_person {
+                    Command::perform(
+                        async {
+                            rfd::FileDialog::new()
+                                .add_filter("All Files", &["*"])
+                                .add_filter("Images", &["jpg", "jpeg", "png", "gif", "bmp", "tiff", "webp"])
+                                .add_filter("Audio", &["mp3", "wav", "flac", "aac", "ogg", "m4a"])
+                                .add_filter("Videos", &["mp4", "avi", "mov", "wmv", "flv", "webm", "mkv"])
+                                .add_filter("Documents", &["pdf", "doc", "docx", "txt", "md", "rtf", "eml"])
+                                .pick_file()
+                        },
+                        |path| {
+                            if let Some(path) = path {
+                                Message::FileSelected(path)
+                            } else {
+                                Message::StatusMessage("File selection cancelled".to_string())
+                            }
+                        }
+                    )
+                } else {
+                    Command::perform(
+                        async { Message::StatusMessage("Please select a person before adding files".to_string()) },
+                        |msg| msg
+                    )
+                }
+            }
+            
+            Message::FileSelected(path) => {
+                if self.selected_person.is_none() {
+                    return Command::none();
+                }
+                
+                // Files added from a custom category's tab go into that category; quick capture
+                // doesn't show the tabs, so there it's always by extension
+                let tab_category = match &self.current_tab {
+                    _ if self.quick_capture => None,
+                    EvidenceTab::Custom(folder_name) => Some(EvidenceType::Custom(folder_name.clone())),
+                    EvidenceTab::Other => Some(EvidenceType::Other),
+                    _ => None,
+                };
+                let evidence_type = tab_category.or_else(|| self.settings.evidence_type_for_file(&path));
+                
+                match evidence_type {
+                    Some(evidence_type) => self.add_file(path, evidence_type),
+                    None => {
+                        // Unknown or missing extension: ask where it belongs rather than refusing it
+                        self.uncategorized_file = Some(path);
+                        Command::none()
+                    }
+                }
+            }
+            
+            Message::UncategorizedFileCategorySelected(evidence_type) => {
+                match self.uncategorized_file.take() {
+                    Some(path) => self.add_file(path, evidence_type),
+                    None => Command::none(),
+                }
+            }
+            
+            Message::CancelUncategorizedFile => {
+                self.uncategorized_file = None;
+                Command::none()
+            }
+            
+            Message::FileQuarantined(result) => {
+                match result {
+                    Ok(file) => {
+                        self.update_status(format!("{} looks like a {} and is held in quarantine until you accept it", file.original_name, file.reason.to_lowercase()));
+                        self.quarantined.push(file);
+                    }
+                    Err(e) => self.update_status(format!("Failed to quarantine file: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::AcceptQuarantinedClicked(id) => {
+                self.quarantine_confirm = id;
+                Command::none()
+            }
+            
+            Message::AcceptQuarantinedConfirmed(id) => {
+                self.quarantine_confirm = None;
+                let Some(file) = self.quarantined.iter().find(|f| f.id == id).cloned() else {
+                    return Command::none();
+                };
+                let Some(person) = self.persons.iter().find(|p| p.id == file.person_id).cloned() else {
+                    return Command::none();
+                };
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let quarantine = Quarantine::new(file_manager.get_evidence_dir());
+                        quarantine.check(&file).map_err(|e| e.to_string())?;
+                        let mut evidence_file = file_manager.intake_file(&person, &quarantine.path(&file), file.evidence_type.clone(), Provenance::default(), false, None)
+                            .map_err(|e| e.to_string())?;
+                        // Where it came from before quarantine, not the quarantine folder
+                        if let Some(provenance) = &mut evidence_file.provenance {
+                            provenance.original_path = file.original_path.clone();
+                            provenance.original_modified = file.original_modified;
+                        }
+                        evidence_file.evidence_date = file.original_modified;
+                        file_manager.update_evidence_metadata(&person, &evidence_file).map_err(|e| e.to_string())?;
+                        let details = format!(
+                            "{} ({}), accepted from quarantine ({}), SHA-256 {}",
+                            evidence_file.original_name, evidence_file.file_type.folder_name(), file.reason, file.sha256,
+                        );
+                        audit_log.record(Some(&person), AuditAction::EvidenceAdded, details).map_err(|e| e.to_string())?;
+                        quarantine.release(file.id).map_err(|e| e.to_string())?;
+                        Ok(evidence_file)
+                    }),
+                    move |result| Message::QuarantinedAccepted(id, result)
+                )
+            }
+            
+            Message::QuarantinedAccepted(id, result) => {
+                match result {
+                    Ok(evidence_file) => {
+                        self.quarantined.retain(|f| f.id != id);
+                        self.update(Message::FileAddedSuccessfully(evidence_file, Vec::new()))
+                    }
+                    Err(e) => {
+                        self.update_status(format!("Failed to accept quarantined file: {}", e));
+                        Command::none()
+                    }
+                }
+            }
+            
+            Message::DiscardQuarantined(id) => {
+                let Some(file) = self.quarantined.iter().find(|f| f.id == id).cloned() else {
+                    return Command::none();
+                };
+                let person = self.persons.iter().find(|p| p.id == file.person_id).cloned();
+                let quarantine = Quarantine::new(self.file_manager.get_evidence_dir());
+                let audit_log = self.audit_log.clone();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        quarantine.release(file.id).map_err(|e| e.to_string())?;
+                        let details = format!("{} discarded from quarantine", file.original_name);
+                        audit_log.record(person.as_ref(), AuditAction::EvidenceQuarantined, details).map_err(|e| e.to_string())
+                    }),
+                    move |result| Message::QuarantinedDiscarded(id, result)
+                )
+            }
+            
+            Message::QuarantinedDiscarded(id, result) => {
+                match result {
+                    Ok(()) => {
+                        self.quarantined.retain(|f| f.id != id);
+                        self.update_status("Quarantined file discarded".to_string());
+                    }
+                    Err(e) => self.update_status(format!("Failed to discard quarantined file: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::IntakeSourceDeviceChanged(value) => {
+                self.intake_source_device = value;
+                Command::none()
+            }
+            
+            Message::IntakeSourceUrlChanged(value) => {
+                self.intake_source_url = value;
+                Command::none()
+            }
+            
+            Message::IntakeProvidedByChanged(value) => {
+                self.intake_provided_by = value;
+                Command::none()
+            }
+            
+            Message::IntakeObtainedAtChanged(value) => {
+                self.intake_obtained_at = value;
+                Command::none()
+            }
+            
+            Message::IntakeCollectedByChanged(value) => {
+                self.intake_collected_by = value;
+                Command::none()
+            }
+            
+            Message::IntakeSubmitted => {
+                match self.intake.take() {
+                    Some(intake) => {
+                        let provenance = Provenance {
+                            source_device: non_empty(std::mem::take(&mut self.intake_source_device)),
+                            source_url: non_empty(std::mem::take(&mut self.intake_source_url)),
+                            provided_by: non_empty(std::mem::take(&mut self.intake_provided_by)),
+                            obtained_at: non_empty(std::mem::take(&mut self.intake_obtained_at)),
+                            // Usually the same person for a whole session, so keep it filled in
+                            collected_by: non_empty(self.intake_collected_by.clone()),
+                            ..Provenance::default()
+                        };
+                        self.store_file(intake.path, intake.evidence_type, Some(provenance))
+                    }
+                    None => Command::none(),
+                }
+            }
+            
+            Message::CancelIntake => {
+                self.intake = None;
+                Command::none()
+            }
+            
+            Message::PasteScreenshotClicked => {
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+                    return Command::none();
+                };
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                let pattern = self.settings.screenshot_name_pattern().to_string();
+                let settings = self.settings.clone();
+                let plugins = self.plugins.clone();
+                
+                Command::perform(
+                    async move {
+                        let mut person = person;
+                        let content = tokio::task::spawn_blocking(clipboard::read_image)
+                            .await
+                            .map_err(|e| e.to_string())?
+                            .map_err(|e| e.to_string())?;
+                        let mut warnings = storage_warnings(&file_manager, &person, content.len() as u64, &settings);
+                        let mut evidence_file = file_manager.store_screenshot(&person, &content, "png", chrono::Utc::now(), &pattern)
+                            .map_err(|e| e.to_string())?;
+                        let details = format!("{} (pasted from clipboard)", evidence_file.original_name);
+                        audit_log.record(Some(&person), AuditAction::EvidenceAdded, details).map_err(|e| e.to_string())?;
+                        warnings.extend(plugins.evidence_added(&file_manager, &audit_log, &mut person, &mut evidence_file));
+                        Ok((evidence_file, warnings))
+                    },
+                    |result: Result<(EvidenceFile, Vec<String>), String>| {
+                        match result {
+                            Ok((evidence_file, warnings)) => Message::FileAddedSuccessfully(evidence_file, warnings),
+                            Err(e) => Message::StatusMessage(format!("Failed to paste screenshot: {}", e)),
+                        }
+                    }
+                )
+            }
+            
+            Message::ExportSettingsClicked => {
+                let settings = self.settings.clone();
+                Command::perform(
+                    async move {
+                        let Some(path) = rfd::AsyncFileDialog::new()
+                            .add_filter("Evidence Manager settings", &["json"])
+                            .set_file_name("evidence_manager_settings.json")
+                            .save_file()
+                            .await
+                        else {
+                            return Ok(None);
+                        };
+                        settings.export_bundle(path.path()).map_err(|e| e.to_string())?;
+                        Ok(Some(path.path().display().to_string()))
+                    },
+                    |result: Result<Option<String>, String>| {
+                        match result {
+                            Ok(Some(path)) => Message::StatusMessage(format!("Settings exported to {}", path)),
+                            Ok(None) => Message::StatusMessage("Settings export cancelled".to_string()),
+                            Err(e) => Message::StatusMessage(format!("Failed to export settings: {}", e)),
+                        }
+                    }
+                )
+            }
+            
+            Message::ImportSettingsClicked => {
+                Command::perform(
+                    async {
+                        let path = rfd::AsyncFileDialog::new()
+                            .add_filter("Evidence Manager settings", &["json"])
+                            .pick_file()
+                            .await
+                            .ok_or_else(|| "No file selected".to_string())?;
+                        Settings::import_bundle(path.path()).map_err(|e| e.to_string())
+                    },
+                    Message::SettingsImported
+                )
+            }
+            
+            Message::SettingsImported(result) => {
+                match result {
+                    Ok(settings) => {
+                        // Window layout and interface scale belong to this machine's screen, and the operator to whoever uses it
+                        let layout = self.settings.layout.clone();
+                        let ui_scale = self.settings.ui_scale;
+                        let operator = self.settings.operator.clone();
+                        let admin_passphrase = self.settings.admin_passphrase.take();
+                        let webhook_secret = std::mem::take(&mut self.settings.webhook_secret);
+                        let enabled_plugins = std::mem::take(&mut self.settings.enabled_plugins);
+                        let export_profiles = std::mem::take(&mut self.settings.export_profiles);
+                        self.settings = settings;
+                        self.settings.layout = layout;
+                        self.settings.ui_scale = ui_scale;
+                        self.settings.operator = operator;
+                        self.settings.admin_passphrase = admin_passphrase;
+                        self.settings.webhook_secret = webhook_secret;
+                        // Plugin scripts are in this machine's config folder
+                        self.settings.enabled_plugins = enabled_plugins;
+                        // Export profiles point at this machine's folders
+                        self.settings.export_profiles = export_profiles;
+                        self.audit_log.set_webhook(Webhook::new(&self.settings.webhook_url, &self.settings.webhook_secret));
+                        self.apply_storage_layout();
+                        self.move_files = self.settings.move_into_evidence;
+                        self.save_settings();
+                        // Categories and mappings may have changed which files belong where
+                        self.map_view = None;
+                        self.refresh_evidence_files();
+                        self.update_status("Settings imported".to_string());
+                    }
+                    Err(e) => self.update_status(format!("Failed to import settings: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::ScreenshotPatternChanged(pattern) => {
+                self.settings.screenshot_name_pattern = pattern;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::ReportTemplateSelected(name) => {
+                self.settings.report_template = name;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::AddReportTemplateClicked => {
+                Command::perform(
+                    async {
+                        let path = rfd::AsyncFileDialog::new()
+                            .add_filter("Report template", &["json"])
+                            .pick_file()
+                            .await
+                            .ok_or_else(|| "No file selected".to_string())?;
+                        ReportTemplate::load(path.path()).map_err(|e| e.to_string())
+                    },
+                    Message::ReportTemplateLoaded
+                )
+            }
+            
+            Message::ReportTemplateLoaded(result) => {
+                match result {
+                    Ok(template) if ReportTemplate::built_in().iter().any(|t| t.name == template.name) => {
+                        self.update_status(format!("\"{}\" is the name of a built-in template", template.name));
+                    }
+                    Ok(template) => {
+                        // Loading a template again replaces the earlier version
+                        let name = template.name.clone();
+                        self.settings.report_templates.retain(|t| t.name != name);
+                        self.settings.report_templates.push(template);
+                        self.save_settings();
+                        self.update_status(format!("Report template \"{}\" added", name));
+                    }
+                    Err(e) => self.update_status(format!("Failed to load report template: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::RemoveReportTemplate(name) => {
+                self.settings.report_templates.retain(|t| t.name != name);
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::FileCopied(copy_id, result) => {
+                self.file_copies.retain(|copy| copy.id != copy_id);
+                match result {
+                    Ok(Intake::Added { evidence_file, storage_warnings, warnings }) => {
+                        self.update(Message::FileAddedSuccessfully(evidence_file, storage_warnings.into_iter().chain(warnings).collect()))
+                    }
+                    Ok(Intake::Quarantined(file)) => self.update(Message::FileQuarantined(Ok(file))),
+                    Err(e) => {
+                        self.update_status(format!("Failed to add file: {}", e));
+                        Command::none()
+                    }
+                }
+            }
+            
+            Message::FileAddedSuccessfully(evidence_file, warnings) => {
+                if warnings.is_empty() {
+                    self.update_status("File successfully added".to_string());
+                } else {
+                    self.update_status(format!("File added. Warning: {}", warnings.join("; ")));
+                }
+                // Plugins may have tagged the person, and an email's risky attachments are held in quarantine
+                self.reload_person(evidence_file.person_id);
+                if let Ok(quarantined) = Quarantine::new(self.file_manager.get_evidence_dir()).list() {
+                    self.quarantined = quarantined;
+                }
+                self.refresh_evidence_files();
+                // Dropped files are timestamped as they're taken in
+                if self.settings.tsa_url.trim().is_empty() || evidence_file.timestamp.is_some() {
+                    return Command::none();
+                }
+                match self.persons.iter().find(|p| p.id == evidence_file.person_id).cloned() {
+                    Some(person) => self.timestamp_evidence(person, vec![evidence_file]),
+                    None => Command::none(),
+                }
+            }
+            
+            Message::StartRecording => {
+                if self.recording.is_some() {
+                    return Command::none();
+                }
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person) else {
+                    self.update_status("Please select a person before recording".to_string());
+                    return Command::none();
+                };
+                let file_name = naming::render_pattern(naming::RECORDING_PATTERN, &naming::NameContext {
+                    date: chrono::Utc::now(),
+                    person: &person.folder_name(),
+                    name: "recording",
+                    ext: "wav",
+                    counter: 1,
+                    counter_width: 1,
+                });
+                match Recording::start(&file_name) {
+                    Ok(recording) => {
+                        self.recording = Some((person.id, recording));
+                        self.update_status("Recording...".to_string());
+                    }
+                    Err(e) => self.update_status(format!("Failed to start recording: {:#}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::StopRecording => {
+                let Some((person_id, recording)) = self.recording.take() else {
+                    return Command::none();
+                };
+                // The person may have been deleted while recording
+                let Some(person) = self.persons.iter().find(|p| p.id == person_id).cloned() else {
+                    recording.discard();
+                    return Command::none();
+                };
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                let settings = self.settings.clone();
+                let plugins = self.plugins.clone();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let mut person = person;
+                        let started_at = recording.started_at;
+                        let path = recording.stop().map_err(|e| format!("{:#}", e))?;
+                        let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or_default();
+                        let mut warnings = storage_warnings(&file_manager, &person, size, &settings);
+                        let mut evidence_file = file_manager.move_file_to_evidence(&person, &path, EvidenceType::Audio, None)
+                            .map_err(|e| format!("{} (the recording is kept at {})", e, path.display()))?;
+                        evidence_file.evidence_date = Some(started_at);
+                        file_manager.update_evidence_metadata(&person, &evidence_file).map_err(|e| e.to_string())?;
+                        let details = format!("{} (audio, recorded in the app)", evidence_file.original_name);
+                        audit_log.record(Some(&person), AuditAction::EvidenceAdded, details).map_err(|e| e.to_string())?;
+                        warnings.extend(plugins.evidence_added(&file_manager, &audit_log, &mut person, &mut evidence_file));
+                        Ok((evidence_file, warnings))
+                    }),
+                    Message::RecordingSaved
+                )
+            }
+            
+            Message::DiscardRecording => {
+                if let Some((_, recording)) = self.recording.take() {
+                    recording.discard();
+                    self.update_status("Recording discarded".to_string());
+                }
+                Command::none()
+            }
+            
+            Message::RecordingTick => Command::none(),
+            
+            Message::RecordingSaved(result) => {
+                match result {
+                    Ok((evidence_file, warnings)) => self.update(Message::FileAddedSuccessfully(evidence_file, warnings)),
+                    Err(e) => {
+                        self.update_status(format!("Failed to save recording: {}", e));
+                        Command::none()
+                    }
+                }
+            }
+            
+            Message::NewNoteClicked => {
+                if self.selected_person.is_none() {
+                    return Command::none();
+                }
+                self.note_editor = Some(NoteEditor {
+                    name: notes::default_name(chrono::Utc::now()),
+                    content: text_editor::Content::new(),
+                    editing: None,
+                });
+                Command::none()
+            }
+            
+            Message::EditNote(evidence_id) => {
+                let Some(file) = self.evidence_files.iter().find(|f| f.id == evidence_id) else {
+                    return Command::none();
+                };
+                let path = file.file_path.clone();
+                let file_manager = self.file_manager.clone();
+                Command::perform(
+                    async move {
+                        file_manager.read_evidence(&path)
+                            .map(|content| String::from_utf8_lossy(&content).into_owned())
+                            .map_err(|e| e.to_string())
+                    },
+                    move |result| Message::NoteLoaded(evidence_id, result)
+                )
+            }
+            
+            Message::NoteLoaded(evidence_id, result) => {
+                let Some(file) = self.evidence_files.iter().find(|f| f.id == evidence_id) else {
+                    return Command::none();
+                };
+                match result {
+                    Ok(content) => {
+                        self.note_editor = Some(NoteEditor {
+                            name: file.original_name.clone(),
+                            content: text_editor::Content::with_text(&content),
+                            editing: Some(evidence_id),
+                        });
+                    }
+                    Err(e) => self.update_status(format!("Failed to open note: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::NoteNameChanged(name) => {
+                if let Some(editor) = &mut self.note_editor {
+                    editor.name = name;
+                }
+                Command::none()
+            }
+            
+            Message::NoteEdited(action) => {
+                if let Some(editor) = &mut self.note_editor {
+                    editor.content.perform(action);
+                }
+                Command::none()
+            }
+            
+            Message::CloseNote => {
+                self.note_editor = None;
+                Command::none()
+            }
+            
+            Message::SaveNote => {
+                let Some(editor) = &self.note_editor else {
+                    return Command::none();
+                };
+                let current = editor.editing.and_then(|id| self.evidence_files.iter().find(|f| f.id == id).cloned());
+                let file_name = notes::file_name(&editor.name);
+                let content = editor.content.text();
+                // An edited note is kept as a new version, which moves the old one
+                if current.is_some() && self.refuse_on_hold(self.selected_person) {
+                    return Command::none();
+                }
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+                    return Command::none();
+                };
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let evidence_file = match current {
+                            Some(current) => file_manager.replace_with_bytes(&person, &current, &file_name, content.as_bytes())
+                                .map_err(|e| e.to_string())?,
+                            None => file_manager.store_bytes_as_evidence(&person, &file_name, content.as_bytes(), EvidenceType::Document)
+                                .map_err(|e| e.to_string())?,
+                        };
+                        let details = match evidence_file.previous_version {
+                            Some(_) => format!("{} (note edited in the app)", evidence_file.original_name),
+                            None => format!("{} (note written in the app)", evidence_file.original_name),
+                        };
+                        audit_log.record(Some(&person), AuditAction::EvidenceAdded, details).map_err(|e| e.to_string())?;
+                        Ok(evidence_file)
+                    }),
+                    Message::NoteSaved
+                )
+            }
+            
+            Message::NoteSaved(result) => {
+                match result {
+                    Ok(evidence_file) => {
+                        self.update_status(format!("Note {} saved", evidence_file.original_name));
+                        self.note_editor = None;
+                        self.refresh_evidence_files();
+                    }
+                    Err(e) => self.update_status(format!("Failed to save note: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::SaveDrafts => {
+                self.save_drafts();
+                Command::none()
+            }
+            
+            Message::RestoreDraft(person_id, form) => {
+                let Some(index) = self.restorable_drafts.iter().position(|d| d.person_id == person_id && d.form == form) else {
+                    return Command::none();
+                };
+                let draft = self.restorable_drafts.remove(index);
+                let command = if self.selected_person != Some(person_id) {
+                    self.update(Message::PersonSelected(person_id))
+                } else {
+                    Command::none()
+                };
+                self.current_tab = self.fill_draft(&draft);
+                self.update_status(format!("Restored {} draft", form.label()));
+                command
+            }
+            
+            Message::PersonListScrolled(offset) => {
+                self.person_list_scroll = offset;
+                Command::none()
+            }
+            
+            Message::TabScrolled(offset) => {
+                self.tab_scroll = offset;
+                Command::none()
+            }
+            
+            Message::SaveSession => {
+                let session = self.session();
+                if session != self.saved_session {
+                    match session.save() {
+                        Ok(()) => self.saved_session = session,
+                        Err(e) => eprintln!("Failed to save session: {}", e),
+                    }
+                }
+                Command::none()
+            }
+            
+            Message::DiscardDraft(person_id, form) => {
+                self.restorable_drafts.retain(|d| d.person_id != person_id || d.form != form);
+                self.drafts.remove(person_id, form);
+                if let Err(e) = self.drafts.save() {
+                    self.update_status(format!("Failed to save drafts: {}", e));
+                }
+                Command::none()
+            }
+            
+            Message::PreviewEmail(evidence_id) => {
+                if let Some(file) = self.evidence_files.iter().find(|f| f.id == evidence_id) {
+                    let path = file.file_path.clone();
+                    let file_manager = self.file_manager.clone();
+                    Command::perform(
+                        async move {
+                            file_manager.read_evidence(&path)
+                                .map_err(|e| e.to_string())
+                                .and_then(|content| email::parse_email(&content).map_err(|e| e.to_string()))
+                        },
+                        Message::EmailPreviewLoaded
+                    )
+                } else {
+                    Command::none()
+                }
+            }
+            
+            Message::EmailPreviewLoaded(result) => {
+                match result {
+                    Ok(parsed) => self.email_preview = Some(parsed),
+                    Err(e) => self.update_status(format!("Failed to preview email: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::CloseEmailPreview => {
+                self.email_preview = None;
+                Command::none()
+            }
+            
+            Message::PreviewImage(evidence_id) => {
+                if let Some(file) = self.evidence_files.iter().find(|f| f.id == evidence_id) {
+                    let path = file.file_path.clone();
+                    let file_manager = self.file_manager.clone();
+                    Command::perform(
+                        async move {
+                            tokio::task::spawn_blocking(move || file_manager.read_evidence(&path).map_err(|e| e.to_string()))
+                                .await
+                                .map_err(|e| e.to_string())?
+                        },
+                        move |result| Message::ImagePreviewLoaded(evidence_id, result)
+                    )
+                } else {
+                    Command::none()
+                }
+            }
+            
+            Message::ImagePreviewLoaded(evidence_id, result) => {
+                match result {
+                    Ok(content) => self.image_preview = Some((evidence_id, image::Handle::from_memory(content))),
+                    Err(e) => self.update_status(format!("Failed to preview image: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::CloseImagePreview => {
+                self.image_preview = None;
+                Command::none()
+            }
+            
+            Message::FindDuplicatesClicked => {
+                let Some(person) = self.selected_person.and_then(|id| self.persons.iter().find(|p| p.id == id)) else {
+                    return Command::none();
+                };
+                let person_clone = person.clone();
+                let files = self.evidence_files.clone();
+                let file_manager = self.file_manager.clone();
+                self.update_status("Checking images for possible duplicates...".to_string());
+                
+                Command::perform(
+                    run_blocking(move || {
+                        // Hashes are cached in the evidence index so repeat checks are cheap
+                        let computed: Vec<(Uuid, u64)> = files
+                            .iter()
+                            .filter(|f| f.file_type == EvidenceType::Image && f.perceptual_hash.is_none())
+                            .filter_map(|f| duplicates::perceptual_hash(&f.file_path).ok().map(|hash| (f.id, hash)))
+                            .collect();
+                        if !computed.is_empty() {
+                            // Written into the index as it is now, so edits made while this ran are kept
+                            let mut index = file_manager.load_evidence_index(&person_clone).map_err(|e| e.to_string())?;
+                            for entry in &mut index {
+                                if let Some((_, hash)) = computed.iter().find(|(id, _)| *id == entry.id) {
+                                    entry.perceptual_hash = Some(*hash);
+                                }
+                            }
+                            file_manager.save_evidence_index(&person_clone, &index).map_err(|e| e.to_string())?;
+                        }
+                        
+                        let hashes: Vec<_> = files
+                            .iter()
+                            .filter_map(|f| f.perceptual_hash.map(|hash| (f.id, hash)))
+                            .chain(computed.iter().copied())
+                            .collect();
+                        let pairs = duplicates::find_near_duplicates(&hashes);
+                        Ok((computed, pairs))
+                    }),
+                    Message::DuplicatesFound
+                )
+            }
+            
+            Message::DuplicatesFound(result) => {
+                match result {
+                    Ok((computed, pairs)) => {
+                        for file in &mut self.evidence_files {
+                            if let Some((_, hash)) = computed.iter().find(|(id, _)| *id == file.id) {
+                                file.perceptual_hash = Some(*hash);
+                            }
+                        }
+                        self.update_status(format!("{} possible duplicate pairs found", pairs.len()));
+                        self.duplicate_review = Some(pairs);
+                    }
+                    Err(e) => {
+                        self.update_status(format!("Failed to check for duplicates: {}", e));
+                    }
+                }
+                Command::none()
+            }
+            
+            Message::DismissDuplicate(first, second) => {
+                if let Some(pairs) = &mut self.duplicate_review {
+                    pairs.retain(|p| !(p.first == first && p.second == second));
+                }
+                Command::none()
+            }
+            
+            Message::CloseDuplicateReview => {
+                self.duplicate_review = None;
+                Command::none()
+            }
+            
+            Message::EvidenceSelectionToggled(evidence_id, selected) => {
+                self.selected_evidence.retain(|id| *id != evidence_id);
+                if selected {
+                    self.selected_evidence.push(evidence_id);
+                }
+                Command::none()
+            }
+            
+            Message::ClearEvidenceSelection => {
+                self.selected_evidence.clear();
+                Command::none()
+            }
+            
+            Message::RenamePatternChanged(pattern) => {
+                self.rename_pattern = pattern;
+                Command::none()
+            }
+            
+            Message::BatchRenameClicked => {
+                if self.rename_pattern.trim().is_empty() || self.selected_evidence.is_empty() || self.refuse_on_hold(self.selected_person) {
+                    return Command::none();
+                }
+                let Some(person) = self.selected_person.and_then(|id| self.persons.iter().find(|p| p.id == id)) else {
+                    return Command::none();
+                };
+                let person_clone = person.clone();
+                let files = self.selected_evidence_files();
+                let pattern = self.rename_pattern.trim().to_string();
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let renamed = file_manager.rename_evidence_files(&person_clone, &files, &pattern)
+                            .map_err(|e| e.to_string())?;
+                        for (before, after) in files.iter().zip(&renamed) {
+                            let details = format!("{} → {}", before.file_path.display(), after.file_path.display());
+                            audit_log.record(Some(&person_clone), AuditAction::EvidenceRenamed, details).map_err(|e| e.to_string())?;
+                        }
+                        Ok(renamed.len())
+                    }),
+                    Message::BatchRenameComplete
+                )
+            }
+            
+            Message::BatchRenameComplete(result) => {
+                match result {
+                    Ok(count) => {
+                        self.selected_evidence.clear();
+                        self.update_status(format!("{} files renamed", count));
+                    }
+                    Err(e) => {
+                        self.update_status(format!("Failed to rename files: {}", e));
+                    }
+                }
+                self.refresh_evidence_files();
+                Command::none()
+            }
+            
+            Message::ImportClicked => {
+                Command::perform(
+                    async {
+                        rfd::FileDialog::new()
+                            .add_filter("Evidence Manager Archive", &["ema"])
+                            .pick_file()
+                    },
+                    |path| {
+                        if let Some(path) = path {
+                            Message::ImportFileSelected(path)
+                        } else {
+                            Message::ShowImportDialog(false)
+                        }
+                    }
+                )
+            }
+            
+            Message::ExportClicked => {
+                Command::perform(
+                    async {
+                        rfd::FileDialog::new()
+                            .add_filter("Evidence Manager Archive", &["ema"])
+                            .set_file_name("evidence_export.ema")
+                            .save_file()
+                    },
+                    |path| {
+                        if let Some(path) = path {
+                            Message::ExportFileSelected(path)
+                        } else {
+                            Message::ShowExportDialog(false)
+                        }
+                    }
+                )
+            }
+            
+            Message::PrintPersonClicked => {
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+                    return Command::none();
+                };
+                let evidence_files = self.evidence_files.clone();
+                let settings = self.settings.clone();
+                let template = self.settings.report_template();
+                
+                Command::perform(
+                    async move {
+                        let pdf = reports::person_summary_pdf(&person, &evidence_files, &settings, &template).map_err(|e| e.to_string())?;
+                        // Handed to the system PDF viewer, which provides the print dialog
+                        let path = std::env::temp_dir().join(format!("{}_summary.pdf", person.folder_name()));
+                        std::fs::write(&path, &pdf).map_err(|e| e.to_string())?;
+                        if settings.sign_exports {
+                            let key = SigningKey::load_or_create().map_err(|e| e.to_string())?;
+                            signing::sign_detached(&key, &path, &pdf).map_err(|e| e.to_string())?;
+                        }
+                        opener::open(&path).map_err(|e| e.to_string())?;
+                        Ok(path)
+                    },
+                    Message::PrintSummaryOpened
+                )
+            }
+            
+            Message::PrintSummaryOpened(result) => {
+                match result {
+                    Ok(path) => self.update_status(format!("Summary opened for printing: {}", path.display())),
+                    Err(e) => self.update_status(format!("Failed to create printable summary: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::ExportSelectionClicked => {
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person) else {
+                    return Command::none();
+                };
+                let file_name = format!("{}_selection.zip", person.folder_name());
+                Command::perform(
+                    async move {
+                        rfd::FileDialog::new()
+                            .add_filter("Zip Archive", &["zip"])
+                            .set_file_name(file_name)
+                            .save_file()
+                    },
+                    |path| {
+                        if let Some(path) = path {
+                            Message::ExportSelectionFileSelected(path)
+                        } else {
+                            Message::StatusMessage("Export cancelled".to_string())
+                        }
+                    }
+                )
+            }
+            
+            Message::ExportSelectionFileSelected(path) => {
+                let Some(person_id) = self.selected_person else {
+                    return Command::none();
+                };
+                let files = self.selected_evidence_files().iter().map(|f| f.id).collect();
+                self.enqueue_job(JobKind::ExportSelection {
+                    path,
+                    person: person_id,
+                    files,
+                    options: self.export_options(),
+                    include_manifest: self.export_selection_manifest,
+                })
+            }
+            
+            Message::ExportSelectionManifestToggled(enabled) => {
+                self.export_selection_manifest = enabled;
+                Command::none()
+            }
+            
+            Message::ExportPersonClicked(person_id) => {
+                self.person_actions = None;
+                if let Some(person) = self.persons.iter().find(|p| p.id == person_id) {
+                    let person_name = person.name.clone();
+                    Command::perform(
+                        async move {
+                            rfd::FileDialog::new()
+                                .add_filter("Evidence Manager Archive", &["ema"])
+                                .set_file_name(format!("{}.ema", person_name.replace(" ", "_")))
+                                .save_file()
+                        },
+                        move |path| {
+                            if let Some(path) = path {
+                                Message::ExportPersonFileSelected(person_id, path)
+                            } else {
+                                Message::StatusMessage("Export cancelled".to_string())
+                            }
+                        }
+                    )
+                } else {
+                    Command::perform(
+                        async { Message::StatusMessage("No person selected for export".to_string()) },
+                        |msg| msg
+                    )
+                }
+            }
+            
+            Message::ImportFileSelected(path) => {
+                self.show_import_dialog = false;
+                self.enqueue_job(JobKind::ImportArchive { path })
+            }
+            
+            Message::ResumeInterruptedImport => {
+                let Some(journal) = self.interrupted_import.take() else {
+                    return Command::none();
+                };
+                if !journal.archive.exists() {
+                    self.update_status(format!("{} is no longer there; roll the import back instead", journal.archive.display()));
+                    self.interrupted_import = Some(journal);
+                    return Command::none();
+                }
+                // The import finds the journal and skips what was already extracted
+                self.enqueue_job(JobKind::ImportArchive { path: journal.archive })
+            }
+            
+            Message::RollBackInterruptedImport => {
+                let Some(journal) = self.interrupted_import.take() else {
+                    return Command::none();
+                };
+                let evidence_dir = self.file_manager.get_evidence_dir().to_path_buf();
+                let audit_log = self.audit_log.clone();
+                Command::perform(
+                    run_blocking(move || {
+                        let archive = journal.archive.display().to_string();
+                        let removed = journal.roll_back(&evidence_dir).map_err(|e| e.to_string())?;
+                        let details = format!("Rolled back interrupted import of {} ({} files removed or restored)", archive, removed);
+                        audit_log.record(None, AuditAction::Imported, details).map_err(|e| e.to_string())?;
+                        Ok(removed)
+                    }),
+                    Message::InterruptedImportRolledBack
+                )
+            }
+            
+            Message::InterruptedImportRolledBack(result) => {
+                match result {
+                    Ok(removed) => {
+                        self.update_status(format!("Interrupted import rolled back; {} files removed or restored", removed));
+                        self.persons = self.file_manager.load_all_persons().unwrap_or_default();
+                        self.persons.sort_by(|a, b| a.name.cmp(&b.name));
+                        self.update_filtered_persons();
+                        self.refresh_evidence_files();
+                        self.refresh_statistics();
+                    }
+                    Err(e) => {
+                        self.update_status(format!("Failed to roll back import: {}", e));
+                        self.interrupted_import = ImportJournal::load(self.file_manager.get_evidence_dir()).ok().flatten();
+                    }
+                }
+                Command::none()
+            }
+            
+            Message::ExportFileSelected(path) => {
+                self.show_export_dialog = false;
+                let persons = self.persons.iter().map(|p| p.id).collect();
+                self.enqueue_job(JobKind::ExportArchive { path, persons, options: self.export_options() })
+            }
+            
+            Message::ExportPersonFileSelected(person_id, path) => {
+                if self.persons.iter().any(|p| p.id == person_id) {
+                    self.enqueue_job(JobKind::ExportArchive { path, persons: vec![person_id], options: self.export_options() })
+                } else {
+                    Command::none()
+                }
+            }
+            
+            Message::ExportEachPersonClicked => {
+                if self.filtered_persons.is_empty() {
+                    self.update_status("No people in the list to export".to_string());
+                    return Command::none();
+                }
+                Command::perform(
+                    async { rfd::FileDialog::new().pick_folder() },
+                    |folder| {
+                        if let Some(folder) = folder {
+                            Message::ExportEachPersonFolderSelected(folder)
+                        } else {
+                            Message::StatusMessage("Export cancelled".to_string())
+                        }
+                    }
+                )
+            }
+            
+            Message::ExportEachPersonFolderSelected(folder) => {
+                // One archive per person in the current list, each its own job
+                let date = Local::now().format("%Y-%m-%d").to_string();
+                let persons: Vec<(Uuid, String)> = self.persons
+                    .iter()
+                    .filter(|p| self.filtered_persons.contains(&p.id))
+                    .map(|p| (p.id, naming::sanitize_file_name(&format!("{}_{}", p.name.replace(' ', "_"), date))))
+                    .collect();
+                let mut taken: Vec<PathBuf> = Vec::new();
+                let mut commands = Vec::new();
+                for (person_id, base_name) in persons {
+                    // People with the same name, or an earlier export today, don't overwrite each other
+                    let path = (1..)
+                        .map(|n| match n {
+                            1 => folder.join(format!("{}.ema", base_name)),
+                            n => folder.join(format!("{}_{}.ema", base_name, n)),
+                        })
+                        .find(|path| !path.exists() && !taken.contains(path))
+                        .unwrap_or_else(|| folder.join(format!("{}.ema", base_name)));
+                    taken.push(path.clone());
+                    commands.push(self.enqueue_job(JobKind::ExportArchive { path, persons: vec![person_id], options: self.export_options() }));
+                }
+                self.update_status(format!("Exporting {} {} to {}", taken.len(), if taken.len() == 1 { "archive" } else { "archives" }, folder.display()));
+                Command::batch(commands)
+            }
+            
+            Message::RunExportProfile(name) => {
+                let Some(profile) = self.settings.export_profiles.iter_mut().find(|p| p.name == name) else {
+                    return Command::none();
+                };
+                // The passphrase isn't saved, so it's asked for once after each start
+                if profile.encrypt && profile.passphrase.is_empty() {
+                    self.export_passphrase_prompt = Some((name, String::new()));
+                    return Command::none();
+                }
+                profile.last_run = Some(chrono::Utc::now());
+                self.save_settings();
+                self.enqueue_job(JobKind::ExportProfile { name })
+            }
+            
+            Message::CheckScheduledExports => {
+                // Profiles fall due again once the Admin profile is open
+                if self.role != Some(Role::Admin) {
+                    return Command::none();
+                }
+                let now = chrono::Utc::now();
+                // A profile whose last run is still queued or running isn't queued again
+                let pending: Vec<String> = self.jobs.jobs
+                    .iter()
+                    .filter(|job| !job.status.is_finished())
+                    .filter_map(|job| match &job.kind {
+                        JobKind::ExportProfile { name } => Some(name.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                // An encrypted profile waits, still due, until its passphrase is entered
+                if self.export_passphrase_prompt.is_none() {
+                    self.export_passphrase_prompt = self.settings.export_profiles
+                        .iter()
+                        .find(|profile| profile.is_due(now) && profile.encrypt && profile.passphrase.is_empty() && !pending.contains(&profile.name))
+                        .map(|profile| (profile.name.clone(), String::new()));
+                }
+                let due: Vec<String> = self.settings.export_profiles
+                    .iter_mut()
+                    .filter(|profile| profile.is_due(now) && !pending.contains(&profile.name))
+                    .filter(|profile| !profile.encrypt || !profile.passphrase.is_empty())
+                    .map(|profile| {
+                        profile.last_run = Some(now);
+                        profile.name.clone()
+                    })
+                    .collect();
+                if due.is_empty() {
+                    return Command::none();
+                }
+                self.save_settings();
+                let commands: Vec<Command<Message>> = due
+                    .into_iter()
+                    .map(|name| self.enqueue_job(JobKind::ExportProfile { name }))
+                    .collect();
+                Command::batch(commands)
+            }
+            
+            Message::JobFinished(job_id, result) => {
+                let cancelled = self.running_job.take().is_some_and(|job| job.cancel.is_cancelled());
+                let label = self.jobs.get_mut(job_id).map(|job| job.kind.label()).unwrap_or_default();
+                let status = match result {
+                    Err(_) if cancelled => {
+                        self.update_status(format!("Cancelled: {}", label));
+                        JobStatus::Cancelled
+                    }
+                    Ok(JobOutcome::Imported(import_report)) => {
+                        let summary = import_report.summary();
+                        // Persons already loaded are replaced by the imported record, matched by ID
+                        for imported in import_report.persons {
+                            match self.persons.iter_mut().find(|p| p.id == imported.id) {
+                                Some(existing) => *existing = imported,
+                                None => self.persons.push(imported),
+                            }
+                        }
+                        self.persons.sort_by(|a, b| a.name.cmp(&b.name));
+                        self.update_filtered_persons();
+                        self.refresh_evidence_files();
+                        // Counts and dashboard totals for the imported people
+                        self.refresh_statistics();
+                        
+                        if import_report.warnings.is_empty() {
+                            self.update_status(format!(".ema imported: {}", summary));
+                        } else {
+                            self.update_status(format!(".ema imported with {} warnings (see Jobs): {}", import_report.warnings.len(), summary));
+                        }
+                        if let Some(job) = self.jobs.get_mut(job_id) {
+                            job.summary = Some(summary);
+                            job.warnings = import_report.warnings;
+                        }
+                        JobStatus::Completed
+                    }
+                    Ok(JobOutcome::Exported(warnings)) => {
+                        if warnings.is_empty() {
+                            self.update_status(format!("Finished: {}", label));
+                        } else {
+                            self.update_status(format!("Finished with {} warnings (see Jobs): {}", warnings.len(), label));
+                        }
+                        if let Some(job) = self.jobs.get_mut(job_id) {
+                            job.warnings = warnings;
+                        }
+                        JobStatus::Completed
+                    }
+                    Err(e) => {
+                        self.update_status(format!("{} failed: {}", label, e));
+                        JobStatus::Failed(e)
+                    }
+                };
+                let notification = match &status {
+                    JobStatus::Completed => {
+                        let summary = self.jobs.get_mut(job_id).and_then(|job| job.summary.clone());
+                        Some(("Evidence Manager: job finished", summary.map(|summary| format!("{}: {}", label, summary)).unwrap_or(label)))
+                    }
+                    JobStatus::Failed(e) => Some(("Evidence Manager: job failed", format!("{}: {}", label, e))),
+                    _ => None,
+                };
+                self.jobs.finish(job_id, status);
+                self.save_jobs();
+                let next = self.start_next_job();
+                match notification {
+                    Some((summary, body)) if self.settings.job_notifications && !self.window_focused => {
+                        Command::batch([next, Command::perform(notify_job_finished(summary, body), Message::JobNotificationClosed)])
+                    }
+                    _ => next,
+                }
+            }
+            
+            Message::JobNotificationClosed(result) => {
+                match result {
+                    // Clicking the notification brings the app up on the Jobs panel
+                    Ok(true) => {
+                        let show_jobs = self.update(Message::ShowJobs(true));
+                        Command::batch([
+                            show_jobs,
+                            window::minimize(window::Id::MAIN, false),
+                            window::gain_focus(window::Id::MAIN),
+                        ])
+                    }
+                    Ok(false) => Command::none(),
+                    Err(e) => {
+                        self.update_status(format!("Failed to show notification: {}", e));
+                        Command::none()
+                    }
+                }
+            }
+            
+            Message::CancelJob(job_id) => {
+                if let Some(running) = self.running_job.as_ref().filter(|job| job.id == job_id) {
+                    running.cancel.cancel();
+                    self.update_status("Cancelling…".to_string());
+                } else if self.jobs.jobs.iter().any(|job| job.id == job_id && job.status == JobStatus::Queued) {
+                    self.jobs.finish(job_id, JobStatus::Cancelled);
+                    self.save_jobs();
+                }
+                Command::none()
+            }
+            
+            Message::JobProgressTick => {
+                if let Some(running) = &mut self.running_job {
+                    running.shown_progress = running.progress.get();
+                }
+                for copy in &mut self.file_copies {
+                    let (done, total) = copy.progress.get();
+                    copy.shown_progress = (done as u64, total as u64);
+                }
+                Command::none()
+            }
+            
+            Message::ClearFinishedJobs => {
+                self.jobs.clear_finished();
+                self.save_jobs();
+                Command::none()
+            }
+            
+            Message::ShowJobs(show) => {
+                self.show_jobs = show;
+                self.show_settings = false;
+                self.show_activity = false;
+                self.show_store_check = false;
+                self.show_evidence_browser = false;
+                Command::none()
+            }
+            
+            Message::SearchQueryChanged(query) => {
+                self.search_query = query;
+                self.update_filtered_persons();
+                Command::none()
+            }
+            
+            Message::ShowAddPersonDialog(show) => {
+                self.show_add_person_dialog = show;
+                if !show {
+                    self.new_person_name.clear();
+                    self.new_person_template = None;
+                }
+                Command::none()
+            }
+            
+            Message::ShowImportDialog(show) => {
+                self.show_import_dialog = show;
+                Command::none()
+            }
+            
+            Message::ShowExportDialog(show) => {
+                self.show_export_dialog = show;
+                Command::none()
+            }
+            
+            Message::WatermarkExportsToggled(enabled) => {
+                self.watermark_exports = enabled;
+                Command::none()
+            }
+            
+            Message::MetadataOnlyExportsToggled(enabled) => {
+                self.metadata_only_exports = enabled;
+                Command::none()
+            }
+            
+            Message::ThumbnailsOnlyExportsToggled(enabled) => {
+                self.thumbnails_only_exports = enabled;
+                Command::none()
+            }
+            
+            Message::WatermarkCaseNameChanged(name) => {
+                self.watermark_case_name = name;
+                Command::none()
+            }
+            
+            Message::ShowSettings(show) => {
+                self.show_settings = show;
+                self.show_activity = false;
+                self.show_jobs = false;
+                self.show_store_check = false;
+                self.show_evidence_browser = false;
+                Command::none()
+            }
+            
+            Message::ShowActivity(show) => {
+                self.show_activity = show;
+                self.show_settings = false;
+                self.show_jobs = false;
+                self.show_store_check = false;
+                self.show_evidence_browser = false;
+                if show {
+                    match self.audit_log.load() {
+                        Ok(entries) => {
+                            // Newest first
+                            self.activity = entries.into_iter().rev().take(ACTIVITY_FEED_LENGTH).collect();
+                        }
+                        Err(e) => {
+                            self.activity.clear();
+                            self.update_status(format!("Failed to load activity: {}", e));
+                        }
+                    }
+                }
+                Command::none()
+            }
+            
+            Message::ShowStoreCheck(show) => {
+                self.show_store_check = show;
+                self.show_settings = false;
+                self.show_activity = false;
+                self.show_jobs = false;
+                self.show_evidence_browser = false;
+                // The first time it's opened, check straight away
+                if show && self.store_report.is_none() && !self.store_checking {
+                    return self.update(Message::VerifyStoreClicked);
+                }
+                Command::none()
+            }
+            
+            Message::ShowEvidenceBrowser(show) => {
+                self.show_evidence_browser = show;
+                self.show_settings = false;
+                self.show_activity = false;
+                self.show_jobs = false;
+                self.show_store_check = false;
+                if !show {
+                    return Command::none();
+                }
+                // Read afresh each time it's opened, as files may have been added since
+                self.evidence_browser.loading = true;
+                let file_manager = self.file_manager.clone();
+                let settings = self.settings.clone();
+                let persons = self.persons.clone();
+                Command::perform(
+                    run_blocking(move || EvidenceBrowser::load(&file_manager, &settings, &persons).map_err(|e| e.to_string())),
+                    Message::EvidenceBrowserLoaded
+                )
+            }
+            
+            Message::EvidenceBrowserLoaded(result) => {
+                self.evidence_browser.loading = false;
+                self.evidence_browser.selected.clear();
+                match result {
+                    Ok(rows) => self.evidence_browser.rows = rows,
+                    Err(e) => {
+                        self.evidence_browser.rows.clear();
+                        self.update_status(format!("Failed to read evidence: {}", e));
+                    }
+                }
+                Command::none()
+            }
+            
+            Message::BrowserFilterChanged(filter) => {
+                self.evidence_browser.filter = filter;
+                Command::none()
+            }
+            
+            Message::BrowserCategorySelected(category) => {
+                self.evidence_browser.category = Some(category);
+                Command::none()
+            }
+            
+            Message::BrowserReviewFilterSelected(status) => {
+                self.evidence_browser.review = Some(status);
+                Command::none()
+            }
+            
+            Message::BrowserFiltersCleared => {
+                self.evidence_browser.filter.clear();
+                self.evidence_browser.category = None;
+                self.evidence_browser.review = None;
+                Command::none()
+            }
+            
+            Message::BrowserSortClicked(column) => {
+                self.evidence_browser.sort_by(column);
+                Command::none()
+            }
+            
+            Message::BrowserRowToggled(evidence_id, selected) => {
+                let browser = &mut self.evidence_browser;
+                browser.selected.retain(|id| *id != evidence_id);
+                if selected {
+                    browser.selected.push(evidence_id);
+                }
+                Command::none()
+            }
+            
+            Message::BrowserSelectAll(select) => {
+                self.evidence_browser.selected = if select {
+                    self.evidence_browser.visible().iter().map(|row| row.file.id).collect()
+                } else {
+                    Vec::new()
+                };
+                Command::none()
+            }
+            
+            Message::BrowserReviewStatusChanged(status) => {
+                let selected = std::mem::take(&mut self.evidence_browser.selected);
+                let mut changed: Vec<(Person, EvidenceFile)> = Vec::new();
+                let mut held = 0;
+                for row in self.evidence_browser.rows.iter_mut().filter(|row| selected.contains(&row.file.id)) {
+                    let Some(person) = self.persons.iter().find(|p| p.id == row.person_id) else {
+                        continue;
+                    };
+                    if person.legal_hold {
+                        held += 1;
+                        continue;
+                    }
+                    row.file.review_status = status;
+                    changed.push((person.clone(), row.file.clone()));
+                }
+                // The open person's list shows the change too
+                for file in self.evidence_files.iter_mut().filter(|f| changed.iter().any(|(_, changed)| changed.id == f.id)) {
+                    file.review_status = status;
+                }
+                self.update_filtered_persons();
+                let mut summary = format!("{} files marked as {}", changed.len(), status.label());
+                if held > 0 {
+                    summary.push_str(&format!("; {} left as they were because their person is under legal hold", held));
+                }
+                self.update_status(summary);
+                
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                Command::perform(
+                    run_blocking(move || {
+                        for (person, file) in &changed {
+                            file_manager.update_evidence_metadata(person, file).map_err(|e| e.to_string())?;
+                            let details = format!("{} marked as {}", file.original_name, status.label());
+                            audit_log.record(Some(person), AuditAction::EvidenceUpdated, details).map_err(|e| e.to_string())?;
+                        }
+                        Ok(())
+                    }),
+                    Message::ReviewStatusSaved
+                )
+            }
+            
+            Message::BrowserCopyPaths => {
+                let paths: Vec<String> = self.evidence_browser.selected_rows()
+                    .iter()
+                    .map(|row| row.file.file_path.display().to_string())
+                    .collect();
+                if paths.is_empty() {
+                    return Command::none();
+                }
+                self.update_status(format!("{} paths copied to clipboard", paths.len()));
+                iced::clipboard::write(paths.join("\n"))
+            }
+            
+            Message::BrowserOpenEvidence(person_id, evidence_id) => {
+                let command = self.update(Message::PersonSelected(person_id));
+                if let Some(file) = self.evidence_files.iter().find(|f| f.id == evidence_id) {
+                    self.current_tab = EvidenceTab::for_evidence_type(&file.file_type);
+                    // Ticking the file makes it easy to spot in the list
+                    self.selected_evidence = vec![evidence_id];
+                }
+                command
+            }
+            
+            Message::VerifyStoreClicked => {
+                self.store_checking = true;
+                let file_manager = self.file_manager.clone();
+                Command::perform(
+                    run_blocking(move || store_check::verify_store(&file_manager).map_err(|e| e.to_string())),
+                    Message::StoreVerified
+                )
+            }
+            
+            Message::StoreVerified(result) => {
+                self.store_checking = false;
+                match result {
+                    Ok(report) => {
+                        self.update_status(format!("Store checked: {} {} found", report.issues.len(), if report.issues.len() == 1 { "issue" } else { "issues" }));
+                        self.store_report = Some(report);
+                    }
+                    Err(e) => self.update_status(format!("Failed to check store: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::RepairStoreClicked => {
+                let Some(report) = &self.store_report else {
+                    return Command::none();
+                };
+                self.store_checking = true;
+                let issues = report.issues.clone();
+                let file_manager = self.file_manager.clone();
+                let settings = self.settings.clone();
+                let audit_log = self.audit_log.clone();
+                Command::perform(
+                    run_blocking(move || {
+                        let summary = store_check::repair(&file_manager, &settings, &audit_log, &issues).map_err(|e| e.to_string())?;
+                        // Checked again so the report shows what's left
+                        let report = store_check::verify_store(&file_manager).map_err(|e| e.to_string())?;
+                        Ok((summary, report))
+                    }),
+                    Message::StoreRepaired
+                )
+            }
+            
+            Message::StoreRepaired(result) => {
+                self.store_checking = false;
+                match result {
+                    Ok((summary, report)) => {
+                        let mut status = format!("Repaired {} {}", summary.repaired, if summary.repaired == 1 { "issue" } else { "issues" });
+                        if let Some(failure) = summary.failed.first() {
+                            status.push_str(&format!("; {} failed, e.g. {}", summary.failed.len(), failure));
+                        }
+                        self.update_status(status);
+                        self.store_report = Some(report);
+                        self.refresh_evidence_files();
+                    }
+                    Err(e) => self.update_status(format!("Failed to repair store: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::NewCategoryNameChanged(value) => {
+                self.new_category_name = value;
+                Command::none()
+            }
+            
+            Message::NewCategoryFolderChanged(value) => {
+                self.new_category_folder = value;
+                Command::none()
+            }
+            
+            Message::NewCategoryIconChanged(value) => {
+                self.new_category_icon = value;
+                Command::none()
+            }
+            
+            Message::NewCategoryExtensionsChanged(value) => {
+                self.new_category_extensions = value;
+                Command::none()
+            }
+            
+            Message::AddCategorySubmitted => {
+                let name = self.new_category_name.trim().to_string();
+                if name.is_empty() {
+                    return Command::none();
+                }
+                
+                let folder_source = if self.new_category_folder.trim().is_empty() {
+                    name.to_lowercase().replace(' ', "_")
+                } else {
+                    self.new_category_folder.trim().to_string()
+                };
+                let folder_name = naming::sanitize_file_name(&folder_source);
+                
+                let clashes_with_built_in = EvidenceType::built_in().iter().any(|t| t.folder_name() == folder_name);
+                if clashes_with_built_in || self.settings.category(&folder_name).is_some() || folder_name.starts_with('.') {
+                    self.update_status(format!("A category already uses the folder \"{}\"", folder_name));
+                    return Command::none();
+                }
+                
+                let extensions = self.new_category_extensions
+                    .split(',')
+                    .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+                    .filter(|e| !e.is_empty())
+                    .collect();
+                let icon = if self.new_category_icon.trim().is_empty() {
+                    "📁".to_string()
+                } else {
+                    self.new_category_icon.trim().to_string()
+                };
+                
+                self.settings.custom_categories.push(CustomCategory {
+                    name,
+                    folder_name,
+                    icon,
+                    extensions,
+                });
+                self.new_category_name.clear();
+                self.new_category_folder.clear();
+                self.new_category_icon.clear();
+                self.new_category_extensions.clear();
+                self.save_settings();
+                self.refresh_evidence_files();
+                Command::none()
+            }
+            
+            Message::RemoveCategory(folder_name) => {
+                // Files already stored in the folder stay on disk and fall back to extension-based types
+                self.settings.custom_categories.retain(|c| c.folder_name != folder_name);
+                self.settings.extension_mappings.retain(|_, t| *t != EvidenceType::Custom(folder_name.clone()));
+                if self.current_tab == EvidenceTab::Custom(folder_name) {
+                    self.current_tab = EvidenceTab::Information;
+                }
+                self.save_settings();
+                self.refresh_evidence_files();
+                Command::none()
+            }
+            
+            Message::NewMappingExtensionChanged(value) => {
+                self.new_mapping_extension = value;
+                Command::none()
+            }
+            
+            Message::NewMappingTypeSelected(evidence_type) => {
+                self.new_mapping_type = Some(evidence_type);
+                Command::none()
+            }
+            
+            Message::AddMappingSubmitted => {
+                let extension = self.new_mapping_extension.trim().trim_start_matches('.').to_lowercase();
+                if let Some(evidence_type) = self.new_mapping_type.clone() && !extension.is_empty() {
+                    self.settings.extension_mappings.insert(extension, evidence_type);
+                    self.new_mapping_extension.clear();
+                    self.new_mapping_type = None;
+                    self.save_settings();
+                    self.refresh_evidence_files();
+                }
+                Command::none()
+            }
+            
+            Message::RemoveMapping(extension) => {
+                self.settings.extension_mappings.remove(&extension);
+                self.save_settings();
+                self.refresh_evidence_files();
+                Command::none()
+            }
+            
+            Message::SignExportsToggled(enabled) => {
+                if enabled {
+                    // The key is created now so its fingerprint can be shared before the first export
+                    match SigningKey::load_or_create() {
+                        Ok(key) => self.signing_fingerprint = Some(key.fingerprint()),
+                        Err(e) => {
+                            self.update_status(format!("Failed to set up signing key: {}", e));
+                            return Command::none();
+                        }
+                    }
+                }
+                self.settings.sign_exports = enabled;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::IntakeModeToggled(enabled) => {
+                self.settings.intake_mode = enabled;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::MoveIntoEvidenceToggled(enabled) => {
+                self.settings.move_into_evidence = enabled;
+                self.move_files = enabled;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::MoveFilesToggled(enabled) => {
+                self.move_files = enabled;
+                Command::none()
+            }
+            
+            Message::EvidenceActionsToggled(evidence_id) => {
+                self.evidence_actions = evidence_id;
+                self.evidence_date_draft = evidence_id
+                    .and_then(|id| self.evidence_files.iter().find(|f| f.id == id))
+                    .and_then(|f| f.evidence_date)
+                    .map(|date| date.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                Command::none()
+            }
+            
+            Message::EvidenceDateDraftChanged(value) => {
+                self.evidence_date_draft = value;
+                Command::none()
+            }
+            
+            Message::SaveEvidenceDate(evidence_id) => {
+                if self.refuse_on_hold(self.selected_person) {
+                    return Command::none();
+                }
+                let text = self.evidence_date_draft.trim();
+                // A bare date means midnight local time; an empty field clears the date
+                let evidence_date = if text.is_empty() {
+                    None
+                } else {
+                    let parsed = chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M")
+                        .or_else(|_| NaiveDate::parse_from_str(text, "%Y-%m-%d").map(|d| d.and_time(chrono::NaiveTime::MIN)))
+                        .ok()
+                        .and_then(|d| d.and_local_timezone(Local).earliest());
+                    match parsed {
+                        Some(date) => Some(date.with_timezone(&chrono::Utc)),
+                        None => {
+                            self.update_status("Evidence date must be YYYY-MM-DD or YYYY-MM-DD HH:MM".to_string());
+                            return Command::none();
+                        }
+                    }
+                };
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+                    return Command::none();
+                };
+                let Some(file) = self.evidence_files.iter_mut().find(|f| f.id == evidence_id) else {
+                    return Command::none();
+                };
+                file.evidence_date = evidence_date;
+                let file = file.clone();
+                self.evidence_actions = None;
+                
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                Command::perform(
+                    async move {
+                        file_manager.update_evidence_metadata(&person, &file).map_err(|e| e.to_string())?;
+                        let date = file.evidence_date.map(|d| d.to_rfc3339()).unwrap_or_else(|| "cleared".to_string());
+                        let details = format!("{} evidence date: {}", file.original_name, date);
+                        audit_log.record(Some(&person), AuditAction::EvidenceUpdated, details).map_err(|e| e.to_string())
+                    },
+                    Message::EvidenceDateSaved
+                )
+            }
+            
+            Message::EvidenceDateSaved(result) => {
+                if let Err(e) = result {
+                    self.update_status(format!("Failed to save evidence date: {}", e));
+                    self.refresh_evidence_files();
+                }
+                Command::none()
+            }
+            
+            Message::TimestampEvidence(evidence_id) => {
+                self.evidence_actions = None;
+                if self.settings.tsa_url.trim().is_empty() {
+                    self.update_status("Set a timestamping authority in Settings first".to_string());
+                    return Command::none();
+                }
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+                    return Command::none();
+                };
+                let Some(file) = self.evidence_files.iter().find(|f| f.id == evidence_id).cloned() else {
+                    return Command::none();
+                };
+                self.update_status(format!("Requesting a timestamp for {}...", file.original_name));
+                self.timestamp_evidence(person, vec![file])
+            }
+            
+            Message::EvidenceTimestamped(result) => {
+                match result {
+                    Ok(count) => {
+                        self.update_status(format!("Timestamped {} {}", count, if count == 1 { "file" } else { "files" }));
+                        self.refresh_evidence_files();
+                    }
+                    Err(e) => self.update_status(format!("Failed to timestamp evidence: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::CheckTimestamp(evidence_id) => {
+                self.evidence_actions = None;
+                let Some(file) = self.evidence_files.iter().find(|f| f.id == evidence_id).cloned() else {
+                    return Command::none();
+                };
+                let Some(stamp) = file.timestamp.clone() else {
+                    return Command::none();
+                };
+                let file_manager = self.file_manager.clone();
+                let dates = self.settings.dates();
+                Command::perform(
+                    run_blocking(move || {
+                        let digest = file_manager.evidence_digest(&file.file_path).map_err(|e| e.to_string())?;
+                        let time = timestamp::check_hash(&stamp, digest).map_err(|e| e.to_string())?;
+                        Ok(format!(
+                            "{} matches the hash in its timestamp from {} ({}); the TSA's signature isn't checked here",
+                            file.original_name,
+                            dates.date_time_seconds(time),
+                            stamp.tsa_url,
+                        ))
+                    }),
+                    Message::TimestampChecked
+                )
+            }
+            
+            Message::TimestampChecked(result) => {
+                match result {
+                    Ok(summary) => self.update_status(summary),
+                    Err(e) => self.update_status(format!("Timestamp hash check failed: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::CopyEvidencePath(evidence_id) => {
+                self.evidence_actions = None;
+                match self.evidence_files.iter().find(|f| f.id == evidence_id).map(|f| f.file_path.display().to_string()) {
+                    Some(path) => {
+                        self.update_status("Path copied to clipboard".to_string());
+                        iced::clipboard::write(path)
+                    }
+                    None => Command::none(),
+                }
+            }
+            
+            Message::CopyEvidenceFile(evidence_id) => {
+                self.evidence_actions = None;
+                let Some(path) = self.evidence_files.iter().find(|f| f.id == evidence_id).map(|f| f.file_path.clone()) else {
+                    return Command::none();
+                };
+                Command::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || clipboard::copy_file(&path).map_err(|e| e.to_string()))
+                            .await
+                            .map_err(|e| e.to_string())?
+                    },
+                    Message::EvidenceFileCopied
+                )
+            }
+            
+            Message::EvidenceFileCopied(result) => {
+                match result {
+                    Ok(()) => self.update_status("File copied to clipboard".to_string()),
+                    Err(e) => self.update_status(format!("Failed to copy file: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::OpenEvidence(evidence_id) => {
+                self.evidence_actions = None;
+                if let Some(file) = self.evidence_files.iter().find(|f| f.id == evidence_id)
+                    && let Err(e) = opener::open(&file.file_path)
+                {
+                    self.update_status(format!("Failed to open file: {}", e));
+                }
+                Command::none()
+            }
+            
+            Message::OpenEvidenceFolder(evidence_id) => {
+                self.evidence_actions = None;
+                if let Some(folder) = self.evidence_files.iter().find(|f| f.id == evidence_id).and_then(|f| f.file_path.parent())
+                    && let Err(e) = opener::open(folder)
+                {
+                    self.update_status(format!("Failed to open folder: {}", e));
+                }
+                Command::none()
+            }
+            
+            Message::ExportEvidenceFile(evidence_id) => {
+                // Exported like a one-file selection
+                self.evidence_actions = None;
+                self.selected_evidence = vec![evidence_id];
+                self.update(Message::ExportSelectionClicked)
+            }
+            
+            Message::DeleteEvidence(evidence_id) => {
+                self.evidence_actions = None;
+                if self.refuse_on_hold(self.selected_person) {
+                    return Command::none();
+                }
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+                    return Command::none();
+                };
+                let Some(file) = self.evidence_files.iter().find(|f| f.id == evidence_id).cloned() else {
+                    return Command::none();
+                };
+                self.selected_evidence.retain(|id| *id != evidence_id);
+                
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                Command::perform(
+                    async move {
+                        file_manager.delete_evidence(&person, &file).map_err(|e| e.to_string())?;
+                        audit_log.record(Some(&person), AuditAction::EvidenceDeleted, file.original_name.clone()).map_err(|e| e.to_string())
+                    },
+                    Message::EvidenceDeleted
+                )
+            }
+            
+            Message::EvidenceDeleted(result) => {
+                match result {
+                    Ok(()) => self.update_status("Evidence file deleted".to_string()),
+                    Err(e) => self.update_status(format!("Failed to delete evidence: {}", e)),
+                }
+                self.refresh_evidence_files();
+                self.refresh_statistics();
+                Command::none()
+            }
+            
+            Message::ReplaceEvidenceClicked(evidence_id) => {
+                self.evidence_actions = None;
+                Command::perform(
+                    async {
+                        rfd::FileDialog::new()
+                            .add_filter("All Files", &["*"])
+                            .pick_file()
+                    },
+                    move |path| {
+                        if let Some(path) = path {
+                            Message::EvidenceVersionSelected(evidence_id, path)
+                        } else {
+                            Message::StatusMessage("File selection cancelled".to_string())
+                        }
+                    }
+                )
+            }
+            
+            Message::EvidenceVersionSelected(evidence_id, path) => {
+                // The old version's file is moved, which a legal hold forbids
+                if self.refuse_on_hold(self.selected_person) {
+                    return Command::none();
+                }
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+                    return Command::none();
+                };
+                let Some(current) = self.evidence_files.iter().find(|f| f.id == evidence_id).cloned() else {
+                    return Command::none();
+                };
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                let settings = self.settings.clone();
+                let plugins = self.plugins.clone();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let mut person = person;
+                        let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or_default();
+                        let mut warnings = storage_warnings(&file_manager, &person, size, &settings);
+                        let mut evidence_file = file_manager.replace_with_version(&person, &current, &path).map_err(|e| e.to_string())?;
+                        let details = format!("{} ({}), new version of {}", evidence_file.original_name, evidence_file.file_type.folder_name(), current.original_name);
+                        audit_log.record(Some(&person), AuditAction::EvidenceAdded, details).map_err(|e| e.to_string())?;
+                        warnings.extend(plugins.evidence_added(&file_manager, &audit_log, &mut person, &mut evidence_file));
+                        Ok((evidence_file, warnings))
+                    }),
+                    |result| {
+                        match result {
+                            Ok((evidence_file, warnings)) => Message::FileAddedSuccessfully(evidence_file, warnings),
+                            Err(e) => Message::StatusMessage(format!("Failed to add new version: {}", e)),
+                        }
+                    }
+                )
+            }
+            
+            Message::ShareEvidenceWith(evidence_id, person_id) => {
+                self.evidence_actions = None;
+                let Some(from) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+                    return Command::none();
+                };
+                let Some(to) = self.persons.iter().find(|p| p.id == person_id).cloned() else {
+                    return Command::none();
+                };
+                let Some(file) = self.evidence_files.iter().find(|f| f.id == evidence_id).cloned() else {
+                    return Command::none();
+                };
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let shared = file_manager.share_evidence(&from, &file, &to).map_err(|e| e.to_string())?;
+                        let details = format!("{} ({}), shared from {}", shared.original_name, shared.file_type.folder_name(), from.name);
+                        audit_log.record(Some(&to), AuditAction::EvidenceAdded, details).map_err(|e| e.to_string())?;
+                        Ok(format!("Added {} to {}", shared.original_name, to.name))
+                    }),
+                    Message::EvidenceShared
+                )
+            }
+            
+            Message::EvidenceShared(result) => {
+                match result {
+                    Ok(status) => self.update_status(status),
+                    Err(e) => self.update_status(format!("Failed to share evidence: {}", e)),
+                }
+                self.refresh_evidence_files();
+                self.refresh_statistics();
+                Command::none()
+            }
+            
+            Message::ReviewStatusChanged(evidence_id, status) => {
+                self.set_review_status(&[evidence_id], status)
+            }
+            
+            Message::SelectedReviewStatusChanged(status) => {
+                let selected = std::mem::take(&mut self.selected_evidence);
+                self.set_review_status(&selected, status)
+            }
+            
+            Message::ReviewStatusSaved(result) => {
+                if let Err(e) = result {
+                    self.update_status(format!("Failed to save review status: {}", e));
+                    self.refresh_evidence_files();
+                }
+                Command::none()
+            }
+            
+            Message::ReviewFilterChanged(filter) => {
+                self.review_filter = filter;
+                Command::none()
+            }
+            
+            Message::EvidenceTypeFilterToggled(evidence_type) => {
+                match self.evidence_type_filter.iter().position(|t| *t == evidence_type) {
+                    Some(index) => {
+                        self.evidence_type_filter.remove(index);
+                    }
+                    None => self.evidence_type_filter.push(evidence_type),
+                }
+                Command::none()
+            }
+            
+            Message::EvidenceTypeFilterCleared => {
+                self.evidence_type_filter.clear();
+                Command::none()
+            }
+            
+            Message::EvidenceSortChanged(sort) => {
+                self.evidence_sort = sort;
+                Command::none()
+            }
+            
+            Message::NewTaskDescriptionChanged(value) => {
+                self.new_task_description = value;
+                Command::none()
+            }
+            
+            Message::NewTaskDueChanged(value) => {
+                self.new_task_due = value;
+                Command::none()
+            }
+            
+            Message::AddTaskSubmitted => {
+                let description = self.new_task_description.trim().to_string();
+                if description.is_empty() {
+                    return Command::none();
+                }
+                
+                let due = self.new_task_due.trim();
+                let due_date = if due.is_empty() {
+                    None
+                } else {
+                    match NaiveDate::parse_from_str(due, "%Y-%m-%d") {
+                        Ok(date) => Some(date),
+                        Err(_) => {
+                            self.update_status("Due date must be in YYYY-MM-DD format".to_string());
+                            return Command::none();
+                        }
+                    }
+                };
+                
+                self.new_task_description.clear();
+                self.new_task_due.clear();
+                self.update_selected_person(AuditAction::TaskAdded, description.clone(), |person| person.add_task(description, due_date))
+            }
+            
+            Message::TaskDoneToggled(task_id, done) => {
+                let details = format!("{} marked as {}", self.task_description(task_id), if done { "done" } else { "not done" });
+                self.update_selected_person(AuditAction::TaskUpdated, details, |person| person.set_task_done(task_id, done))
+            }
+            
+            Message::AttachChecklist(template) => {
+                let details = format!("{} ({} questions)", template.name, template.questions.len());
+                self.update_selected_person(AuditAction::ChecklistAdded, details, |person| person.add_checklist(template.name, &template.questions))
+            }
+            
+            Message::ChecklistAnswerChanged(item_id, answer) => {
+                self.checklist_drafts.insert(item_id, answer);
+                Command::none()
+            }
+            
+            Message::ChecklistAnswerSubmitted(item_id) => {
+                let Some(answer) = self.checklist_drafts.remove(&item_id).map(|a| a.trim().to_string()) else {
+                    return Command::none();
+                };
+                let previous = self.persons.iter()
+                    .flat_map(|p| &p.checklists)
+                    .flat_map(|c| &c.items)
+                    .find(|i| i.id == item_id)
+                    .map(|i| i.answer.clone())
+                    .unwrap_or_default();
+                if answer == previous {
+                    return Command::none();
+                }
+                // Answering a new question adds to the record; changing an answer alters it
+                let action = if previous.trim().is_empty() { AuditAction::ChecklistAnswered } else { AuditAction::ChecklistUpdated };
+                let details = format!("{}: {}", self.checklist_question(item_id), answer);
+                self.update_selected_person(action, details, |person| person.set_checklist_answer(item_id, answer))
+            }
+            
+            Message::RemoveChecklist(checklist_id) => {
+                let name = self.persons.iter()
+                    .flat_map(|p| &p.checklists)
+                    .find(|c| c.id == checklist_id)
+                    .map(|c| c.name.clone())
+                    .unwrap_or_default();
+                self.update_selected_person(AuditAction::ChecklistRemoved, name, |person| person.remove_checklist(checklist_id))
+            }
+            
+            Message::RemoveTask(task_id) => {
+                let details = self.task_description(task_id);
+                self.update_selected_person(AuditAction::TaskRemoved, details, |person| person.remove_task(task_id))
+            }
+            
+            Message::CheckDueTasks => {
+                if !self.settings.task_notifications {
+                    return Command::none();
+                }
+                
+                let today = Local::now().date_naive();
+                let mut reminders = Vec::new();
+                for person in &self.persons {
+                    for task in person.tasks.iter().filter(|t| t.is_due(today)) {
+                        if !self.notified_tasks.contains(&task.id) {
+                            self.notified_tasks.push(task.id);
+                            reminders.push(format!("{}: {}", person.name, task.description));
+                        }
+                    }
+                }
+                
+                if reminders.is_empty() {
+                    return Command::none();
+                }
+                
+                Command::perform(
+                    async move {
+                        // Showing a notification blocks on the platform's notification service
+                        tokio::task::spawn_blocking(move || {
+                            for reminder in reminders {
+                                notify_rust::Notification::new()
+                                    .summary("Evidence Manager: task due")
+                                    .body(&reminder)
+                                    .show()
+                                    .map_err(|e| e.to_string())?;
+                            }
+                            Ok::<(), String>(())
+                        })
+                        .await
+                        .map_err(|e| e.to_string())?
+                    },
+                    |result| {
+                        match result {
+                            Ok(()) => Message::StatusMessage("Reminder: tasks are due".to_string()),
+                            Err(e) => Message::StatusMessage(format!("Failed to show notification: {}", e)),
+                        }
+                    }
+                )
+            }
+            
+            Message::CaseStatusChanged(status) => {
+                self.update_selected_person(AuditAction::PersonUpdated, format!("Case status set to {}", status.label()), |person| person.case_status = status)
+            }
+            
+            Message::PriorityChanged(priority) => {
+                self.update_selected_person(AuditAction::PersonUpdated, format!("Priority set to {}", priority.label()), |person| person.priority = priority)
+            }
+            
+            Message::CaseRoleChanged(role) => {
+                self.update_selected_person(AuditAction::PersonUpdated, format!("Role set to {}", role.label()), |person| person.role = role)
+            }
+            
+            Message::PersonActionsToggled(person_id) => {
+                self.person_actions = person_id;
+                self.person_name_draft = person_id
+                    .and_then(|id| self.persons.iter().find(|p| p.id == id))
+                    .map(|p| p.name.clone())
+                    .unwrap_or_default();
+                self.person_tag_draft.clear();
+                Command::none()
+            }
+            
+            Message::PersonNameDraftChanged(value) => {
+                self.person_name_draft = value;
+                Command::none()
+            }
+            
+            Message::RenamePerson(person_id) => {
+                let new_name = self.person_name_draft.trim().to_string();
+                let Some(person) = self.persons.iter().find(|p| p.id == person_id).cloned() else {
+                    return Command::none();
+                };
+                if new_name.is_empty() || new_name == person.name || self.refuse_on_hold(Some(person_id)) {
+                    return Command::none();
+                }
+                self.person_actions = None;
+                
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                Command::perform(
+                    async move {
+                        let renamed = file_manager.rename_person(&person, &new_name).map_err(|e| e.to_string())?;
+                        let details = format!("Renamed from {}", person.name);
+                        audit_log.record(Some(&renamed), AuditAction::PersonUpdated, details).map_err(|e| e.to_string())?;
+                        Ok(renamed)
+                    },
+                    move |result| Message::PersonRenamed(person_id, result)
+                )
+            }
+            
+            Message::PersonRenamed(person_id, result) => {
+                match result {
+                    Ok(renamed) => {
+                        if let Some(person) = self.persons.iter_mut().find(|p| p.id == person_id) {
+                            *person = renamed;
+                        }
+                        self.person_changed(person_id);
+                        // Evidence paths point into the old folder
+                        if self.selected_person == Some(person_id) {
+                            self.refresh_evidence_files();
+                        }
+                        self.update_status("Person renamed".to_string());
+                    }
+                    Err(e) => self.update_status(format!("Failed to rename person: {}", e)),
+                }
+                Command::none()
+            }
+            
+            // For scripts using the ingest command
+            Message::CopyPersonId(person_id) => {
+                self.update_status(format!("Copied ID {}", person_id));
+                iced::clipboard::write(person_id.to_string())
+            }
+            
+            Message::TogglePersonPinned(person_id) => {
+                self.person_actions = None;
+                let Some(pinned) = self.persons.iter().find(|p| p.id == person_id).map(|p| !p.pinned) else {
+                    return Command::none();
+                };
+                let details = if pinned { "Pinned" } else { "Unpinned" }.to_string();
+                self.update_person(person_id, AuditAction::PersonUpdated, details, |person| person.pinned = pinned)
+            }
+            
+            Message::SetColorLabel(person_id, color_label) => {
+                let details = match color_label {
+                    Some(color) => format!("Color label set to {}", color.label()),
+                    None => "Color label removed".to_string(),
+                };
+                self.update_person(person_id, AuditAction::PersonUpdated, details, |person| person.color_label = color_label)
+            }
+            
+            Message::PersonTagDraftChanged(value) => {
+                self.person_tag_draft = value;
+                Command::none()
+            }
+            
+            Message::AddPersonTag(person_id) => {
+                let tag = self.person_tag_draft.trim().to_string();
+                let is_new = self.persons.iter().find(|p| p.id == person_id).is_some_and(|p| !p.tags.contains(&tag));
+                if tag.is_empty() || !is_new {
+                    return Command::none();
+                }
+                self.person_tag_draft.clear();
+                let details = format!("Tag added: {}", tag);
+                self.update_person(person_id, AuditAction::PersonUpdated, details, |person| person.tags.push(tag))
+            }
+            
+            Message::RemovePersonTag(person_id, tag) => {
+                if self.refuse_on_hold(Some(person_id)) {
+                    return Command::none();
+                }
+                let details = format!("Tag removed: {}", tag);
+                self.update_person(person_id, AuditAction::PersonUpdated, details, |person| person.tags.retain(|t| *t != tag))
+            }
+            
+            Message::PlaceLegalHold(person_id) => {
+                self.person_actions = None;
+                self.update_person(person_id, AuditAction::LegalHoldPlaced, String::new(), |person| person.legal_hold = true)
+            }
+            
+            Message::ReleaseLegalHoldClicked(person_id) => {
+                self.person_actions = None;
+                // The confirmation is shown above the person's evidence
+                let command = match person_id {
+                    Some(id) if self.selected_person != Some(id) => self.update(Message::PersonSelected(id)),
+                    _ => Command::none(),
+                };
+                self.confirm_hold_release = person_id;
+                command
+            }
+            
+            Message::ReleaseLegalHoldConfirmed(person_id) => {
+                self.confirm_hold_release = None;
+                self.update_person(person_id, AuditAction::LegalHoldReleased, String::new(), |person| person.legal_hold = false)
+            }
+            
+            Message::OpenPersonFolder(person_id) => {
+                self.person_actions = None;
+                if let Some(person) = self.persons.iter().find(|p| p.id == person_id) {
+                    let folder = self.file_manager.get_evidence_dir().join(person.folder_name());
+                    if let Err(e) = opener::open(&folder) {
+                        self.update_status(format!("Failed to open folder: {}", e));
+                    }
+                }
+                Command::none()
+            }
+            
+            Message::RebuildIndex(person_id) => {
+                self.person_actions = None;
+                let Some(person) = self.persons.iter().find(|p| p.id == person_id).cloned() else {
+                    return Command::none();
+                };
+                let file_manager = self.file_manager.clone();
+                let settings = self.settings.clone();
+                let audit_log = self.audit_log.clone();
+                Command::perform(
+                    run_blocking(move || {
+                        let summary = file_manager.rebuild_evidence_index(&person, &settings).map_err(|e| e.to_string())?;
+                        let description = summary.describe();
+                        audit_log.record(Some(&person), AuditAction::EvidenceUpdated, format!("Evidence index rebuilt: {}", description))
+                            .map_err(|e| e.to_string())?;
+                        Ok(description)
+                    }),
+                    move |result| Message::IndexRebuilt(person_id, result)
+                )
+            }
+            
+            Message::IndexRebuilt(person_id, result) => {
+                match result {
+                    Ok(description) => {
+                        self.update_status(format!("Evidence index rebuilt: {}", description));
+                        if self.selected_person == Some(person_id) {
+                            self.refresh_evidence_files();
+                        }
+                    }
+                    Err(e) => self.update_status(format!("Failed to rebuild evidence index: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::WindowResized(width, height) => {
+                // Minimizing reports a zero size on some platforms; quick capture's size isn't kept
+                if width > 0 && height > 0 && !self.quick_capture {
+                    self.settings.layout.window_width = width as f32;
+                    self.settings.layout.window_height = height as f32;
+                }
+                Command::none()
+            }
+            
+            Message::WindowMoved(x, y) => {
+                // Minimized windows on Windows are parked at -32000
+                if x > -32000 && y > -32000 {
+                    self.settings.layout.window_position = Some((x, y));
+                }
+                Command::none()
+            }
+            
+            Message::WindowFocused(focused) => {
+                self.window_focused = focused;
+                Command::none()
+            }
+            
+            Message::WindowCloseRequested(id) => {
+                // Finished so what was recorded is left playable in the temp folder
+                if let Some((_, recording)) = self.recording.take() {
+                    let _ = recording.stop();
+                }
+                if self.drafts_changed {
+                    self.save_drafts();
+                }
+                self.settings.layout.selected_person = self.selected_person;
+                self.settings.layout.selected_tab = Some(self.current_tab.clone());
+                self.save_settings();
+                if let Err(e) = Session::clear() {
+                    eprintln!("Failed to remove session: {}", e);
+                }
+                window::close(id)
+            }
+            
+            Message::QuickCaptureOpened => {
+                self.quick_capture = true;
+                Command::batch([
+                    window::minimize(window::Id::MAIN, false),
+                    window::resize(window::Id::MAIN, quick_capture::WINDOW_SIZE),
+                    window::change_level(window::Id::MAIN, window::Level::AlwaysOnTop),
+                    window::gain_focus(window::Id::MAIN),
+                ])
+            }
+            
+            Message::QuickCaptureClosed => {
+                self.quick_capture = false;
+                let size = iced::Size::new(self.settings.layout.window_width, self.settings.layout.window_height);
+                Command::batch([
+                    window::change_level(window::Id::MAIN, window::Level::Normal),
+                    window::resize(window::Id::MAIN, size),
+                ])
+            }
+            
+            // Back to the taskbar at full size, until the shortcut brings quick capture up again
+            Message::QuickCaptureHidden => {
+                let restore = self.update(Message::QuickCaptureClosed);
+                Command::batch([restore, window::minimize(window::Id::MAIN, true)])
+            }
+            
+            Message::QuickCaptureTextChanged(value) => {
+                self.quick_capture_text = value;
+                Command::none()
+            }
+            
+            Message::QuickCaptureQuoteSubmitted => {
+                let Some(person) = self.persons.iter().find(|p| Some(p.id) == self.selected_person).cloned() else {
+                    self.update_status("Pick a person first".to_string());
+                    return Command::none();
+                };
+                let quote_text = self.quick_capture_text.trim().to_string();
+                if quote_text.is_empty() {
+                    return Command::none();
+                }
+                self.quick_capture_text.clear();
+                let now = Local::now();
+                let quote_date = now.format("%Y-%m-%d").to_string();
+                let quote_time = now.format("%H:%M").to_string();
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                
+                Command::perform(
+                    async move {
+                        let mut person = person;
+                        let details = format!("\"{}\" ({}, quick capture)", quote_text, quote_date);
+                        person.add_quote(quote_text, quote_date, Some(quote_time), None, None, Vec::new()).utc_offset = Some(UtcOffset::system());
+                        file_manager.save_person_data(&person).map_err(|e| e.to_string())?;
+                        audit_log.record(Some(&person), AuditAction::QuoteAdded, details).map_err(|e| e.to_string())
+                    },
+                    Message::QuoteAdded
+                )
+            }
+            
+            Message::QuickCaptureHotkeyChanged(value) => {
+                self.settings.quick_capture_hotkey = value;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::CheckQuickCaptureHotkey => {
+                if let Some(id) = self.quick_capture_hotkey
+                    && quick_capture::hotkey_pressed(id)
+                {
+                    return self.update(Message::QuickCaptureOpened);
+                }
+                Command::none()
+            }
+            
+            Message::SidebarDragStarted => {
+                self.sidebar_dragging = true;
+                Command::none()
+            }
+            
+            Message::SidebarDragged(x) => {
+                // The sidebar starts at the window's 10px padding
+                self.settings.layout.sidebar_width = (x - 10.0).clamp(SIDEBAR_MIN_WIDTH, SIDEBAR_MAX_WIDTH);
+                Command::none()
+            }
+            
+            Message::SidebarDragEnded => {
+                self.sidebar_dragging = false;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::PersonSaved(result) => {
+                if let Err(e) = result {
+                    self.update_status(format!("Failed to save person: {}", e));
+                }
+                Command::none()
+            }
+            
+            Message::CaseStatusFilterChanged(filter) => {
+                self.case_status_filter = filter;
+                self.update_filtered_persons();
+                Command::none()
+            }
+            
+            Message::ColorLabelFilterChanged(filter) => {
+                self.color_label_filter = filter;
+                self.update_filtered_persons();
+                Command::none()
+            }
+            
+            Message::SortByPriorityToggled(enabled) => {
+                self.sort_by_priority = enabled;
+                self.update_filtered_persons();
+                Command::none()
+            }
+            
+            Message::PersonGroupingChanged(grouping) => {
+                self.settings.layout.person_grouping = grouping;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::PersonGroupToggled(key) => {
+                let collapsed = &mut self.settings.layout.collapsed_groups;
+                if let Some(index) = collapsed.iter().position(|k| *k == key) {
+                    collapsed.remove(index);
+                } else {
+                    collapsed.push(key);
+                }
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::JumpToLetter(letter) => {
+                // A collapsed section is opened, so there's someone to see once there
+                let key = PersonGrouping::Letter.group_key(&letter.to_string());
+                if let Some(index) = self.settings.layout.collapsed_groups.iter().position(|k| *k == key) {
+                    self.settings.layout.collapsed_groups.remove(index);
+                    self.save_settings();
+                }
+                match gui::letter_offset(self, letter) {
+                    Some(offset) => scrollable::scroll_to(
+                        scrollable::Id::new(gui::PERSON_LIST_SCROLL),
+                        scrollable::AbsoluteOffset { x: 0.0, y: offset },
+                    ),
+                    None => Command::none(),
+                }
+            }
+            
+            Message::InfoGroupingToggled(grouped) => {
+                self.settings.layout.group_information = grouped;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::InfoTypeGroupToggled(info_type) => {
+                let collapsed = &mut self.settings.layout.collapsed_info_types;
+                if let Some(index) = collapsed.iter().position(|t| *t == info_type) {
+                    collapsed.remove(index);
+                } else {
+                    collapsed.push(info_type);
+                }
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::ShowDashboard => {
+                self.selected_person = None;
+                self.show_settings = false;
+                self.show_activity = false;
+                self.show_jobs = false;
+                self.show_store_check = false;
+                self.show_evidence_browser = false;
+                self.evidence_files.clear();
+                self.selected_evidence.clear();
+                self.evidence_actions = None;
+                self.refresh_statistics();
+                Command::none()
+            }
+            
+            Message::TaskNotificationsToggled(enabled) => {
+                self.settings.task_notifications = enabled;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::JobNotificationsToggled(enabled) => {
+                self.settings.job_notifications = enabled;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::UiScaleChanged(percent) => {
+                self.settings.ui_scale = percent;
+                self.save_settings();
+                self.update_status(format!("Interface scale {}%", self.settings.ui_scale()));
+                Command::none()
+            }
+            
+            Message::DateFormatSelected(format) => {
+                self.settings.date_format = format;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::TimeFormatSelected(format) => {
+                self.settings.time_format = format;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::DisplayZoneSelected(zone) => {
+                self.settings.display_zone = zone;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::ZoomIn => {
+                let percent = self.settings.next_ui_scale(true);
+                self.update(Message::UiScaleChanged(percent))
+            }
+            
+            Message::ZoomOut => {
+                let percent = self.settings.next_ui_scale(false);
+                self.update(Message::UiScaleChanged(percent))
+            }
+            
+            Message::AddPersonTemplateSelected(template) => {
+                self.new_person_template = Some(template);
+                Command::none()
+            }
+            
+            Message::NewCustomFieldNameChanged(value) => {
+                self.new_custom_field_name = value;
+                Command::none()
+            }
+            
+            Message::NewCustomFieldTypeSelected(field_type) => {
+                self.new_custom_field_type = field_type;
+                Command::none()
+            }
+            
+            Message::AddCustomFieldSubmitted => {
+                let name = self.new_custom_field_name.trim().to_string();
+                if name.is_empty() {
+                    return Command::none();
+                }
+                if self.settings.custom_fields.iter().any(|f| f.name.eq_ignore_ascii_case(&name)) {
+                    self.update_status(format!("A custom field named \"{}\" already exists", name));
+                    return Command::none();
+                }
+                
+                self.settings.custom_fields.push(CustomField {
+                    name,
+                    field_type: self.new_custom_field_type,
+                });
+                self.new_custom_field_name.clear();
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::NewExportProfileNameChanged(name) => {
+                self.new_export_profile.name = name;
+                Command::none()
+            }
+            
+            Message::ChooseExportProfileDestination => {
+                Command::perform(
+                    async { rfd::FileDialog::new().pick_folder() },
+                    |folder| match folder {
+                        Some(folder) => Message::ExportProfileDestinationChosen(folder),
+                        None => Message::StatusMessage("No folder chosen".to_string()),
+                    }
+                )
+            }
+            
+            Message::ExportProfileDestinationChosen(folder) => {
+                self.new_export_profile.destination = folder;
+                Command::none()
+            }
+            
+            Message::NewExportProfilePersonAdded(person_id) => {
+                if !self.new_export_profile.persons.contains(&person_id) {
+                    self.new_export_profile.persons.push(person_id);
+                }
+                Command::none()
+            }
+            
+            Message::NewExportProfilePersonRemoved(person_id) => {
+                self.new_export_profile.persons.retain(|id| *id != person_id);
+                Command::none()
+            }
+            
+            Message::NewExportProfileTagsChanged(tags) => {
+                self.new_export_profile_tags = tags;
+                Command::none()
+            }
+            
+            Message::NewExportProfileEncryptToggled(encrypt) => {
+                self.new_export_profile.encrypt = encrypt;
+                Command::none()
+            }
+            
+            Message::NewExportProfileThumbnailsToggled(thumbnails_only) => {
+                self.new_export_profile.thumbnails_only = thumbnails_only;
+                Command::none()
+            }
+            
+            Message::NewExportProfilePassphraseChanged(passphrase) => {
+                self.new_export_profile.passphrase = passphrase;
+                Command::none()
+            }
+            
+            Message::NewExportProfileSplitChanged(value) => {
+                self.new_export_profile_split = value;
+                Command::none()
+            }
+            
+            Message::NewExportProfileScheduleSelected(schedule) => {
+                self.new_export_profile.schedule = schedule;
+                Command::none()
+            }
+            
+            Message::AddExportProfileSubmitted => {
+                let mut profile = self.new_export_profile.clone();
+                profile.name = profile.name.trim().to_string();
+                let problem = if profile.name.is_empty() {
+                    Some("Give the export profile a name".to_string())
+                } else if self.settings.export_profiles.iter().any(|p| p.name.eq_ignore_ascii_case(&profile.name)) {
+                    Some(format!("An export profile named \"{}\" already exists", profile.name))
+                } else if profile.destination.as_os_str().is_empty() {
+                    Some("Choose a folder for the export profile".to_string())
+                } else if profile.encrypt && profile.passphrase.chars().count() < 8 {
+                    Some("Use a passphrase of at least 8 characters for encrypted exports".to_string())
+                } else {
+                    None
+                };
+                if let Some(problem) = problem {
+                    self.update_status(problem);
+                    return Command::none();
+                }
+                let Some(split_size_mb) = parse_limit(&self.new_export_profile_split) else {
+                    self.update_status("Split size must be a whole number of MB".to_string());
+                    return Command::none();
+                };
+                profile.split_size_mb = split_size_mb;
+                profile.tags = self.new_export_profile_tags
+                    .split(',')
+                    .map(|tag| tag.trim().to_string())
+                    .filter(|tag| !tag.is_empty())
+                    .collect();
+                if profile.encrypt {
+                    match Passphrase::new(&profile.passphrase) {
+                        Ok(check) => profile.passphrase_check = Some(check),
+                        Err(e) => {
+                            self.update_status(format!("Failed to set passphrase: {}", e));
+                            return Command::none();
+                        }
+                    }
+                } else {
+                    profile.passphrase.clear();
+                }
+                self.settings.export_profiles.push(profile);
+                self.new_export_profile = ExportProfile::default();
+                self.new_export_profile_tags.clear();
+                self.new_export_profile_split.clear();
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::RemoveExportProfile(name) => {
+                self.settings.export_profiles.retain(|p| p.name != name);
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::ExportProfilePassphraseChanged(value) => {
+                if let Some((_, passphrase)) = &mut self.export_passphrase_prompt {
+                    *passphrase = value;
+                }
+                Command::none()
+            }
+            
+            Message::ExportProfilePassphraseSubmitted => {
+                let Some((name, passphrase)) = self.export_passphrase_prompt.take() else {
+                    return Command::none();
+                };
+                let Some(profile) = self.settings.export_profiles.iter_mut().find(|p| p.name == name) else {
+                    return Command::none();
+                };
+                // A mistyped passphrase would lock the archive away for good
+                if profile.passphrase_check.as_ref().is_some_and(|check| !check.matches(&passphrase)) {
+                    self.export_passphrase_prompt = Some((name, String::new()));
+                    self.update_status("Wrong passphrase".to_string());
+                    return Command::none();
+                }
+                profile.passphrase = passphrase;
+                profile.last_run = Some(chrono::Utc::now());
+                self.save_settings();
+                self.enqueue_job(JobKind::ExportProfile { name })
+            }
+            
+            Message::ExportProfilePassphraseCancelled => {
+                self.export_passphrase_prompt = None;
+                Command::none()
+            }
+            
+            Message::RemoveCustomField(name) => {
+                // Values already recorded on persons are kept in case the field is added back
+                self.settings.custom_fields.retain(|f| f.name != name);
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::CustomFieldDraftChanged(name, value) => {
+                self.custom_field_drafts.insert(name, value);
+                Command::none()
+            }
+            
+            Message::CustomFieldSubmitted(name) => {
+                let Some(field) = self.settings.custom_fields.iter().find(|f| f.name == name).cloned() else {
+                    return Command::none();
+                };
+                let Some(text) = self.custom_field_drafts.get(&name).cloned() else {
+                    return Command::none();
+                };
+                
+                // An empty editor clears the field
+                let value = if text.trim().is_empty() {
+                    None
+                } else {
+                    match FieldValue::parse(field.field_type, &text) {
+                        Ok(value) => Some(value),
+                        Err(e) => {
+                            self.update_status(format!("{}: {}", field.name, e));
+                            return Command::none();
+                        }
+                    }
+                };
+                self.custom_field_drafts.remove(&name);
+                
+                let details = format!("{}: {}", name, value.as_ref().map(|v| v.to_string()).unwrap_or_else(|| "cleared".to_string()));
+                self.update_selected_person(AuditAction::CustomFieldUpdated, details, |person| {
+                    match value {
+                        Some(value) => person.custom_fields.insert(name, value),
+                        None => person.custom_fields.remove(&name),
+                    };
+                })
+            }
+            
+            Message::CustomFieldToggled(name, value) => {
+                let details = format!("{}: {}", name, FieldValue::Boolean(value));
+                self.update_selected_person(AuditAction::CustomFieldUpdated, details, |person| {
+                    person.custom_fields.insert(name, FieldValue::Boolean(value));
+                })
+            }
+            
+            Message::OpenUrl(url) => {
+                if let Err(e) = opener::open(&url) {
+                    self.update_status(format!("Failed to open {}: {}", url, e));
+                }
+                Command::none()
+            }
+            
+            Message::DefaultCountryCodeChanged(value) => {
+                self.settings.default_country_code = value.chars().filter(char::is_ascii_digit).collect();
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::NormalizeAllInformation => {
+                let mut changed_persons = Vec::new();
+                let mut count = 0;
+                // People under legal hold keep their values as entered
+                for person in self.persons.iter_mut().filter(|p| !p.legal_hold) {
+                    let changed = person.normalize_information(&self.settings.default_country_code);
+                    if changed > 0 {
+                        count += changed;
+                        person.update_timestamp();
+                        changed_persons.push((person.clone(), changed));
+                    }
+                }
+                self.update_filtered_persons();
+                
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                Command::perform(
+                    async move {
+                        for (person, changed) in changed_persons {
+                            file_manager.save_person_data(&person).map_err(|e| e.to_string())?;
+                            let details = format!("Normalized {} phone numbers and emails", changed);
+                            audit_log.record(Some(&person), AuditAction::InformationUpdated, details).map_err(|e| e.to_string())?;
+                        }
+                        Ok(count)
+                    },
+                    Message::InformationNormalized
+                )
+            }
+            
+            Message::InformationNormalized(result) => {
+                match result {
+                    Ok(count) => self.update_status(format!("Normalized {} information entries", count)),
+                    Err(e) => self.update_status(format!("Failed to normalize information: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::CopyText(value) => {
+                self.update_status("Copied to clipboard".to_string());
+                iced::clipboard::write(value)
+            }
+            
+            Message::ExportCsvClicked => {
+                Command::perform(
+                    async {
+                        rfd::FileDialog::new()
+                            .add_filter("CSV", &["csv"])
+                            .set_file_name("people.csv")
+                            .save_file()
+                    },
+                    |path| {
+                        if let Some(path) = path {
+                            Message::ExportCsvFileSelected(path)
+                        } else {
+                            Message::ShowExportDialog(false)
+                        }
+                    }
+                )
+            }
+            
+            Message::ExportCsvFileSelected(path) => {
+                let export_import_manager = self.export_import_manager.clone();
+                let persons = self.persons.clone();
+                let custom_fields = self.settings.custom_fields.clone();
+                let audit_log = self.audit_log.clone();
+                
+                Command::perform(
+                    async move {
+                        export_import_manager.export_persons_csv(&path, &persons, &custom_fields).map_err(|e| e.to_string())?;
+                        audit_log.record(None, AuditAction::Exported, path.display().to_string()).map_err(|e| e.to_string())
+                    },
+                    Message::CsvExported
+                )
+            }
+            
+            Message::CsvExported(result) => {
+                match result {
+                    Ok(()) => self.update_status("CSV successfully exported".to_string()),
+                    Err(e) => self.update_status(format!("Failed to export CSV: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::ExportHashManifestClicked(person_id) => {
+                self.person_actions = None;
+                let file_name = match person_id.and_then(|id| self.persons.iter().find(|p| p.id == id)) {
+                    Some(person) => format!("{}_hashes.csv", person.name.replace(" ", "_")),
+                    None => "hashes.csv".to_string(),
+                };
+                Command::perform(
+                    async move {
+                        rfd::FileDialog::new()
+                            .add_filter("CSV", &["csv"])
+                            .set_file_name(file_name)
+                            .save_file()
+                    },
+                    move |path| {
+                        if let Some(path) = path {
+                            Message::ExportHashManifestFileSelected(person_id, path)
+                        } else {
+                            Message::StatusMessage("Export cancelled".to_string())
+                        }
+                    }
+                )
+            }
+            
+            Message::ExportHashManifestFileSelected(person_id, path) => {
+                let persons: Vec<Person> = match person_id {
+                    Some(id) => self.persons.iter().filter(|p| p.id == id).cloned().collect(),
+                    None => self.persons.iter().filter(|p| self.filtered_persons.contains(&p.id)).cloned().collect(),
+                };
+                let file_manager = self.file_manager.clone();
+                let export_import_manager = self.export_import_manager.clone();
+                let settings = self.settings.clone();
+                let audit_log = self.audit_log.clone();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let evidence = persons.into_iter()
+                            .map(|person| {
+                                let files = file_manager.scan_person_evidence(&person, &settings)?;
+                                Ok((person, files))
+                            })
+                            .collect::<Result<Vec<_>>>()
+                            .map_err(|e| e.to_string())?;
+                        let count = export_import_manager.export_hash_manifest(&path, &evidence).map_err(|e| e.to_string())?;
+                        let person = match evidence.as_slice() {
+                            [(person, _)] => Some(person),
+                            _ => None,
+                        };
+                        let details = format!("Hash manifest of {} files to {}", count, path.display());
+                        audit_log.record(person, AuditAction::Exported, details).map_err(|e| e.to_string())?;
+                        Ok(count)
+                    }),
+                    Message::HashManifestExported
+                )
+            }
+            
+            Message::HashManifestExported(result) => {
+                match result {
+                    Ok(count) => self.update_status(format!("Hash manifest of {} {} exported", count, if count == 1 { "file" } else { "files" })),
+                    Err(e) => self.update_status(format!("Failed to export hash manifest: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::ExportCustodyLogClicked(person_id) => {
+                self.person_actions = None;
+                let file_name = match person_id.and_then(|id| self.persons.iter().find(|p| p.id == id)) {
+                    Some(person) => format!("{}_custody_log.pdf", person.name.replace(" ", "_")),
+                    None => "custody_log.pdf".to_string(),
+                };
+                Command::perform(
+                    async move {
+                        rfd::FileDialog::new()
+                            .add_filter("PDF", &["pdf"])
+                            .add_filter("CSV", &["csv"])
+                            .set_file_name(file_name)
+                            .save_file()
+                    },
+                    move |path| {
+                        if let Some(path) = path {
+                            Message::ExportCustodyLogFileSelected(person_id, path)
+                        } else {
+                            Message::StatusMessage("Export cancelled".to_string())
+                        }
+                    }
+                )
+            }
+            
+            Message::ExportCustodyLogFileSelected(person_id, path) => {
+                let persons: Vec<Person> = match person_id {
+                    Some(id) => self.persons.iter().filter(|p| p.id == id).cloned().collect(),
+                    None => self.persons.iter().filter(|p| self.filtered_persons.contains(&p.id)).cloned().collect(),
+                };
+                let audit_log = self.audit_log.clone();
+                let dates = self.settings.dates();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let log = audit_log.load().map_err(|e| e.to_string())?;
+                        let custody_log = CustodyLog::build(log, &persons);
+                        let content = custody_log.write(CustodyLogFormat::from_path(&path), dates).map_err(|e| e.to_string())?;
+                        std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+                        let person = match persons.as_slice() {
+                            [person] => Some(person),
+                            _ => None,
+                        };
+                        let details = format!("Custody log of {} entries to {}", custody_log.entries.len(), path.display());
+                        audit_log.record(person, AuditAction::Exported, details).map_err(|e| e.to_string())?;
+                        Ok((custody_log.entries.len(), custody_log.chain_intact))
+                    }),
+                    Message::CustodyLogExported
+                )
+            }
+            
+            Message::CustodyLogExported(result) => {
+                match result {
+                    Ok((count, true)) => self.update_status(format!("Custody log of {} entries exported; hash chain verified", count)),
+                    Ok((count, false)) => self.update_status(format!("Custody log of {} entries exported, but the audit log's hash chain is BROKEN", count)),
+                    Err(e) => self.update_status(format!("Failed to export custody log: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::ExportInventoryClicked(person_id) => {
+                self.person_actions = None;
+                let file_name = match person_id.and_then(|id| self.persons.iter().find(|p| p.id == id)) {
+                    Some(person) => format!("{}_inventory.json", person.name.replace(" ", "_")),
+                    None => "inventory.json".to_string(),
+                };
+                Command::perform(
+                    async move {
+                        rfd::FileDialog::new()
+                            .add_filter("JSON", &["json"])
+                            .set_file_name(file_name)
+                            .save_file()
+                    },
+                    move |path| {
+                        if let Some(path) = path {
+                            Message::ExportInventoryFileSelected(person_id, path)
+                        } else {
+                            Message::StatusMessage("Export cancelled".to_string())
+                        }
+                    }
+                )
+            }
+            
+            Message::ExportInventoryFileSelected(person_id, path) => {
+                let persons: Vec<Person> = match person_id {
+                    Some(id) => self.persons.iter().filter(|p| p.id == id).cloned().collect(),
+                    None => self.persons.iter().filter(|p| self.filtered_persons.contains(&p.id)).cloned().collect(),
+                };
+                let file_manager = self.file_manager.clone();
+                let settings = self.settings.clone();
+                let audit_log = self.audit_log.clone();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let evidence = persons.into_iter()
+                            .map(|person| {
+                                let files = file_manager.scan_person_evidence(&person, &settings)?;
+                                Ok((person, files))
+                            })
+                            .collect::<Result<Vec<_>>>()
+                            .map_err(|e| e.to_string())?;
+                        let inventory = Inventory::build(&file_manager, &settings, &evidence).map_err(|e| e.to_string())?;
+                        let json = inventory.to_json().map_err(|e| e.to_string())?;
+                        std::fs::write(&path, &json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+                        if settings.sign_exports {
+                            let key = SigningKey::load_or_create().map_err(|e| e.to_string())?;
+                            signing::sign_detached(&key, &path, json.as_bytes()).map_err(|e| e.to_string())?;
+                        }
+                        let person = match evidence.as_slice() {
+                            [(person, _)] => Some(person),
+                            _ => None,
+                        };
+                        let count = inventory.file_count();
+                        let details = format!("Inventory of {} files to {}", count, path.display());
+                        audit_log.record(person, AuditAction::Exported, details).map_err(|e| e.to_string())?;
+                        Ok((count, settings.sign_exports))
+                    }),
+                    Message::InventoryExported
+                )
+            }
+            
+            Message::InventoryExported(result) => {
+                match result {
+                    Ok((count, signed)) => self.update_status(format!(
+                        "Inventory of {} {} exported{}",
+                        count,
+                        if count == 1 { "file" } else { "files" },
+                        if signed { " and signed" } else { "" },
+                    )),
+                    Err(e) => self.update_status(format!("Failed to export inventory: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::ExportTimelineClicked(person_id) => {
+                self.person_actions = None;
+                let file_name = match person_id.and_then(|id| self.persons.iter().find(|p| p.id == id)) {
+                    Some(person) => format!("{}_timeline.html", person.name.replace(" ", "_")),
+                    None => "timeline.html".to_string(),
+                };
+                Command::perform(
+                    async move {
+                        rfd::FileDialog::new()
+                            .add_filter("HTML", &["html"])
+                            .set_file_name(file_name)
+                            .save_file()
+                    },
+                    move |path| {
+                        if let Some(path) = path {
+                            Message::ExportTimelineFileSelected(person_id, path)
+                        } else {
+                            Message::StatusMessage("Export cancelled".to_string())
+                        }
+                    }
+                )
+            }
+            
+            Message::ExportTimelineFileSelected(person_id, path) => {
+                let persons: Vec<Person> = match person_id {
+                    Some(id) => self.persons.iter().filter(|p| p.id == id).cloned().collect(),
+                    None => self.persons.iter().filter(|p| self.filtered_persons.contains(&p.id)).cloned().collect(),
+                };
+                let title = match persons.as_slice() {
+                    [person] => format!("Timeline: {}", person.name),
+                    _ => format!("Timeline: {} people", persons.len()),
+                };
+                let file_manager = self.file_manager.clone();
+                let settings = self.settings.clone();
+                let audit_log = self.audit_log.clone();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let mut timeline = Timeline::default();
+                        for person in &persons {
+                            let files = file_manager.scan_person_evidence(person, &settings).map_err(|e| e.to_string())?;
+                            timeline.add_person(person, &files, &settings);
+                        }
+                        let html = timeline.to_html(&title, settings.dates()).map_err(|e| e.to_string())?;
+                        std::fs::write(&path, html).map_err(|e| e.to_string())?;
+                        let person = match persons.as_slice() {
+                            [person] => Some(person),
+                            _ => None,
+                        };
+                        let details = format!("Timeline of {} entries to {}", timeline.events.len(), path.display());
+                        audit_log.record(person, AuditAction::Exported, details).map_err(|e| e.to_string())?;
+                        Ok(timeline.events.len())
+                    }),
+                    Message::TimelineExported
+                )
+            }
+            
+            Message::TimelineExported(result) => {
+                match result {
+                    Ok(count) => self.update_status(format!("Timeline of {} {} exported", count, if count == 1 { "entry" } else { "entries" })),
+                    Err(e) => self.update_status(format!("Failed to export timeline: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::ExportGraphClicked => {
+                Command::perform(
+                    async {
+                        rfd::FileDialog::new()
+                            .add_filter("GraphML", &["graphml"])
+                            .add_filter("Graphviz DOT", &["dot", "gv"])
+                            .set_file_name("relationships.graphml")
+                            .save_file()
+                    },
+                    |path| {
+                        if let Some(path) = path {
+                            Message::ExportGraphFileSelected(path)
+                        } else {
+                            Message::StatusMessage("Export cancelled".to_string())
+                        }
+                    }
+                )
+            }
+            
+            Message::ExportGraphFileSelected(path) => {
+                let persons: Vec<Person> = self.persons.iter().filter(|p| self.filtered_persons.contains(&p.id)).cloned().collect();
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let boards = persons
+                            .iter()
+                            .map(|person| file_manager.load_board(person))
+                            .collect::<Result<Vec<_>>>()
+                            .map_err(|e| e.to_string())?;
+                        let graph = reports::RelationshipGraph::build(&persons, &boards);
+                        std::fs::write(&path, graph.write(reports::GraphFormat::from_path(&path))).map_err(|e| e.to_string())?;
+                        let details = format!("Relationship graph of {} people to {}", persons.len(), path.display());
+                        audit_log.record(None, AuditAction::Exported, details).map_err(|e| e.to_string())?;
+                        Ok((persons.len(), graph.edges.len()))
+                    }),
+                    Message::GraphExported
+                )
+            }
+            
+            Message::GraphExported(result) => {
+                match result {
+                    Ok((persons, links)) => self.update_status(format!("Relationship graph of {} people and {} links exported", persons, links)),
+                    Err(e) => self.update_status(format!("Failed to export graph: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::ExportLineupClicked => {
+                self.lineup = Some(LineupDialog {
+                    persons: self.filtered_persons.clone(),
+                    order: LineupOrder::Random,
+                    show_names: false,
+                });
+                Command::none()
+            }
+            
+            Message::LineupPersonToggled(person_id, included) => {
+                if let Some(dialog) = &mut self.lineup {
+                    dialog.persons.retain(|id| *id != person_id);
+                    if included {
+                        dialog.persons.push(person_id);
+                    }
+                }
+                Command::none()
+            }
+            
+            Message::LineupOrderSelected(order) => {
+                if let Some(dialog) = &mut self.lineup {
+                    dialog.order = order;
+                }
+                Command::none()
+            }
+            
+            Message::LineupShowNamesToggled(show_names) => {
+                if let Some(dialog) = &mut self.lineup {
+                    dialog.show_names = show_names;
+                }
+                Command::none()
+            }
+            
+            Message::LineupCancelled => {
+                self.lineup = None;
+                Command::none()
+            }
+            
+            Message::LineupSaveClicked => {
+                Command::perform(
+                    async {
+                        rfd::FileDialog::new()
+                            .add_filter("PDF", &["pdf"])
+                            .add_filter("PNG image", &["png"])
+                            .set_file_name("photo_lineup.pdf")
+                            .save_file()
+                    },
+                    |path| {
+                        if let Some(path) = path {
+                            Message::LineupFileSelected(path)
+                        } else {
+                            Message::StatusMessage("Export cancelled".to_string())
+                        }
+                    }
+                )
+            }
+            
+            Message::LineupFileSelected(path) => {
+                let Some(dialog) = self.lineup.take() else {
+                    return Command::none();
+                };
+                // In the order of the people list, which "List order" keeps
+                let persons: Vec<Person> = self.filtered_persons
+                    .iter()
+                    .filter(|id| dialog.persons.contains(id))
+                    .filter_map(|id| self.persons.iter().find(|p| p.id == *id))
+                    .cloned()
+                    .collect();
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                let dates = self.settings.dates();
+                
+                Command::perform(
+                    run_blocking(move || {
+                        let lineup = Lineup::build(&file_manager, &persons, dialog.order).map_err(|e| format!("{:#}", e))?;
+                        let content = lineup.write(&file_manager, dialog.show_names, LineupFormat::from_path(&path), dates).map_err(|e| format!("{:#}", e))?;
+                        std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+                        // The key is kept in the log, since the sheet itself may not show names
+                        let details = format!("Photo lineup ({}) to {}: {}", dialog.order, path.display(), lineup.key());
+                        audit_log.record(None, AuditAction::Exported, details).map_err(|e| e.to_string())?;
+                        Ok((lineup.entries.len(), lineup.skipped))
+                    }),
+                    Message::LineupExported
+                )
+            }
+            
+            Message::LineupExported(result) => {
+                match result {
+                    Ok((count, skipped)) if skipped.is_empty() => self.update_status(format!("Photo lineup of {} people exported", count)),
+                    Ok((count, skipped)) => self.update_status(format!("Photo lineup of {} people exported; left off without an image: {}", count, skipped.join(", "))),
+                    Err(e) => self.update_status(format!("Failed to export lineup: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::NewTemplateNameChanged(value) => {
+                self.new_template_name = value;
+                Command::none()
+            }
+            
+            Message::NewTemplateTypesChanged(value) => {
+                self.new_template_types = value;
+                Command::none()
+            }
+            
+            Message::AddInfoTemplateSubmitted => {
+                let name = self.new_template_name.trim().to_string();
+                let info_types: Vec<String> = self.new_template_types
+                    .split(',')
+                    .map(|t| t.trim().to_string())
+                    .filter(|t| !t.is_empty())
+                    .collect();
+                if name.is_empty() || info_types.is_empty() {
+                    return Command::none();
+                }
+                
+                let template = InfoTemplate { name: name.clone(), info_types };
+                match self.settings.info_templates.iter_mut().find(|t| t.name == name) {
+                    Some(existing) => *existing = template,
+                    None => self.settings.info_templates.push(template),
+                }
+                self.new_template_name.clear();
+                self.new_template_types.clear();
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::RemoveInfoTemplate(name) => {
+                self.settings.info_templates.retain(|t| t.name != name);
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::CompletenessTemplateSelected(name) => {
+                self.settings.completeness_template = name;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::ShowCompleteness(show) => {
+                self.show_completeness = show;
+                Command::none()
+            }
+            
+            Message::NewChecklistTemplateNameChanged(value) => {
+                self.new_checklist_template_name = value;
+                Command::none()
+            }
+            
+            Message::NewChecklistTemplateQuestionsChanged(value) => {
+                self.new_checklist_template_questions = value;
+                Command::none()
+            }
+            
+            Message::AddChecklistTemplateSubmitted => {
+                let name = self.new_checklist_template_name.trim().to_string();
+                let questions: Vec<String> = self.new_checklist_template_questions
+                    .split(';')
+                    .map(|q| q.trim().to_string())
+                    .filter(|q| !q.is_empty())
+                    .collect();
+                if name.is_empty() || questions.is_empty() {
+                    return Command::none();
+                }
+                
+                let template = ChecklistTemplate { name: name.clone(), questions };
+                match self.settings.checklist_templates.iter_mut().find(|t| t.name == name) {
+                    Some(existing) => *existing = template,
+                    None => self.settings.checklist_templates.push(template),
+                }
+                self.new_checklist_template_name.clear();
+                self.new_checklist_template_questions.clear();
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::RemoveChecklistTemplate(name) => {
+                self.settings.checklist_templates.retain(|t| t.name != name);
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::InfoDraftChanged(info_id, value) => {
+                self.info_drafts.insert(info_id, value);
+                Command::none()
+            }
+            
+            Message::InfoDraftSubmitted(info_id) => {
+                let Some(value) = self.info_drafts.remove(&info_id).map(|v| v.trim().to_string()).filter(|v| !v.is_empty()) else {
+                    return Command::none();
+                };
+                let info_type = self.persons.iter()
+                    .flat_map(|p| &p.information)
+                    .find(|i| i.id == info_id)
+                    .map(|i| i.info_type.clone())
+                    .unwrap_or_default();
+                let details = format!("{}: {}", info_type, value);
+                let country_code = self.settings.default_country_code.clone();
+                self.update_selected_person(AuditAction::InformationUpdated, details, |person| {
+                    if let Some(info) = person.information.iter_mut().find(|i| i.id == info_id) {
+                        info.value = value;
+                    }
+                    person.normalize_information(&country_code);
+                })
+            }
+            
+            Message::BulkEditFieldChanged(field) => {
+                self.bulk_edit.field = field;
+                Command::none()
+            }
+            
+            Message::BulkEditFindChanged(value) => {
+                self.bulk_edit.find = value;
+                Command::none()
+            }
+            
+            Message::BulkEditReplaceChanged(value) => {
+                self.bulk_edit.replace = value;
+                Command::none()
+            }
+            
+            Message::BulkEditWholeValueToggled(enabled) => {
+                self.bulk_edit.whole_value = enabled;
+                Command::none()
+            }
+            
+            Message::ApplyBulkEdit => {
+                let changes = self.bulk_edit.preview(&self.persons);
+                if changes.is_empty() {
+                    return Command::none();
+                }
+                let field = self.bulk_edit.field.label();
+                
+                // Update in memory right away, then save each changed person with one audit entry per row
+                let mut changed_persons = Vec::new();
+                for person in self.persons.iter_mut() {
+                    let person_changes: Vec<String> = changes.iter()
+                        .filter(|c| c.person_id == person.id)
+                        .map(|c| format!("{} \"{}\" → \"{}\"", field, c.before, c.after))
+                        .collect();
+                    if !person_changes.is_empty() {
+                        self.bulk_edit.apply(person, &changes);
+                        person.normalize_information(&self.settings.default_country_code);
+                        changed_persons.push((person.clone(), person_changes));
+                    }
+                }
+                self.update_filtered_persons();
+                
+                let file_manager = self.file_manager.clone();
+                let audit_log = self.audit_log.clone();
+                let count = changes.len();
+                Command::perform(
+                    async move {
+                        for (person, details) in changed_persons {
+                            file_manager.save_person_data(&person).map_err(|e| e.to_string())?;
+                            for detail in details {
+                                audit_log.record(Some(&person), AuditAction::InformationUpdated, detail).map_err(|e| e.to_string())?;
+                            }
+                        }
+                        Ok(count)
+                    },
+                    Message::BulkEditApplied
+                )
+            }
+            
+            Message::BulkEditApplied(result) => {
+                match result {
+                    Ok(count) => {
+                        self.update_status(format!("Updated {} information entries", count));
+                        self.bulk_edit.find.clear();
+                        self.bulk_edit.replace.clear();
+                    }
+                    Err(e) => self.update_status(format!("Failed to apply bulk edit: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::SavedSearchNameChanged(name) => {
+                self.new_saved_search_name = name;
+                Command::none()
+            }
+            
+            Message::SaveSearch => {
+                let name = self.new_saved_search_name.trim().to_string();
+                if name.is_empty() {
+                    return Command::none();
+                }
+                let saved_search = SavedSearch {
+                    name: name.clone(),
+                    query: self.search_query.trim().to_string(),
+                    case_status: self.case_status_filter,
+                    sort_by_priority: self.sort_by_priority,
+                    color_label: self.color_label_filter,
+                };
+                // Saving under an existing name replaces that view
+                match self.settings.saved_searches.iter_mut().find(|s| s.name == name) {
+                    Some(existing) => *existing = saved_search,
+                    None => self.settings.saved_searches.push(saved_search),
+                }
+                self.save_settings();
+                self.new_saved_search_name.clear();
+                self.update_status(format!("Saved search \"{}\"", name));
+                Command::none()
+            }
+            
+            Message::ApplySavedSearch(name) => {
+                if let Some(saved_search) = self.settings.saved_searches.iter().find(|s| s.name == name).cloned() {
+                    self.search_query = saved_search.query;
+                    self.case_status_filter = saved_search.case_status;
+                    self.sort_by_priority = saved_search.sort_by_priority;
+                    self.color_label_filter = saved_search.color_label;
+                    self.update_filtered_persons();
+                }
+                Command::none()
+            }
+            
+            Message::RemoveSavedSearch(name) => {
+                self.settings.saved_searches.retain(|s| s.name != name);
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::GeocodingUrlChanged(url) => {
+                self.settings.geocoding_url = url;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::TsaUrlChanged(url) => {
+                self.settings.tsa_url = url;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::WebhookUrlChanged(url) => {
+                self.settings.webhook_url = url;
+                self.audit_log.set_webhook(Webhook::new(&self.settings.webhook_url, &self.settings.webhook_secret));
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::WebhookSecretChanged(secret) => {
+                self.settings.webhook_secret = secret;
+                self.audit_log.set_webhook(Webhook::new(&self.settings.webhook_url, &self.settings.webhook_secret));
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::SendTestWebhook => {
+                let Some(webhook) = Webhook::new(&self.settings.webhook_url, &self.settings.webhook_secret) else {
+                    return Command::none();
+                };
+                let payload = webhook::Payload::test(&self.settings.operator);
+                Command::perform(
+                    run_blocking(move || webhook.deliver(&payload).map_err(|e| format!("{:#}", e))),
+                    Message::TestWebhookSent
+                )
+            }
+            
+            Message::PluginToggled(name, enabled) => {
+                self.settings.enabled_plugins.retain(|n| *n != name);
+                if enabled {
+                    self.settings.enabled_plugins.push(name);
+                }
+                self.save_settings();
+                self.load_plugins();
+                Command::none()
+            }
+            
+            Message::ReloadPlugins => {
+                if self.load_plugins() {
+                    self.update_status(format!("{} plugin(s) loaded", self.plugins.count()));
+                }
+                Command::none()
+            }
+            
+            Message::OpenPluginsFolder => {
+                let opened = plugins::plugins_dir()
+                    .and_then(|dir| {
+                        std::fs::create_dir_all(&dir)?;
+                        opener::open(&dir)?;
+                        Ok(())
+                    });
+                if let Err(e) = opened {
+                    self.update_status(format!("Failed to open plugins folder: {}", e));
+                }
+                Command::none()
+            }
+            
+            Message::TestWebhookSent(result) => {
+                match result {
+                    Ok(()) => self.update_status("Test event delivered".to_string()),
+                    Err(e) => self.update_status(format!("Test event failed: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::PersonQuotaSizeChanged(value) => {
+                match parse_limit(&value) {
+                    Some(limit) => {
+                        self.settings.person_quota_mb = limit;
+                        self.save_settings();
+                    }
+                    None => self.update_status("The size limit must be a whole number of MB".to_string()),
+                }
+                Command::none()
+            }
+            
+            Message::PersonQuotaFilesChanged(value) => {
+                match parse_limit(&value) {
+                    Some(limit) => {
+                        self.settings.person_quota_files = limit as usize;
+                        self.save_settings();
+                    }
+                    None => self.update_status("The file limit must be a whole number".to_string()),
+                }
+                Command::none()
+            }
+            
+            Message::FreeSpaceFloorChanged(value) => {
+                match parse_limit(&value) {
+                    Some(limit) => {
+                        self.settings.free_space_floor_mb = limit;
+                        self.save_settings();
+                    }
+                    None => self.update_status("The free space minimum must be a whole number of MB".to_string()),
+                }
+                Command::none()
+            }
+            
+            Message::StorageLayoutSelected(layout) => {
+                if layout == self.settings.storage_layout {
+                    return Command::none();
+                }
+                self.settings.storage_layout = layout;
+                self.apply_storage_layout();
+                self.save_settings();
+                self.update_status(match layout {
+                    StorageLayout::Blobs => "Evidence added from now on is stored once; run \"evidence-manager migrate-blobs\" to move existing evidence into the blob store",
+                    StorageLayout::PersonFolders => "Evidence added from now on is copied into each person's folder",
+                }.to_string());
+                Command::none()
+            }
+            
+            Message::OperatorChanged(operator) => {
+                // The intake form follows unless someone else was entered there
+                if self.intake_collected_by == self.settings.operator {
+                    self.intake_collected_by = operator.clone();
+                }
+                self.audit_log.set_operator(&operator);
+                self.settings.operator = operator;
+                self.save_settings();
+                Command::none()
+            }
+            
+            Message::MapTileUrlChanged(url) => {
+                self.settings.map_tile_url = url;
+                self.save_settings();
+                // Re-rendered with the new tiles next time the tab is opened
+                self.map_view = None;
+                Command::none()
+            }
+            
+            Message::ProfilePassphraseChanged(value) => {
+                self.profile_passphrase = value;
+                Command::none()
+            }
+            
+            Message::OpenAsAdmin => {
+                let passphrase = std::mem::take(&mut self.profile_passphrase);
+                match &self.settings.admin_passphrase {
+                    Some(admin) if !admin.matches(&passphrase) => {
+                        self.update_status("Wrong passphrase".to_string());
+                    }
+                    _ => {
+                        self.role = Some(Role::Admin);
+                        self.status_message.clear();
+                        return self.resume_jobs();
+                    }
+                }
+                Command::none()
+            }
+            
+            Message::OpenAsViewer => {
+                self.role = Some(Role::Viewer);
+                self.profile_passphrase.clear();
+                self.show_settings = false;
+                self.resume_jobs()
+            }
+            
+            Message::SwitchProfile => {
+                self.role = None;
+                Command::none()
+            }
+            
+            Message::NewAdminPassphraseChanged(value) => {
+                self.new_admin_passphrase = value;
+                Command::none()
+            }
+            
+            Message::SetAdminPassphrase => {
+                match Passphrase::new(&self.new_admin_passphrase) {
+                    Ok(passphrase) => {
+                        self.settings.admin_passphrase = Some(passphrase);
+                        self.new_admin_passphrase.clear();
+                        self.save_settings();
+                        self.update_status("Admin passphrase set; a profile is picked each time the app opens".to_string());
+                    }
+                    Err(e) => self.update_status(format!("Failed to set passphrase: {}", e)),
+                }
+                Command::none()
+            }
+            
+            Message::RemoveAdminPassphrase => {
+                self.settings.admin_passphrase = None;
+                self.save_settings();
+                self.update_status("Admin passphrase removed".to_string());
+                Command::none()
+            }
+            
+            Message::StatusMessage(message) => {
+                self.update_status(message);
+                Command::none()
+            }
+            
+        }
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        crate::gui::view(self)
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.settings.ui_scale() as f64 / 100.0
+    }
+    
+    fn subscription(&self) -> Subscription<Message> {
+        Subscription::batch([
+            // Files dropped onto the window are added like ones picked in the file dialog
+            event::listen_with(|event, _status| match event {
+                Event::Window(_, window::Event::FileDropped(path)) => Some(Message::FileSelected(path)),
+                Event::Window(_, window::Event::Resized { width, height }) => Some(Message::WindowResized(width, height)),
+                Event::Window(_, window::Event::Moved { x, y }) => Some(Message::WindowMoved(x, y)),
+                Event::Window(id, window::Event::CloseRequested) => Some(Message::WindowCloseRequested(id)),
+                Event::Window(_, window::Event::Focused) => Some(Message::WindowFocused(true)),
+                Event::Window(_, window::Event::Unfocused) => Some(Message::WindowFocused(false)),
+                _ => None,
+            }),
+            // The sidebar follows the cursor while its edge is dragged
+            if self.sidebar_dragging {
+                event::listen_with(|event, _status| match event {
+                    Event::Mouse(mouse::Event::CursorMoved { position }) => Some(Message::SidebarDragged(position.x)),
+                    Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => Some(Message::SidebarDragEnded),
+                    _ => None,
+                })
+            } else {
+                Subscription::none()
+            },
+            // Ctrl (Cmd on macOS) with +, - or 0 scales the whole interface
+            keyboard::on_key_press(|key, modifiers| match key.as_ref() {
+                keyboard::Key::Character("+" | "=") if modifiers.command() => Some(Message::ZoomIn),
+                keyboard::Key::Character("-") if modifiers.command() => Some(Message::ZoomOut),
+                keyboard::Key::Character("0") if modifiers.command() => Some(Message::UiScaleChanged(100)),
+                _ => None,
+            }),
+            time::every(Duration::from_secs(60)).map(|_| Message::CheckDueTasks),
+            if self.settings.export_profiles.iter().any(|p| p.schedule != ExportSchedule::Manual) {
+                time::every(Duration::from_secs(60)).map(|_| Message::CheckScheduledExports)
+            } else {
+                Subscription::none()
+            },
+            // Global shortcuts arrive on a channel of their own, outside the window's events
+            if self.quick_capture_hotkey.is_some() {
+                time::every(Duration::from_millis(200)).map(|_| Message::CheckQuickCaptureHotkey)
+            } else {
+                Subscription::none()
+            },
+            // A snapshot of what's open, restored if the app doesn't get to close properly
+            time::every(Duration::from_secs(5)).map(|_| Message::SaveSession),
+            // Typing is written to the drafts file a little after it happens, not on every key
+            if self.drafts_changed {
+                time::every(Duration::from_secs(2)).map(|_| Message::SaveDrafts)
+            } else {
+                Subscription::none()
+            },
+            // Keeps the recording time shown up to date
+            if self.recording.is_some() {
+                time::every(Duration::from_secs(1)).map(|_| Message::RecordingTick)
+            } else {
+                Subscription::none()
+            },
+            // Progress is only polled while a job runs or a file is being copied
+            if self.running_job.is_some() || !self.file_copies.is_empty() {
+                time::every(Duration::from_millis(250)).map(|_| Message::JobProgressTick)
+            } else {
+                Subscription::none()
+            },
+        ])
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+/// Limits are entered as whole numbers; an empty field turns the limit off
+fn parse_limit(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Some(0);
+    }
+    value.parse().ok()
+}
+
+/// Checks the storage limits before evidence is added, showing a system notification for each
+/// warning so it's seen even when the app is in the background. A failed check isn't a reason
+/// to refuse the file; running out of space is caught by the copy itself.
+fn storage_warnings(file_manager: &FileManager, person: &Person, size: u64, settings: &Settings) -> Vec<String> {
+    let warnings = file_manager.storage_warnings(person, size, settings).unwrap_or_else(|e| vec![e.to_string()]);
+    notify_storage_warnings(&warnings);
+    warnings
+}
+
+fn notify_storage_warnings(warnings: &[String]) {
+    for warning in warnings {
+        let _ = notify_rust::Notification::new()
+            .summary("Evidence Manager: storage")
+            .body(warning)
+            .show();
+    }
+}
+
+/// Shows a system notification about a finished job, resolving to whether it was clicked once
+/// it's clicked or dismissed. The wait holds a blocking thread for as long as the notification is up.
+async fn notify_job_finished(summary: &'static str, body: String) -> Result<bool, String> {
+    run_blocking(move || {
+        let handle = notify_rust::Notification::new()
+            .summary(summary)
+            .body(&body)
+            .action("default", "Open")
+            .show()
+            .map_err(|e| e.to_string())?;
+        let mut clicked = false;
+        handle.wait_for_action(|action| clicked = action != "__closed");
+        Ok(clicked)
+    })
+    .await
+}
+
+/// Runs file work on the blocking thread pool so exports, imports and copies don't stall other commands
+async fn run_blocking<T: Send + 'static>(work: impl FnOnce() -> Result<T, String> + Send + 'static) -> Result<T, String> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Evidence with GPS data in its EXIF and quotes whose place is given as coordinates
+fn map_points(person: &Person, evidence_files: &[EvidenceFile]) -> Vec<MapPoint> {
+    let mut points = Vec::new();
+    for file in evidence_files.iter().filter(|f| f.file_type == EvidenceType::Image) {
+        // EXIF sits near the start of the file, so there's no need to read whole photos
+        let mut header = Vec::new();
+        let read = std::fs::File::open(&file.file_path)
+            .and_then(|f| std::io::Read::read_to_end(&mut std::io::Read::take(f, 256 * 1024), &mut header));
+        if read.is_ok() && let Some(coordinates) = exif::gps_coordinates(&header) {
+            points.push(MapPoint {
+                label: file.original_name.clone(),
+                coordinates,
+                target: MapTarget::Evidence(file.id),
+            });
+        }
+    }
+    for quote in &person.quotes {
+        if let Some(coordinates) = quote.coordinates.or_else(|| quote.place.as_deref().and_then(Coordinates::parse)) {
+            points.push(MapPoint {
+                label: format!("\"{}\" ({})", quote.quote, quote.date),
+                coordinates,
+                target: MapTarget::Quote(quote.id),
+            });
+        }
+    }
+    points
+}
@@ -0,0 +1,49 @@
+use crate::links::{self, SharedIdentifier};
+use crate::models::{EvidenceFile, EvidenceType, Person, ReviewStatus};
+use crate::settings::Settings;
+
+/// Totals across every person, shown on the dashboard
+#[derive(Debug, Clone, Default)]
+pub struct Statistics {
+    pub person_count: usize,
+    pub quote_count: usize,
+    pub total_size: u64,
+    pub files_by_type: Vec<(EvidenceType, usize)>,
+    pub files_by_status: Vec<(ReviewStatus, usize)>,
+    pub shared_identifiers: Vec<SharedIdentifier>,
+}
+
+impl Statistics {
+    pub fn collect(persons: &[Person], evidence_files: &[EvidenceFile], settings: &Settings) -> Self {
+        let files_by_type = settings.file_categories()
+            .into_iter()
+            .map(|evidence_type| {
+                let count = evidence_files.iter().filter(|f| f.file_type == evidence_type).count();
+                (evidence_type, count)
+            })
+            .collect();
+
+        Self {
+            person_count: persons.len(),
+            quote_count: persons.iter().map(|p| p.quotes.len()).sum(),
+            total_size: evidence_files.iter().map(|f| f.size).sum(),
+            files_by_type,
+            files_by_status: status_counts(evidence_files),
+            shared_identifiers: links::shared_identifiers(persons),
+        }
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.files_by_type.iter().map(|(_, count)| count).sum()
+    }
+}
+
+pub fn status_counts<'a>(evidence_files: impl IntoIterator<Item = &'a EvidenceFile> + Clone) -> Vec<(ReviewStatus, usize)> {
+    ReviewStatus::all()
+        .into_iter()
+        .map(|status| {
+            let count = evidence_files.clone().into_iter().filter(|f| f.review_status == status).count();
+            (status, count)
+        })
+        .collect()
+}
@@ -0,0 +1,354 @@
+use crate::audit::{AuditAction, AuditLog};
+use crate::file_manager::{FileManager, METADATA_FILES};
+use crate::hashing;
+use crate::models::{EvidenceType, Person};
+use crate::profiles;
+use crate::settings::Settings;
+use anyhow::{Result, Context, bail};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    UnreadablePerson,
+    FolderMismatch,
+    UnreadableIndex,
+    MissingFolder,
+    MissingFile,
+    UnindexedFile,
+    HashMismatch,
+    NoHash,
+}
+
+impl IssueKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            IssueKind::UnreadablePerson => "Unreadable person data",
+            IssueKind::FolderMismatch => "Folder doesn't match name",
+            IssueKind::UnreadableIndex => "Unreadable evidence index",
+            IssueKind::MissingFolder => "Missing folder",
+            IssueKind::MissingFile => "Missing file",
+            IssueKind::UnindexedFile => "File not in index",
+            IssueKind::HashMismatch => "Hash mismatch",
+            IssueKind::NoHash => "No recorded hash",
+        }
+    }
+
+    /// What a repair does, for the issues that can be repaired without guessing. A changed hash
+    /// is never repaired: recording the new one would hide the change.
+    pub fn repair_label(&self) -> Option<&'static str> {
+        match self {
+            IssueKind::UnreadableIndex => Some("Rebuild index"),
+            IssueKind::MissingFolder => Some("Create folder"),
+            IssueKind::MissingFile => Some("Remove from index"),
+            IssueKind::UnindexedFile => Some("Add to index"),
+            IssueKind::NoHash => Some("Record hash"),
+            _ => None,
+        }
+    }
+}
+
+/// Something `verify_store` found wrong
+#[derive(Debug, Clone)]
+pub struct StoreIssue {
+    pub kind: IssueKind,
+    pub person_id: Option<Uuid>, // None when the person data couldn't be read
+    pub person_name: String, // The folder name when the person data couldn't be read
+    pub file_id: Option<Uuid>, // The index entry, for issues with one
+    pub path: PathBuf,
+    pub details: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct StoreReport {
+    pub checked_at: DateTime<Utc>,
+    pub people: usize,
+    pub files: usize,
+    pub issues: Vec<StoreIssue>,
+}
+
+impl StoreReport {
+    pub fn repairable(&self) -> usize {
+        self.issues.iter().filter(|issue| issue.kind.repair_label().is_some()).count()
+    }
+}
+
+/// Cross-checks the Evidence folder: every person folder has readable data and index files and
+/// its subfolders, every index entry has its file and the hash recorded for it, and every file
+/// is in the index
+pub fn verify_store(file_manager: &FileManager) -> Result<StoreReport> {
+    let evidence_dir = file_manager.get_evidence_dir();
+    let mut report = StoreReport {
+        checked_at: Utc::now(),
+        people: 0,
+        files: 0,
+        issues: Vec::new(),
+    };
+
+    // Hidden folders, like the import staging folder, aren't people
+    let mut folders: Vec<PathBuf> = fs::read_dir(evidence_dir)
+        .context("Failed to read Evidence directory")?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && !folder_name(path).starts_with('.'))
+        .collect();
+    folders.sort();
+
+    for folder in folders {
+        let person = match file_manager.load_person_data(&folder) {
+            Ok(person) => person,
+            Err(e) => {
+                report.issues.push(StoreIssue {
+                    kind: IssueKind::UnreadablePerson,
+                    person_id: None,
+                    person_name: folder_name(&folder),
+                    file_id: None,
+                    path: folder.join("person_data.json"),
+                    details: format!("{:#}", e),
+                });
+                continue;
+            }
+        };
+        report.people += 1;
+
+        // The app finds a person's files through the folder named after them
+        if folder != evidence_dir.join(person.folder_name()) {
+            report.issues.push(person_issue(&person, IssueKind::FolderMismatch, folder.clone(), format!("Expected a folder named \"{}\"", person.folder_name())));
+            continue;
+        }
+        check_person(file_manager, &person, &folder, &mut report);
+    }
+
+    Ok(report)
+}
+
+fn check_person(file_manager: &FileManager, person: &Person, folder: &Path, report: &mut StoreReport) {
+    for evidence_type in EvidenceType::built_in() {
+        let subfolder = folder.join(evidence_type.folder_name());
+        if !subfolder.is_dir() {
+            report.issues.push(person_issue(person, IssueKind::MissingFolder, subfolder, String::new()));
+        }
+    }
+
+    let index = match file_manager.load_evidence_index(person) {
+        Ok(index) => index,
+        Err(e) => {
+            // Every file would look unindexed, so there's nothing more to check
+            report.issues.push(person_issue(person, IssueKind::UnreadableIndex, folder.join("evidence_index.json"), format!("{:#}", e)));
+            return;
+        }
+    };
+
+    for file in &index {
+        report.files += 1;
+        let mut issue = |kind, details| {
+            report.issues.push(StoreIssue {
+                file_id: Some(file.id),
+                ..person_issue(person, kind, file.file_path.clone(), details)
+            });
+        };
+        if !file.file_path.is_file() {
+            issue(IssueKind::MissingFile, file.original_name.clone());
+            continue;
+        }
+        // Files taken in before hashes were recorded may still have one from their timestamp
+        let Some(expected) = file.sha256.as_ref().or(file.timestamp.as_ref().map(|t| &t.sha256)) else {
+            issue(IssueKind::NoHash, String::new());
+            continue;
+        };
+        match hashing::sha256_file(&file.file_path) {
+            Ok(actual) if actual == *expected => {}
+            Ok(actual) => issue(IssueKind::HashMismatch, format!("Recorded {}, now {}", expected, actual)),
+            Err(e) => issue(IssueKind::HashMismatch, format!("{:#}", e)),
+        }
+    }
+
+    for entry in WalkDir::new(folder)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let is_metadata = entry.file_name().to_str().is_some_and(|name| METADATA_FILES.contains(&name));
+        if !is_metadata && !index.iter().any(|file| file.file_path == entry.path()) {
+            report.issues.push(person_issue(person, IssueKind::UnindexedFile, entry.path().to_path_buf(), String::new()));
+        }
+    }
+}
+
+fn person_issue(person: &Person, kind: IssueKind, path: PathBuf, details: String) -> StoreIssue {
+    StoreIssue {
+        kind,
+        person_id: Some(person.id),
+        person_name: person.name.clone(),
+        file_id: None,
+        path,
+        details,
+    }
+}
+
+fn folder_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RepairSummary {
+    pub repaired: usize,
+    pub failed: Vec<String>,
+}
+
+/// Repairs the issues that have a repair, recording what was done in the audit log, one entry
+/// per person. A missing file stays in the index of a person under legal hold.
+pub fn repair(file_manager: &FileManager, settings: &Settings, audit_log: &AuditLog, issues: &[StoreIssue]) -> Result<RepairSummary> {
+    let persons = file_manager.load_all_persons()?;
+    let mut summary = RepairSummary::default();
+    let mut done: BTreeMap<Uuid, Vec<String>> = BTreeMap::new();
+
+    for issue in issues.iter().filter(|issue| issue.kind.repair_label().is_some()) {
+        let Some(person) = persons.iter().find(|p| Some(p.id) == issue.person_id) else {
+            continue;
+        };
+        match repair_issue(file_manager, settings, person, issue) {
+            Ok(description) => {
+                summary.repaired += 1;
+                done.entry(person.id).or_default().push(description);
+            }
+            Err(e) => summary.failed.push(format!("{}: {}: {:#}", person.name, issue.path.display(), e)),
+        }
+    }
+
+    for (person_id, descriptions) in done {
+        let person = persons.iter().find(|p| p.id == person_id);
+        audit_log.record(person, AuditAction::EvidenceUpdated, format!("Store repair: {}", descriptions.join("; ")))?;
+    }
+    Ok(summary)
+}
+
+fn repair_issue(file_manager: &FileManager, settings: &Settings, person: &Person, issue: &StoreIssue) -> Result<String> {
+    let name = issue.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    match issue.kind {
+        IssueKind::MissingFolder => {
+            fs::create_dir_all(&issue.path)
+                .context("Failed to create folder")?;
+            Ok(format!("created folder {}", name))
+        }
+        IssueKind::UnreadableIndex => {
+            let summary = file_manager.rebuild_evidence_index(person, settings)?;
+            Ok(format!("rebuilt the evidence index: {}", summary.describe()))
+        }
+        IssueKind::UnindexedFile => {
+            let file = file_manager.index_file(person, &issue.path, settings)?;
+            Ok(format!("added {} to the index as {}", name, file.file_type.label()))
+        }
+        IssueKind::MissingFile => {
+            if person.legal_hold {
+                bail!("{} is under legal hold", person.name);
+            }
+            // Only if it's still missing; it may have been put back since the check
+            if issue.path.exists() {
+                bail!("The file is there again");
+            }
+            let mut index = file_manager.load_evidence_index(person)?;
+            index.retain(|file| Some(file.id) != issue.file_id);
+            file_manager.save_evidence_index(person, &index)?;
+            Ok(format!("removed missing {} from the index", name))
+        }
+        IssueKind::NoHash => {
+            let mut file = file_manager.load_evidence_index(person)?
+                .into_iter()
+                .find(|file| Some(file.id) == issue.file_id)
+                .context("The file is no longer in the index")?;
+            let sha256 = hashing::sha256_file(&file.file_path)?;
+            file.sha256 = Some(sha256.clone());
+            file_manager.update_evidence_metadata(person, &file)?;
+            Ok(format!("recorded SHA-256 {} for {}", sha256, name))
+        }
+        _ => bail!("{} can't be repaired", issue.kind.label()),
+    }
+}
+
+/// `evidence-manager verify [--repair]`: prints the store's issues, one per line, and with
+/// `--repair` fixes the ones that can be. Returns the process exit code: 0 when nothing is
+/// left wrong, 1 when something is.
+pub fn run(flag: Option<String>) -> i32 {
+    let repair = match flag.as_deref() {
+        None => false,
+        Some("--repair") => true,
+        Some(_) => {
+            eprintln!("usage: evidence-manager verify [--repair]");
+            return 2;
+        }
+    };
+    match verify(repair) {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(e) => {
+            eprintln!("verify: {:#}", e);
+            2
+        }
+    }
+}
+
+/// Whether the store has no issues left
+fn verify(repair: bool) -> Result<bool> {
+    let settings = Settings::load().context("Failed to load settings")?;
+    let file_manager = FileManager::new()?;
+    let mut report = verify_store(&file_manager)?;
+
+    for issue in &report.issues {
+        println!("{}\t{}\t{}\t{}", issue.kind.label(), issue.person_name, issue.path.display(), issue.details);
+    }
+    println!("Checked {} people and {} files: {} issues, {} repairable", report.people, report.files, report.issues.len(), report.repairable());
+
+    if repair && report.repairable() > 0 {
+        profiles::require_admin(settings.admin_passphrase.as_ref())?;
+        let mut audit_log = AuditLog::new(file_manager.get_evidence_dir());
+        audit_log.set_operator(&settings.operator);
+        let summary = self::repair(&file_manager, &settings, &audit_log, &report.issues)?;
+        for failure in &summary.failed {
+            eprintln!("verify: not repaired: {}", failure);
+        }
+        println!("Repaired {} issues", summary.repaired);
+        report = verify_store(&file_manager)?;
+    }
+    Ok(report.issues.is_empty())
+}
+
+/// `evidence-manager rebuild-index [person ID]`: rebuilds the evidence index of one person, or
+/// of everyone, from the files on disk. Returns the process exit code.
+pub fn run_rebuild(person: Option<String>) -> i32 {
+    match rebuild(person) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("rebuild-index: {:#}", e);
+            1
+        }
+    }
+}
+
+fn rebuild(person: Option<String>) -> Result<()> {
+    let settings = Settings::load().context("Failed to load settings")?;
+    profiles::require_admin(settings.admin_passphrase.as_ref())?;
+    let file_manager = FileManager::new()?;
+    let mut audit_log = AuditLog::new(file_manager.get_evidence_dir());
+    audit_log.set_operator(&settings.operator);
+
+    let mut persons = file_manager.load_all_persons()?;
+    if let Some(id) = person {
+        let id = Uuid::parse_str(&id).context("Invalid person ID")?;
+        persons.retain(|p| p.id == id);
+        if persons.is_empty() {
+            bail!("No person with ID {}", id);
+        }
+    }
+    for person in &persons {
+        let summary = file_manager.rebuild_evidence_index(person, &settings)?;
+        let description = summary.describe();
+        println!("{}\t{}", person.name, description);
+        audit_log.record(Some(person), AuditAction::EvidenceUpdated, format!("Evidence index rebuilt: {}", description))?;
+    }
+    Ok(())
+}
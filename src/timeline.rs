@@ -0,0 +1,275 @@
+use crate::models::{EvidenceFile, Person};
+use crate::dates::DateDisplay;
+use crate::settings::Settings;
+use anyhow::{Result, Context};
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::Serialize;
+
+/// One point on the timeline
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEvent {
+    pub start: Option<NaiveDateTime>, // Local time; None for quotes whose date can't be read
+    pub shown: String, // The start in the chosen date format, or "Undated"
+    pub kind: &'static str, // "quote", "evidence" or "task"
+    pub person: String,
+    pub title: String,
+    pub detail: String,
+}
+
+/// Quotes, evidence and task due dates of one or more people, oldest first
+#[derive(Debug, Default)]
+pub struct Timeline {
+    pub events: Vec<TimelineEvent>,
+}
+
+impl Timeline {
+    pub fn add_person(&mut self, person: &Person, evidence_files: &[EvidenceFile], settings: &Settings) {
+        let dates = settings.dates();
+        let shown = |start: Option<NaiveDateTime>| start.map(|start| dates.local(start, false)).unwrap_or_else(|| "Undated".to_string());
+        for quote in &person.quotes {
+            // Quote dates are entered by hand; ones that aren't YYYY-MM-DD are listed as undated.
+            // Quotes with the zone they were given in are placed by this computer's clock, as
+            // evidence and tasks are.
+            let start = match quote.instant() {
+                Some(said) => Some(said.with_timezone(&Local).naive_local()),
+                None => NaiveDate::parse_from_str(quote.date.trim(), "%Y-%m-%d").ok().map(|date| {
+                    let time = quote.time.as_deref()
+                        .and_then(|time| NaiveTime::parse_from_str(time.trim(), "%H:%M").ok())
+                        .unwrap_or(NaiveTime::MIN);
+                    date.and_time(time)
+                }),
+            };
+            let mut detail = Vec::new();
+            if start.is_none() {
+                detail.push(format!("Date: {}", quote.date));
+            }
+            if let Some(place) = &quote.place {
+                detail.push(format!("Place: {}", place));
+            }
+            if let Some(source) = &quote.source {
+                detail.push(format!("Source: {}", source));
+            }
+            self.events.push(TimelineEvent {
+                start,
+                shown: if start.is_some() { dates.quote_line(quote) } else { shown(None) },
+                kind: "quote",
+                person: person.name.clone(),
+                title: format!("\"{}\"", quote.quote),
+                detail: detail.join("\n"),
+            });
+        }
+
+        for file in evidence_files {
+            // When the evidence itself was made if known, otherwise when it was added
+            let (time, when) = match file.evidence_date {
+                Some(date) => (date, "Dated"),
+                None => (file.created_at, "Added"),
+            };
+            let start = Some(time.with_timezone(&Local).naive_local());
+            self.events.push(TimelineEvent {
+                start,
+                shown: shown(start),
+                kind: "evidence",
+                person: person.name.clone(),
+                title: file.original_name.clone(),
+                detail: format!("{} · {} · {}", settings.display_name(&file.file_type), when, file.review_status.label()),
+            });
+        }
+
+        for task in &person.tasks {
+            if let Some(due) = task.due_date {
+                // Due dates have no time of day
+                self.events.push(TimelineEvent {
+                    start: Some(due.and_time(NaiveTime::MIN)),
+                    shown: dates.date(due),
+                    kind: "task",
+                    person: person.name.clone(),
+                    title: task.description.clone(),
+                    detail: if task.done { "Due · done".to_string() } else { "Due".to_string() },
+                });
+            }
+        }
+
+        // Undated entries sort last
+        self.events.sort_by_key(|event| (event.start.is_none(), event.start));
+    }
+
+    /// A single HTML file with the data, styles and script inside, viewable offline
+    pub fn to_html(&self, title: &str, dates: DateDisplay) -> Result<String> {
+        let data = serde_json::to_string(&self.events)
+            .context("Failed to serialize timeline")?
+            // Keep the data from ending the script element early
+            .replace('<', "\\u003c");
+        Ok(TEMPLATE
+            .replace("{{title}}", &escape_html(title))
+            .replace("{{generated}}", &dates.now())
+            .replace("{{data}}", &data))
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{{title}}</title>
+<style>
+body { font-family: sans-serif; margin: 20px; color: #222; }
+h1 { font-size: 20px; margin: 0 0 4px; }
+.generated { color: #777; font-size: 12px; margin-bottom: 12px; }
+.toolbar { margin-bottom: 8px; }
+.toolbar button { margin-right: 4px; }
+.legend span { display: inline-block; margin-left: 12px; font-size: 12px; }
+.legend i { display: inline-block; width: 10px; height: 10px; border-radius: 2px; margin-right: 4px; }
+#viewport { position: relative; overflow-x: auto; border: 1px solid #ccc; background: #fafafa; cursor: grab; }
+#canvas { position: relative; height: 100%; }
+.lane { position: absolute; left: 0; right: 0; border-bottom: 1px solid #e4e4e4; }
+.lane-label { position: sticky; left: 0; font-size: 12px; font-weight: bold; padding: 2px 6px; background: rgba(250,250,250,.9); display: inline-block; }
+.tick { position: absolute; top: 0; bottom: 0; border-left: 1px solid #e0e0e0; font-size: 11px; color: #666; padding-left: 3px; }
+.item { position: absolute; height: 20px; line-height: 20px; font-size: 12px; padding: 0 6px; border-radius: 3px; white-space: nowrap; overflow: hidden; text-overflow: ellipsis; max-width: 240px; color: #fff; cursor: pointer; }
+.item.quote, i.quote { background: #3a6fc4; }
+.item.evidence, i.evidence { background: #3a9a5b; }
+.item.task, i.task { background: #c47f1d; }
+.item.selected { outline: 2px solid #222; }
+#details { min-height: 48px; margin: 8px 0 16px; padding: 8px; border: 1px solid #ddd; white-space: pre-wrap; font-size: 13px; }
+table { border-collapse: collapse; width: 100%; font-size: 13px; }
+th, td { text-align: left; border-bottom: 1px solid #ddd; padding: 4px 6px; vertical-align: top; }
+td.detail { white-space: pre-wrap; color: #555; }
+@media print { .toolbar, #viewport, #details { display: none; } }
+</style>
+</head>
+<body>
+<h1>{{title}}</h1>
+<div class="generated">Generated {{generated}} by Evidence Manager</div>
+<div class="toolbar">
+  <button id="zoom-in">Zoom in</button><button id="zoom-out">Zoom out</button><button id="fit">Fit</button>
+  <span class="legend"><span><i class="quote"></i>Quote</span><span><i class="evidence"></i>Evidence</span><span><i class="task"></i>Task</span></span>
+</div>
+<div id="viewport"><div id="canvas"></div></div>
+<div id="details">Click an entry for its details.</div>
+<table>
+  <thead><tr><th>When</th><th>Person</th><th>Type</th><th>Entry</th><th>Details</th></tr></thead>
+  <tbody id="rows"></tbody>
+</table>
+<script>
+const events = {{data}};
+const ROW = 24, LANE_PADDING = 22, DAY = 86400000;
+const dated = events.filter(e => e.start).map(e => Object.assign({}, e, { time: new Date(e.start).getTime() }));
+const people = [...new Set(events.map(e => e.person))];
+const viewport = document.getElementById('viewport');
+const canvas = document.getElementById('canvas');
+const details = document.getElementById('details');
+const min = dated.length ? Math.min(...dated.map(e => e.time)) - DAY : Date.now() - DAY;
+const max = dated.length ? Math.max(...dated.map(e => e.time)) + DAY : Date.now() + DAY;
+let scale = 1;
+
+function fitScale() { return (viewport.clientWidth - 40) / (max - min); }
+function x(time) { return 20 + (time - min) * scale; }
+
+function show(event, element) {
+  document.querySelectorAll('.item.selected').forEach(item => item.classList.remove('selected'));
+  if (element) element.classList.add('selected');
+  details.textContent = event.shown + ' · ' + event.person + '\n' + event.title + (event.detail ? '\n' + event.detail : '');
+}
+
+function tickStep() {
+  const steps = [3600000, 6 * 3600000, DAY, 7 * DAY, 30 * DAY, 91 * DAY, 365 * DAY];
+  return steps.find(step => step * scale > 90) || 365 * DAY;
+}
+
+function draw() {
+  canvas.innerHTML = '';
+  const width = x(max) + 20;
+  canvas.style.width = width + 'px';
+  let top = 20;
+  for (const person of people) {
+    const items = dated.filter(e => e.person === person);
+    // Stack items that would overlap into rows
+    const rowEnds = [];
+    const placed = items.map(e => {
+      const left = x(e.time);
+      let row = rowEnds.findIndex(end => end < left);
+      if (row < 0) { row = rowEnds.length; rowEnds.push(0); }
+      rowEnds[row] = left + 250;
+      return { e, left, row };
+    });
+    const height = LANE_PADDING + Math.max(1, rowEnds.length) * ROW;
+    const lane = document.createElement('div');
+    lane.className = 'lane';
+    lane.style.top = top + 'px';
+    lane.style.height = height + 'px';
+    if (people.length > 1) {
+      const label = document.createElement('div');
+      label.className = 'lane-label';
+      label.textContent = person;
+      lane.appendChild(label);
+    }
+    for (const { e, left, row } of placed) {
+      const item = document.createElement('div');
+      item.className = 'item ' + e.kind;
+      item.style.left = left + 'px';
+      item.style.top = (LANE_PADDING + row * ROW) + 'px';
+      item.textContent = e.title;
+      item.title = e.title;
+      item.addEventListener('click', () => show(e, item));
+      lane.appendChild(item);
+    }
+    canvas.appendChild(lane);
+    top += height;
+  }
+  const step = tickStep();
+  for (let time = Math.ceil(min / step) * step; time <= max; time += step) {
+    const tick = document.createElement('div');
+    tick.className = 'tick';
+    tick.style.left = x(time) + 'px';
+    const date = new Date(time);
+    tick.textContent = step < DAY ? date.toLocaleString() : date.toLocaleDateString();
+    canvas.appendChild(tick);
+  }
+  viewport.style.height = (top + 10) + 'px';
+}
+
+function zoom(factor, anchor) {
+  const anchorX = anchor === undefined ? viewport.clientWidth / 2 : anchor;
+  const time = min + (viewport.scrollLeft + anchorX - 20) / scale;
+  scale = Math.min(Math.max(scale * factor, fitScale()), 1 / 60000);
+  draw();
+  viewport.scrollLeft = x(time) - anchorX;
+}
+
+document.getElementById('zoom-in').onclick = () => zoom(2);
+document.getElementById('zoom-out').onclick = () => zoom(0.5);
+document.getElementById('fit').onclick = () => { scale = fitScale(); draw(); };
+viewport.addEventListener('wheel', event => {
+  if (!event.ctrlKey && !event.metaKey) return;
+  event.preventDefault();
+  zoom(event.deltaY < 0 ? 1.25 : 0.8, event.clientX - viewport.getBoundingClientRect().left);
+}, { passive: false });
+let drag = null;
+viewport.addEventListener('mousedown', event => { drag = { x: event.clientX, scroll: viewport.scrollLeft }; });
+window.addEventListener('mouseup', () => { drag = null; });
+window.addEventListener('mousemove', event => { if (drag) viewport.scrollLeft = drag.scroll - (event.clientX - drag.x); });
+
+const rows = document.getElementById('rows');
+for (const e of events) {
+  const row = rows.insertRow();
+  [e.shown, e.person, e.kind, e.title, e.detail].forEach((value, index) => {
+    const cell = row.insertCell();
+    cell.textContent = value;
+    if (index === 4) cell.className = 'detail';
+  });
+  row.addEventListener('click', () => show(e, null));
+}
+
+scale = fitScale();
+draw();
+</script>
+</body>
+</html>
+"#;
@@ -0,0 +1,352 @@
+use crate::hashing;
+use anyhow::{Result, Context, anyhow, bail};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::time::Duration;
+
+// DER object identifiers, without tag and length
+const SHA256_OID: [u8; 9] = [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+const TST_INFO_OID: [u8; 11] = [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x10, 0x01, 0x04];
+
+// DER tags
+const BOOLEAN: u8 = 0x01;
+const INTEGER: u8 = 0x02;
+const OCTET_STRING: u8 = 0x04;
+const NULL: u8 = 0x05;
+const OID: u8 = 0x06;
+const GENERALIZED_TIME: u8 = 0x18;
+const SEQUENCE: u8 = 0x30;
+const SET: u8 = 0x31;
+const EXPLICIT_0: u8 = 0xa0;
+
+// Responses are a few kilobytes; anything far larger isn't a timestamp
+const MAX_RESPONSE_SIZE: u64 = 1024 * 1024;
+
+/// An RFC 3161 timestamp token from a trusted timestamping authority (TSA), proving a file
+/// with this SHA-256 existed at `time`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrustedTimestamp {
+    pub tsa_url: String,
+    pub sha256: String,
+    pub time: DateTime<Utc>, // As stated by the TSA
+    pub token: String, // DER TimeStampToken in hex, for checking the TSA's signature with other tools
+}
+
+/// Asks the TSA at `tsa_url` to timestamp a file's SHA-256 `digest`
+pub fn request(tsa_url: &str, digest: [u8; 32]) -> Result<TrustedTimestamp> {
+    // A fresh nonce ties the response to this request; positive and minimal as DER requires
+    let mut nonce = [0u8; 8];
+    SystemRandom::new().fill(&mut nonce)
+        .map_err(|_| anyhow!("Failed to generate a nonce"))?;
+    nonce[0] = (nonce[0] & 0x7f) | 0x01;
+
+    let algorithm = der(SEQUENCE, &[der(OID, &SHA256_OID), der(NULL, &[])].concat());
+    let message_imprint = der(SEQUENCE, &[algorithm, der(OCTET_STRING, &digest)].concat());
+    let timestamp_request = der(SEQUENCE, &[
+        der(INTEGER, &[1]),
+        message_imprint,
+        der(INTEGER, &nonce),
+        // Include the TSA's certificate so the token can be checked on its own
+        der(BOOLEAN, &[0xff]),
+    ].concat());
+
+    let response = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .post(tsa_url)
+        .set("Content-Type", "application/timestamp-query")
+        .set("User-Agent", concat!("Evidence-Manager/", env!("CARGO_PKG_VERSION")))
+        .send_bytes(&timestamp_request)
+        .context("Failed to reach the timestamping authority")?;
+    let mut body = Vec::new();
+    response.into_reader()
+        .take(MAX_RESPONSE_SIZE)
+        .read_to_end(&mut body)
+        .context("Failed to read the timestamp response")?;
+
+    // TimeStampResp ::= SEQUENCE { status PKIStatusInfo, timeStampToken OPTIONAL }
+    let mut response = Der::new(Der::new(&body).read(SEQUENCE)?);
+    let status = Der::new(response.read(SEQUENCE)?).read(INTEGER)?;
+    // 0 is granted, 1 granted with modifications
+    if status != [0] && status != [1] {
+        bail!("The timestamping authority refused the request (status {})", status.last().copied().unwrap_or_default());
+    }
+    let token = response.read_element(SEQUENCE)?;
+
+    let info = TstInfo::parse(token)?;
+    if info.hashed_message != digest {
+        bail!("The timestamp is for a different hash than the one sent");
+    }
+    if info.nonce.as_deref() != Some(&nonce[..]) {
+        bail!("The timestamp response doesn't answer this request");
+    }
+
+    Ok(TrustedTimestamp {
+        tsa_url: tsa_url.to_string(),
+        sha256: hashing::to_hex(&digest),
+        time: info.time,
+        token: hashing::to_hex(token),
+    })
+}
+
+/// Checks that the stored token covers a file whose SHA-256 is now `digest`, returning the
+/// timestamped time. This only compares hashes: the TSA's signature on the token and its
+/// certificate aren't checked, so it's no proof on its own that the TSA issued the token.
+pub fn check_hash(timestamp: &TrustedTimestamp, digest: [u8; 32]) -> Result<DateTime<Utc>> {
+    let token = hashing::from_hex(&timestamp.token)?;
+    let info = TstInfo::parse(&token)?;
+    if info.hashed_message != digest {
+        bail!("The file has changed since it was timestamped");
+    }
+    if hashing::to_hex(&info.hashed_message) != timestamp.sha256 || info.time != timestamp.time {
+        bail!("The timestamp token doesn't match the recorded hash and time");
+    }
+    Ok(info.time)
+}
+
+/// The parts of a token's TSTInfo that tie it to a file and a time
+struct TstInfo {
+    hashed_message: Vec<u8>,
+    time: DateTime<Utc>,
+    nonce: Option<Vec<u8>>,
+}
+
+impl TstInfo {
+    fn parse(token: &[u8]) -> Result<Self> {
+        // ContentInfo ::= SEQUENCE { contentType, [0] EXPLICIT SignedData }
+        let mut content_info = Der::new(Der::new(token).read(SEQUENCE)?);
+        content_info.read(OID)?;
+        let mut signed_data = Der::new(Der::new(content_info.read(EXPLICIT_0)?).read(SEQUENCE)?);
+        signed_data.read(INTEGER)?;
+        signed_data.read(SET)?;
+        // EncapsulatedContentInfo ::= SEQUENCE { eContentType, [0] EXPLICIT OCTET STRING }
+        let mut encapsulated = Der::new(signed_data.read(SEQUENCE)?);
+        if encapsulated.read(OID)? != TST_INFO_OID {
+            bail!("The token doesn't contain timestamp information");
+        }
+        let tst_info = Der::new(encapsulated.read(EXPLICIT_0)?).read(OCTET_STRING)?;
+
+        // TSTInfo ::= SEQUENCE { version, policy, messageImprint, serialNumber, genTime, accuracy?, ordering?, nonce?, ... }
+        let mut tst_info = Der::new(Der::new(tst_info).read(SEQUENCE)?);
+        tst_info.read(INTEGER)?;
+        tst_info.read(OID)?;
+        let mut message_imprint = Der::new(tst_info.read(SEQUENCE)?);
+        if Der::new(message_imprint.read(SEQUENCE)?).read(OID)? != SHA256_OID {
+            bail!("The timestamp isn't over a SHA-256 hash");
+        }
+        let hashed_message = message_imprint.read(OCTET_STRING)?.to_vec();
+        tst_info.read(INTEGER)?;
+        let time = parse_generalized_time(tst_info.read(GENERALIZED_TIME)?)?;
+
+        if tst_info.peek() == Some(SEQUENCE) {
+            tst_info.read(SEQUENCE)?;
+        }
+        if tst_info.peek() == Some(BOOLEAN) {
+            tst_info.read(BOOLEAN)?;
+        }
+        let nonce = if tst_info.peek() == Some(INTEGER) {
+            Some(tst_info.read(INTEGER)?.to_vec())
+        } else {
+            None
+        };
+
+        Ok(Self { hashed_message, time, nonce })
+    }
+}
+
+/// "YYYYMMDDHHMMSS[.fff]Z", always UTC in a timestamp token
+fn parse_generalized_time(value: &[u8]) -> Result<DateTime<Utc>> {
+    let text = std::str::from_utf8(value).context("Invalid time in timestamp")?;
+    let text = text.strip_suffix('Z').context("Timestamp time isn't in UTC")?;
+    let (seconds, _fraction) = text.split_once('.').unwrap_or((text, ""));
+    let time = NaiveDateTime::parse_from_str(seconds, "%Y%m%d%H%M%S")
+        .context("Invalid time in timestamp")?;
+    Ok(time.and_utc())
+}
+
+fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut encoded = vec![tag];
+    if content.len() < 0x80 {
+        encoded.push(content.len() as u8);
+    } else {
+        let length = content.len().to_be_bytes();
+        let significant: Vec<u8> = length.into_iter().skip_while(|byte| *byte == 0).collect();
+        encoded.push(0x80 | significant.len() as u8);
+        encoded.extend(significant);
+    }
+    encoded.extend_from_slice(content);
+    encoded
+}
+
+/// Reads DER elements one after another; only single-byte tags are needed here
+struct Der<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Der<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.first().copied()
+    }
+
+    /// Reads the next element, which must have the given tag, and returns its content
+    fn read(&mut self, tag: u8) -> Result<&'a [u8]> {
+        let (header, content) = self.next(tag)?;
+        Ok(&content[header..])
+    }
+
+    /// Like `read`, but returns the whole element including its tag and length
+    fn read_element(&mut self, tag: u8) -> Result<&'a [u8]> {
+        self.next(tag).map(|(_, element)| element)
+    }
+
+    fn next(&mut self, tag: u8) -> Result<(usize, &'a [u8])> {
+        let malformed = || anyhow!("Malformed timestamp data");
+        if self.peek() != Some(tag) {
+            bail!("Unexpected timestamp data (expected tag {:#04x})", tag);
+        }
+        let first = *self.data.get(1).ok_or_else(malformed)?;
+        let (header, length) = if first < 0x80 {
+            (2, first as usize)
+        } else {
+            let count = (first & 0x7f) as usize;
+            if count == 0 || count > 4 {
+                return Err(malformed());
+            }
+            let bytes = self.data.get(2..2 + count).ok_or_else(malformed)?;
+            (2 + count, bytes.iter().fold(0usize, |length, byte| (length << 8) | *byte as usize))
+        };
+        let end = header.checked_add(length).ok_or_else(malformed)?;
+        let element = self.data.get(..end).ok_or_else(malformed)?;
+        self.data = &self.data[end..];
+        Ok((header, element))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const SIGNED_DATA_OID: [u8; 9] = [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x07, 0x02];
+
+    fn algorithm() -> Vec<u8> {
+        der(SEQUENCE, &[der(OID, &SHA256_OID), der(NULL, &[])].concat())
+    }
+
+    /// A TimeStampToken laid out as a TSA sends it. The certificate stands in for a real one at a
+    /// realistic size, so the outer elements need two-byte lengths.
+    fn token(digest: [u8; 32], time: &str, nonce: &[u8]) -> Vec<u8> {
+        let tst_info = der(SEQUENCE, &[
+            der(INTEGER, &[1]),
+            der(OID, &[0x2b, 0x06, 0x01, 0x04, 0x01, 0xb2, 0x31, 0x03, 0x02]),
+            der(SEQUENCE, &[algorithm(), der(OCTET_STRING, &digest)].concat()),
+            der(INTEGER, &[0x01, 0x5f, 0x3a]),
+            der(GENERALIZED_TIME, time.as_bytes()),
+            der(SEQUENCE, &der(INTEGER, &[1])), // accuracy
+            der(BOOLEAN, &[0]), // ordering
+            der(INTEGER, nonce),
+        ].concat());
+        let encapsulated = der(SEQUENCE, &[
+            der(OID, &TST_INFO_OID),
+            der(EXPLICIT_0, &der(OCTET_STRING, &tst_info)),
+        ].concat());
+        let certificate = der(SEQUENCE, &[0x55; 1200]);
+        let signed_data = der(SEQUENCE, &[
+            der(INTEGER, &[3]),
+            der(SET, &algorithm()),
+            encapsulated,
+            der(EXPLICIT_0, &certificate),
+            der(SET, &der(SEQUENCE, &[0x66; 300])), // signerInfos
+        ].concat());
+        der(SEQUENCE, &[der(OID, &SIGNED_DATA_OID), der(EXPLICIT_0, &signed_data)].concat())
+    }
+
+    #[test]
+    fn der_encodes_and_reads_short_and_long_lengths() {
+        let short = der(OCTET_STRING, &[7; 0x7f]);
+        assert_eq!(short[..2], [OCTET_STRING, 0x7f]);
+        let one_byte = der(OCTET_STRING, &[7; 200]);
+        assert_eq!(one_byte[..3], [OCTET_STRING, 0x81, 0xc8]);
+        let two_bytes = der(OCTET_STRING, &[7; 300]);
+        assert_eq!(two_bytes[..4], [OCTET_STRING, 0x82, 0x01, 0x2c]);
+        let three_bytes = der(OCTET_STRING, &[7; 70_000]);
+        assert_eq!(three_bytes[..5], [OCTET_STRING, 0x83, 0x01, 0x11, 0x70]);
+
+        for (encoded, length) in [(short, 0x7f), (one_byte, 200), (two_bytes, 300), (three_bytes, 70_000)] {
+            let mut reader = Der::new(&encoded);
+            assert_eq!(reader.read(OCTET_STRING).unwrap().len(), length);
+            assert_eq!(reader.peek(), None);
+        }
+    }
+
+    #[test]
+    fn der_reads_elements_in_turn() {
+        let data = [der(INTEGER, &[5]), der(SEQUENCE, &[0x11; 260]), der(NULL, &[])].concat();
+        let mut reader = Der::new(&data);
+        assert_eq!(reader.read(INTEGER).unwrap(), [5]);
+        assert_eq!(reader.peek(), Some(SEQUENCE));
+        let element = reader.read_element(SEQUENCE).unwrap();
+        assert_eq!(element[..4], [SEQUENCE, 0x82, 0x01, 0x04]);
+        assert_eq!(element.len(), 4 + 260);
+        assert!(reader.read(NULL).unwrap().is_empty());
+    }
+
+    #[test]
+    fn der_refuses_malformed_data() {
+        // Wrong tag
+        assert!(Der::new(&der(INTEGER, &[1])).read(SEQUENCE).is_err());
+        // Missing length
+        assert!(Der::new(&[SEQUENCE]).read(SEQUENCE).is_err());
+        // Indefinite length, and more length bytes than any token needs
+        assert!(Der::new(&[SEQUENCE, 0x80, 0x00, 0x00]).read(SEQUENCE).is_err());
+        assert!(Der::new(&[SEQUENCE, 0x85, 0, 0, 0, 0, 1, 0]).read(SEQUENCE).is_err());
+        // Length bytes cut off, and content shorter than the length
+        assert!(Der::new(&[SEQUENCE, 0x82, 0x01]).read(SEQUENCE).is_err());
+        let mut truncated = der(OCTET_STRING, &[7; 300]);
+        truncated.truncate(200);
+        assert!(Der::new(&truncated).read(OCTET_STRING).is_err());
+    }
+
+    #[test]
+    fn tst_info_is_read_from_a_token() {
+        let digest = [0xab; 32];
+        let token = token(digest, "20240301101530.25Z", &[0x00, 0x9c, 0x41]);
+        assert_eq!(token[1], 0x82);
+
+        let info = TstInfo::parse(&token).unwrap();
+        assert_eq!(info.hashed_message, digest);
+        assert_eq!(info.time, Utc.with_ymd_and_hms(2024, 3, 1, 10, 15, 30).unwrap());
+        assert_eq!(info.nonce.as_deref(), Some(&[0x00, 0x9c, 0x41][..]));
+    }
+
+    #[test]
+    fn check_hash_compares_the_token_with_the_file() {
+        let digest = [0xab; 32];
+        let token = token(digest, "20240301101530Z", &[0x01]);
+        let time = Utc.with_ymd_and_hms(2024, 3, 1, 10, 15, 30).unwrap();
+        let timestamp = TrustedTimestamp {
+            tsa_url: "https://freetsa.org/tsr".to_string(),
+            sha256: hashing::to_hex(&digest),
+            time,
+            token: hashing::to_hex(&token),
+        };
+        assert_eq!(check_hash(&timestamp, digest).unwrap(), time);
+        assert!(check_hash(&timestamp, [0xcd; 32]).is_err());
+
+        let moved = TrustedTimestamp { time: time + chrono::Duration::hours(1), ..timestamp };
+        assert!(check_hash(&moved, digest).is_err());
+    }
+
+    #[test]
+    fn generalized_time_must_be_utc() {
+        assert_eq!(parse_generalized_time(b"20231231235959Z").unwrap(), Utc.with_ymd_and_hms(2023, 12, 31, 23, 59, 59).unwrap());
+        assert!(parse_generalized_time(b"20231231235959+0100").is_err());
+        assert!(parse_generalized_time(b"2023123123Z").is_err());
+    }
+}
@@ -0,0 +1,257 @@
+use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use lopdf::{Dictionary, Object, Stream};
+use lopdf::content::{Content, Operation};
+use std::io::Cursor;
+use std::path::Path;
+
+/// Text stamped onto exported media so copies handed to third parties can be traced back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Watermark {
+    pub case_name: String,
+    pub exported_at: DateTime<Utc>,
+}
+
+impl Watermark {
+    pub fn new(case_name: String) -> Self {
+        Self {
+            case_name,
+            exported_at: Utc::now(),
+        }
+    }
+
+    pub fn text(&self) -> String {
+        let date = self.exported_at.format("%Y-%m-%d %H:%M UTC");
+        if self.case_name.trim().is_empty() {
+            format!("CONFIDENTIAL - EXPORTED {}", date)
+        } else {
+            format!("CONFIDENTIAL - {} - EXPORTED {}", self.case_name.trim().to_uppercase(), date)
+        }
+    }
+
+    /// Returns true if the file extension is something we know how to stamp
+    pub fn supports_extension(ext: &str) -> bool {
+        let ext = ext.to_lowercase();
+        ext == "pdf" || ImageFormat::from_extension(&ext).is_some()
+    }
+
+    /// Whether a watermarked export stamps the file at this path
+    pub fn supports_path(path: &Path) -> bool {
+        path.extension().is_some_and(|ext| Self::supports_extension(&ext.to_string_lossy()))
+    }
+
+    pub fn apply(&self, ext: &str, content: &[u8]) -> Result<Vec<u8>> {
+        if ext.eq_ignore_ascii_case("pdf") {
+            self.apply_to_pdf(content)
+        } else {
+            self.apply_to_image(ext, content)
+        }
+    }
+
+    pub fn apply_to_image(&self, ext: &str, content: &[u8]) -> Result<Vec<u8>> {
+        let format = ImageFormat::from_extension(ext)
+            .context("Unsupported image format for watermarking")?;
+        let image = image::load_from_memory_with_format(content, format)
+            .context("Failed to decode image for watermarking")?;
+
+        let mut canvas = image.to_rgba8();
+        draw_banner(&mut canvas, &self.text());
+
+        // Formats without an alpha channel (e.g. JPEG) must be written as RGB
+        let stamped = if image.color().has_alpha() {
+            DynamicImage::ImageRgba8(canvas)
+        } else {
+            DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(canvas).to_rgb8())
+        };
+
+        let mut output = Cursor::new(Vec::new());
+        stamped.write_to(&mut output, format)
+            .context("Failed to encode watermarked image")?;
+
+        Ok(output.into_inner())
+    }
+
+    pub fn apply_to_pdf(&self, content: &[u8]) -> Result<Vec<u8>> {
+        let mut document = lopdf::Document::load_mem(content)
+            .context("Failed to parse PDF for watermarking")?;
+
+        let font_id = document.add_object(Dictionary::from_iter(vec![
+            ("Type", Object::Name(b"Font".to_vec())),
+            ("Subtype", Object::Name(b"Type1".to_vec())),
+            ("BaseFont", Object::Name(b"Helvetica".to_vec())),
+        ]));
+
+        let text = self.text();
+        let page_ids: Vec<_> = document.page_iter().collect();
+        for page_id in page_ids {
+            add_font_resource(&mut document, page_id, font_id)
+                .context("Failed to add watermark font to PDF page")?;
+
+            let stamp = Content {
+                operations: vec![
+                    Operation::new("Q", vec![]),
+                    Operation::new("q", vec![]),
+                    Operation::new("rg", vec![0.8.into(), 0.0.into(), 0.0.into()]),
+                    Operation::new("BT", vec![]),
+                    Operation::new("Tf", vec![WATERMARK_FONT.into(), 10.into()]),
+                    Operation::new("Td", vec![36.into(), 24.into()]),
+                    Operation::new("Tj", vec![Object::string_literal(text.as_str())]),
+                    Operation::new("ET", vec![]),
+                    Operation::new("Q", vec![]),
+                ],
+            };
+            let stamp = stamp.encode().context("Failed to encode watermark content")?;
+
+            // Wrap the existing page content in q/Q so its graphics state can't move our stamp
+            let mut contents = vec![Object::Reference(document.add_object(Stream::new(Dictionary::new(), b"q".to_vec())))];
+            contents.extend(document.get_page_contents(page_id).into_iter().map(Object::Reference));
+            contents.push(Object::Reference(document.add_object(Stream::new(Dictionary::new(), stamp))));
+
+            document.get_object_mut(page_id)
+                .and_then(Object::as_dict_mut)
+                .context("Failed to read PDF page")?
+                .set("Contents", contents);
+        }
+
+        let mut output = Vec::new();
+        document.save_to(&mut output)
+            .context("Failed to write watermarked PDF")?;
+
+        Ok(output)
+    }
+}
+
+const WATERMARK_FONT: &str = "EMWatermark";
+
+fn add_font_resource(document: &mut lopdf::Document, page_id: lopdf::ObjectId, font_id: lopdf::ObjectId) -> lopdf::Result<()> {
+    let resources = document.get_or_create_resources(page_id)?.as_dict_mut()?;
+    if !resources.has(b"Font") {
+        resources.set("Font", Dictionary::new());
+    }
+
+    let fonts_ref = match resources.get(b"Font")? {
+        Object::Reference(id) => Some(*id),
+        _ => None,
+    };
+
+    let fonts = match fonts_ref {
+        Some(id) => document.get_object_mut(id)?.as_dict_mut()?,
+        None => document.get_or_create_resources(page_id)?.as_dict_mut()?.get_mut(b"Font")?.as_dict_mut()?,
+    };
+    fonts.set(WATERMARK_FONT, Object::Reference(font_id));
+
+    Ok(())
+}
+
+/// Draws the watermark text on a translucent band along the bottom of the image
+fn draw_banner(canvas: &mut RgbaImage, text: &str) {
+    let (width, height) = canvas.dimensions();
+    let text_width = text.chars().count() as u32 * (GLYPH_WIDTH + 1);
+
+    // Scale the text to roughly 60% of the image width, but never below 1px per dot
+    let scale = ((width * 6 / 10) / text_width.max(1)).max(1);
+    let padding = 2 * scale;
+    let band_height = (GLYPH_HEIGHT * scale + padding * 2).min(height);
+    let band_top = height - band_height;
+
+    for y in band_top..height {
+        for x in 0..width {
+            let pixel = canvas.get_pixel_mut(x, y);
+            blend(pixel, Rgba([0, 0, 0, 140]));
+        }
+    }
+
+    draw_text(canvas, text, padding, band_top + padding, scale, Rgba([255, 255, 255, 255]));
+}
+
+/// Draws text in the built-in bitmap font with its top-left corner at (x, y)
+pub fn draw_text(canvas: &mut RgbaImage, text: &str, x: u32, y: u32, scale: u32, color: Rgba<u8>) {
+    let (width, height) = canvas.dimensions();
+    let mut cursor_x = x;
+    for c in text.chars() {
+        let glyph = glyph(c);
+        for (row, bits) in glyph.iter().enumerate() {
+            for col in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - col)) == 0 {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let px = cursor_x + col * scale + dx;
+                        let py = y + row as u32 * scale + dy;
+                        if px < width && py < height {
+                            canvas.put_pixel(px, py, color);
+                        }
+                    }
+                }
+            }
+        }
+        cursor_x += (GLYPH_WIDTH + 1) * scale;
+    }
+}
+
+fn blend(pixel: &mut Rgba<u8>, overlay: Rgba<u8>) {
+    let alpha = overlay[3] as u32;
+    for channel in 0..3 {
+        pixel[channel] = ((pixel[channel] as u32 * (255 - alpha) + overlay[channel] as u32 * alpha) / 255) as u8;
+    }
+    pixel[3] = pixel[3].max(overlay[3]);
+}
+
+pub const GLYPH_WIDTH: u32 = 5;
+pub const GLYPH_HEIGHT: u32 = 7;
+
+/// 5x7 bitmap glyphs, one byte per row with the low five bits used
+fn glyph(c: char) -> [u8; 7] {
+    match c.to_ascii_uppercase() {
+        'A' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' => [0x1E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1E],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+        'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        '-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+        ':' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+        ',' => [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08],
+        '/' => [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00],
+        '_' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F],
+        '#' => [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A],
+        '(' => [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02],
+        ')' => [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08],
+        ' ' => [0x00; 7],
+        _ => [0x1F, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1F],
+    }
+}
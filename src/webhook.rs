@@ -0,0 +1,112 @@
+use crate::audit::AuditEntry;
+use crate::hashing;
+use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use ring::hmac;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Header carrying "sha256=<hex HMAC of the body>", keyed with the shared secret
+pub const SIGNATURE_HEADER: &str = "X-Evidence-Manager-Signature";
+pub const EVENT_HEADER: &str = "X-Evidence-Manager-Event";
+
+// Deliveries still running, so a command-line run can wait for them before exiting
+static DELIVERIES: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+
+/// What is posted for each event
+#[derive(Debug, Serialize)]
+pub struct Payload {
+    pub event: String, // e.g. "evidence.added"
+    pub occurred_at: DateTime<Utc>,
+    pub person_id: Option<Uuid>,
+    pub person_name: String,
+    pub details: String,
+    pub operator: String,
+    pub audit_hash: String, // Hash of the matching activity log entry; empty for test events
+}
+
+impl Payload {
+    pub fn from_entry(event: &str, entry: &AuditEntry) -> Self {
+        Self {
+            event: event.to_string(),
+            occurred_at: entry.timestamp,
+            person_id: entry.person_id,
+            person_name: entry.person_name.clone(),
+            details: entry.details.clone(),
+            operator: entry.operator.clone(),
+            audit_hash: entry.hash.clone(),
+        }
+    }
+
+    pub fn test(operator: &str) -> Self {
+        Self {
+            event: "test".to_string(),
+            occurred_at: Utc::now(),
+            person_id: None,
+            person_name: String::new(),
+            details: "Test event from Evidence Manager".to_string(),
+            operator: operator.to_string(),
+            audit_hash: String::new(),
+        }
+    }
+}
+
+/// An endpoint that is sent a JSON payload for key events, signed with a shared secret
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    url: String,
+    secret: String,
+}
+
+impl Webhook {
+    /// None when no URL is set
+    pub fn new(url: &str, secret: &str) -> Option<Self> {
+        let url = url.trim();
+        (!url.is_empty()).then(|| Self { url: url.to_string(), secret: secret.to_string() })
+    }
+
+    /// Posts the payload and waits for the endpoint to accept it
+    pub fn deliver(&self, payload: &Payload) -> Result<()> {
+        let body = serde_json::to_vec(payload)
+            .context("Failed to serialize webhook payload")?;
+        let mut request = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .post(&self.url)
+            .set("Content-Type", "application/json")
+            .set("User-Agent", concat!("Evidence-Manager/", env!("CARGO_PKG_VERSION")))
+            .set(EVENT_HEADER, &payload.event);
+        if !self.secret.is_empty() {
+            let key = hmac::Key::new(hmac::HMAC_SHA256, self.secret.as_bytes());
+            let signature = hmac::sign(&key, &body);
+            request = request.set(SIGNATURE_HEADER, &format!("sha256={}", hashing::to_hex(signature.as_ref())));
+        }
+        request.send_bytes(&body)
+            .with_context(|| format!("Failed to deliver webhook to {}", self.url))?;
+        Ok(())
+    }
+
+    /// Delivers in the background; a failure is reported on stderr and doesn't affect the change
+    pub fn send(&self, payload: Payload) {
+        let webhook = self.clone();
+        let delivery = std::thread::spawn(move || {
+            if let Err(e) = webhook.deliver(&payload) {
+                eprintln!("{:#}", e);
+            }
+        });
+        let mut deliveries = DELIVERIES.lock().unwrap_or_else(|e| e.into_inner());
+        deliveries.retain(|delivery| !delivery.is_finished());
+        deliveries.push(delivery);
+    }
+}
+
+/// Waits for background deliveries to finish
+pub fn wait_for_deliveries() {
+    let deliveries = std::mem::take(&mut *DELIVERIES.lock().unwrap_or_else(|e| e.into_inner()));
+    for delivery in deliveries {
+        let _ = delivery.join();
+    }
+}